@@ -1,51 +1,247 @@
-use crate::github::models::{PrDetail, PullRequest, RateLimit, Repo};
+use crate::app::state::{ContentView, RefreshSummary};
+use crate::github::CancelToken;
+use crate::github::models::{PrDetail, PrHistoryKind, PrStateFilter, PullRequest, RateLimit, Repo};
+use crate::util::config::{AppConfig, Owner, OwnerKind};
+use crate::util::pr_history::PrCountHistory;
+use crate::util::watchlist::WatchedPr;
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum Action {
     MoveUp,
     MoveDown,
+    /// First row, or row N with a count.
+    JumpTop,
+    /// Last row, or row N with a count.
+    JumpBottom,
+    /// A digit typed in normal mode, building up a count for the next motion.
+    CountDigit(u8),
+    /// `'` in the nav pane: the next letter jumps to an org/repo starting with it.
+    StartLetterJump,
+    JumpToLetter(char),
+    /// Esc while a count or jump prefix is pending drops it.
+    ClearPending,
     Select,
     Back,
     SwitchPane,
     Refresh,
+    /// Re-read the config file. The event loop owns the config, so it handles
+    /// this itself and follows up with `ConfigReloaded` or `LoadError`.
+    ReloadConfig,
+    ConfigReloaded(Box<AppConfig>),
+    /// GitHub rejected the token mid-session (401).
+    TokenRejected,
+    /// Resolve a token again after a rejection. Handled by the event loop,
+    /// which rebuilds the client and follows up with `TokenRenewed` or
+    /// `LoadError`.
+    ResolveToken,
+    TokenRenewed,
+    /// The terminal window regained focus; refreshes unless the last refresh
+    /// was within `dashboard.focus_refresh_debounce_secs`.
+    FocusGained,
+    /// A scheduled full refresh. Does what `Refresh` does, but isn't user
+    /// input, so it doesn't hold off the next one.
+    AutoRefresh,
+    /// Offline mode served an entry written at this time.
+    CachedDataAge(chrono::DateTime<chrono::Utc>),
+    /// A ghdash release newer than this build exists (version without `v`).
+    UpdateAvailable(String),
+    /// The token signs in as this login, not the cached one the session
+    /// started with. The event loop picks it up too.
+    ViewerChanged(String),
+    /// GitHub was unreachable when the cached login was verified. The event
+    /// loop swaps in the offline source first; the dashboard then reloads
+    /// from the cache.
+    WentOffline,
+    /// Bypass the cache and re-fetch only what backs the current view.
+    RefreshCurrentView,
+    /// Bypass the cache and re-fetch only the inbox, wherever the user is.
+    RefreshInbox,
     OpenInBrowser,
+    /// Open every inbox PR updated since it was last seen, asking first when
+    /// there are more than `dashboard.open_unread_limit`.
+    OpenUnreadInbox,
     ToggleSearch,
     ToggleGitLog,
     ToggleDiff,
     CloseOverlay,
     ToggleHelp,
     CycleMergeFilter,
+    CycleReviewFilter,
+    /// In All PRs, show only PRs involving the viewer; pressed again, all.
+    ToggleInvolvesMe,
+    /// Show or hide repos without open PRs in the nav tree.
+    ToggleEmptyRepos,
+    /// Copy the highlighted PR as a Markdown link.
+    CopyPrLink,
+    /// Open the "open…" menu of repo/owner pages.
+    OpenMenu,
+    /// Show the next (previous) owner's overview, in nav order, wrapping.
+    NextOwner,
+    PrevOwner,
+    /// Open the chooser of recently viewed views.
+    OpenRecentViews,
+    /// Open the chooser of labels in the current list.
+    OpenLabelMenu,
+    ChooserUp,
+    ChooserDown,
+    ChooserConfirm,
+    CloseChooser,
+    /// Copy every PR in the current list as a Markdown table.
+    CopyPrList,
+    /// Narrow to one age bucket (0-based); selecting the active one clears it.
+    SelectAgeBucket(usize),
+    CycleSort,
+    /// Cycle the inbox between newest-first, least recently updated first
+    /// and oldest opened first.
+    CycleInboxSort,
+    /// In a repo view, cycle between open, merged and closed PRs.
+    CyclePrStateFilter,
+    /// Shift the PR table's visible columns; Title always stays.
+    ScrollColumnsLeft,
+    ScrollColumnsRight,
+    /// Show the highlighted PR's untruncated title in the status bar until the
+    /// next key; pressed again, hide it.
+    ShowFullTitle,
+    /// Popup with the highlighted PR's full title, branch and URL.
+    TogglePrInfo,
+    /// Pause auto-refresh and freeze the current list order, or release.
+    ToggleRefreshLock,
+    /// Stop or resume auto-refresh without freezing the list order.
+    ToggleAutoRefreshPause,
+    /// Move to the next (previous) PR with changes requested, or in the
+    /// active review filter's category, wrapping around.
+    NextMatch,
+    PrevMatch,
+    /// Filter the list to the highlighted PR's author; pressed again, clear it.
+    FilterByAuthor,
+    /// Watch the highlighted PR, or stop watching it.
+    ToggleWatch,
     SearchInput(char),
     SearchBackspace,
     SearchClear,
     DataLoaded(DataPayload),
     LoadError(String),
+    /// A fetch backing `view` failed; reported like `LoadError` and remembered
+    /// so the empty view can say so.
+    FetchFailed {
+        view: ContentView,
+        msg: String,
+    },
+    /// Every section of a full refresh has reported back; show what changed.
+    RefreshSummary(RefreshSummary),
     DismissError,
+    /// Copy the full text of the error on screen.
+    CopyError,
     Quit,
     Tick,
 }
 
+impl Action {
+    /// Whether this comes from the app rather than a key press: fetch results,
+    /// timers, focus and config changes. These leave pending keys, transient
+    /// status text and the idle timer alone.
+    pub fn is_background(&self) -> bool {
+        matches!(
+            self,
+            Action::Tick
+                | Action::DataLoaded(_)
+                | Action::LoadError(_)
+                | Action::FetchFailed { .. }
+                | Action::RefreshSummary(_)
+                | Action::CachedDataAge(_)
+                | Action::UpdateAvailable(_)
+                | Action::ViewerChanged(_)
+                | Action::WentOffline
+                | Action::ConfigReloaded(_)
+                | Action::FocusGained
+                | Action::AutoRefresh
+                | Action::TokenRejected
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum DataPayload {
     OrgRepos {
         org: String,
         repos: Vec<Repo>,
-        rate_limit: RateLimit,
+        rate_limit: Option<RateLimit>,
+    },
+    /// GitHub has no user or organization by this name.
+    OwnerNotFound {
+        owner: String,
     },
     InboxPrs {
         prs: Vec<PullRequest>,
-        rate_limit: RateLimit,
+        rate_limit: Option<RateLimit>,
     },
     AllOpenPrs {
         prs: Vec<PullRequest>,
-        rate_limit: RateLimit,
+        rate_limit: Option<RateLimit>,
+    },
+    /// Open PRs the `involves:` search matched, for the "involving me" filter.
+    InvolvedPrs {
+        prs: Vec<PullRequest>,
+        rate_limit: Option<RateLimit>,
+    },
+    /// The `involves:` search failed; the filter falls back to what the
+    /// loaded PRs say.
+    InvolvedPrsFailed {
+        msg: String,
+    },
+    PrHistory {
+        kind: PrHistoryKind,
+        prs: Vec<PullRequest>,
+        rate_limit: Option<RateLimit>,
+    },
+    RepoPrs {
+        owner: String,
+        name: String,
+        prs: Vec<PullRequest>,
+        rate_limit: Option<RateLimit>,
+        /// The view was left mid-fetch, so `prs` holds only the pages
+        /// fetched before that.
+        partial: bool,
+        /// The fetch's token, echoed back so its view can forget it.
+        cancel: CancelToken,
+    },
+    /// Prefetching a repo's open PRs failed; the view falls back to
+    /// `all_open_prs`.
+    RepoPrsFailed {
+        owner: String,
+        name: String,
+        cancel: CancelToken,
+    },
+    /// A repo's merged or closed PRs, fetched when the repo view is switched
+    /// to them.
+    RepoFinishedPrs {
+        owner: String,
+        name: String,
+        state: PrStateFilter,
+        prs: Vec<PullRequest>,
+        rate_limit: Option<RateLimit>,
+        cancel: CancelToken,
+    },
+    /// Fetching a repo's merged or closed PRs failed.
+    RepoFinishedPrsFailed {
+        owner: String,
+        name: String,
+        state: PrStateFilter,
+        msg: String,
+        cancel: CancelToken,
+    },
+    /// A watched PR fetched on its own because no loaded list had it.
+    WatchedPr {
+        watched: WatchedPr,
+        pr: Box<PullRequest>,
+        rate_limit: Option<RateLimit>,
     },
     PrDetailLoaded {
         /// PR url — the key into `AppState::pr_details`.
         key: String,
         detail: PrDetail,
-        rate_limit: RateLimit,
+        rate_limit: Option<RateLimit>,
     },
     PrDetailFailed {
         key: String,
@@ -65,10 +261,53 @@ pub enum DataPayload {
 #[derive(Debug)]
 pub enum SideEffect {
     RefreshAll,
+    /// Invalidate and re-fetch the data behind one content view.
+    RefreshView(ContentView),
     FetchOrgRepos(String),
+    /// Several orgs' repo lists in one request (`github.batch_queries`).
+    FetchOrgReposBatched(Vec<String>),
     FetchUserRepos(String),
+    /// Repos of an owner of unknown type, resolved (and cached) first.
+    FetchOwnerRepos(String),
     FetchInbox,
     FetchAllOpenPrs,
+    /// Search for open PRs involving the viewer, for the All PRs filter.
+    FetchInvolvedPrs,
+    FetchPrHistory(PrHistoryKind),
+    /// Look for a newer ghdash release, at most once a day. Silent on failure.
+    CheckForUpdate,
+    /// Check that the token still signs in as `expected`, the login cached
+    /// under `cache_key` that startup went ahead with.
+    VerifyViewer {
+        expected: String,
+        cache_key: String,
+    },
+    /// Background fetch of one repo's PR list, issued when its org is expanded.
+    PrefetchRepoPrs {
+        owner: String,
+        name: String,
+        cancel: CancelToken,
+    },
+    /// Re-fetch a repo view's open PRs past the cache. Stops paginating if
+    /// the view is left.
+    RefreshRepoPrs {
+        owner: String,
+        name: String,
+        cancel: CancelToken,
+    },
+    /// Fetch a repo's merged or closed PRs for the repo view's state filter.
+    FetchRepoFinishedPrs {
+        owner: String,
+        name: String,
+        state: PrStateFilter,
+        cancel: CancelToken,
+    },
+    /// Fetch one watched PR, whatever its state.
+    FetchWatchedPr(WatchedPr),
+    /// Persist the watchlist.
+    SaveWatchlist(Vec<WatchedPr>),
+    /// Persist the per-repo open PR counts.
+    SavePrHistory(PrCountHistory),
     FetchPrDetail {
         owner: String,
         name: String,
@@ -84,4 +323,17 @@ pub enum SideEffect {
         key: String,
     },
     OpenUrl(String),
+    CopyToClipboard(String),
+}
+
+impl SideEffect {
+    /// The fetch of `owner`'s repo list, by what kind of account it is.
+    pub fn fetch_repos(owner: &Owner) -> Self {
+        let login = owner.login.clone();
+        match owner.kind {
+            OwnerKind::Org => SideEffect::FetchOrgRepos(login),
+            OwnerKind::User => SideEffect::FetchUserRepos(login),
+            OwnerKind::Unknown => SideEffect::FetchOwnerRepos(login),
+        }
+    }
 }
@@ -1,35 +1,107 @@
+use std::collections::BTreeMap;
 use std::io;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{self, DisableFocusChange, EnableFocusChange, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use futures::StreamExt;
-use ratatui::{Terminal, backend::CrosstermBackend};
-use tokio::sync::{Semaphore, mpsc};
-use tracing::{debug, error};
+use ratatui::{Terminal, backend::CrosstermBackend, layout::Rect};
+use tokio::sync::{AcquireError, Semaphore, SemaphorePermit, mpsc};
+use tracing::{debug, error, info, warn};
 
 use crate::app::actions::{Action, DataPayload, SideEffect};
-use crate::app::state::{AppState, DiffEntry, FocusedPane, Overlay, PrDetailEntry};
-use crate::app::update::update;
+use crate::app::scheduler::RefreshScheduler;
+use crate::app::session::Session;
+use crate::app::state::{
+    AppState, ContentView, DiffEntry, FocusedPane, OrgData, Overlay, PrDetailEntry,
+};
+use crate::app::update::{self, update};
 use crate::app::view;
-use crate::cache::CacheStore;
-use crate::github::GithubClient;
-use crate::util::config::AppConfig;
+use crate::cache::{CacheStore, Negative};
+use crate::github::fixtures::FixtureSource;
+use crate::github::graphql::{FailureKind, failure_kind, is_network_error, is_no_access};
+use crate::github::models::{PrHistoryKind, RateLimit};
+use crate::github::{DataSource, GithubClient};
+use crate::ui::glyphs::SPINNER_FRAME_MS;
+use crate::ui::theme::{self, TerminalCaps};
+use crate::ui::widgets;
+use crate::util::config::{AppConfig, Owner, OwnerKind};
+use crate::util::glob::repo_matches;
+use crate::util::pr_history::PrCountHistory;
+use crate::util::time;
+use crate::util::update_check::{self, UpdateCheck};
+use crate::util::watchlist::Watchlist;
+
+/// Bounds how many side effects hit the network at once, and counts the ones
+/// still waiting for a slot so the status bar can show them.
+#[derive(Clone)]
+pub struct TaskQueue {
+    semaphore: Arc<Semaphore>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl TaskQueue {
+    pub fn new(max_concurrency: usize) -> Self {
+        Self {
+            // Zero permits would stall every fetch forever.
+            semaphore: Arc::new(Semaphore::new(max_concurrency.max(1))),
+            queued: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub async fn acquire(&self) -> Result<SemaphorePermit<'_>, AcquireError> {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        let permit = self.semaphore.acquire().await;
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+        permit
+    }
+
+    /// Tasks spawned but still waiting for a permit.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+}
+
+/// Cache key holding the last authenticated login, so offline sessions know
+/// whose inbox to show.
+pub const VIEWER_CACHE_KEY: &str = "viewer";
+
+/// Who the dashboard runs as.
+#[derive(Debug, Clone)]
+pub struct Viewer {
+    pub login: String,
+    /// Set when `login` came from the cache (under this key) instead of
+    /// GitHub; it's then verified in the background once the TUI is up.
+    pub unverified_key: Option<String>,
+}
+
+impl Viewer {
+    pub fn verified(login: String) -> Self {
+        Self {
+            login,
+            unverified_key: None,
+        }
+    }
+}
 
 pub async fn run(
     config: AppConfig,
+    config_path: Option<PathBuf>,
     client: GithubClient,
-    viewer_login: String,
+    fixtures: Option<FixtureSource>,
+    viewer: Viewer,
     cache_store: Option<CacheStore>,
 ) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -37,15 +109,28 @@ pub async fn run(
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableFocusChange, LeaveAlternateScreen);
         original_hook(panic_info);
     }));
 
-    let result = run_loop(&mut terminal, config, client, viewer_login, cache_store).await;
+    let result = run_loop(
+        &mut terminal,
+        config,
+        config_path,
+        client,
+        fixtures.map(Arc::new),
+        viewer,
+        cache_store,
+    )
+    .await;
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        LeaveAlternateScreen
+    )?;
 
     result
 }
@@ -53,43 +138,72 @@ pub async fn run(
 async fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     config: AppConfig,
-    client: GithubClient,
-    viewer_login: String,
+    config_path: Option<PathBuf>,
+    mut client: GithubClient,
+    fixtures: Option<Arc<FixtureSource>>,
+    viewer: Viewer,
     cache_store: Option<CacheStore>,
 ) -> Result<()> {
-    let all_owners: Vec<String> = config
-        .github
-        .orgs
-        .iter()
-        .chain(config.github.users.iter())
-        .cloned()
-        .collect();
-    let mut state = AppState::new(viewer_login.clone(), all_owners);
-
-    let (action_tx, mut action_rx) = mpsc::unbounded_channel::<Action>();
-    let semaphore = Arc::new(Semaphore::new(4));
+    let source = data_source(&client, &fixtures);
+    let mut state = AppState::new(viewer.login.clone(), config.github.owner_logins());
+    state.standalone_repos = config.github.standalone_repos();
+    state.rebuild_nav_tree();
+    // Fixture runs leave the user's data dir alone.
+    let persist = !source.is_fixture();
+    let session_path = config.session_path();
+    if persist {
+        let session = Session::load(&session_path);
+        state.last_seen = session.seen;
+        state.restore_recent_views(session.recent_views);
+        state.watched = Watchlist::load(&config.watchlist_path()).prs;
+        state.pr_history = PrCountHistory::load(&config.pr_history_path());
+    }
+    state.apply_ui_config(&config.ui);
+    state.terminal = TerminalCaps::detect(&|v| std::env::var(v).ok());
+    state.config_path = config.source.clone();
+    state.apply_org_colors(&config.github.org_color);
+    state.mute_repos = config.github.mute_repos.clone();
+    state.offline = source.is_offline();
+    state.focus_refresh_debounce_secs = config.dashboard.focus_refresh_debounce_secs;
+    state.idle_refresh_delay_secs = config.dashboard.idle_refresh_delay_secs;
+    state.open_unread_limit = config.dashboard.open_unread_limit;
+    state.inbox_sort = config.dashboard.inbox_sort;
+
+    // Paint whatever the previous run cached straight away; RefreshAll below
+    // replaces it as responses come in.
+    if let Some(cache) = &cache_store {
+        hydrate_from_cache(&mut state, cache, &config);
+    }
+
+    let mut d = Dispatcher::new(state, config, source, viewer.login, cache_store);
 
     // Initial data fetch
-    let effects = vec![SideEffect::RefreshAll];
+    let mut effects = vec![SideEffect::RefreshAll];
+    effects.extend(update::standalone_prefetches(&mut d.state));
+    if d.config.dashboard.check_updates {
+        effects.push(SideEffect::CheckForUpdate);
+    }
+    if let Some(cache_key) = viewer.unverified_key {
+        effects.push(SideEffect::VerifyViewer {
+            expected: d.viewer_login.clone(),
+            cache_key,
+        });
+    }
     for effect in effects {
-        spawn_side_effect(
-            effect,
-            &config,
-            &client,
-            &viewer_login,
-            &cache_store,
-            &action_tx,
-            &semaphore,
-        );
+        d.spawn(effect);
     }
 
     let mut event_stream = crossterm::event::EventStream::new();
-    let refresh_interval = config.dashboard.refresh_interval_secs;
 
-    let mut refresh_timer =
-        tokio::time::interval(tokio::time::Duration::from_secs(refresh_interval));
-    // First tick fires immediately (already handled by initial fetch above)
-    refresh_timer.tick().await;
+    // Redraws the backoff countdown once a second while a secondary rate limit
+    // is being waited out, and the reset and retry countdowns while throttled
+    // or backing off after failures. With `ui.reduce_motion` they only move
+    // when something else redraws.
+    let mut backoff_ticker = tokio::time::interval(tokio::time::Duration::from_secs(1));
+    // Steps the Inbox spinner while just the inbox refreshes; never ticks with
+    // `ui.reduce_motion`.
+    let mut spinner_ticker =
+        tokio::time::interval(tokio::time::Duration::from_millis(SPINNER_FRAME_MS));
 
     // PR detail debounce: when the highlighted PR changes while the detail pane is
     // open, wait for ~200ms of stable selection before fetching, so holding j/k
@@ -101,14 +215,26 @@ async fn run_loop(
 
     loop {
         // Render
-        terminal.draw(|f| view::render(f, &state))?;
+        let size = terminal.size()?;
+        let screen = Rect::new(0, 0, size.width, size.height);
+        let (_, content_area, _) = view::layout(screen);
+        d.state.table_rows = widgets::pr_table_rows(content_area, &d.state);
+        d.state.column_offset = widgets::fitted_column_offset(content_area, &d.state);
+        d.state.error_max_scroll = widgets::error_max_scroll(screen, &d.state);
+        d.state.queued_tasks = d.queue.queued();
+        d.state.rate_limit_backoff = client
+            .backoff_remaining()
+            .map(|wait| wait.as_secs_f64().ceil() as u64);
+        d.state.refresh_retry_at = d.scheduler.retry_at();
+        terminal.draw(|f| view::render(f, &d.state))?;
 
-        if state.should_quit {
+        if d.state.should_quit {
             break;
         }
 
         // (Re)arm the debounce whenever the highlighted PR or the open overlay
         // changes and we don't already have (or are fetching) the data it needs.
+        let state = &d.state;
         let desired_pr = if state.overlay != Overlay::None {
             state.selected_pr()
         } else {
@@ -132,64 +258,77 @@ async fn run_loop(
             }
         }
 
+        let ticking = !d.state.reduce_motion
+            && (d.state.rate_limit_backoff.is_some()
+                || d.state.refresh_retry_at.is_some()
+                || d.state.rate_limit.throttled_until(time::now()).is_some());
+        let spinning = !d.state.reduce_motion && d.state.inbox_loading;
+        let refresh_in = until(d.next_refresh_at());
+
         // Wait for events
         tokio::select! {
             // Terminal events
             maybe_event = event_stream.next() => {
                 if let Some(Ok(event)) = maybe_event
-                    && let Some(action) = map_event_to_action(&event, &state) {
-                        let effects = update(&mut state, action);
-                        for effect in effects {
-                            spawn_side_effect(
-                                effect,
-                                &config,
-                                &client,
-                                &viewer_login,
-                                &cache_store,
-                                &action_tx,
-                                &semaphore,
-                            );
-                        }
+                    && let Some(action) = map_event_to_action(&event, &d.state) {
+                        let action = match action {
+                            Action::ReloadConfig => {
+                                match reload_config(config_path.as_deref(), &client) {
+                                    Ok((new_config, new_client)) => {
+                                        client = new_client;
+                                        d.reconfigure(new_config, data_source(&client, &fixtures));
+                                        Action::ConfigReloaded(Box::new(d.config.clone()))
+                                    }
+                                    Err(e) => Action::LoadError(format!(
+                                        "Failed to reload config: {:#}",
+                                        e
+                                    )),
+                                }
+                            }
+                            Action::ResolveToken => match renew_client(&d.config).await {
+                                Ok(new_client) => {
+                                    client = new_client;
+                                    d.source = data_source(&client, &fixtures);
+                                    Action::TokenRenewed
+                                }
+                                Err(e) => Action::LoadError(format!(
+                                    "Failed to resolve a new token: {:#}",
+                                    e
+                                )),
+                            },
+                            action => action,
+                        };
+                        d.dispatch(action);
                     }
             }
             // Actions from background tasks
-            Some(action) = action_rx.recv() => {
-                let effects = update(&mut state, action);
-                for effect in effects {
-                    spawn_side_effect(
-                        effect,
-                        &config,
-                        &client,
-                        &viewer_login,
-                        &cache_store,
-                        &action_tx,
-                        &semaphore,
-                    );
+            Some(action) = d.action_rx.recv() => {
+                // A 401 anywhere means the token is dead: prompt for a new one
+                // instead of reporting each failed fetch.
+                if client.token_rejected() && !d.state.token_rejected {
+                    d.dispatch(Action::TokenRejected);
                 }
-            }
-            // Auto-refresh timer
-            _ = refresh_timer.tick() => {
-                if !state.loading {
-                    let effects = update(&mut state, Action::Refresh);
-                    for effect in effects {
-                        spawn_side_effect(
-                            effect,
-                            &config,
-                            &client,
-                            &viewer_login,
-                            &cache_store,
-                            &action_tx,
-                            &semaphore,
-                        );
-                    }
+                if let Action::WentOffline = action {
+                    client = GithubClient::offline(&d.config.github);
+                    d.go_offline(data_source(&client, &fixtures));
                 }
+                d.handle(action);
+            }
+            // Auto-refresh: sleep until the next source is due, pushed back
+            // while the user is typing.
+            _ = tokio::time::sleep(refresh_in) => {
+                d.auto_refresh();
             }
+            // A backoff that starts mid-request is shown straight away.
+            _ = client.backoff_started() => {}
+            _ = backoff_ticker.tick(), if ticking => {}
+            _ = spinner_ticker.tick(), if spinning => {}
             // Debounced overlay fetch (only polled while a fetch is pending)
             _ = &mut detail_debounce, if pending_fetch.is_some() => {
                 if let Some((pr, overlay)) = pending_fetch.take() {
                     let effect = match overlay {
                         Overlay::GitLog => {
-                            state.pr_details.insert(pr.url.clone(), PrDetailEntry::Loading);
+                            d.state.pr_details.insert(pr.url.clone(), PrDetailEntry::Loading);
                             SideEffect::FetchPrDetail {
                                 owner: pr.repo_owner.clone(),
                                 name: pr.repo_name.clone(),
@@ -198,7 +337,7 @@ async fn run_loop(
                             }
                         }
                         Overlay::Diff => {
-                            state.pr_diffs.insert(pr.url.clone(), DiffEntry::Loading);
+                            d.state.pr_diffs.insert(pr.url.clone(), DiffEntry::Loading);
                             SideEffect::FetchPrDiff {
                                 owner: pr.repo_owner.clone(),
                                 name: pr.repo_name.clone(),
@@ -208,24 +347,222 @@ async fn run_loop(
                         }
                         Overlay::None => continue,
                     };
-                    spawn_side_effect(
-                        effect,
-                        &config,
-                        &client,
-                        &viewer_login,
-                        &cache_store,
-                        &action_tx,
-                        &semaphore,
-                    );
+                    d.spawn(effect);
                 }
             }
         }
     }
 
+    // Persist what was on screen so the next run can mark what changed since.
+    if persist {
+        let session = Session {
+            seen: d.state.seen_snapshot(),
+            recent_views: d.state.recent_views.iter().cloned().collect(),
+        };
+        if let Err(e) = session.save(&session_path) {
+            error!(error = %e, "Failed to save session");
+        }
+    }
+
     Ok(())
 }
 
-fn map_event_to_action(event: &Event, state: &AppState) -> Option<Action> {
+/// Everything the event loop runs apart from the terminal: the state, where
+/// fetches go, and the channel background tasks report back on. `run_loop`
+/// feeds it key presses and timer ticks; tests drive it directly.
+pub struct Dispatcher {
+    pub state: AppState,
+    pub config: AppConfig,
+    source: Arc<dyn DataSource>,
+    viewer_login: String,
+    cache_store: Option<CacheStore>,
+    action_tx: mpsc::UnboundedSender<Action>,
+    action_rx: mpsc::UnboundedReceiver<Action>,
+    queue: TaskQueue,
+    scheduler: RefreshScheduler,
+}
+
+impl Dispatcher {
+    pub fn new(
+        mut state: AppState,
+        config: AppConfig,
+        source: Arc<dyn DataSource>,
+        viewer_login: String,
+        cache_store: Option<CacheStore>,
+    ) -> Self {
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+        let queue = TaskQueue::new(config.dashboard.max_concurrency);
+        // Nothing is due until a full interval from now; the initial fetch
+        // covers everything.
+        let scheduler = RefreshScheduler::new(&config, time::now());
+        state.refresh_intervals = scheduler.intervals();
+        Self {
+            state,
+            config,
+            source,
+            viewer_login,
+            cache_store,
+            action_tx,
+            action_rx,
+            queue,
+            scheduler,
+        }
+    }
+
+    /// Start `effect` in the background.
+    pub fn spawn(&self, effect: SideEffect) {
+        spawn_side_effect(
+            effect,
+            &self.config,
+            &self.source,
+            &self.viewer_login,
+            &self.cache_store,
+            &self.action_tx,
+            &self.queue,
+        );
+    }
+
+    /// Apply a user action and start the effects it asks for.
+    pub fn dispatch(&mut self, action: Action) {
+        for effect in update(&mut self.state, action) {
+            self.scheduler.record(&effect, time::now());
+            self.spawn(effect);
+        }
+    }
+
+    /// Apply an action a background task reported.
+    pub fn handle(&mut self, action: Action) {
+        self.scheduler.observe(&action, time::now());
+        if let Action::ViewerChanged(login) = &action {
+            self.viewer_login = login.clone();
+        }
+        self.dispatch(action);
+    }
+
+    /// The next action a background task reports, or `None` once every task
+    /// has finished and all they reported has been taken. Lets tests run
+    /// effects to completion.
+    #[allow(dead_code)]
+    pub async fn next_until_idle(&mut self) -> Option<Action> {
+        loop {
+            // Tasks drop their sender when they finish, after anything they
+            // sent, so checking this first can't miss a last action.
+            let idle = self.action_tx.strong_count() == 1;
+            if let Ok(action) = self.action_rx.try_recv() {
+                return Some(action);
+            }
+            if idle {
+                return None;
+            }
+            tokio::select! {
+                action = self.action_rx.recv() => return action,
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(1)) => {}
+            }
+        }
+    }
+
+    /// When the next auto-refresh is due, pushed back while the user is
+    /// typing.
+    fn next_refresh_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.scheduler
+            .next_due()
+            .max(self.state.auto_refresh_deferred_until())
+    }
+
+    /// Start whatever refreshes are due. Sources that fall due while
+    /// refreshing isn't allowed are skipped this round.
+    fn auto_refresh(&mut self) {
+        let due = self.scheduler.due(time::now());
+        if !self.state.auto_refresh_allowed() {
+            return;
+        }
+        let effects = due
+            .into_iter()
+            .flat_map(|effect| match effect {
+                SideEffect::RefreshAll => update(&mut self.state, Action::AutoRefresh),
+                effect => vec![effect],
+            })
+            .collect::<Vec<_>>();
+        for effect in effects {
+            self.spawn(effect);
+        }
+    }
+
+    /// Switch to `source`, the cache-only one, after GitHub turned out to be
+    /// unreachable. Fetches already started against the old source are
+    /// abandoned: their results would only be network errors.
+    pub fn go_offline(&mut self, source: Arc<dyn DataSource>) {
+        (self.action_tx, self.action_rx) = mpsc::unbounded_channel();
+        self.source = source;
+    }
+
+    /// Swap in a reloaded config and the source built for it. Refresh
+    /// intervals restart from now.
+    fn reconfigure(&mut self, config: AppConfig, source: Arc<dyn DataSource>) {
+        self.scheduler = RefreshScheduler::new(&config, time::now());
+        self.state.refresh_intervals = self.scheduler.intervals();
+        self.config = config;
+        self.source = source;
+    }
+}
+
+/// Run `save` on a blocking thread, so that of the writes to `path` the last
+/// one issued is the one left on disk. Each write is numbered as it is issued
+/// and they run one at a time; one that finds a later write already done is
+/// skipped.
+fn save_in_order(path: PathBuf, save: impl FnOnce(&Path) + Send + 'static) {
+    static ISSUED: AtomicU64 = AtomicU64::new(0);
+    static WRITTEN: Mutex<BTreeMap<PathBuf, u64>> = Mutex::new(BTreeMap::new());
+    let seq = ISSUED.fetch_add(1, Ordering::SeqCst) + 1;
+    tokio::task::spawn_blocking(move || {
+        let mut written = WRITTEN.lock().unwrap_or_else(|e| e.into_inner());
+        if written.get(&path).is_some_and(|&done| done > seq) {
+            debug!(path = %path.display(), "Skipping a stale write");
+            return;
+        }
+        save(&path);
+        written.insert(path, seq);
+    });
+}
+
+/// Where fetches go: the fixture set when running with `--fixtures`, GitHub
+/// through `client` otherwise.
+fn data_source(
+    client: &GithubClient,
+    fixtures: &Option<Arc<FixtureSource>>,
+) -> Arc<dyn DataSource> {
+    match fixtures {
+        Some(fixtures) => fixtures.clone(),
+        None => Arc::new(client.clone()),
+    }
+}
+
+/// Re-read the config file (the `--config` path, or the usual search) and
+/// build a client for its `[github]` section. Nothing is swapped in unless both
+/// succeed.
+fn reload_config(path: Option<&Path>, client: &GithubClient) -> Result<(AppConfig, GithubClient)> {
+    let config = AppConfig::load(path)?;
+    config.log_warnings();
+    theme::check_org_colors(&config.github.org_color)?;
+    let client = client.reconfigured(&config.github)?;
+    Ok((config, client))
+}
+
+/// A client with a freshly resolved token, after GitHub rejected the old one.
+async fn renew_client(config: &AppConfig) -> Result<GithubClient> {
+    let resolved = crate::github::auth::resolve_token(&config.github).await?;
+    info!(
+        method = resolved.method.label(),
+        "Resolved a new GitHub token"
+    );
+    Ok(GithubClient::new(&resolved.token, &config.github)?.with_refresher(resolved.refresher))
+}
+
+/// The action a terminal event stands for in the current state, if any.
+pub fn map_event_to_action(event: &Event, state: &AppState) -> Option<Action> {
+    if let Event::FocusGained = event {
+        return Some(Action::FocusGained);
+    }
     let Event::Key(KeyEvent {
         code,
         modifiers,
@@ -240,6 +577,9 @@ fn map_event_to_action(event: &Event, state: &AppState) -> Option<Action> {
     if state.error_message.is_some() {
         return match code {
             KeyCode::Esc => Some(Action::DismissError),
+            KeyCode::Char('j') | KeyCode::Down => Some(Action::MoveDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(Action::MoveUp),
+            KeyCode::Char('y') => Some(Action::CopyError),
             _ => None,
         };
     }
@@ -255,6 +595,20 @@ fn map_event_to_action(event: &Event, state: &AppState) -> Option<Action> {
         };
     }
 
+    // An open chooser takes every key until something is picked or it closes.
+    if state.chooser.is_some() {
+        return match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left => {
+                Some(Action::CloseChooser)
+            }
+            KeyCode::Char('j') | KeyCode::Down => Some(Action::ChooserDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(Action::ChooserUp),
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => Some(Action::ChooserConfirm),
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+            _ => None,
+        };
+    }
+
     // Handle an open overlay (git log / diff): keys act on the overlay itself, so
     // l/d switch between views, j/k scroll (diff), and Esc/h close.
     if state.overlay != Overlay::None {
@@ -273,12 +627,49 @@ fn map_event_to_action(event: &Event, state: &AppState) -> Option<Action> {
 
     let in_content = state.focused_pane == FocusedPane::Content;
 
+    // After `'`, the next character is a jump target, not a command. Ctrl+C
+    // still quits; other Ctrl/Alt chords just cancel the jump.
+    if state.pending_jump {
+        let chord = modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        return match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+            KeyCode::Char(c) if !chord => Some(Action::JumpToLetter(*c)),
+            _ => Some(Action::ClearPending),
+        };
+    }
+
     // Normal mode
     match code {
+        KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::ReloadConfig)
+        }
+        KeyCode::Char('X') if state.token_rejected => Some(Action::ResolveToken),
+        // Alt-digit picks an age bucket; plain digits are count prefixes.
+        KeyCode::Char(c @ '1'..='4') if modifiers.contains(KeyModifiers::ALT) => {
+            Some(Action::SelectAgeBucket((*c as u8 - b'1') as usize))
+        }
+        KeyCode::Char(c @ '1'..='9') => Some(Action::CountDigit(*c as u8 - b'0')),
+        KeyCode::Char('0') if state.pending_count.is_some() => Some(Action::CountDigit(0)),
+        KeyCode::Esc if state.pending_count.is_some() => Some(Action::ClearPending),
+        KeyCode::Char('\'') if state.letter_jump && !in_content => Some(Action::StartLetterJump),
+        KeyCode::Char('y') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::CopyPrLink),
+        KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::ToggleAutoRefreshPause)
+        }
+        KeyCode::Char('y') if modifiers.contains(KeyModifiers::ALT) => Some(Action::CopyPrList),
+        KeyCode::Char('g') => Some(Action::JumpTop),
+        KeyCode::Char('G') => Some(Action::JumpBottom),
         KeyCode::Char('q') => Some(Action::Quit),
         KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Char('j') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::NextOwner),
+        KeyCode::Char('k') if modifiers.contains(KeyModifiers::CONTROL) => Some(Action::PrevOwner),
         KeyCode::Char('j') | KeyCode::Down => Some(Action::MoveDown),
         KeyCode::Char('k') | KeyCode::Up => Some(Action::MoveUp),
+        // Shift-arrows scroll the table's columns; plain arrows navigate.
+        KeyCode::Left if modifiers.contains(KeyModifiers::SHIFT) => Some(Action::ScrollColumnsLeft),
+        KeyCode::Right if modifiers.contains(KeyModifiers::SHIFT) => {
+            Some(Action::ScrollColumnsRight)
+        }
         KeyCode::Enter | KeyCode::Right => Some(Action::Select),
         // In the content pane, `l` opens the git-log overlay for the highlighted
         // PR; in the nav tree it keeps its vim-style expand/select meaning.
@@ -290,120 +681,295 @@ fn map_event_to_action(event: &Event, state: &AppState) -> Option<Action> {
         KeyCode::Tab => Some(Action::SwitchPane),
         KeyCode::BackTab => Some(Action::SwitchPane),
         KeyCode::Char('r') => Some(Action::Refresh),
+        KeyCode::Char('R') => Some(Action::RefreshCurrentView),
+        KeyCode::Char('u') => Some(Action::RefreshInbox),
         KeyCode::Char('o') => Some(Action::OpenInBrowser),
+        KeyCode::Char('O') => Some(Action::OpenMenu),
+        KeyCode::Char('U') if in_content && state.content_view == ContentView::Inbox => {
+            Some(Action::OpenUnreadInbox)
+        }
+        KeyCode::Char('b') => Some(Action::OpenRecentViews),
+        KeyCode::Char('T') if in_content => Some(Action::OpenLabelMenu),
         KeyCode::Char('f') => Some(Action::CycleMergeFilter),
+        KeyCode::Char('F') => Some(Action::CycleReviewFilter),
+        KeyCode::Char('I') => Some(Action::ToggleInvolvesMe),
+        KeyCode::Char('e') => Some(Action::ToggleEmptyRepos),
+        KeyCode::Char('n') if in_content => Some(Action::NextMatch),
+        KeyCode::Char('N') if in_content => Some(Action::PrevMatch),
+        KeyCode::Char('s') => Some(Action::CycleSort),
+        KeyCode::Char('i') => Some(Action::CycleInboxSort),
+        KeyCode::Char('L') => Some(Action::ToggleRefreshLock),
+        KeyCode::Char('<') => Some(Action::ScrollColumnsLeft),
+        KeyCode::Char('>') => Some(Action::ScrollColumnsRight),
+        KeyCode::Char('t') if in_content => Some(Action::ShowFullTitle),
+        KeyCode::Char('v') if in_content => Some(Action::TogglePrInfo),
+        KeyCode::Char('a') if in_content => Some(Action::FilterByAuthor),
+        KeyCode::Char('w') if in_content => Some(Action::ToggleWatch),
+        KeyCode::Char('m') if in_content => Some(Action::CyclePrStateFilter),
         KeyCode::Char('?') => Some(Action::ToggleHelp),
         KeyCode::Char('/') => Some(Action::ToggleSearch),
         _ => None,
     }
 }
 
-fn spawn_side_effect(
+/// Carry out `effect`: fetches run as tasks on `queue` against `source` (or
+/// the cache) and report back as actions on `action_tx`.
+pub fn spawn_side_effect(
     effect: SideEffect,
     config: &AppConfig,
-    client: &GithubClient,
+    source: &Arc<dyn DataSource>,
     viewer_login: &str,
     cache_store: &Option<CacheStore>,
     action_tx: &mpsc::UnboundedSender<Action>,
-    semaphore: &Arc<Semaphore>,
+    queue: &TaskQueue,
 ) {
+    let cache_key = cache_key(&effect, config, viewer_login).unwrap_or_default();
+    let probe_owner = matches!(effect, SideEffect::FetchOwnerRepos(_));
     match effect {
-        SideEffect::RefreshAll => {
-            // Invalidate cache so refresh fetches fresh data
+        SideEffect::RefreshView(view) => {
+            let Some(fetch) = fetch_for_view(&view, config) else {
+                debug!(view = ?view, "No view-independent fetch to refresh");
+                return;
+            };
             if let Some(cache) = cache_store
-                && let Err(e) = cache.invalidate_all()
+                && let Some(key) = self::cache_key(&fetch, config, viewer_login)
+            {
+                cache.expire(&key);
+            }
+            spawn_side_effect(
+                fetch,
+                config,
+                source,
+                viewer_login,
+                cache_store,
+                action_tx,
+                queue,
+            );
+        }
+        SideEffect::RefreshAll => {
+            // Expire the cache so refresh fetches fresh data, keeping the old
+            // entries until they're replaced. Offline, the cache is all there
+            // is, so it is left alone.
+            if !source.is_offline()
+                && let Some(cache) = cache_store
             {
-                error!(error = %e, "Failed to invalidate cache on refresh");
+                cache.expire_all();
             }
-            // Spawn org fetches
-            for org in &config.github.orgs {
+            // Spawn repo list fetches, batching the orgs if asked to
+            let owners = config.github.owners();
+            let orgs: Vec<String> = owners
+                .iter()
+                .filter(|o| o.kind == OwnerKind::Org)
+                .map(|o| o.login.clone())
+                .collect();
+            let batched = config.github.batch_queries && orgs.len() > 1;
+            if batched {
                 spawn_side_effect(
-                    SideEffect::FetchOrgRepos(org.clone()),
+                    SideEffect::FetchOrgReposBatched(orgs),
                     config,
-                    client,
+                    source,
                     viewer_login,
                     cache_store,
                     action_tx,
-                    semaphore,
+                    queue,
                 );
             }
-            // Spawn user fetches
-            for user in &config.github.users {
+            for owner in owners
+                .iter()
+                .filter(|o| !(batched && o.kind == OwnerKind::Org))
+            {
                 spawn_side_effect(
-                    SideEffect::FetchUserRepos(user.clone()),
+                    SideEffect::fetch_repos(owner),
                     config,
-                    client,
+                    source,
                     viewer_login,
                     cache_store,
                     action_tx,
-                    semaphore,
+                    queue,
                 );
             }
             // Fetch inbox
             spawn_side_effect(
                 SideEffect::FetchInbox,
                 config,
-                client,
+                source,
                 viewer_login,
                 cache_store,
                 action_tx,
-                semaphore,
+                queue,
             );
             // Fetch all open PRs
             spawn_side_effect(
                 SideEffect::FetchAllOpenPrs,
                 config,
-                client,
+                source,
                 viewer_login,
                 cache_store,
                 action_tx,
-                semaphore,
+                queue,
             );
+            // Fetch recently merged / closed-unmerged PRs
+            for kind in [PrHistoryKind::Merged, PrHistoryKind::Closed] {
+                spawn_side_effect(
+                    SideEffect::FetchPrHistory(kind),
+                    config,
+                    source,
+                    viewer_login,
+                    cache_store,
+                    action_tx,
+                    queue,
+                );
+            }
+        }
+        SideEffect::FetchOrgReposBatched(orgs) => {
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let sem = queue.clone();
+            let cache = cache_store.clone();
+            let github = config.github.clone();
+            let orgs: Vec<(String, String)> = orgs
+                .into_iter()
+                .map(|org| {
+                    let key = self::cache_key(
+                        &SideEffect::FetchOrgRepos(org.clone()),
+                        config,
+                        viewer_login,
+                    )
+                    .unwrap_or_default();
+                    (org, key)
+                })
+                .collect();
+
+            tokio::spawn(async move {
+                let _permit = sem.acquire().await;
+                let deliver = |org: String, repos: Vec<crate::github::Repo>, rate_limit| {
+                    let repos = filter_repos(
+                        repos,
+                        &github.include_repos,
+                        &github.exclude_repos,
+                        &github.include_topics,
+                        &github.exclude_topics,
+                    );
+                    let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
+                        org,
+                        repos,
+                        rate_limit,
+                    }));
+                };
+
+                // Cached orgs are served straight away; only the rest are fetched.
+                let mut uncached = Vec::new();
+                for (org, key) in orgs {
+                    match cached::<Vec<crate::github::Repo>>(&cache, &key, source.is_offline(), &tx)
+                    {
+                        Some(repos) => deliver(org, repos, None),
+                        None if serve_negative(&cache, &key, &org, &tx) => {}
+                        None => uncached.push((org, key)),
+                    }
+                }
+                if uncached.is_empty() {
+                    return;
+                }
+                debug!(orgs = uncached.len(), "Fetching org repos in a batch");
+
+                let names: Vec<String> = uncached.iter().map(|(org, _)| org.clone()).collect();
+                let results = match source.fetch_orgs_batched(&names).await {
+                    Ok((repos, rate_limit)) => repos
+                        .into_iter()
+                        .map(|repos| Ok((repos, rate_limit.clone())))
+                        .collect(),
+                    // One bad org fails the whole batch; fetch them one by one
+                    // so the others still load and the failure names the org.
+                    Err(e) => {
+                        debug!(error = %e, "Batched org fetch failed, fetching orgs one by one");
+                        let mut results = Vec::new();
+                        for org in &names {
+                            results.push(source.fetch_org_repos(org).await);
+                        }
+                        results
+                    }
+                };
+                for ((org, key), result) in uncached.into_iter().zip(results) {
+                    cache_repos_result(&cache, &key, &result);
+                    match result {
+                        Ok((repos, rate_limit)) => {
+                            deliver(org, repos, rate_limit);
+                        }
+                        Err(e) => {
+                            error!(org = %org, error = %e, "Failed to fetch org repos");
+                            let _ = tx.send(Action::FetchFailed {
+                                view: ContentView::OrgOverview(org.clone()),
+                                msg: format!("Failed to fetch repos for {}: {}", org, e),
+                            });
+                        }
+                    }
+                }
+            });
         }
-        SideEffect::FetchOrgRepos(org) => {
-            let client = client.clone();
+        SideEffect::FetchOrgRepos(org) | SideEffect::FetchOwnerRepos(org) => {
+            let source = source.clone();
             let tx = action_tx.clone();
-            let sem = semaphore.clone();
+            let sem = queue.clone();
             let cache = cache_store.clone();
             let include_repos = config.github.include_repos.clone();
             let exclude_repos = config.github.exclude_repos.clone();
+            let include_topics = config.github.include_topics.clone();
+            let exclude_topics = config.github.exclude_topics.clone();
             let org_clone = org.clone();
 
-            // Mark org as loading via action
-            let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
-                org: org.clone(),
-                repos: Vec::new(),
-                rate_limit: crate::github::RateLimit::default(),
-            }));
-
             tokio::spawn(async move {
                 let _permit = sem.acquire().await;
                 debug!(org = %org_clone, "Fetching org repos");
 
                 // Check cache
-                let cache_key = format!("org_repos_{}", org_clone);
-                if let Some(ref cache) = cache
-                    && let Some(repos) = cache.get::<Vec<crate::github::Repo>>(&cache_key)
+                if let Some(repos) =
+                    cached::<Vec<crate::github::Repo>>(&cache, &cache_key, source.is_offline(), &tx)
                 {
-                    let filtered = filter_repos(repos, &include_repos, &exclude_repos);
+                    let filtered = filter_repos(
+                        repos,
+                        &include_repos,
+                        &exclude_repos,
+                        &include_topics,
+                        &exclude_topics,
+                    );
                     let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
                         org: org_clone,
                         repos: filtered,
-                        rate_limit: crate::github::RateLimit::default(),
+                        rate_limit: None,
                     }));
                     return;
                 }
+                if serve_negative(&cache, &cache_key, &org_clone, &tx) {
+                    return;
+                }
 
-                match client.fetch_org_repos(&org_clone).await {
+                let kind = if probe_owner {
+                    resolve_owner_kind(source.as_ref(), &cache, &org_clone).await
+                } else {
+                    Ok(Some(OwnerKind::Org))
+                };
+                let result = match kind {
+                    Ok(Some(OwnerKind::User)) => source.fetch_user_repos(&org_clone).await,
+                    Ok(Some(_)) => source.fetch_org_repos(&org_clone).await,
+                    Ok(None) => {
+                        warn!(owner = %org_clone, "No GitHub user or organization by this name");
+                        let _ = tx.send(Action::DataLoaded(DataPayload::OwnerNotFound {
+                            owner: org_clone,
+                        }));
+                        return;
+                    }
+                    Err(e) => Err(e),
+                };
+                cache_repos_result(&cache, &cache_key, &result);
+                match result {
                     Ok((repos, rate_limit)) => {
-                        // Cache the raw repos
-                        if let Some(ref cache) = cache
-                            && let Err(e) = cache.set(&cache_key, &repos)
-                        {
-                            error!(error = %e, "Failed to cache org repos");
-                        }
-
-                        let filtered = filter_repos(repos, &include_repos, &exclude_repos);
+                        let filtered = filter_repos(
+                            repos,
+                            &include_repos,
+                            &exclude_repos,
+                            &include_topics,
+                            &exclude_topics,
+                        );
                         let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
                             org: org_clone,
                             repos: filtered,
@@ -412,56 +978,61 @@ fn spawn_side_effect(
                     }
                     Err(e) => {
                         error!(org = %org_clone, error = %e, "Failed to fetch org repos");
-                        let _ = tx.send(Action::LoadError(format!(
-                            "Failed to fetch repos for {}: {}",
-                            org_clone, e
-                        )));
+                        let _ = tx.send(Action::FetchFailed {
+                            view: ContentView::OrgOverview(org_clone.clone()),
+                            msg: format!("Failed to fetch repos for {}: {}", org_clone, e),
+                        });
                     }
                 }
             });
         }
         SideEffect::FetchUserRepos(user) => {
-            let client = client.clone();
+            let source = source.clone();
             let tx = action_tx.clone();
-            let sem = semaphore.clone();
+            let sem = queue.clone();
             let cache = cache_store.clone();
             let include_repos = config.github.include_repos.clone();
             let exclude_repos = config.github.exclude_repos.clone();
+            let include_topics = config.github.include_topics.clone();
+            let exclude_topics = config.github.exclude_topics.clone();
             let user_clone = user.clone();
 
-            // Mark user as loading via action (reuse OrgRepos payload)
-            let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
-                org: user.clone(),
-                repos: Vec::new(),
-                rate_limit: crate::github::RateLimit::default(),
-            }));
-
             tokio::spawn(async move {
                 let _permit = sem.acquire().await;
                 debug!(user = %user_clone, "Fetching user repos");
 
-                let cache_key = format!("user_repos_{}", user_clone);
-                if let Some(ref cache) = cache
-                    && let Some(repos) = cache.get::<Vec<crate::github::Repo>>(&cache_key)
+                if let Some(repos) =
+                    cached::<Vec<crate::github::Repo>>(&cache, &cache_key, source.is_offline(), &tx)
                 {
-                    let filtered = filter_repos(repos, &include_repos, &exclude_repos);
+                    let filtered = filter_repos(
+                        repos,
+                        &include_repos,
+                        &exclude_repos,
+                        &include_topics,
+                        &exclude_topics,
+                    );
                     let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
                         org: user_clone,
                         repos: filtered,
-                        rate_limit: crate::github::RateLimit::default(),
+                        rate_limit: None,
                     }));
                     return;
                 }
+                if serve_negative(&cache, &cache_key, &user_clone, &tx) {
+                    return;
+                }
 
-                match client.fetch_user_repos(&user_clone).await {
+                let result = source.fetch_user_repos(&user_clone).await;
+                cache_repos_result(&cache, &cache_key, &result);
+                match result {
                     Ok((repos, rate_limit)) => {
-                        if let Some(ref cache) = cache
-                            && let Err(e) = cache.set(&cache_key, &repos)
-                        {
-                            error!(error = %e, "Failed to cache user repos");
-                        }
-
-                        let filtered = filter_repos(repos, &include_repos, &exclude_repos);
+                        let filtered = filter_repos(
+                            repos,
+                            &include_repos,
+                            &exclude_repos,
+                            &include_topics,
+                            &exclude_topics,
+                        );
                         let _ = tx.send(Action::DataLoaded(DataPayload::OrgRepos {
                             org: user_clone,
                             repos: filtered,
@@ -470,37 +1041,40 @@ fn spawn_side_effect(
                     }
                     Err(e) => {
                         error!(user = %user_clone, error = %e, "Failed to fetch user repos");
-                        let _ = tx.send(Action::LoadError(format!(
-                            "Failed to fetch repos for {}: {}",
-                            user_clone, e
-                        )));
+                        let _ = tx.send(Action::FetchFailed {
+                            view: ContentView::OrgOverview(user_clone.clone()),
+                            msg: format!("Failed to fetch repos for {}: {}", user_clone, e),
+                        });
                     }
                 }
             });
         }
         SideEffect::FetchInbox => {
-            let client = client.clone();
+            let source = source.clone();
             let tx = action_tx.clone();
-            let sem = semaphore.clone();
+            let sem = queue.clone();
             let cache = cache_store.clone();
             let login = viewer_login.to_string();
+            let teams = config.github.teams.clone();
 
             tokio::spawn(async move {
                 let _permit = sem.acquire().await;
                 debug!("Fetching inbox");
 
-                let cache_key = format!("inbox_{}", login);
-                if let Some(ref cache) = cache
-                    && let Some(prs) = cache.get::<Vec<crate::github::PullRequest>>(&cache_key)
-                {
+                if let Some(prs) = cached::<Vec<crate::github::PullRequest>>(
+                    &cache,
+                    &cache_key,
+                    source.is_offline(),
+                    &tx,
+                ) {
                     let _ = tx.send(Action::DataLoaded(DataPayload::InboxPrs {
                         prs,
-                        rate_limit: crate::github::RateLimit::default(),
+                        rate_limit: None,
                     }));
                     return;
                 }
 
-                match client.fetch_inbox(&login).await {
+                match source.fetch_inbox(&login, &teams).await {
                     Ok((prs, rate_limit)) => {
                         if let Some(ref cache) = cache
                             && let Err(e) = cache.set(&cache_key, &prs)
@@ -514,35 +1088,40 @@ fn spawn_side_effect(
                     }
                     Err(e) => {
                         error!(error = %e, "Failed to fetch inbox");
-                        let _ = tx.send(Action::LoadError(format!("Failed to fetch inbox: {}", e)));
+                        let _ = tx.send(Action::FetchFailed {
+                            view: ContentView::Inbox,
+                            msg: format!("Failed to fetch inbox: {}", e),
+                        });
                     }
                 }
             });
         }
         SideEffect::FetchAllOpenPrs => {
-            let client = client.clone();
+            let source = source.clone();
             let tx = action_tx.clone();
-            let sem = semaphore.clone();
+            let sem = queue.clone();
             let cache = cache_store.clone();
-            let orgs = config.github.orgs.clone();
-            let users = config.github.users.clone();
+            let (orgs, users) = config.github.search_scope();
+            let repos = config.github.standalone_repos();
 
             tokio::spawn(async move {
                 let _permit = sem.acquire().await;
                 debug!("Fetching all open PRs");
 
-                let cache_key = "all_open_prs".to_string();
-                if let Some(ref cache) = cache
-                    && let Some(prs) = cache.get::<Vec<crate::github::PullRequest>>(&cache_key)
-                {
+                if let Some(prs) = cached::<Vec<crate::github::PullRequest>>(
+                    &cache,
+                    &cache_key,
+                    source.is_offline(),
+                    &tx,
+                ) {
                     let _ = tx.send(Action::DataLoaded(DataPayload::AllOpenPrs {
                         prs,
-                        rate_limit: crate::github::RateLimit::default(),
+                        rate_limit: None,
                     }));
                     return;
                 }
 
-                match client.fetch_all_open_prs(&orgs, &users).await {
+                match source.fetch_all_open_prs(&orgs, &users, &repos).await {
                     Ok((prs, rate_limit)) => {
                         if let Some(ref cache) = cache
                             && let Err(e) = cache.set(&cache_key, &prs)
@@ -556,29 +1135,317 @@ fn spawn_side_effect(
                     }
                     Err(e) => {
                         error!(error = %e, "Failed to fetch all open PRs");
-                        let _ = tx.send(Action::LoadError(format!(
-                            "Failed to fetch all open PRs: {}",
+                        let _ = tx.send(Action::FetchFailed {
+                            view: ContentView::AllOpenPrs,
+                            msg: format!("Failed to fetch all open PRs: {}", e),
+                        });
+                    }
+                }
+            });
+        }
+        SideEffect::FetchInvolvedPrs => {
+            // Offline there's nothing to search; the filter makes do with the
+            // loaded PRs.
+            if source.is_offline() {
+                return;
+            }
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let sem = queue.clone();
+            let login = viewer_login.to_string();
+            let (orgs, users) = config.github.search_scope();
+            let repos = config.github.standalone_repos();
+
+            tokio::spawn(async move {
+                let _permit = sem.acquire().await;
+                debug!("Fetching PRs involving the viewer");
+
+                match source
+                    .fetch_involved_prs(&login, &orgs, &users, &repos)
+                    .await
+                {
+                    Ok((prs, rate_limit)) => {
+                        let _ = tx.send(Action::DataLoaded(DataPayload::InvolvedPrs {
+                            prs,
+                            rate_limit,
+                        }));
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Failed to fetch PRs involving the viewer");
+                        let _ = tx.send(Action::DataLoaded(DataPayload::InvolvedPrsFailed {
+                            msg: format!("Failed to search for PRs involving you: {}", e),
+                        }));
+                    }
+                }
+            });
+        }
+        SideEffect::FetchPrHistory(kind) => {
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let sem = queue.clone();
+            let cache = cache_store.clone();
+            let (orgs, users) = config.github.search_scope();
+            let repos = config.github.standalone_repos();
+            let window_days = match kind {
+                PrHistoryKind::Merged => config.dashboard.merged_window_days,
+                PrHistoryKind::Closed => config.dashboard.closed_window_days,
+            };
+
+            tokio::spawn(async move {
+                let _permit = sem.acquire().await;
+                debug!(kind = kind.as_str(), "Fetching PR history");
+
+                if let Some(prs) = cached::<Vec<crate::github::PullRequest>>(
+                    &cache,
+                    &cache_key,
+                    source.is_offline(),
+                    &tx,
+                ) {
+                    let _ = tx.send(Action::DataLoaded(DataPayload::PrHistory {
+                        kind,
+                        prs,
+                        rate_limit: None,
+                    }));
+                    return;
+                }
+
+                match source
+                    .fetch_pr_history(kind, &orgs, &users, &repos, window_days)
+                    .await
+                {
+                    Ok((prs, rate_limit)) => {
+                        if let Some(ref cache) = cache
+                            && let Err(e) = cache.set(&cache_key, &prs)
+                        {
+                            error!(error = %e, "Failed to cache PR history");
+                        }
+                        let _ = tx.send(Action::DataLoaded(DataPayload::PrHistory {
+                            kind,
+                            prs,
+                            rate_limit,
+                        }));
+                    }
+                    Err(e) => {
+                        error!(kind = kind.as_str(), error = %e, "Failed to fetch PR history");
+                        let view = match kind {
+                            PrHistoryKind::Merged => ContentView::RecentlyMerged,
+                            PrHistoryKind::Closed => ContentView::RecentlyClosed,
+                        };
+                        let _ = tx.send(Action::FetchFailed {
+                            view,
+                            msg: format!("Failed to fetch recently {} PRs: {}", kind.as_str(), e),
+                        });
+                    }
+                }
+            });
+        }
+        SideEffect::RefreshRepoPrs {
+            owner,
+            name,
+            cancel,
+        } => {
+            let fetch = SideEffect::PrefetchRepoPrs {
+                owner,
+                name,
+                cancel,
+            };
+            if let Some(cache) = cache_store
+                && let Some(key) = self::cache_key(&fetch, config, viewer_login)
+            {
+                cache.expire(&key);
+            }
+            spawn_side_effect(
+                fetch,
+                config,
+                source,
+                viewer_login,
+                cache_store,
+                action_tx,
+                queue,
+            );
+        }
+        SideEffect::PrefetchRepoPrs {
+            owner,
+            name,
+            cancel,
+        } => {
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let sem = queue.clone();
+            let cache = cache_store.clone();
+
+            tokio::spawn(async move {
+                let _permit = sem.acquire().await;
+                // The view was left while this waited for a slot.
+                if cancel.is_cancelled() {
+                    return;
+                }
+                debug!(owner = %owner, name = %name, "Prefetching repo PRs");
+
+                if let Some(prs) = cached::<Vec<crate::github::PullRequest>>(
+                    &cache,
+                    &cache_key,
+                    source.is_offline(),
+                    &tx,
+                ) {
+                    let _ = tx.send(Action::DataLoaded(DataPayload::RepoPrs {
+                        owner,
+                        name,
+                        prs,
+                        rate_limit: None,
+                        partial: false,
+                        cancel,
+                    }));
+                    return;
+                }
+
+                match source.fetch_repo_prs(&owner, &name, &cancel).await {
+                    Ok((prs, rate_limit)) => {
+                        // Pages fetched before the view was left are still
+                        // shown, but aren't cached as the whole list.
+                        let partial = cancel.is_cancelled();
+                        if partial {
+                            debug!(owner = %owner, name = %name, count = prs.len(), "Repo PR fetch cancelled");
+                        } else if let Some(ref cache) = cache
+                            && let Err(e) = cache.set(&cache_key, &prs)
+                        {
+                            error!(error = %e, "Failed to cache repo PRs");
+                        }
+                        let _ = tx.send(Action::DataLoaded(DataPayload::RepoPrs {
+                            owner,
+                            name,
+                            prs,
+                            rate_limit,
+                            partial,
+                            cancel,
+                        }));
+                    }
+                    Err(e) => {
+                        // Best-effort: the repo view falls back to `all_open_prs`.
+                        error!(owner = %owner, name = %name, error = %e, "Failed to prefetch repo PRs");
+                        let _ = tx.send(Action::DataLoaded(DataPayload::RepoPrsFailed {
+                            owner,
+                            name,
+                            cancel,
+                        }));
+                    }
+                }
+            });
+        }
+        SideEffect::FetchRepoFinishedPrs {
+            owner,
+            name,
+            state,
+            cancel,
+        } => {
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let sem = queue.clone();
+
+            tokio::spawn(async move {
+                let _permit = sem.acquire().await;
+                if cancel.is_cancelled() {
+                    return;
+                }
+                debug!(owner = %owner, name = %name, state = state.graphql_state(), "Fetching repo PRs");
+
+                match source
+                    .fetch_repo_prs_in_state(&owner, &name, state, &cancel)
+                    .await
+                {
+                    Ok((prs, rate_limit)) => {
+                        let _ = tx.send(Action::DataLoaded(DataPayload::RepoFinishedPrs {
+                            owner,
+                            name,
+                            state,
+                            prs,
+                            rate_limit,
+                            cancel,
+                        }));
+                    }
+                    Err(e) => {
+                        error!(owner = %owner, name = %name, error = %e, "Failed to fetch repo PRs");
+                        let msg = format!(
+                            "Failed to fetch {} PRs for {}/{}: {}",
+                            state.label().unwrap_or("open"),
+                            owner,
+                            name,
                             e
-                        )));
+                        );
+                        let _ = tx.send(Action::DataLoaded(DataPayload::RepoFinishedPrsFailed {
+                            owner,
+                            name,
+                            state,
+                            msg,
+                            cancel,
+                        }));
                     }
                 }
             });
         }
+        SideEffect::FetchWatchedPr(watched) => {
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let sem = queue.clone();
+
+            tokio::spawn(async move {
+                let _permit = sem.acquire().await;
+                debug!(owner = %watched.owner, name = %watched.name, number = watched.number, "Fetching watched PR");
+
+                match source
+                    .fetch_pr(&watched.owner, &watched.name, watched.number)
+                    .await
+                {
+                    Ok((pr, rate_limit)) => {
+                        let _ = tx.send(Action::DataLoaded(DataPayload::WatchedPr {
+                            watched,
+                            pr: Box::new(pr),
+                            rate_limit,
+                        }));
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Failed to fetch watched PR");
+                        let _ = tx.send(Action::FetchFailed {
+                            view: ContentView::Watched,
+                            msg: format!(
+                                "Failed to fetch watched PR {}/{}#{}: {}",
+                                watched.owner, watched.name, watched.number, e
+                            ),
+                        });
+                    }
+                }
+            });
+        }
+        SideEffect::SavePrHistory(_) | SideEffect::SaveWatchlist(_) if source.is_fixture() => {}
+        SideEffect::SavePrHistory(history) => {
+            save_in_order(config.pr_history_path(), move |path| {
+                if let Err(e) = history.save(path) {
+                    error!(error = %e, "Failed to save PR history");
+                }
+            });
+        }
+        SideEffect::SaveWatchlist(prs) => {
+            save_in_order(config.watchlist_path(), move |path| {
+                if let Err(e) = (Watchlist { prs }).save(path) {
+                    error!(error = %e, "Failed to save watchlist");
+                }
+            });
+        }
         SideEffect::FetchPrDetail {
             owner,
             name,
             number,
             key,
         } => {
-            let client = client.clone();
+            let source = source.clone();
             let tx = action_tx.clone();
-            let sem = semaphore.clone();
+            let sem = queue.clone();
 
             tokio::spawn(async move {
                 let _permit = sem.acquire().await;
                 debug!(owner = %owner, name = %name, number = number, "Fetching PR detail");
 
-                match client.fetch_pr_detail(&owner, &name, number).await {
+                match source.fetch_pr_detail(&owner, &name, number).await {
                     Ok((detail, rate_limit)) => {
                         let _ = tx.send(Action::DataLoaded(DataPayload::PrDetailLoaded {
                             key,
@@ -602,15 +1469,15 @@ fn spawn_side_effect(
             number,
             key,
         } => {
-            let client = client.clone();
+            let source = source.clone();
             let tx = action_tx.clone();
-            let sem = semaphore.clone();
+            let sem = queue.clone();
 
             tokio::spawn(async move {
                 let _permit = sem.acquire().await;
                 debug!(owner = %owner, name = %name, number = number, "Fetching PR diff");
 
-                match client.fetch_pr_diff(&owner, &name, number).await {
+                match source.fetch_pr_diff(&owner, &name, number).await {
                     Ok(diff) => {
                         let _ =
                             tx.send(Action::DataLoaded(DataPayload::PrDiffLoaded { key, diff }));
@@ -625,6 +1492,98 @@ fn spawn_side_effect(
                 }
             });
         }
+        SideEffect::CopyToClipboard(text) => {
+            if let Err(e) = crate::util::clipboard::copy(&text) {
+                error!(error = %e, "Failed to copy to clipboard");
+            }
+        }
+        SideEffect::VerifyViewer {
+            expected,
+            cache_key,
+        } => {
+            let source = source.clone();
+            let tx = action_tx.clone();
+            let cache = cache_store.clone();
+
+            tokio::spawn(async move {
+                match source.fetch_viewer().await {
+                    Ok(login) if login == expected => {
+                        debug!(login = %login, "Cached viewer login verified");
+                    }
+                    Ok(login) => {
+                        warn!(cached = %expected, actual = %login, "Cached viewer login is stale");
+                        if let Some(cache) = &cache {
+                            for key in [cache_key.as_str(), VIEWER_CACHE_KEY] {
+                                if let Err(e) = cache.set(key, &login) {
+                                    error!(error = %e, key = %key, "Failed to cache viewer login");
+                                }
+                            }
+                        }
+                        let _ = tx.send(Action::ViewerChanged(login));
+                    }
+                    // Startup skipped its reachability check for the cached
+                    // login; fall back to the cache as it would have.
+                    Err(e) if is_network_error(&e) => {
+                        warn!(error = %e, "GitHub unreachable, falling back to offline mode");
+                        let _ = tx.send(Action::WentOffline);
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Failed to verify cached viewer login");
+                        if let Some(cache) = &cache
+                            && let Err(e) = cache.invalidate(&cache_key)
+                        {
+                            error!(error = %e, "Failed to invalidate cached viewer login");
+                        }
+                        let _ = tx.send(match failure_kind(&e) {
+                            FailureKind::Unauthorized => Action::TokenRejected,
+                            _ => Action::LoadError(format!(
+                                "Failed to verify the signed-in user: {:#}",
+                                e
+                            )),
+                        });
+                    }
+                }
+            });
+        }
+        SideEffect::CheckForUpdate => {
+            if source.is_offline() || source.is_fixture() {
+                return;
+            }
+            let path = config.update_check_path();
+            let tx = action_tx.clone();
+
+            tokio::spawn(async move {
+                let now = time::now();
+                let due = tokio::task::spawn_blocking(move || {
+                    let mut check = UpdateCheck::load(&path);
+                    if !check.is_due(now) {
+                        return false;
+                    }
+                    // Recorded up front so a failing check isn't retried every start.
+                    check.last_checked = Some(now);
+                    if let Err(e) = check.save(&path) {
+                        warn!(error = %e, "Failed to save update check time");
+                    }
+                    true
+                })
+                .await
+                .unwrap_or(false);
+                if !due {
+                    return;
+                }
+                match update_check::fetch_latest_version().await {
+                    Ok(tag) => {
+                        debug!(latest = %tag, "Checked for updates");
+                        if let Some(version) =
+                            update_check::newer_version(&tag, env!("CARGO_PKG_VERSION"))
+                        {
+                            let _ = tx.send(Action::UpdateAvailable(version));
+                        }
+                    }
+                    Err(e) => debug!(error = %e, "Update check failed"),
+                }
+            });
+        }
         SideEffect::OpenUrl(url) => {
             tokio::task::spawn_blocking(move || {
                 if let Err(e) = crate::util::browser::open_url(&url) {
@@ -635,10 +1594,229 @@ fn spawn_side_effect(
     }
 }
 
-fn filter_repos(
+/// Answer a repo list fetch for `owner` from a negative cache entry: an
+/// owner known to have no repos gets an empty list, one known to be off
+/// limits a failure. `false` when there's no live marker.
+fn serve_negative(
+    cache: &Option<CacheStore>,
+    key: &str,
+    owner: &str,
+    tx: &mpsc::UnboundedSender<Action>,
+) -> bool {
+    let Some(cache) = cache else {
+        return false;
+    };
+    let Some(negative) = cache.get_negative(key) else {
+        return false;
+    };
+    let _ = tx.send(match negative {
+        Negative::Empty => Action::DataLoaded(DataPayload::OrgRepos {
+            org: owner.to_string(),
+            repos: Vec::new(),
+            rate_limit: None,
+        }),
+        Negative::NoAccess => Action::FetchFailed {
+            view: ContentView::OrgOverview(owner.to_string()),
+            msg: format!(
+                "No access to {}; not retrying for {}s",
+                owner,
+                cache.negative_ttl_secs()
+            ),
+        },
+    });
+    true
+}
+
+/// Cache how a repo list fetch went: the raw repos, or a negative marker if
+/// there were none or GitHub refused. Other failures aren't cached.
+fn cache_repos_result(
+    cache: &Option<CacheStore>,
+    key: &str,
+    result: &Result<(Vec<crate::github::Repo>, Option<RateLimit>)>,
+) {
+    let Some(cache) = cache else {
+        return;
+    };
+    let stored = match result {
+        Ok((repos, _)) if repos.is_empty() => cache.set_negative(key, Negative::Empty),
+        Ok((repos, _)) => cache.set(key, repos),
+        Err(e) if is_no_access(e) => cache.set_negative(key, Negative::NoAccess),
+        Err(_) => return,
+    };
+    if let Err(e) = stored {
+        error!(error = %e, key = %key, "Failed to cache repos");
+    }
+}
+
+/// `login`'s account type: as resolved before, else asked of `source` and
+/// cached. Owners that don't exist aren't cached, so a fixed typo or a new
+/// account is picked up on the next fetch.
+async fn resolve_owner_kind(
+    source: &dyn DataSource,
+    cache: &Option<CacheStore>,
+    login: &str,
+) -> anyhow::Result<Option<OwnerKind>> {
+    let key = format!("owner_kind_{}", login);
+    if let Some((kind, _)) = cache
+        .as_ref()
+        .and_then(|c| c.get_ignoring_ttl::<OwnerKind>(&key))
+    {
+        return Ok(Some(kind));
+    }
+    let (kind, _) = source.fetch_owner_kind(login).await?;
+    if let (Some(cache), Some(kind)) = (cache, kind)
+        && let Err(e) = cache.set(&key, &kind)
+    {
+        error!(error = %e, key = %key, "Failed to cache owner type");
+    }
+    Ok(kind)
+}
+
+/// Cache lookup for a fetch. Online, expired entries are misses; offline, any
+/// entry is used and its write time is reported so the status bar can say how
+/// old the data is.
+fn cached<T: serde::de::DeserializeOwned>(
+    cache: &Option<CacheStore>,
+    key: &str,
+    offline: bool,
+    tx: &mpsc::UnboundedSender<Action>,
+) -> Option<T> {
+    let cache = cache.as_ref()?;
+    if !offline {
+        return cache.get(key);
+    }
+    let (data, written_at) = cache.get_ignoring_ttl(key)?;
+    let _ = tx.send(Action::CachedDataAge(written_at));
+    Some(data)
+}
+
+/// Cache key a fetch side effect reads and writes; `None` for effects that
+/// aren't cached.
+pub fn cache_key(effect: &SideEffect, config: &AppConfig, viewer_login: &str) -> Option<String> {
+    match effect {
+        // v2: repos carry their topics. Older entries have none, so topic
+        // filters would drop every repo until the next fetch.
+        SideEffect::FetchOrgRepos(org) => Some(format!("org_repos_v2_{}", org)),
+        SideEffect::FetchUserRepos(user) => Some(format!("user_repos_v2_{}", user)),
+        SideEffect::FetchOwnerRepos(owner) => Some(format!("owner_repos_v2_{}", owner)),
+        // PR keys are v2 too: PRs gained review state, comment and review
+        // counts, assignees and closed_at, which older entries lack.
+        // Teams change what the inbox contains, so they're part of the key.
+        SideEffect::FetchInbox if config.github.teams.is_empty() => {
+            Some(format!("inbox_v2_{}", viewer_login))
+        }
+        SideEffect::FetchInbox => Some(format!(
+            "inbox_v2_{}_{}",
+            viewer_login,
+            config.github.teams.join("+")
+        )),
+        SideEffect::FetchAllOpenPrs => Some("all_open_prs_v2".to_string()),
+        SideEffect::FetchPrHistory(kind) => {
+            let window_days = match kind {
+                PrHistoryKind::Merged => config.dashboard.merged_window_days,
+                PrHistoryKind::Closed => config.dashboard.closed_window_days,
+            };
+            Some(format!("pr_history_v2_{}_{}", kind.as_str(), window_days))
+        }
+        SideEffect::PrefetchRepoPrs { owner, name, .. } => {
+            Some(format!("repo_prs_v2_{}/{}", owner, name))
+        }
+        _ => None,
+    }
+}
+
+/// Fill `state` from the cache, ignoring TTL, so the first frame isn't empty
+/// while the initial fetch runs. Keys come from [`cache_key`], so they always
+/// match what `spawn_side_effect` writes. Returns whether anything was found;
+/// if so the state is marked stale until the first refresh lands.
+pub fn hydrate_from_cache(state: &mut AppState, cache: &CacheStore, config: &AppConfig) -> bool {
+    let viewer_login = state.viewer_login.clone();
+    let key = |effect: SideEffect| cache_key(&effect, config, &viewer_login).unwrap_or_default();
+    let mut found = false;
+
+    for owner in config.github.owners() {
+        if let Some((repos, _)) = cache.get_ignoring_ttl(&key(SideEffect::fetch_repos(&owner))) {
+            let repos = filter_repos(
+                repos,
+                &config.github.include_repos,
+                &config.github.exclude_repos,
+                &config.github.include_topics,
+                &config.github.exclude_topics,
+            );
+            state.orgs.insert(
+                owner.login.clone(),
+                OrgData {
+                    name: owner.login,
+                    repos,
+                },
+            );
+            found = true;
+        }
+    }
+
+    if let Some((prs, _)) = cache.get_ignoring_ttl(&key(SideEffect::FetchInbox)) {
+        state.inbox = prs;
+        state.sort_inbox();
+        state.rebuild_inbox_keys();
+        found = true;
+    }
+    if let Some((prs, _)) = cache.get_ignoring_ttl(&key(SideEffect::FetchAllOpenPrs)) {
+        state.all_open_prs = prs;
+        found = true;
+    }
+    for kind in [PrHistoryKind::Merged, PrHistoryKind::Closed] {
+        if let Some((prs, _)) = cache.get_ignoring_ttl(&key(SideEffect::FetchPrHistory(kind))) {
+            match kind {
+                PrHistoryKind::Merged => state.recently_merged = prs,
+                PrHistoryKind::Closed => state.recently_closed = prs,
+            }
+            found = true;
+        }
+    }
+
+    if found {
+        state.stale = true;
+        state.rebuild_nav_tree();
+    }
+    found
+}
+
+/// How long to sleep until `at`; a day when nothing is scheduled.
+fn until(at: Option<chrono::DateTime<chrono::Utc>>) -> tokio::time::Duration {
+    at.map_or(tokio::time::Duration::from_secs(86_400), |at| {
+        (at - time::now()).to_std().unwrap_or_default()
+    })
+}
+
+/// The fetch whose result backs `view`, used to refresh just that view. A
+/// repo's list has none: it is refreshed with [`SideEffect::RefreshRepoPrs`]
+/// under its view's token.
+pub fn fetch_for_view(view: &ContentView, config: &AppConfig) -> Option<SideEffect> {
+    Some(match view {
+        ContentView::Inbox => SideEffect::FetchInbox,
+        ContentView::AllOpenPrs => SideEffect::FetchAllOpenPrs,
+        ContentView::RecentlyMerged => SideEffect::FetchPrHistory(PrHistoryKind::Merged),
+        ContentView::RecentlyClosed => SideEffect::FetchPrHistory(PrHistoryKind::Closed),
+        ContentView::FailingChecks => SideEffect::FetchAllOpenPrs,
+        // The reducer re-fetches watched PRs one by one; this only covers the
+        // ones found in the All PRs list.
+        ContentView::Watched => SideEffect::FetchAllOpenPrs,
+        ContentView::RepoPrList { .. } => return None,
+        ContentView::OrgOverview(owner) => SideEffect::fetch_repos(&Owner {
+            login: owner.clone(),
+            kind: config.github.owner_kind(owner).unwrap_or(OwnerKind::Org),
+        }),
+    })
+}
+
+/// Apply the configured name globs and topic lists. Topics compare
+/// case-insensitively; empty lists don't filter anything.
+pub fn filter_repos(
     repos: Vec<crate::github::Repo>,
     include_patterns: &[String],
     exclude_patterns: &[String],
+    include_topics: &[String],
+    exclude_topics: &[String],
 ) -> Vec<crate::github::Repo> {
     repos
         .into_iter()
@@ -647,57 +1825,25 @@ fn filter_repos(
             let name = &repo.name;
 
             // If include patterns specified, repo must match at least one
-            if !include_patterns.is_empty() {
-                let matches = include_patterns
-                    .iter()
-                    .any(|pattern| glob_match(pattern, &full_name) || glob_match(pattern, name));
-                if !matches {
-                    return false;
-                }
+            if !include_patterns.is_empty() && !repo_matches(include_patterns, &full_name, name) {
+                return false;
             }
 
             // If exclude patterns specified, repo must not match any
-            if !exclude_patterns.is_empty() {
-                let excluded = exclude_patterns
-                    .iter()
-                    .any(|pattern| glob_match(pattern, &full_name) || glob_match(pattern, name));
-                if excluded {
-                    return false;
-                }
+            if repo_matches(exclude_patterns, &full_name, name) {
+                return false;
+            }
+
+            let has_topic =
+                |wanted: &String| repo.topics.iter().any(|t| t.eq_ignore_ascii_case(wanted));
+            if !include_topics.is_empty() && !include_topics.iter().any(has_topic) {
+                return false;
+            }
+            if exclude_topics.iter().any(has_topic) {
+                return false;
             }
 
             true
         })
         .collect()
 }
-
-fn glob_match(pattern: &str, text: &str) -> bool {
-    // Simple glob matching: * matches any sequence
-    let parts: Vec<&str> = pattern.split('*').collect();
-    if parts.len() == 1 {
-        return pattern == text;
-    }
-
-    let mut pos = 0;
-    for (i, part) in parts.iter().enumerate() {
-        if part.is_empty() {
-            continue;
-        }
-        match text[pos..].find(part) {
-            Some(idx) => {
-                if i == 0 && idx != 0 {
-                    return false;
-                }
-                pos += idx + part.len();
-            }
-            None => return false,
-        }
-    }
-
-    // If the pattern doesn't end with *, the text must end at pos
-    if !pattern.ends_with('*') {
-        return pos == text.len();
-    }
-
-    true
-}
@@ -1,5 +1,7 @@
 pub mod actions;
 pub mod event_loop;
+pub mod scheduler;
+pub mod session;
 pub mod state;
 pub mod update;
 pub mod view;
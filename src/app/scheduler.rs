@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::app::actions::{Action, DataPayload, SideEffect};
+use crate::app::state::ContentView;
+use crate::util::config::AppConfig;
+
+/// One auto-refreshed data source, keyed by the view whose fetch it repeats.
+#[derive(Debug, Clone)]
+struct Source {
+    view: ContentView,
+    interval: Duration,
+    next_due: DateTime<Utc>,
+}
+
+/// Longest wait between automatic retries while refreshes keep failing,
+/// unless a configured interval is longer still.
+const MAX_BACKOFF_SECS: i64 = 30 * 60;
+
+/// Tracks when each data source (inbox, all PRs, history, each owner's repo
+/// list) is next due for an automatic refresh. Time is passed in, so the
+/// event loop owns the clock and tests can drive it.
+///
+/// While refreshes keep failing (the network is down, say), automatic ones
+/// back off: the wait doubles per failed round, up to 30 minutes, until a
+/// fetch succeeds again.
+#[derive(Debug, Clone)]
+pub struct RefreshScheduler {
+    sources: Vec<Source>,
+    /// Failed refresh rounds in a row.
+    failures: u32,
+    /// Whether the round in flight already counted towards `failures`; each
+    /// source failing separately shouldn't multiply the backoff.
+    failure_counted: bool,
+    retry_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshScheduler {
+    /// A schedule for `config` where nothing is due until a full interval
+    /// after `now`, since startup already fetches everything.
+    pub fn new(config: &AppConfig, now: DateTime<Utc>) -> Self {
+        let dashboard = &config.dashboard;
+        let secs = |specific: Option<u64>| {
+            Duration::seconds(specific.unwrap_or(dashboard.refresh_interval_secs).max(1) as i64)
+        };
+        let mut sources = vec![
+            (ContentView::Inbox, secs(dashboard.inbox_interval_secs)),
+            (
+                ContentView::AllOpenPrs,
+                secs(dashboard.all_prs_interval_secs),
+            ),
+            (
+                ContentView::RecentlyMerged,
+                secs(dashboard.history_interval_secs),
+            ),
+            (
+                ContentView::RecentlyClosed,
+                secs(dashboard.history_interval_secs),
+            ),
+        ];
+        for owner in config.github.owner_logins() {
+            sources.push((
+                ContentView::OrgOverview(owner),
+                secs(dashboard.repos_interval_secs),
+            ));
+        }
+        Self {
+            sources: sources
+                .into_iter()
+                .map(|(view, interval)| Source {
+                    view,
+                    interval,
+                    next_due: now + interval,
+                })
+                .collect(),
+            failures: 0,
+            failure_counted: false,
+            retry_at: None,
+        }
+    }
+
+    /// The refreshes due at `now`, rescheduling each from `now`. When every
+    /// source is due at once this is a single [`SideEffect::RefreshAll`].
+    pub fn due(&mut self, now: DateTime<Utc>) -> Vec<SideEffect> {
+        if self.retry_at.is_some_and(|at| now < at) {
+            return vec![];
+        }
+        let all_due = self.sources.iter().all(|s| s.next_due <= now);
+        let mut effects = Vec::new();
+        for source in &mut self.sources {
+            if source.next_due <= now {
+                source.next_due = now + source.interval;
+                effects.push(SideEffect::RefreshView(source.view.clone()));
+            }
+        }
+        if !effects.is_empty() {
+            self.failure_counted = false;
+        }
+        if all_due && !effects.is_empty() {
+            return vec![SideEffect::RefreshAll];
+        }
+        effects
+    }
+
+    /// When the next source falls due, if any are scheduled, allowing for
+    /// any failure backoff.
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        let next = self.sources.iter().map(|s| s.next_due).min()?;
+        Some(next.max(self.retry_at.unwrap_or(next)))
+    }
+
+    /// Each source's interval in seconds, keyed by its view.
+    pub fn intervals(&self) -> HashMap<ContentView, u64> {
+        self.sources
+            .iter()
+            .map(|s| (s.view.clone(), s.interval.num_seconds() as u64))
+            .collect()
+    }
+
+    /// When automatic refreshes resume while backing off after failures.
+    pub fn retry_at(&self) -> Option<DateTime<Utc>> {
+        self.retry_at
+    }
+
+    /// Note the outcome of a scheduled source's fetch: a failure extends the
+    /// backoff (once per round), a success ends it. Other actions are ignored.
+    pub fn observe(&mut self, action: &Action, now: DateTime<Utc>) {
+        match action {
+            Action::FetchFailed { view, .. } if self.sources.iter().any(|s| s.view == *view) => {
+                if self.failure_counted {
+                    return;
+                }
+                self.failure_counted = true;
+                self.failures += 1;
+                self.retry_at = Some(now + self.backoff());
+            }
+            Action::DataLoaded(
+                DataPayload::OrgRepos { .. }
+                | DataPayload::InboxPrs { .. }
+                | DataPayload::AllOpenPrs { .. }
+                | DataPayload::PrHistory { .. },
+            ) => {
+                self.failures = 0;
+                self.retry_at = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// The shortest interval, doubled per failed round and capped.
+    fn backoff(&self) -> Duration {
+        let base = self
+            .sources
+            .iter()
+            .map(|s| s.interval)
+            .min()
+            .unwrap_or(Duration::seconds(MAX_BACKOFF_SECS));
+        let cap = base.max(Duration::seconds(MAX_BACKOFF_SECS));
+        let factor = 1i32 << self.failures.min(16);
+        (base * factor).min(cap)
+    }
+
+    /// Restart the timers of whatever `effect` refreshes, so a manual refresh
+    /// isn't followed by a redundant automatic one.
+    pub fn record(&mut self, effect: &SideEffect, now: DateTime<Utc>) {
+        if matches!(effect, SideEffect::RefreshAll | SideEffect::RefreshView(_)) {
+            self.failure_counted = false;
+        }
+        let view = match effect {
+            SideEffect::RefreshAll => None,
+            SideEffect::RefreshView(view) => match view {
+                // Both views are derived from the all-PRs list.
+                ContentView::FailingChecks | ContentView::Watched => Some(&ContentView::AllOpenPrs),
+                view => Some(view),
+            },
+            _ => return,
+        };
+        for source in &mut self.sources {
+            if view.is_none_or(|v| *v == source.view) {
+                source.next_due = now + source.interval;
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{debug, warn};
+
+use crate::app::state::ContentView;
+
+/// What the user saw last session: each PR's `updated_at`, keyed by PR url.
+/// Compared against fresh data to mark PRs that changed since. Also the
+/// recently viewed list, most recent first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Session {
+    #[serde(default)]
+    pub seen: HashMap<String, DateTime<Utc>>,
+    #[serde(default)]
+    pub recent_views: Vec<ContentView>,
+}
+
+impl Session {
+    /// Load the session file. A missing or unreadable file yields an empty
+    /// session (first run), never an error.
+    pub fn load(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str(&content) {
+            Ok(session) => session,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to parse session file");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create session directory: {}", dir.display())
+            })?;
+        }
+        let content = serde_json::to_string(self).context("Failed to serialize session")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write session file: {}", path.display()))?;
+        debug!(path = %path.display(), prs = self.seen.len(), "Session saved");
+        Ok(())
+    }
+}
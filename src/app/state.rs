@@ -1,6 +1,32 @@
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 
-use crate::github::models::{PrDetail, PullRequest, RateLimit, Repo};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::github::CancelToken;
+use crate::github::models::{
+    CiStatus, PrDetail, PrHistoryKind, PrStateFilter, PullRequest, RateLimit, Repo,
+};
+use crate::ui::chooser::Chooser;
+use crate::ui::theme::{TerminalCaps, Theme, parse_color};
+use crate::util::config::{
+    InboxSort, OrgColor, PrColumn, SecondarySort, UiConfig, default_age_buckets,
+    default_column_priority, default_columns, default_secondary_sort, default_size_thresholds,
+};
+use crate::util::glob::repo_matches;
+use crate::util::pr_diff::{ListDiff, diff_prs, diff_repos};
+use crate::util::pr_history::PrCountHistory;
+use crate::util::time;
+use crate::util::watchlist::WatchedPr;
+
+/// Heading for repos without topics when the nav is grouped by topic.
+pub const UNTAGGED_TOPIC: &str = "untagged";
+
+/// How many views the recently viewed list keeps.
+pub const MAX_RECENT_VIEWS: usize = 10;
 
 /// State of an on-demand PR detail fetch, keyed by PR url in `AppState::pr_details`.
 #[derive(Debug, Clone)]
@@ -64,18 +90,420 @@ impl MergeFilter {
     }
 }
 
+/// What picking an option in the chooser popup does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChooserChoice {
+    OpenUrl(String),
+    /// Narrow the PR list to PRs that also carry this label.
+    FilterLabel(String),
+    /// Go back to a recently viewed view.
+    SwitchView(ContentView),
+    /// Open the first this many unread inbox PRs.
+    OpenUnread(usize),
+}
+
+/// The search box, split into structured filters and free text. Everything is
+/// lowercased.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// `author:<login>`: exact author.
+    pub author: Option<String>,
+    /// `base:<branch>`: exact target branch.
+    pub base: Option<String>,
+    /// The remaining words, matched as a substring of title, author or repo.
+    pub text: String,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Self {
+        let query = query.to_lowercase();
+        let mut parsed = SearchQuery::default();
+        let mut terms = Vec::new();
+        for word in query.split_whitespace() {
+            if let Some(login) = word.strip_prefix("author:").filter(|s| !s.is_empty()) {
+                parsed.author = Some(login.to_string());
+            } else if let Some(branch) = word.strip_prefix("base:").filter(|s| !s.is_empty()) {
+                parsed.base = Some(branch.to_string());
+            } else {
+                terms.push(word);
+            }
+        }
+        parsed.text = terms.join(" ");
+        parsed
+    }
+}
+
+/// Every label on `prs` with how many PRs carry it, most common first (ties
+/// alphabetical).
+pub fn label_counts(prs: &[PullRequest]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for pr in prs {
+        for label in &pr.labels {
+            *counts.entry(label.as_str()).or_default() += 1;
+        }
+    }
+    let mut counts: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(label, n)| (label.to_string(), n))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Whether `pr` carries every one of `labels` (case-insensitively).
+pub fn has_labels(pr: &PullRequest, labels: &[String]) -> bool {
+    labels
+        .iter()
+        .all(|want| pr.labels.iter().any(|l| l.eq_ignore_ascii_case(want)))
+}
+
+/// The first row to show so that `cursor` has `scrolloff` rows of context
+/// above and below it in a viewport of `rows`, moving `offset` as little as
+/// possible. The margin shrinks when the viewport is too short for it, and
+/// the last page stays full.
+pub fn scroll_offset(
+    offset: usize,
+    cursor: usize,
+    len: usize,
+    rows: usize,
+    scrolloff: usize,
+) -> usize {
+    if rows == 0 {
+        return 0;
+    }
+    let margin = scrolloff.min((rows - 1) / 2);
+    let mut offset = offset;
+    if cursor < offset + margin {
+        offset = cursor.saturating_sub(margin);
+    }
+    if cursor + margin >= offset + rows {
+        offset = cursor + margin + 1 - rows;
+    }
+    offset.min(len.saturating_sub(rows))
+}
+
+/// When an automatic refresh may go ahead given the last key press: `None`
+/// if it may go ahead now, else the moment `delay_secs` of idleness is up.
+pub fn refresh_deferred_until(
+    last_input: Option<chrono::DateTime<chrono::Utc>>,
+    now: chrono::DateTime<chrono::Utc>,
+    delay_secs: u64,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let resume = last_input? + chrono::Duration::seconds(delay_secs as i64);
+    (resume > now).then_some(resume)
+}
+
+/// When a section's data last arrived, and when a fetch for it last ended,
+/// successfully or not.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Freshness {
+    pub last_success: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_attempt: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl Freshness {
+    /// The most recent fetch failed.
+    pub fn failing(&self) -> bool {
+        self.last_attempt > self.last_success
+    }
+}
+
+/// Whether data fetched at `last_success` is overdue: older than twice the
+/// section's refresh interval, so at least one refresh has been missed.
+pub fn is_stale(
+    last_success: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+    interval_secs: u64,
+) -> bool {
+    now - last_success > chrono::Duration::seconds(2 * interval_secs as i64)
+}
+
+/// A full refresh in flight: the sections yet to report, and what the lists
+/// held before it, so the changes can be summarized once the last is in.
+#[derive(Debug, Clone, Default)]
+pub struct RefreshTracker {
+    pending: HashSet<ContentView>,
+    failed: Vec<ContentView>,
+    inbox: Vec<PullRequest>,
+    all_open_prs: Vec<PullRequest>,
+    repos: HashMap<String, Vec<Repo>>,
+}
+
+/// What a full refresh changed, for the status bar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshSummary {
+    pub inbox: ListDiff,
+    pub all_open_prs: ListDiff,
+    /// Repos added, removed or changed, across every owner.
+    pub repos_changed: usize,
+    /// Orgs and users whose repo list failed to load.
+    pub failed_owners: usize,
+    /// Other sections (inbox, PR lists) that failed to load.
+    pub failed_lists: usize,
+}
+
+impl RefreshSummary {
+    /// E.g. "Refreshed: +3 PRs in inbox, 2 repos updated, 1 org failed".
+    pub fn message(&self) -> String {
+        let count =
+            |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+        let mut parts = Vec::new();
+        if self.inbox.added > 0 {
+            parts.push(format!(
+                "+{} in inbox",
+                count(self.inbox.added, "PR", "PRs")
+            ));
+        }
+        if self.inbox.removed > 0 {
+            parts.push(format!(
+                "-{} from inbox",
+                count(self.inbox.removed, "PR", "PRs")
+            ));
+        }
+        let prs_changed = self.all_open_prs.added + self.all_open_prs.updated;
+        if prs_changed > 0 {
+            parts.push(format!("{} updated", count(prs_changed, "PR", "PRs")));
+        }
+        if self.repos_changed > 0 {
+            parts.push(format!(
+                "{} updated",
+                count(self.repos_changed, "repo", "repos")
+            ));
+        }
+        if self.failed_owners > 0 {
+            parts.push(format!(
+                "{} failed",
+                count(self.failed_owners, "org", "orgs")
+            ));
+        }
+        if self.failed_lists > 0 {
+            parts.push(format!(
+                "{} failed",
+                count(self.failed_lists, "list", "lists")
+            ));
+        }
+        if parts.is_empty() {
+            return "Refreshed: no changes".to_string();
+        }
+        format!("Refreshed: {}", parts.join(", "))
+    }
+}
+
+/// How `view` is named in the recently viewed menu, as in the nav tree.
+pub fn view_label(view: &ContentView) -> String {
+    match view {
+        ContentView::OrgOverview(owner) => owner.clone(),
+        ContentView::RepoPrList { owner, name } => format!("{}/{}", owner, name),
+        ContentView::AllOpenPrs => "All PRs".to_string(),
+        ContentView::Inbox => "Inbox".to_string(),
+        ContentView::RecentlyMerged => "Recently merged".to_string(),
+        ContentView::RecentlyClosed => "Recently closed".to_string(),
+        ContentView::FailingChecks => "Failing checks".to_string(),
+        ContentView::Watched => "Watched".to_string(),
+    }
+}
+
+/// The section whose refresh backs `view`; views derived from the all PRs
+/// list share its entry.
+fn data_section(view: &ContentView) -> &ContentView {
+    match view {
+        ContentView::RepoPrList { .. } | ContentView::FailingChecks | ContentView::Watched => {
+            &ContentView::AllOpenPrs
+        }
+        view => view,
+    }
+}
+
+/// Order two PRs by the `ui.secondary_sort` key.
+pub fn compare_secondary(key: SecondarySort, a: &PullRequest, b: &PullRequest) -> Ordering {
+    match key {
+        SecondarySort::None => Ordering::Equal,
+        SecondarySort::Number => a.number.cmp(&b.number),
+        SecondarySort::Repo => a.repo_full_name().cmp(&b.repo_full_name()),
+        SecondarySort::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+        SecondarySort::Author => a.author.to_lowercase().cmp(&b.author.to_lowercase()),
+    }
+}
+
+/// Review-decision filter, kept per view for the session. Cycled with `F`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReviewFilter {
+    #[default]
+    All,
+    /// No review decision yet.
+    NeedsReview,
+    ChangesRequested,
+    Approved,
+}
+
+impl ReviewFilter {
+    /// Cycle All -> Needs review -> Changes requested -> Approved -> All.
+    pub fn next(self) -> Self {
+        match self {
+            ReviewFilter::All => ReviewFilter::NeedsReview,
+            ReviewFilter::NeedsReview => ReviewFilter::ChangesRequested,
+            ReviewFilter::ChangesRequested => ReviewFilter::Approved,
+            ReviewFilter::Approved => ReviewFilter::All,
+        }
+    }
+
+    /// Short label for the table title; `None` when inactive (All).
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            ReviewFilter::All => None,
+            ReviewFilter::NeedsReview => Some("needs review"),
+            ReviewFilter::ChangesRequested => Some("changes requested"),
+            ReviewFilter::Approved => Some("approved"),
+        }
+    }
+
+    pub fn matches(self, pr: &PullRequest) -> bool {
+        let decision = pr.review_decision.as_deref();
+        match self {
+            ReviewFilter::All => true,
+            // GitHub reports REVIEW_REQUIRED when branch protection wants a
+            // review nobody has given yet; that is still "no decision".
+            ReviewFilter::NeedsReview => matches!(decision, None | Some("REVIEW_REQUIRED")),
+            ReviewFilter::ChangesRequested => decision == Some("CHANGES_REQUESTED"),
+            ReviewFilter::Approved => decision == Some("APPROVED"),
+        }
+    }
+}
+
+/// Ordering applied to PR lists. Cycled with the sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrSort {
+    /// As fetched (most recently updated first).
+    Default,
+    /// Smallest diff (`additions + deletions`) first.
+    SmallestFirst,
+}
+
+impl PrSort {
+    pub fn next(self) -> Self {
+        match self {
+            PrSort::Default => PrSort::SmallestFirst,
+            PrSort::SmallestFirst => PrSort::Default,
+        }
+    }
+
+    /// Short label for the table title; `None` for the default order.
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            PrSort::Default => None,
+            PrSort::SmallestFirst => Some("smallest first"),
+        }
+    }
+}
+
+/// Folds the rate-limit snapshots reported by concurrent fetches into one stable
+/// value. Responses land out of order, so within a reset window the lowest
+/// `remaining` wins; a snapshot with a later `reset_at` starts a new window.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitTracker {
+    current: RateLimit,
+    /// `remaining` when the current refresh cycle began, for the cost readout.
+    cycle_start: Option<u32>,
+}
+
+impl RateLimitTracker {
+    /// Latest merged snapshot.
+    pub fn current(&self) -> &RateLimit {
+        &self.current
+    }
+
+    /// Whether any request has reported a rate limit yet. GitHub App tokens
+    /// never do, in which case the status bar hides the readout.
+    pub fn is_known(&self) -> bool {
+        self.current.limit > 0
+    }
+
+    /// Mark the start of a refresh cycle; cost is measured from here.
+    pub fn begin_cycle(&mut self) {
+        self.cycle_start = (self.current.limit > 0).then_some(self.current.remaining);
+    }
+
+    /// Merge a snapshot from a completed request.
+    pub fn observe(&mut self, rl: RateLimit) {
+        // An empty snapshot carries nothing to learn from.
+        if rl.limit == 0 {
+            return;
+        }
+        if self.current.limit == 0 {
+            self.cycle_start.get_or_insert(rl.remaining);
+            self.current = rl;
+            return;
+        }
+        match (self.current.reset_at, rl.reset_at) {
+            // Window rolled over: the budget was refilled, so cost restarts from
+            // the full limit.
+            (Some(cur), Some(new)) if new > cur => {
+                if self.cycle_start.is_some() {
+                    self.cycle_start = Some(rl.limit);
+                }
+                self.current = rl;
+            }
+            // Late response from the previous window; already superseded.
+            (Some(cur), Some(new)) if new < cur => {}
+            _ => {
+                if rl.remaining < self.current.remaining {
+                    self.current = rl;
+                }
+            }
+        }
+    }
+
+    /// When auto-refresh may resume, while the remaining budget is under 5%
+    /// of the limit and the window hasn't reset yet.
+    pub fn throttled_until(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let rl = &self.current;
+        if rl.limit == 0 || rl.remaining >= rl.limit / 20 {
+            return None;
+        }
+        rl.reset_at.filter(|reset| *reset > now)
+    }
+
+    /// Points spent since the current refresh cycle began, if known.
+    pub fn cycle_cost(&self) -> Option<u32> {
+        self.cycle_start
+            .map(|start| start.saturating_sub(self.current.remaining))
+    }
+}
+
+/// How a PR relates to the viewer, for tinting rows in the All PRs view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrRelation {
+    /// Authored by the viewer. Wins over `ReviewRequested`.
+    Mine,
+    /// In the viewer's inbox: review requested (or assigned).
+    ReviewRequested,
+    Other,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FocusedPane {
     Navigation,
     Content,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ContentView {
     OrgOverview(String),
-    RepoPrList { owner: String, name: String },
+    RepoPrList {
+        owner: String,
+        name: String,
+    },
     AllOpenPrs,
     Inbox,
+    RecentlyMerged,
+    RecentlyClosed,
+    /// Open PRs with failing checks, derived from already-fetched lists.
+    FailingChecks,
+    /// PRs on the watchlist, in whatever state they are now.
+    Watched,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +516,17 @@ pub enum NavNode {
     },
     AllPrs,
     MyInbox,
+    RecentlyMerged,
+    RecentlyClosed,
+    FailingChecks,
+    /// Only listed while something is watched.
+    Watched,
+    /// "… and N more" after an org's capped repo list; opens the org overview.
+    MoreRepos(String, usize),
+    /// Topic heading under an org when the nav is grouped by topic.
+    Topic(String),
+    /// Heading above the repos listed in `github.repos` without their owner.
+    ReposSection,
 }
 
 #[derive(Debug, Clone)]
@@ -97,21 +536,161 @@ pub struct OrgData {
     pub repos: Vec<Repo>,
 }
 
+/// Who is active in an org, derived from its open PRs for the org overview.
+#[derive(Debug, Clone, Default)]
+pub struct OrgActivity {
+    /// Most recently updated open PRs, newest first (at most 10).
+    pub recent: Vec<PullRequest>,
+    /// Authors by open PR count, busiest first (at most 5).
+    pub top_authors: Vec<(String, usize)>,
+}
+
+/// Aggregate `prs` belonging to `org` into an [`OrgActivity`].
+pub fn org_activity(org: &str, prs: &[PullRequest]) -> OrgActivity {
+    let mut org_prs: Vec<&PullRequest> = prs
+        .iter()
+        .filter(|pr| pr.repo_owner.eq_ignore_ascii_case(org))
+        .collect();
+    org_prs.sort_by_key(|pr| std::cmp::Reverse(pr.updated_at));
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for pr in &org_prs {
+        *counts.entry(pr.author.as_str()).or_default() += 1;
+    }
+    let mut top_authors: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(author, n)| (author.to_string(), n))
+        .collect();
+    top_authors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    top_authors.truncate(5);
+
+    OrgActivity {
+        recent: org_prs.into_iter().take(10).cloned().collect(),
+        top_authors,
+    }
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct AppState {
     // Data
     pub orgs: HashMap<String, OrgData>,
     pub all_open_prs: Vec<PullRequest>,
+    /// Per-repo PR lists prefetched on org expand, keyed by `owner/name`. When
+    /// present these take precedence over filtering `all_open_prs`.
+    pub repo_prs: HashMap<String, Vec<PullRequest>>,
+    /// `owner/name` of repos followed on their own via `github.repos`, listed
+    /// under a "Repos" section after the orgs.
+    pub standalone_repos: Vec<String>,
+    /// Merged / closed PRs per repo (`owner/name`), fetched when a repo view's
+    /// state filter asks for them. Kept for the session, dropped on refresh.
+    pub repo_finished_prs: HashMap<(String, PrStateFilter), Vec<PullRequest>>,
     pub inbox: Vec<PullRequest>,
+    /// `(owner/name, number)` of every inbox PR, rebuilt whenever the inbox or
+    /// the All PRs list is loaded.
+    pub inbox_keys: HashSet<(String, u32)>,
+    /// PRs merged within `dashboard.merged_window_days`, newest first.
+    pub recently_merged: Vec<PullRequest>,
+    /// PRs closed without merging within `dashboard.closed_window_days`.
+    pub recently_closed: Vec<PullRequest>,
+    /// Watched PRs, in the order they were added. Persisted on every change.
+    pub watched: Vec<WatchedPr>,
+    /// Open PR counts per repo over time, for the sparklines. Persisted after
+    /// every repo list fetch.
+    pub pr_history: PrCountHistory,
+    /// Watched PRs fetched one by one because no loaded list had them.
+    /// Dropped on refresh.
+    pub watched_prs: HashMap<WatchedPr, PullRequest>,
+    /// Watched PRs already fetched (or being fetched) since the last refresh.
+    pub watched_requested: HashSet<WatchedPr>,
     pub viewer_login: String,
-    pub rate_limit: RateLimit,
+    /// Each PR's `updated_at` as of the previous session (plus PRs opened this
+    /// session), keyed by url. Drives the "changed since I last looked" mark.
+    pub last_seen: HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// PRs shown on screen since the current view was entered, with the
+    /// `updated_at` they were shown at. They join `last_seen` when the view
+    /// is left, so their unread marks stay up while the user is looking.
+    pub displayed: HashMap<String, chrono::DateTime<chrono::Utc>>,
+    pub rate_limit: RateLimitTracker,
     pub last_refresh: Option<chrono::DateTime<chrono::Utc>>,
+    /// Cancellation of the fetch in flight for a view, cancelled when the
+    /// view is left or a newer fetch for it starts.
+    pub view_fetches: HashMap<ContentView, CancelToken>,
+    /// Fetch times per section, keyed by the view the data backs (inbox, all
+    /// PRs, merged, closed, each owner's repos).
+    pub freshness: HashMap<ContentView, Freshness>,
+    /// The full refresh being summarized, if one is in flight.
+    pub refresh_tracker: Option<RefreshTracker>,
+    /// Auto-refresh interval per section in seconds, mirrored from the
+    /// refresh scheduler.
+    pub refresh_intervals: HashMap<ContentView, u64>,
+    /// Mirrors `dashboard.focus_refresh_debounce_secs`.
+    pub focus_refresh_debounce_secs: u64,
+    /// Mirrors `dashboard.idle_refresh_delay_secs`.
+    pub idle_refresh_delay_secs: u64,
+    /// Mirrors `dashboard.open_unread_limit`.
+    pub open_unread_limit: usize,
+    /// When the user last pressed a key; auto-refresh holds off for a while
+    /// after.
+    pub last_input: Option<chrono::DateTime<chrono::Utc>>,
+    /// Auto-refresh switched off with Ctrl-p; manual refreshes still work.
+    pub auto_refresh_paused: bool,
+    /// Started with `--offline` (or fell back to it): data comes only from the
+    /// cache and refreshes are disabled.
+    pub offline: bool,
+    /// Oldest cache entry shown while offline.
+    pub offline_data_as_of: Option<chrono::DateTime<chrono::Utc>>,
 
     // Navigation
+    /// Views opened from the nav pane, most recent first, without repeats.
+    /// Persisted in the session file.
+    pub recent_views: VecDeque<ContentView>,
     pub nav_nodes: Vec<NavNode>,
     pub nav_cursor: usize,
     pub nav_expanded: HashSet<String>,
+    /// Mirrors `ui.group_by_topic`.
+    pub group_by_topic: bool,
+    /// Mirrors `ui.expand_shortcodes`.
+    pub expand_shortcodes: bool,
+    /// Mirrors `ui.columns`.
+    pub columns: Vec<PrColumn>,
+    /// Mirrors `ui.column_priority`.
+    pub column_priority: Vec<PrColumn>,
+    /// Mirrors `ui.size_thresholds`.
+    pub size_thresholds: [u32; 4],
+    /// `github.org_color` entries, keyed by lowercased org name.
+    pub org_colors: HashMap<String, Color>,
+    /// Mirrors `github.mute_repos`.
+    pub mute_repos: Vec<String>,
+    /// Mirrors `ui.age_buckets`.
+    pub age_buckets: [u32; 3],
+    /// Age bucket the PR lists are narrowed to, if any (index into the summary).
+    pub age_bucket_filter: Option<usize>,
+    /// Mirrors `ui.secondary_sort`.
+    pub secondary_sort: SecondarySort,
+    /// Mirrors `ui.group_all_prs_by_repo`.
+    pub group_all_prs_by_repo: bool,
+    /// Show at most this many repos under each org in the nav (`ui.max_repos_per_org`).
+    pub max_repos_per_org: Option<usize>,
+    /// Leave repos without open PRs out of the nav (`ui.hide_empty_repos`,
+    /// toggled with `e`).
+    pub hide_empty_repos: bool,
+    /// How many non-title columns the PR table is scrolled to the right.
+    pub column_offset: usize,
+    /// Mirrors `ui.scrolloff`.
+    pub scrolloff: usize,
+    /// Mirrors `ui.reduce_motion`.
+    pub reduce_motion: bool,
+    /// Mirrors `ui.ascii`.
+    pub ascii: Option<bool>,
+    /// What the terminal can draw, detected at startup.
+    pub terminal: TerminalCaps,
+    /// The config file in use, shown in the help overlay.
+    pub config_path: Option<PathBuf>,
+    /// First table row shown, kept near the cursor by the reducer.
+    pub content_offset: usize,
+    /// PR rows that fit in the table as of the last frame; 0 before the first.
+    pub table_rows: usize,
     pub focused_pane: FocusedPane,
     pub content_view: ContentView,
     pub content_cursor: usize,
@@ -126,14 +705,70 @@ pub struct AppState {
     pub pr_diffs: HashMap<String, DiffEntry>,
     /// Vertical scroll offset (in lines) for the diff overlay.
     pub diff_scroll: u16,
+    /// Lines the error modal is scrolled down, for errors too long to fit.
+    pub error_scroll: u16,
+    /// How far the error modal could scroll as of the last frame.
+    pub error_max_scroll: u16,
 
     // Merge-state filter + help overlay
     pub merge_filter: MergeFilter,
+    /// Labels the current PR list must all carry (empty: no label filter).
+    pub label_filter: Vec<String>,
+    /// Review filter per view; views without an entry show everything.
+    pub review_filters: HashMap<ContentView, ReviewFilter>,
+    /// Restrict All PRs to PRs the viewer authored, reviews or is assigned.
+    pub involves_me_only: bool,
+    /// URLs from the server-side `involves:` search; `None` until it has
+    /// answered, when the filter goes by author and review requests alone.
+    pub involved_urls: Option<HashSet<String>>,
+    pub sort: PrSort,
+    /// Order of the inbox; starts as `dashboard.inbox_sort`.
+    pub inbox_sort: InboxSort,
+    /// Which PRs the repo view lists: open (default), merged or closed.
+    pub pr_state_filter: PrStateFilter,
     pub help_open: bool,
+    /// Popup with the highlighted PR's full title, branch and URL.
+    pub pr_info_open: bool,
+    /// Open chooser popup, if any.
+    pub chooser: Option<Chooser<ChooserChoice>>,
 
     // UI flags
     pub loading: bool,
     pub loading_orgs: HashSet<String>,
+    /// The inbox alone is being re-fetched (`u`); marked on its nav entry
+    /// rather than with the global `loading`.
+    pub inbox_loading: bool,
+    /// Views whose most recent fetch failed, until one succeeds.
+    pub failed_views: HashSet<ContentView>,
+    /// Repo merged/closed lists, by full name and state, whose most recent
+    /// fetch failed, until one succeeds.
+    pub failed_finished_prs: HashSet<(String, PrStateFilter)>,
+    /// Configured owners GitHub has no user or organization for.
+    pub unknown_owners: HashSet<String>,
+    /// Showing data cached by a previous run until the first refresh completes.
+    pub stale: bool,
+    /// Transient status-bar text, cleared by the next key press.
+    pub status_message: Option<String>,
+    /// Count typed so far for the next motion (`5j`, `12G`, `3<Enter>`).
+    pub pending_count: Option<usize>,
+    /// Whether `'<letter>` jumping is enabled (`ui.letter_jump`).
+    pub letter_jump: bool,
+    /// `'` was pressed; the next letter picks the jump target.
+    pub pending_jump: bool,
+    /// Presentation lock: auto-refresh is paused and the list order frozen.
+    pub refresh_locked: bool,
+    /// Row order of the view that was on screen when the lock was taken, by URL.
+    pub locked_order: Option<(ContentView, HashMap<String, usize>)>,
+    /// Fetches waiting for a concurrency slot, refreshed before each frame.
+    pub queued_tasks: usize,
+    /// Seconds left waiting out a secondary rate limit, refreshed before each frame.
+    pub rate_limit_backoff: Option<u64>,
+    /// When auto-refresh tries again after repeated failures, refreshed
+    /// before each frame. Set only while a failure streak lasts.
+    pub refresh_retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// GitHub rejected the token; a persistent prompt offers to resolve a
+    /// new one, and auto-refresh stops until then.
+    pub token_rejected: bool,
     pub error_message: Option<String>,
     pub should_quit: bool,
 }
@@ -157,13 +792,58 @@ impl AppState {
         let mut state = Self {
             orgs,
             all_open_prs: Vec::new(),
+            repo_prs: HashMap::new(),
+            standalone_repos: Vec::new(),
+            repo_finished_prs: HashMap::new(),
             inbox: Vec::new(),
+            inbox_keys: HashSet::new(),
+            recently_merged: Vec::new(),
+            recently_closed: Vec::new(),
+            watched: Vec::new(),
+            pr_history: PrCountHistory::default(),
+            watched_prs: HashMap::new(),
+            watched_requested: HashSet::new(),
             viewer_login,
-            rate_limit: RateLimit::default(),
+            last_seen: HashMap::new(),
+            displayed: HashMap::new(),
+            rate_limit: RateLimitTracker::default(),
             last_refresh: None,
+            view_fetches: HashMap::new(),
+            freshness: HashMap::new(),
+            refresh_tracker: None,
+            refresh_intervals: HashMap::new(),
+            focus_refresh_debounce_secs: 60,
+            idle_refresh_delay_secs: 10,
+            open_unread_limit: 10,
+            last_input: None,
+            auto_refresh_paused: false,
+            offline: false,
+            offline_data_as_of: None,
+            recent_views: VecDeque::new(),
             nav_nodes: Vec::new(),
             nav_cursor: 0,
             nav_expanded,
+            group_by_topic: false,
+            expand_shortcodes: false,
+            columns: default_columns(),
+            column_priority: default_column_priority(),
+            size_thresholds: default_size_thresholds(),
+            org_colors: HashMap::new(),
+            mute_repos: Vec::new(),
+            age_buckets: default_age_buckets(),
+            age_bucket_filter: None,
+            secondary_sort: default_secondary_sort(),
+            group_all_prs_by_repo: false,
+            max_repos_per_org: None,
+            hide_empty_repos: true,
+            column_offset: 0,
+            scrolloff: 0,
+            reduce_motion: false,
+            ascii: None,
+            terminal: TerminalCaps::FULL,
+            config_path: None,
+            content_offset: 0,
+            table_rows: 0,
             focused_pane: FocusedPane::Navigation,
             content_view: ContentView::Inbox,
             content_cursor: 0,
@@ -173,10 +853,36 @@ impl AppState {
             pr_details: HashMap::new(),
             pr_diffs: HashMap::new(),
             diff_scroll: 0,
+            error_scroll: 0,
+            error_max_scroll: 0,
             merge_filter: MergeFilter::All,
+            label_filter: Vec::new(),
+            review_filters: HashMap::new(),
+            involves_me_only: false,
+            involved_urls: None,
+            sort: PrSort::Default,
+            inbox_sort: InboxSort::default(),
+            pr_state_filter: PrStateFilter::Open,
             help_open: false,
+            pr_info_open: false,
+            chooser: None,
             loading: true,
             loading_orgs: HashSet::new(),
+            inbox_loading: false,
+            failed_views: HashSet::new(),
+            failed_finished_prs: HashSet::new(),
+            unknown_owners: HashSet::new(),
+            stale: false,
+            status_message: None,
+            pending_count: None,
+            letter_jump: false,
+            pending_jump: false,
+            queued_tasks: 0,
+            rate_limit_backoff: None,
+            refresh_retry_at: None,
+            token_rejected: false,
+            refresh_locked: false,
+            locked_order: None,
             error_message: None,
             should_quit: false,
         };
@@ -185,12 +891,101 @@ impl AppState {
         state
     }
 
-    pub fn rebuild_nav_tree(&mut self) {
-        let mut nodes = Vec::new();
+    /// Copy the `[ui]` settings the renderer reads into state.
+    pub fn apply_ui_config(&mut self, ui: &UiConfig) {
+        self.group_by_topic = ui.group_by_topic;
+        self.expand_shortcodes = ui.expand_shortcodes;
+        self.letter_jump = ui.letter_jump;
+        self.columns = ui.columns.clone();
+        self.column_priority = ui.column_priority.clone();
+        self.size_thresholds = ui.size_thresholds;
+        self.age_buckets = ui.age_buckets;
+        self.secondary_sort = ui.secondary_sort;
+        self.group_all_prs_by_repo = ui.group_all_prs_by_repo;
+        self.max_repos_per_org = ui.max_repos_per_org;
+        self.hide_empty_repos = ui.hide_empty_repos;
+        self.scrolloff = ui.scrolloff;
+        self.reduce_motion = ui.reduce_motion;
+        self.ascii = ui.ascii;
+        self.rebuild_nav_tree();
+    }
+
+    /// Glyphs and color depth for this terminal, after `ui.ascii`.
+    pub fn theme(&self) -> Theme {
+        Theme::new(self.terminal, self.ascii)
+    }
+
+    /// Take the per-org nav colors from `github.org_color`, parsed once here
+    /// rather than on every draw. Entries that don't parse are skipped;
+    /// [`crate::ui::theme::check_org_colors`] reports them when the config
+    /// loads.
+    pub fn apply_org_colors(&mut self, entries: &[OrgColor]) {
+        self.org_colors = entries
+            .iter()
+            .filter_map(|e| Some((e.org.to_lowercase(), parse_color(&e.color)?)))
+            .collect();
+    }
+
+    /// Whether `pr` is from a repo `github.mute_repos` matches.
+    pub fn is_muted(&self, pr: &PullRequest) -> bool {
+        repo_matches(&self.mute_repos, &pr.repo_full_name(), &pr.repo_name)
+    }
 
+    /// `prs` without muted repos' PRs, as the inbox and All PRs show them.
+    /// The lists themselves keep those PRs for the views derived from them.
+    pub fn without_muted<'a>(&self, prs: &'a [PullRequest]) -> Cow<'a, [PullRequest]> {
+        if self.mute_repos.is_empty() {
+            return Cow::Borrowed(prs);
+        }
+        Cow::Owned(
+            prs.iter()
+                .filter(|pr| !self.is_muted(pr))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// The configured color for `org`, if any.
+    pub fn org_color(&self, org: &str) -> Option<Color> {
+        self.org_colors.get(&org.to_lowercase()).copied()
+    }
+
+    /// Make the nav tree match `owners` after a config reload: owners no longer
+    /// configured are dropped, new ones are added expanded and empty. Returns
+    /// the added owners, which still need fetching.
+    pub fn sync_owners(&mut self, owners: &[String]) -> Vec<String> {
+        self.orgs.retain(|name, _| owners.contains(name));
+        self.nav_expanded.retain(|name| owners.contains(name));
+        let mut added = Vec::new();
+        for name in owners {
+            if !self.orgs.contains_key(name) {
+                self.orgs.insert(
+                    name.clone(),
+                    OrgData {
+                        name: name.clone(),
+                        repos: Vec::new(),
+                    },
+                );
+                self.nav_expanded.insert(name.clone());
+                added.push(name.clone());
+            }
+        }
+        self.rebuild_nav_tree();
+        added
+    }
+
+    pub fn rebuild_nav_tree(&mut self) {
         // Virtual entries at top
-        nodes.push(NavNode::MyInbox);
-        nodes.push(NavNode::AllPrs);
+        let mut nodes = vec![
+            NavNode::MyInbox,
+            NavNode::AllPrs,
+            NavNode::RecentlyMerged,
+            NavNode::RecentlyClosed,
+            NavNode::FailingChecks,
+        ];
+        if !self.watched.is_empty() {
+            nodes.push(NavNode::Watched);
+        }
 
         // Org entries sorted by name
         let mut org_names: Vec<_> = self.orgs.keys().cloned().collect();
@@ -202,20 +997,91 @@ impl AppState {
             if self.nav_expanded.contains(org_name)
                 && let Some(org_data) = self.orgs.get(org_name)
             {
-                let mut repos: Vec<_> = org_data.repos.iter().filter(|r| !r.is_archived).collect();
+                let mut repos: Vec<_> = org_data
+                    .repos
+                    .iter()
+                    .filter(|r| !r.is_archived)
+                    .filter(|r| !self.hide_empty_repos || r.open_pr_count > 0)
+                    .collect();
                 repos.sort_by(|a, b| {
                     b.open_pr_count
                         .cmp(&a.open_pr_count)
                         .then(a.name.cmp(&b.name))
                 });
+                let hidden = match self.max_repos_per_org {
+                    Some(max) if repos.len() > max => {
+                        let hidden = repos.len() - max;
+                        repos.truncate(max);
+                        hidden
+                    }
+                    _ => 0,
+                };
 
-                for repo in repos {
-                    nodes.push(NavNode::Repo {
-                        owner: repo.owner.clone(),
-                        name: repo.name.clone(),
-                        open_prs: repo.open_pr_count,
+                if self.group_by_topic {
+                    // Each repo sits under its first topic; untagged repos go last.
+                    let mut groups: Vec<(Option<&String>, Vec<&Repo>)> = Vec::new();
+                    for repo in repos {
+                        let topic = repo.topics.first();
+                        match groups.iter_mut().find(|(t, _)| *t == topic) {
+                            Some((_, members)) => members.push(repo),
+                            None => groups.push((topic, vec![repo])),
+                        }
+                    }
+                    groups.sort_by(|a, b| match (a.0, b.0) {
+                        (Some(x), Some(y)) => x.cmp(y),
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
                     });
+                    for (topic, members) in groups {
+                        nodes.push(NavNode::Topic(
+                            topic.cloned().unwrap_or_else(|| UNTAGGED_TOPIC.to_string()),
+                        ));
+                        for repo in members {
+                            nodes.push(NavNode::Repo {
+                                owner: repo.owner.clone(),
+                                name: repo.name.clone(),
+                                open_prs: repo.open_pr_count,
+                            });
+                        }
+                    }
+                } else {
+                    for repo in repos {
+                        nodes.push(NavNode::Repo {
+                            owner: repo.owner.clone(),
+                            name: repo.name.clone(),
+                            open_prs: repo.open_pr_count,
+                        });
+                    }
+                }
+                if hidden > 0 {
+                    nodes.push(NavNode::MoreRepos(org_name.clone(), hidden));
+                }
+            }
+        }
+
+        if !self.standalone_repos.is_empty() {
+            nodes.push(NavNode::ReposSection);
+            for full_name in &self.standalone_repos {
+                let Some((owner, name)) = full_name.split_once('/') else {
+                    continue;
+                };
+                let open_prs = match self.repo_prs.get(full_name) {
+                    Some(prs) => prs.len(),
+                    None => self
+                        .all_open_prs
+                        .iter()
+                        .filter(|pr| pr.repo_full_name().eq_ignore_ascii_case(full_name))
+                        .count(),
+                };
+                if self.hide_empty_repos && open_prs == 0 {
+                    continue;
                 }
+                nodes.push(NavNode::Repo {
+                    owner: owner.to_string(),
+                    name: name.to_string(),
+                    open_prs: open_prs as u32,
+                });
             }
         }
 
@@ -228,42 +1094,563 @@ impl AppState {
     }
 
     pub fn filtered_prs(&self, prs: &[PullRequest]) -> Vec<PullRequest> {
-        let query = self.search_query.to_lowercase();
-        prs.iter()
+        let SearchQuery { author, base, text } = SearchQuery::parse(&self.search_query);
+        let mut prs: Vec<PullRequest> = prs
+            .iter()
             .filter(|pr| self.merge_filter.matches(pr))
             .filter(|pr| {
-                query.is_empty()
-                    || pr.title.to_lowercase().contains(&query)
-                    || pr.author.to_lowercase().contains(&query)
-                    || pr.repo_name.to_lowercase().contains(&query)
-                    || pr.repo_full_name().to_lowercase().contains(&query)
+                author
+                    .as_ref()
+                    .is_none_or(|login| pr.author.eq_ignore_ascii_case(login))
+            })
+            .filter(|pr| {
+                base.as_ref().is_none_or(|branch| {
+                    pr.base_ref
+                        .as_deref()
+                        .is_some_and(|b| b.eq_ignore_ascii_case(branch))
+                })
+            })
+            .filter(|pr| {
+                text.is_empty()
+                    || pr.title.to_lowercase().contains(&text)
+                    || pr.author.to_lowercase().contains(&text)
+                    || pr.repo_name.to_lowercase().contains(&text)
+                    || pr.repo_full_name().to_lowercase().contains(&text)
             })
             .cloned()
-            .collect()
+            .collect();
+        let tiebreak = self.secondary_sort;
+        match self.sort {
+            // Keep the fetched order, but put each run of PRs with the same
+            // timestamp (update, or merge/close in the history views) in a
+            // fixed order.
+            PrSort::Default if tiebreak != SecondarySort::None => {
+                let history = match self.content_view {
+                    ContentView::RecentlyMerged => Some(PrHistoryKind::Merged),
+                    ContentView::RecentlyClosed => Some(PrHistoryKind::Closed),
+                    _ => None,
+                };
+                let by_age =
+                    self.content_view == ContentView::Inbox && self.inbox_sort == InboxSort::Age;
+                let newest = |pr: &PullRequest| match history {
+                    Some(kind) => kind.finished_at(pr),
+                    None if by_age => Some(pr.created_at),
+                    None => Some(pr.updated_at),
+                };
+                for run in prs.chunk_by_mut(|a, b| newest(a) == newest(b)) {
+                    run.sort_by(|a, b| compare_secondary(tiebreak, a, b));
+                }
+            }
+            PrSort::Default => {}
+            // Stable, so equal sizes without a tiebreaker keep their fetched order.
+            PrSort::SmallestFirst => prs.sort_by(|a, b| {
+                a.diff_size()
+                    .cmp(&b.diff_size())
+                    .then_with(|| compare_secondary(tiebreak, a, b))
+            }),
+        }
+        prs
     }
 
+    /// PRs shown in the current view, after search/filter/sort. While the
+    /// refresh lock is on, the locked view keeps the order it had when locked;
+    /// PRs that arrived since go after the rest. When grouped by repo, each
+    /// repo's PRs are contiguous, in the order the repos first appear.
     pub fn current_pr_list(&self) -> Vec<PullRequest> {
-        let prs = match &self.content_view {
-            ContentView::Inbox => &self.inbox,
-            ContentView::AllOpenPrs => &self.all_open_prs,
+        let mut prs = self.view_pr_list();
+        if let Some((view, order)) = &self.locked_order
+            && *view == self.content_view
+        {
+            prs.sort_by_key(|pr| order.get(&pr.url).copied().unwrap_or(usize::MAX));
+        }
+        if self.groups_by_repo() {
+            let mut first_seen: HashMap<String, usize> = HashMap::new();
+            for (i, pr) in prs.iter().enumerate() {
+                first_seen.entry(pr.repo_full_name()).or_insert(i);
+            }
+            // Stable, so PRs keep their order within a repo.
+            prs.sort_by_key(|pr| first_seen[&pr.repo_full_name()]);
+        }
+        prs
+    }
+
+    /// Where the cursor sits among the table's rows, and how many rows there
+    /// are. Differ from the PR index and count only when repo header rows are
+    /// shown.
+    pub fn cursor_table_position(&self) -> (usize, usize) {
+        let prs = self.current_pr_list();
+        if !self.groups_by_repo() {
+            return (self.content_cursor, prs.len());
+        }
+        let mut headers_before_cursor = 0;
+        let mut headers = 0;
+        for (i, pr) in prs.iter().enumerate() {
+            if i == 0 || prs[i - 1].repo_full_name() != pr.repo_full_name() {
+                headers += 1;
+                if i <= self.content_cursor {
+                    headers_before_cursor = headers;
+                }
+            }
+        }
+        (
+            self.content_cursor + headers_before_cursor,
+            prs.len() + headers,
+        )
+    }
+
+    /// Scroll the table just enough to keep `scrolloff` rows around the cursor.
+    pub fn scroll_to_cursor(&mut self) {
+        let (cursor, len) = self.cursor_table_position();
+        self.content_offset = scroll_offset(
+            self.content_offset,
+            cursor,
+            len,
+            self.table_rows,
+            self.scrolloff,
+        );
+    }
+
+    /// Whether the table shows repo header rows. The cursor only ever indexes
+    /// PRs, so headers are never selectable.
+    pub fn groups_by_repo(&self) -> bool {
+        self.group_all_prs_by_repo && self.content_view == ContentView::AllOpenPrs
+    }
+
+    /// Whether a timed or focus refresh may start now: not offline or with a
+    /// rejected token, while one is running, the view is locked or paused,
+    /// the user is mid-input, or the rate limit is nearly spent.
+    pub fn auto_refresh_allowed(&self) -> bool {
+        !self.offline
+            && !self.token_rejected
+            && !self.loading
+            && !self.refresh_locked
+            && !self.auto_refresh_paused
+            && self.auto_refresh_deferred_until().is_none()
+            && self.rate_limit.throttled_until(time::now()).is_none()
+    }
+
+    /// A token for a new fetch backing `view`, cancelling the one in flight.
+    pub fn begin_view_fetch(&mut self, view: ContentView) -> CancelToken {
+        let cancel = CancelToken::new();
+        if let Some(old) = self.view_fetches.insert(view, cancel.clone()) {
+            old.cancel();
+        }
+        cancel
+    }
+
+    /// Forget `view`'s fetch once it has finished, unless a newer one has
+    /// taken its place.
+    pub fn end_view_fetch(&mut self, view: &ContentView, cancel: &CancelToken) {
+        if self
+            .view_fetches
+            .get(view)
+            .is_some_and(|c| c.is_same(cancel))
+        {
+            self.view_fetches.remove(view);
+        }
+    }
+
+    /// Stop the fetch in flight for `view`, if any.
+    pub fn cancel_view_fetch(&mut self, view: &ContentView) {
+        if let Some(cancel) = self.view_fetches.remove(view) {
+            cancel.cancel();
+        }
+    }
+
+    /// Note that a fetch for `section` ended, successfully or not.
+    pub fn record_fetch(&mut self, section: ContentView, ok: bool) {
+        let now = time::now();
+        let entry = self.freshness.entry(section).or_default();
+        entry.last_attempt = Some(now);
+        if ok {
+            entry.last_success = Some(now);
+        }
+    }
+
+    /// Put the inbox in `inbox_sort` order. Stable, so PRs with the same
+    /// timestamp keep their fetched order.
+    pub fn sort_inbox(&mut self) {
+        match self.inbox_sort {
+            InboxSort::Newest => self
+                .inbox
+                .sort_by_key(|pr| std::cmp::Reverse(pr.updated_at)),
+            InboxSort::Oldest => self.inbox.sort_by_key(|pr| pr.updated_at),
+            InboxSort::Age => self.inbox.sort_by_key(|pr| pr.created_at),
+        }
+    }
+
+    /// Start summarizing a full refresh: every section is expected to
+    /// report, and the lists as they are now are kept to diff against.
+    pub fn begin_refresh_summary(&mut self) {
+        let mut pending: HashSet<ContentView> = [
+            ContentView::Inbox,
+            ContentView::AllOpenPrs,
+            ContentView::RecentlyMerged,
+            ContentView::RecentlyClosed,
+        ]
+        .into();
+        pending.extend(self.orgs.keys().cloned().map(ContentView::OrgOverview));
+        self.refresh_tracker = Some(RefreshTracker {
+            pending,
+            failed: Vec::new(),
+            inbox: self.inbox.clone(),
+            all_open_prs: self.all_open_prs.clone(),
+            repos: self
+                .orgs
+                .iter()
+                .map(|(owner, data)| (owner.clone(), data.repos.clone()))
+                .collect(),
+        });
+    }
+
+    /// Note that `section` reported back, with its new data already in
+    /// place. Returns the summary once it was the last one the refresh
+    /// awaited.
+    pub fn refresh_section_done(
+        &mut self,
+        section: &ContentView,
+        ok: bool,
+    ) -> Option<RefreshSummary> {
+        // Repo lists are fetched on their own, never as part of a refresh.
+        if matches!(section, ContentView::RepoPrList { .. }) {
+            return None;
+        }
+        let tracker = self.refresh_tracker.as_mut()?;
+        let section = data_section(section);
+        if !tracker.pending.remove(section) {
+            return None;
+        }
+        if !ok {
+            tracker.failed.push(section.clone());
+        }
+        if !tracker.pending.is_empty() {
+            return None;
+        }
+        let tracker = self.refresh_tracker.take()?;
+        let repos_changed = self
+            .orgs
+            .iter()
+            .map(|(owner, data)| {
+                let before = tracker.repos.get(owner).map_or(&[][..], Vec::as_slice);
+                let diff = diff_repos(before, &data.repos);
+                diff.added + diff.removed + diff.updated
+            })
+            .sum();
+        let failed_owners = tracker
+            .failed
+            .iter()
+            .filter(|v| matches!(v, ContentView::OrgOverview(_)))
+            .count();
+        Some(RefreshSummary {
+            inbox: diff_prs(&tracker.inbox, &self.inbox),
+            all_open_prs: diff_prs(&tracker.all_open_prs, &self.all_open_prs),
+            repos_changed,
+            failed_owners,
+            failed_lists: tracker.failed.len() - failed_owners,
+        })
+    }
+
+    /// Fetch times of the section behind `view`, once it has loaded. A repo
+    /// list fetched on its own has its own; until then it shows the all PRs
+    /// list's.
+    pub fn view_freshness(&self, view: &ContentView) -> Option<Freshness> {
+        let own = match view {
+            ContentView::RepoPrList { .. } => self.freshness.get(view),
+            _ => None,
+        };
+        let freshness = *own.or_else(|| self.freshness.get(data_section(view)))?;
+        freshness.last_success.map(|_| freshness)
+    }
+
+    /// Whether the data behind `view` is older than twice its refresh
+    /// interval. Sections without an interval or a successful fetch never are.
+    pub fn view_is_stale(&self, view: &ContentView, now: chrono::DateTime<chrono::Utc>) -> bool {
+        match (
+            self.view_freshness(view).and_then(|f| f.last_success),
+            self.refresh_intervals.get(data_section(view)),
+        ) {
+            (Some(at), Some(&interval)) => is_stale(at, now, interval),
+            _ => false,
+        }
+    }
+
+    /// When recent input stops holding off auto-refresh, if it still does.
+    pub fn auto_refresh_deferred_until(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        refresh_deferred_until(self.last_input, time::now(), self.idle_refresh_delay_secs)
+    }
+
+    /// Capture the current view's order and pause auto-refresh, or release both.
+    pub fn toggle_refresh_lock(&mut self) {
+        self.refresh_locked = !self.refresh_locked;
+        self.locked_order = self.refresh_locked.then(|| {
+            let order = self
+                .view_pr_list()
+                .into_iter()
+                .enumerate()
+                .map(|(i, pr)| (pr.url, i))
+                .collect();
+            (self.content_view.clone(), order)
+        });
+    }
+
+    /// The review filter for the current view.
+    pub fn review_filter(&self) -> ReviewFilter {
+        self.review_filters
+            .get(&self.content_view)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Review filtering is per view, so it applies here rather than in
+    /// `filtered_prs`, which also feeds the nav counts of other views.
+    fn view_pr_list(&self) -> Vec<PullRequest> {
+        let mut prs = self.unbucketed_pr_list();
+        if let Some(bucket) = self.age_bucket_filter {
+            let now = time::now();
+            prs.retain(|pr| time::age_bucket(&pr.updated_at, &now, &self.age_buckets) == bucket);
+        }
+        prs
+    }
+
+    /// The current view's PRs under every filter except the age bucket.
+    fn unbucketed_pr_list(&self) -> Vec<PullRequest> {
+        let review = self.review_filter();
+        let mut prs = self.filtered_prs(&self.source_prs());
+        prs.retain(|pr| review.matches(pr) && has_labels(pr, &self.label_filter));
+        if self.involves_me_only && self.content_view == ContentView::AllOpenPrs {
+            prs.retain(|pr| self.involves_me(pr));
+        }
+        prs
+    }
+
+    /// Whether the viewer is involved in `pr`: per the `involves:` search
+    /// once it has answered, else as author or requested reviewer.
+    pub fn involves_me(&self, pr: &PullRequest) -> bool {
+        self.involved_urls
+            .as_ref()
+            .is_some_and(|urls| urls.contains(&pr.url))
+            || self.pr_relation(pr) != PrRelation::Other
+    }
+
+    /// The label chooser for the current list: every label not already being
+    /// filtered on, with how many listed PRs carry it.
+    pub fn label_menu(&self) -> Option<Chooser<ChooserChoice>> {
+        let items: Vec<(String, ChooserChoice)> = label_counts(&self.current_pr_list())
+            .into_iter()
+            .filter(|(label, _)| {
+                !self
+                    .label_filter
+                    .iter()
+                    .any(|l| l.eq_ignore_ascii_case(label))
+            })
+            .map(|(label, count)| {
+                (
+                    format!("{} ({})", label, count),
+                    ChooserChoice::FilterLabel(label),
+                )
+            })
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+        Some(Chooser::new("Filter by label", items))
+    }
+
+    /// Per-bucket PR counts for the age summary line. The age filter itself is
+    /// left out, so the other buckets still show what selecting them would give.
+    pub fn age_histogram(&self) -> [usize; 4] {
+        let prs = self.unbucketed_pr_list();
+        time::age_histogram(
+            prs.iter().map(|pr| &pr.updated_at),
+            &time::now(),
+            &self.age_buckets,
+        )
+    }
+
+    /// Everything the current view could show, before search and filters.
+    fn source_prs(&self) -> Cow<'_, [PullRequest]> {
+        match &self.content_view {
+            ContentView::Inbox => self.without_muted(&self.inbox),
+            ContentView::AllOpenPrs => self.without_muted(&self.all_open_prs),
+            ContentView::RecentlyMerged => Cow::Borrowed(&self.recently_merged),
+            ContentView::RecentlyClosed => Cow::Borrowed(&self.recently_closed),
+            ContentView::FailingChecks => Cow::Owned(self.failing_check_prs()),
+            ContentView::Watched => Cow::Owned(self.watched_pr_list()),
             ContentView::RepoPrList { owner, name } => {
                 let full_name = format!("{}/{}", owner, name);
-                let filtered: Vec<PullRequest> = self
-                    .all_open_prs
-                    .iter()
-                    .filter(|pr| pr.repo_full_name() == full_name)
-                    .cloned()
-                    .collect();
-                return self.filtered_prs(&filtered);
+                if self.pr_state_filter != PrStateFilter::Open {
+                    return match self
+                        .repo_finished_prs
+                        .get(&(full_name, self.pr_state_filter))
+                    {
+                        Some(prs) => Cow::Borrowed(prs),
+                        None => Cow::Borrowed(&[]),
+                    };
+                }
+                if let Some(prs) = self.repo_prs.get(&full_name) {
+                    return Cow::Borrowed(prs);
+                }
+                Cow::Owned(
+                    self.all_open_prs
+                        .iter()
+                        .filter(|pr| pr.repo_full_name() == full_name)
+                        .cloned()
+                        .collect(),
+                )
             }
-            ContentView::OrgOverview(_) => return Vec::new(),
+            ContentView::OrgOverview(_) => Cow::Borrowed(&[]),
+        }
+    }
+
+    /// How many PRs the current view has before search and filters.
+    pub fn unfiltered_pr_count(&self) -> usize {
+        self.source_prs().len()
+    }
+
+    /// Whether the search or any filter may be hiding PRs.
+    pub fn filters_active(&self) -> bool {
+        !self.search_query.is_empty()
+            || self.merge_filter != MergeFilter::All
+            || self.review_filter() != ReviewFilter::All
+            || self.age_bucket_filter.is_some()
+            || !self.label_filter.is_empty()
+            || (self.involves_me_only && self.content_view == ContentView::AllOpenPrs)
+    }
+
+    /// `(visible, total)` PR counts for the current view.
+    pub fn view_counts(&self) -> (usize, usize) {
+        (self.current_pr_list().len(), self.unfiltered_pr_count())
+    }
+
+    /// Whether the search or merge filter may be hiding PRs. Unlike the
+    /// review, label and age filters, these apply to every view alike.
+    pub fn shared_filters_active(&self) -> bool {
+        !self.search_query.is_empty() || self.merge_filter != MergeFilter::All
+    }
+
+    /// `(visible, total)` for `prs` under the current search and merge filter
+    /// (see [`Self::shared_filters_active`]).
+    pub fn filtered_counts(&self, prs: &[PullRequest]) -> (usize, usize) {
+        (self.filtered_prs(prs).len(), prs.len())
+    }
+
+    /// Whether the last fetch backing the current view failed.
+    pub fn view_fetch_failed(&self) -> bool {
+        let scope = match &self.content_view {
+            ContentView::RepoPrList { owner, name }
+                if self.pr_state_filter != PrStateFilter::Open =>
+            {
+                return self
+                    .failed_finished_prs
+                    .contains(&(format!("{}/{}", owner, name), self.pr_state_filter));
+            }
+            // Derived from, or falling back to, the all-open-PRs list.
+            ContentView::FailingChecks => ContentView::AllOpenPrs,
+            ContentView::RepoPrList { owner, name }
+                if !self.repo_prs.contains_key(&format!("{}/{}", owner, name)) =>
+            {
+                ContentView::AllOpenPrs
+            }
+            view => view.clone(),
         };
-        self.filtered_prs(prs)
+        self.failed_views.contains(&scope)
     }
 
-    pub fn selected_pr_url(&self) -> Option<String> {
-        let prs = self.current_pr_list();
-        prs.get(self.content_cursor).map(|pr| pr.url.clone())
+    /// Index of the next org or repo node after the nav cursor whose name starts
+    /// with `letter` (case-insensitive), wrapping around.
+    pub fn next_nav_match(&self, letter: char) -> Option<usize> {
+        let len = self.nav_nodes.len();
+        (1..=len)
+            .map(|offset| (self.nav_cursor + offset) % len)
+            .find(|&i| {
+                let name = match &self.nav_nodes[i] {
+                    NavNode::Org(name) | NavNode::Repo { name, .. } => name,
+                    _ => return false,
+                };
+                name.chars()
+                    .next()
+                    .is_some_and(|c| c.to_lowercase().eq(letter.to_lowercase()))
+            })
+    }
+
+    /// Open PRs whose checks fail, from `all_open_prs` and any per-repo lists,
+    /// oldest first.
+    pub fn failing_check_prs(&self) -> Vec<PullRequest> {
+        let mut seen = HashSet::new();
+        let mut prs: Vec<PullRequest> = self
+            .all_open_prs
+            .iter()
+            .chain(self.repo_prs.values().flatten())
+            .filter(|pr| pr.ci_status() == CiStatus::Failing)
+            .filter(|pr| seen.insert(pr.url.clone()))
+            .cloned()
+            .collect();
+        prs.sort_by_key(|pr| pr.created_at);
+        prs
+    }
+
+    /// Watched PRs that are loaded or fetched, most recently updated first.
+    pub fn watched_pr_list(&self) -> Vec<PullRequest> {
+        let mut prs: Vec<PullRequest> = self
+            .watched
+            .iter()
+            .filter_map(|w| self.find_watched(w))
+            .cloned()
+            .collect();
+        prs.sort_by_key(|pr| std::cmp::Reverse(pr.updated_at));
+        prs
+    }
+
+    /// The current copy of a watched PR. An individually fetched copy wins,
+    /// since it is at least as fresh as the lists it was missing from.
+    fn find_watched(&self, w: &WatchedPr) -> Option<&PullRequest> {
+        self.watched_prs.get(w).or_else(|| {
+            self.all_open_prs
+                .iter()
+                .chain(&self.inbox)
+                .chain(self.repo_prs.values().flatten())
+                .chain(&self.recently_merged)
+                .chain(&self.recently_closed)
+                .chain(self.repo_finished_prs.values().flatten())
+                .find(|pr| w.matches(&pr.repo_owner, &pr.repo_name, pr.number))
+        })
+    }
+
+    /// Watched PRs to fetch individually: those no loaded list has, or with
+    /// `all`, every one. Each is requested at most once per refresh.
+    pub fn watched_to_fetch(&mut self, all: bool) -> Vec<WatchedPr> {
+        let wanted: Vec<WatchedPr> = self
+            .watched
+            .iter()
+            .filter(|w| !self.watched_requested.contains(*w))
+            .filter(|w| all || self.find_watched(w).is_none())
+            .cloned()
+            .collect();
+        self.watched_requested.extend(wanted.iter().cloned());
+        wanted
+    }
+
+    pub fn is_watched(&self, pr: &PullRequest) -> bool {
+        self.watched
+            .iter()
+            .any(|w| w.matches(&pr.repo_owner, &pr.repo_name, pr.number))
+    }
+
+    /// Watch `pr`, or stop watching it. Returns whether it is watched now.
+    pub fn toggle_watch(&mut self, pr: &PullRequest) -> bool {
+        let watching = !self.is_watched(pr);
+        if watching {
+            self.watched.push(WatchedPr {
+                owner: pr.repo_owner.clone(),
+                name: pr.repo_name.clone(),
+                number: pr.number,
+            });
+        } else {
+            self.watched
+                .retain(|w| !w.matches(&pr.repo_owner, &pr.repo_name, pr.number));
+        }
+        self.rebuild_nav_tree();
+        watching
     }
 
     /// The currently highlighted PR (in the content pane), cloned.
@@ -271,6 +1658,34 @@ impl AppState {
         self.current_pr_list().into_iter().nth(self.content_cursor)
     }
 
+    /// What `n` / `N` jump between: the active review filter's category,
+    /// else PRs with changes requested.
+    pub fn match_category(&self) -> ReviewFilter {
+        match self.review_filter() {
+            ReviewFilter::All => ReviewFilter::ChangesRequested,
+            active => active,
+        }
+    }
+
+    /// Row of the next PR after the cursor (before it, when `!forward`) in
+    /// [`Self::match_category`], wrapping around; `None` if none matches.
+    /// Rows are those of the filtered list the cursor moves over, so with a
+    /// review filter active every row matches and this steps through them.
+    pub fn next_match(&self, forward: bool) -> Option<usize> {
+        let prs = self.current_pr_list();
+        let len = prs.len();
+        let category = self.match_category();
+        (1..=len)
+            .map(|step| {
+                if forward {
+                    (self.content_cursor + step) % len
+                } else {
+                    (self.content_cursor + len - step % len) % len
+                }
+            })
+            .find(|&row| category.matches(&prs[row]))
+    }
+
     /// Apply a freshly fetched merge state to the matching PR in every list, so
     /// the list column reflects the authoritative value once detail resolves.
     pub fn apply_fresh_merge_state(
@@ -282,11 +1697,248 @@ impl AppState {
         for pr in self.all_open_prs.iter_mut().chain(self.inbox.iter_mut()) {
             if pr.url == url {
                 pr.mergeable = mergeable.clone();
+                pr.is_behind = merge_state_status.as_deref() == Some("BEHIND");
                 pr.merge_state_status = merge_state_status.clone();
             }
         }
     }
 
+    /// The busiest repos of `org` (by open PR count) whose PR lists have not
+    /// been prefetched yet, at most `limit` of them.
+    pub fn repos_to_prefetch(&self, org: &str, limit: usize) -> Vec<(String, String)> {
+        let Some(org_data) = self.orgs.get(org) else {
+            return Vec::new();
+        };
+        let mut repos: Vec<_> = org_data
+            .repos
+            .iter()
+            .filter(|r| !r.is_archived && r.open_pr_count > 0)
+            .filter(|r| !self.repo_prs.contains_key(&r.full_name()))
+            .collect();
+        repos.sort_by(|a, b| {
+            b.open_pr_count
+                .cmp(&a.open_pr_count)
+                .then(a.name.cmp(&b.name))
+        });
+        repos
+            .into_iter()
+            .take(limit)
+            .map(|r| (r.owner.clone(), r.name.clone()))
+            .collect()
+    }
+
+    /// Rebuild `inbox_keys` from the current inbox.
+    pub fn rebuild_inbox_keys(&mut self) {
+        self.inbox_keys = self
+            .inbox
+            .iter()
+            .map(|pr| (pr.repo_full_name(), pr.number))
+            .collect();
+    }
+
+    /// Classify `pr` for the All PRs row tint.
+    pub fn pr_relation(&self, pr: &PullRequest) -> PrRelation {
+        if pr.author.eq_ignore_ascii_case(&self.viewer_login) {
+            PrRelation::Mine
+        } else if self.inbox_keys.contains(&(pr.repo_full_name(), pr.number)) {
+            PrRelation::ReviewRequested
+        } else {
+            PrRelation::Other
+        }
+    }
+
+    /// Whether `pr` changed since the user last looked at it. PRs that are new
+    /// since the previous session count too, except on the very first run when
+    /// there is nothing to compare against.
+    pub fn is_unread(&self, pr: &PullRequest) -> bool {
+        match self.last_seen.get(&pr.url) {
+            Some(seen) => pr.updated_at > *seen,
+            None => !self.last_seen.is_empty(),
+        }
+    }
+
+    /// Inbox PRs updated since the user last looked at them, in inbox order.
+    pub fn unread_inbox(&self) -> Vec<PullRequest> {
+        self.inbox
+            .iter()
+            .filter(|pr| self.is_unread(pr) && !self.is_muted(pr))
+            .cloned()
+            .collect()
+    }
+
+    /// Confirmation before opening more than `open_unread_limit` unread inbox
+    /// PRs at once.
+    pub fn open_unread_menu(&self, count: usize) -> Chooser<ChooserChoice> {
+        let limit = self.open_unread_limit;
+        Chooser::new(
+            format!("Open {} unread PRs?", count),
+            vec![
+                (
+                    format!("Open the first {}", limit),
+                    ChooserChoice::OpenUnread(limit),
+                ),
+                (
+                    format!("Open all {}", count),
+                    ChooserChoice::OpenUnread(count),
+                ),
+            ],
+        )
+    }
+
+    /// Clear the unread mark for `pr` by recording its current `updated_at`.
+    pub fn mark_seen(&mut self, pr: &PullRequest) {
+        self.last_seen.insert(pr.url.clone(), pr.updated_at);
+    }
+
+    /// Note the PRs in the table rows currently on screen as displayed.
+    pub fn note_displayed(&mut self) {
+        let prs = self.current_pr_list();
+        let grouped = self.groups_by_repo();
+        let end = self.content_offset + self.table_rows;
+        let mut row = 0;
+        for (i, pr) in prs.iter().enumerate() {
+            if grouped && (i == 0 || prs[i - 1].repo_full_name() != pr.repo_full_name()) {
+                row += 1;
+            }
+            if row >= end {
+                break;
+            }
+            if row >= self.content_offset {
+                self.displayed.insert(pr.url.clone(), pr.updated_at);
+            }
+            row += 1;
+        }
+    }
+
+    /// Count everything displayed in the view being left as seen.
+    pub fn mark_displayed_seen(&mut self) {
+        self.last_seen.extend(self.displayed.drain());
+    }
+
+    /// When each PR was last seen, to persist as next session's baseline:
+    /// those opened or displayed this session, those seen before it, and the
+    /// current view's displayed ones. PRs no longer loaded are dropped.
+    pub fn seen_snapshot(&self) -> HashMap<String, chrono::DateTime<chrono::Utc>> {
+        let loaded: HashSet<&str> = self
+            .inbox
+            .iter()
+            .chain(self.all_open_prs.iter())
+            .chain(self.repo_prs.values().flatten())
+            .map(|pr| pr.url.as_str())
+            .collect();
+        self.last_seen
+            .iter()
+            .chain(self.displayed.iter())
+            .filter(|(url, _)| loaded.contains(url.as_str()))
+            .map(|(url, seen)| (url.clone(), *seen))
+            .collect()
+    }
+
+    /// Move the current view to the front of the recently viewed list.
+    pub fn remember_view(&mut self) {
+        let view = self.content_view.clone();
+        self.recent_views.retain(|v| *v != view);
+        self.recent_views.push_front(view);
+        self.recent_views.truncate(MAX_RECENT_VIEWS);
+    }
+
+    /// Take the recently viewed list from the previous session, dropping
+    /// views of owners and standalone repos that are no longer configured.
+    pub fn restore_recent_views(&mut self, views: Vec<ContentView>) {
+        let standalone_owner = |owner: &str| {
+            self.standalone_repos
+                .iter()
+                .any(|r| r.split_once('/').is_some_and(|(o, _)| o == owner))
+        };
+        self.recent_views = views
+            .into_iter()
+            .filter(|view| match view {
+                ContentView::OrgOverview(owner) => {
+                    self.orgs.contains_key(owner) || standalone_owner(owner)
+                }
+                ContentView::RepoPrList { owner, name } => {
+                    self.orgs.contains_key(owner)
+                        || self
+                            .standalone_repos
+                            .contains(&format!("{}/{}", owner, name))
+                }
+                _ => true,
+            })
+            .take(MAX_RECENT_VIEWS)
+            .collect();
+    }
+
+    /// The quick-switch menu of recently viewed views, leaving out the
+    /// current one.
+    pub fn recent_views_menu(&self) -> Option<Chooser<ChooserChoice>> {
+        let items: Vec<(String, ChooserChoice)> = self
+            .recent_views
+            .iter()
+            .filter(|view| **view != self.content_view)
+            .map(|view| (view_label(view), ChooserChoice::SwitchView(view.clone())))
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+        Some(Chooser::new("Recently viewed", items))
+    }
+
+    /// The "open…" menu for the highlighted repo or owner: the nav node in the
+    /// nav pane, the highlighted PR's repo in the content pane.
+    pub fn open_menu(&self) -> Option<Chooser<ChooserChoice>> {
+        let (owner, repo) = match self.focused_pane {
+            FocusedPane::Content => {
+                let pr = self.selected_pr()?;
+                (pr.repo_owner, Some(pr.repo_name))
+            }
+            FocusedPane::Navigation => match self.nav_nodes.get(self.nav_cursor)? {
+                NavNode::Repo { owner, name, .. } => (owner.clone(), Some(name.clone())),
+                NavNode::Org(owner) => (owner.clone(), None),
+                _ => return None,
+            },
+        };
+        let items: Vec<(String, ChooserChoice)> = match &repo {
+            Some(name) => {
+                let base = format!("https://github.com/{}/{}", owner, name);
+                [
+                    ("Code", ""),
+                    ("Pull requests", "/pulls"),
+                    ("Issues", "/issues"),
+                    ("Actions", "/actions"),
+                    ("Releases", "/releases"),
+                    ("Settings", "/settings"),
+                ]
+                .into_iter()
+                .map(|(label, path)| {
+                    (
+                        label.to_string(),
+                        ChooserChoice::OpenUrl(format!("{}{}", base, path)),
+                    )
+                })
+                .collect()
+            }
+            // Owners may be users or orgs, so only pages both have.
+            None => vec![
+                (
+                    "Overview".to_string(),
+                    ChooserChoice::OpenUrl(format!("https://github.com/{}", owner)),
+                ),
+                (
+                    "Repositories".to_string(),
+                    ChooserChoice::OpenUrl(format!(
+                        "https://github.com/{}?tab=repositories",
+                        owner
+                    )),
+                ),
+            ],
+        };
+        let title = match repo {
+            Some(name) => format!("Open {}/{}", owner, name),
+            None => format!("Open {}", owner),
+        };
+        Some(Chooser::new(title, items))
+    }
+
     pub fn selected_nav_url(&self) -> Option<String> {
         self.nav_nodes
             .get(self.nav_cursor)
@@ -1,74 +1,147 @@
+use std::collections::HashSet;
+
 use crate::app::actions::{Action, DataPayload, SideEffect};
 use crate::app::state::{
-    AppState, ContentView, DiffEntry, FocusedPane, NavNode, OrgData, Overlay, PrDetailEntry,
+    AppState, ChooserChoice, ContentView, DiffEntry, FocusedPane, NavNode, OrgData, Overlay,
+    PrDetailEntry, ReviewFilter,
 };
+use crate::github::models::{PrHistoryKind, PrStateFilter, PullRequest};
+use crate::util::{export, time};
+
+/// How many of an org's busiest repos get their PR lists prefetched on expand.
+const PREFETCH_TOP_REPOS: usize = 5;
+
+/// Upper bound on a typed count prefix, so holding a digit can't overflow.
+const MAX_COUNT: usize = 9999;
 
 pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
+    let view = state.content_view.clone();
+    let effects = reduce(state, action);
+    // Nobody is looking at the old view's data any more.
+    if state.content_view != view {
+        state.cancel_view_fetch(&view);
+        state.mark_displayed_seen();
+    }
+    state.scroll_to_cursor();
+    state.note_displayed();
+    effects
+}
+
+fn reduce(state: &mut AppState, action: Action) -> Vec<SideEffect> {
+    let background = action.is_background();
+    if !background && !matches!(action, Action::StartLetterJump) {
+        state.pending_jump = false;
+    }
+
+    // A pending count applies to the next key only; motions consume it and any
+    // other key discards it.
+    let count = if background || matches!(action, Action::CountDigit(_)) {
+        None
+    } else {
+        state.pending_count.take()
+    };
+
+    // Transient status text lasts until the next key; background results
+    // don't dismiss it.
+    if !background && !matches!(action, Action::ShowFullTitle) {
+        state.status_message = None;
+    }
+
+    if !background {
+        state.last_input = Some(time::now());
+    }
+
     match action {
         Action::Quit => {
             state.should_quit = true;
             vec![]
         }
         Action::MoveUp => {
+            let steps = count.unwrap_or(1);
+            if state.error_message.is_some() {
+                state.error_scroll = state.error_scroll.saturating_sub(steps as u16);
+                return vec![];
+            }
             // While the diff overlay is open, j/k scroll the diff instead of moving
             // the underlying selection.
             if state.overlay == Overlay::Diff {
-                state.diff_scroll = state.diff_scroll.saturating_sub(1);
+                state.diff_scroll = state.diff_scroll.saturating_sub(steps as u16);
                 return vec![];
             }
             if state.overlay == Overlay::GitLog {
                 return vec![];
             }
-            match state.focused_pane {
-                FocusedPane::Navigation => {
-                    if state.nav_cursor > 0 {
-                        state.nav_cursor -= 1;
-                    }
-                }
-                FocusedPane::Content => {
-                    if state.content_cursor > 0 {
-                        state.content_cursor -= 1;
-                    }
-                }
-            }
+            let row = cursor_row(state).saturating_sub(steps);
+            set_cursor_row(state, row);
             vec![]
         }
         Action::MoveDown => {
+            let steps = count.unwrap_or(1);
+            if state.error_message.is_some() {
+                state.error_scroll = state
+                    .error_scroll
+                    .saturating_add(steps as u16)
+                    .min(state.error_max_scroll);
+                return vec![];
+            }
             if state.overlay == Overlay::Diff {
-                state.diff_scroll = state.diff_scroll.saturating_add(1);
+                state.diff_scroll = state.diff_scroll.saturating_add(steps as u16);
                 return vec![];
             }
             if state.overlay == Overlay::GitLog {
                 return vec![];
             }
-            match state.focused_pane {
-                FocusedPane::Navigation => {
-                    if state.nav_cursor + 1 < state.nav_nodes.len() {
-                        state.nav_cursor += 1;
-                    }
-                }
-                FocusedPane::Content => {
-                    let max = state.current_pr_list().len().saturating_sub(1);
-                    if state.content_cursor < max {
-                        state.content_cursor += 1;
-                    }
-                }
+            let row = cursor_row(state).saturating_add(steps);
+            set_cursor_row(state, row);
+            vec![]
+        }
+        Action::JumpTop => {
+            set_cursor_row(state, count.map_or(0, |n| n.saturating_sub(1)));
+            vec![]
+        }
+        Action::JumpBottom => {
+            set_cursor_row(state, count.map_or(usize::MAX, |n| n.saturating_sub(1)));
+            vec![]
+        }
+        Action::CountDigit(digit) => {
+            let n = state.pending_count.unwrap_or(0) * 10 + digit as usize;
+            state.pending_count = Some(n.min(MAX_COUNT));
+            vec![]
+        }
+        // Pending prefixes were already discarded above.
+        Action::ClearPending => vec![],
+        Action::StartLetterJump => {
+            state.pending_jump = true;
+            vec![]
+        }
+        Action::JumpToLetter(letter) => {
+            match state.next_nav_match(letter) {
+                Some(i) => state.nav_cursor = i,
+                None => state.status_message = Some(format!("No entry starting with '{}'", letter)),
             }
             vec![]
         }
         Action::Select => {
+            // `3<Enter>` selects the third row.
+            if let Some(n) = count {
+                set_cursor_row(state, n.saturating_sub(1));
+            }
             if state.focused_pane == FocusedPane::Navigation {
                 if let Some(node) = state.nav_nodes.get(state.nav_cursor).cloned() {
                     match node {
                         NavNode::Org(ref org) => {
+                            let mut effects = Vec::new();
                             if state.nav_expanded.contains(org) {
                                 state.nav_expanded.remove(org);
                             } else {
                                 state.nav_expanded.insert(org.clone());
+                                effects = org_prefetches(state, org);
                             }
                             state.content_view = ContentView::OrgOverview(org.clone());
                             state.content_cursor = 0;
+                            state.remember_view();
                             state.rebuild_nav_tree();
+                            return effects;
                         }
                         NavNode::Repo { owner, name, .. } => {
                             state.content_view = ContentView::RepoPrList {
@@ -76,6 +149,7 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
                                 name: name.clone(),
                             };
                             state.content_cursor = 0;
+                            state.pr_state_filter = PrStateFilter::Open;
                         }
                         NavNode::AllPrs => {
                             state.content_view = ContentView::AllOpenPrs;
@@ -85,19 +159,49 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
                             state.content_view = ContentView::Inbox;
                             state.content_cursor = 0;
                         }
+                        NavNode::RecentlyMerged => {
+                            state.content_view = ContentView::RecentlyMerged;
+                            state.content_cursor = 0;
+                        }
+                        NavNode::RecentlyClosed => {
+                            state.content_view = ContentView::RecentlyClosed;
+                            state.content_cursor = 0;
+                        }
+                        NavNode::FailingChecks => {
+                            state.content_view = ContentView::FailingChecks;
+                            state.content_cursor = 0;
+                        }
+                        NavNode::Watched => {
+                            state.content_view = ContentView::Watched;
+                            state.content_cursor = 0;
+                            state.remember_view();
+                            return watched_fetches(state, false);
+                        }
+                        NavNode::MoreRepos(org, _) => {
+                            state.content_view = ContentView::OrgOverview(org);
+                            state.content_cursor = 0;
+                        }
+                        // Topic headings are labels only.
+                        NavNode::Topic(_) | NavNode::ReposSection => return vec![],
                     }
+                    state.remember_view();
                 }
             } else {
                 // In content pane, Enter opens PR in browser
-                if let Some(url) = state.selected_pr_url() {
-                    return vec![SideEffect::OpenUrl(url)];
+                if let Some(pr) = state.selected_pr() {
+                    state.mark_seen(&pr);
+                    return vec![SideEffect::OpenUrl(pr.url)];
                 }
             }
             vec![]
         }
+        Action::NextOwner => cycle_owner(state, true),
+        Action::PrevOwner => cycle_owner(state, false),
         Action::Back => {
             if state.help_open {
                 state.help_open = false;
+            } else if state.pr_info_open {
+                state.pr_info_open = false;
             } else if state.search_active {
                 state.search_active = false;
                 state.search_query.clear();
@@ -105,6 +209,15 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
                 state.error_message = None;
             } else if state.overlay != Overlay::None {
                 state.overlay = Overlay::None;
+            } else if state.review_filter() != ReviewFilter::All {
+                state.review_filters.remove(&state.content_view);
+                state.content_cursor = 0;
+            } else if !state.label_filter.is_empty() {
+                state.label_filter.clear();
+                state.content_cursor = 0;
+            } else if state.involves_me_only && state.content_view == ContentView::AllOpenPrs {
+                state.involves_me_only = false;
+                state.content_cursor = 0;
             } else if state.focused_pane == FocusedPane::Content {
                 state.focused_pane = FocusedPane::Navigation;
             }
@@ -117,17 +230,166 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
             };
             vec![]
         }
-        Action::Refresh => {
-            state.loading = true;
+        Action::Refresh | Action::AutoRefresh if state.offline => vec![],
+        Action::RefreshCurrentView if state.offline => vec![],
+        Action::RefreshInbox if state.offline => vec![],
+        Action::RefreshInbox => {
+            state.inbox_loading = true;
+            vec![SideEffect::RefreshView(ContentView::Inbox)]
+        }
+        // Intercepted by the event loop before it gets here.
+        Action::ReloadConfig | Action::ResolveToken => vec![],
+        Action::ViewerChanged(login) => {
+            let cached = std::mem::replace(&mut state.viewer_login, login);
+            let effects = refresh(state);
+            state.error_message = Some(format!(
+                "The token signs in as {}, not {} as cached. Reloading as {}.",
+                state.viewer_login, cached, state.viewer_login
+            ));
+            state.error_scroll = 0;
+            effects
+        }
+        Action::WentOffline => {
+            state.offline = true;
+            // Whatever the unreachable source was fetching is abandoned;
+            // start over from the cache.
+            state.loading_orgs.clear();
+            state.inbox_loading = false;
+            let effects = refresh(state);
+            state.status_message = Some("GitHub unreachable, showing cached data".to_string());
+            effects
+        }
+        Action::UpdateAvailable(version) => {
+            state.status_message = Some(format!("v{} available", version));
+            vec![]
+        }
+        Action::TokenRejected => {
+            state.token_rejected = true;
+            state.loading = false;
+            state.loading_orgs.clear();
+            state.inbox_loading = false;
+            vec![]
+        }
+        Action::TokenRenewed => {
+            state.token_rejected = false;
+            let effects = update(state, Action::Refresh);
+            state.status_message = Some("Token resolved, refreshing".to_string());
+            effects
+        }
+        Action::FocusGained => {
+            let debounce = chrono::Duration::seconds(state.focus_refresh_debounce_secs as i64);
+            // No refresh has finished yet means one is still in flight.
+            let due = state
+                .last_refresh
+                .is_some_and(|at| time::now() - at >= debounce);
+            if due && state.auto_refresh_allowed() {
+                refresh(state)
+            } else {
+                vec![]
+            }
+        }
+        Action::ConfigReloaded(config) => {
+            state.focus_refresh_debounce_secs = config.dashboard.focus_refresh_debounce_secs;
+            state.idle_refresh_delay_secs = config.dashboard.idle_refresh_delay_secs;
+            state.open_unread_limit = config.dashboard.open_unread_limit;
+            state.inbox_sort = config.dashboard.inbox_sort;
+            state.sort_inbox();
+            state.apply_ui_config(&config.ui);
+            state.apply_org_colors(&config.github.org_color);
+            state.config_path = config.source.clone();
+            state.mute_repos = config.github.mute_repos.clone();
+            let typed = config.github.owners();
+            let owners: Vec<String> = typed.iter().map(|o| o.login.clone()).collect();
+            let before: HashSet<String> = state.orgs.keys().cloned().collect();
+            let added = state.sync_owners(&owners);
+            let repos = config.github.standalone_repos();
+            let added_repos: Vec<String> = repos
+                .iter()
+                .filter(|r| !state.standalone_repos.contains(r))
+                .cloned()
+                .collect();
+            let repos_changed = repos != state.standalone_repos;
+            state.standalone_repos = repos;
+            state.rebuild_nav_tree();
+            state.status_message = Some("Config reloaded".to_string());
+            if before == state.orgs.keys().cloned().collect() && !repos_changed {
+                return vec![];
+            }
+            let mut effects: Vec<SideEffect> = added
+                .into_iter()
+                .filter_map(|login| typed.iter().find(|o| o.login == login))
+                .map(SideEffect::fetch_repos)
+                .collect();
+            for full_name in &added_repos {
+                if let Some((owner, name)) = full_name.split_once('/') {
+                    effects.push(prefetch_repo(state, owner.to_string(), name.to_string()));
+                }
+            }
+            // Cross-owner searches are cached under fixed keys, so they have
+            // to be expired rather than just re-fetched.
+            effects.extend(
+                [
+                    ContentView::AllOpenPrs,
+                    ContentView::RecentlyMerged,
+                    ContentView::RecentlyClosed,
+                ]
+                .into_iter()
+                .map(SideEffect::RefreshView),
+            );
+            effects
+        }
+        Action::CachedDataAge(written_at) => {
+            state.offline_data_as_of = Some(match state.offline_data_as_of {
+                Some(prev) => prev.min(written_at),
+                None => written_at,
+            });
+            vec![]
+        }
+        Action::Refresh | Action::AutoRefresh => refresh(state),
+        Action::RefreshCurrentView => {
             state.error_message = None;
-            // Drop cached PR details / diffs so they are re-fetched fresh.
-            state.pr_details.clear();
-            state.pr_diffs.clear();
-            vec![SideEffect::RefreshAll]
+            if state.content_view == ContentView::Watched {
+                // Watched PRs span every list; re-fetch each one directly.
+                state.watched_requested.clear();
+                return watched_fetches(state, true);
+            }
+            if let ContentView::RepoPrList { owner, name } = &state.content_view
+                && state.pr_state_filter != PrStateFilter::Open
+            {
+                // Merged/closed lists live only in memory; re-fetch directly.
+                state
+                    .repo_finished_prs
+                    .remove(&(format!("{}/{}", owner, name), state.pr_state_filter));
+                let (owner, name) = (owner.clone(), name.clone());
+                let cancel = state.begin_view_fetch(state.content_view.clone());
+                return vec![SideEffect::FetchRepoFinishedPrs {
+                    owner,
+                    name,
+                    state: state.pr_state_filter,
+                    cancel,
+                }];
+            }
+            if let ContentView::RepoPrList { owner, name } = &state.content_view {
+                let (owner, name) = (owner.clone(), name.clone());
+                let cancel = state.begin_view_fetch(state.content_view.clone());
+                return vec![SideEffect::RefreshRepoPrs {
+                    owner,
+                    name,
+                    cancel,
+                }];
+            }
+            let mut effects = vec![SideEffect::RefreshView(state.content_view.clone())];
+            if state.involves_me_only && state.content_view == ContentView::AllOpenPrs {
+                effects.push(SideEffect::FetchInvolvedPrs);
+            }
+            effects
         }
         Action::OpenInBrowser => {
             let url = match state.focused_pane {
-                FocusedPane::Content => state.selected_pr_url(),
+                FocusedPane::Content => state.selected_pr().map(|pr| {
+                    state.mark_seen(&pr);
+                    pr.url
+                }),
                 FocusedPane::Navigation => state.selected_nav_url(),
             };
             if let Some(url) = url {
@@ -136,6 +398,18 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
                 vec![]
             }
         }
+        Action::OpenUnreadInbox => {
+            let prs = state.unread_inbox();
+            if prs.is_empty() {
+                state.status_message = Some("No unread inbox PRs".into());
+                return vec![];
+            }
+            if prs.len() > state.open_unread_limit {
+                state.chooser = Some(state.open_unread_menu(prs.len()));
+                return vec![];
+            }
+            open_prs(state, prs)
+        }
         Action::ToggleSearch => {
             if state.search_active {
                 state.search_active = false;
@@ -179,6 +453,243 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
             state.content_cursor = 0;
             vec![]
         }
+        Action::CycleReviewFilter => {
+            let next = state.review_filter().next();
+            if next == ReviewFilter::All {
+                state.review_filters.remove(&state.content_view);
+            } else {
+                state
+                    .review_filters
+                    .insert(state.content_view.clone(), next);
+            }
+            state.content_cursor = 0;
+            vec![]
+        }
+        Action::ToggleInvolvesMe => {
+            if state.content_view != ContentView::AllOpenPrs {
+                state.status_message = Some("Only All PRs filters by involvement".into());
+                return vec![];
+            }
+            state.involves_me_only = !state.involves_me_only;
+            state.content_cursor = 0;
+            if state.involves_me_only && state.involved_urls.is_none() {
+                vec![SideEffect::FetchInvolvedPrs]
+            } else {
+                vec![]
+            }
+        }
+        Action::ToggleEmptyRepos => {
+            state.hide_empty_repos = !state.hide_empty_repos;
+            state.rebuild_nav_tree();
+            state.status_message = Some(if state.hide_empty_repos {
+                "Hiding repos without open PRs".to_string()
+            } else {
+                "Showing all repos".to_string()
+            });
+            vec![]
+        }
+        Action::SelectAgeBucket(bucket) => {
+            if bucket < 4 {
+                state.age_bucket_filter = if state.age_bucket_filter == Some(bucket) {
+                    None
+                } else {
+                    Some(bucket)
+                };
+                state.content_cursor = 0;
+            }
+            vec![]
+        }
+        Action::CycleSort => {
+            state.sort = state.sort.next();
+            state.content_cursor = 0;
+            vec![]
+        }
+        Action::CycleInboxSort => {
+            state.inbox_sort = state.inbox_sort.next();
+            state.sort_inbox();
+            if state.content_view == ContentView::Inbox {
+                state.content_cursor = 0;
+            }
+            vec![]
+        }
+        Action::ScrollColumnsLeft => {
+            state.column_offset = state.column_offset.saturating_sub(1);
+            vec![]
+        }
+        Action::ScrollColumnsRight => {
+            // The last non-title column stays reachable; the renderer trims
+            // further when the table is wide enough to show everything.
+            let others = state.columns.len().saturating_sub(1);
+            state.column_offset = (state.column_offset + 1).min(others.saturating_sub(1));
+            vec![]
+        }
+        Action::CyclePrStateFilter => {
+            let ContentView::RepoPrList { owner, name } = &state.content_view else {
+                return vec![];
+            };
+            let (owner, name) = (owner.clone(), name.clone());
+            state.pr_state_filter = state.pr_state_filter.next();
+            state.content_cursor = 0;
+            let key = (format!("{}/{}", owner, name), state.pr_state_filter);
+            if state.pr_state_filter == PrStateFilter::Open
+                || state.repo_finished_prs.contains_key(&key)
+            {
+                return vec![];
+            }
+            let cancel = state.begin_view_fetch(state.content_view.clone());
+            vec![SideEffect::FetchRepoFinishedPrs {
+                owner,
+                name,
+                state: state.pr_state_filter,
+                cancel,
+            }]
+        }
+        Action::ToggleRefreshLock => {
+            state.toggle_refresh_lock();
+            vec![]
+        }
+        Action::ToggleAutoRefreshPause => {
+            state.auto_refresh_paused = !state.auto_refresh_paused;
+            vec![]
+        }
+        Action::ShowFullTitle => {
+            state.status_message = match state.status_message.take() {
+                Some(_) => None,
+                None => state.selected_pr().map(|pr| pr.title),
+            };
+            vec![]
+        }
+        Action::OpenMenu => {
+            state.chooser = state.open_menu();
+            vec![]
+        }
+        Action::OpenRecentViews => {
+            state.chooser = state.recent_views_menu();
+            if state.chooser.is_none() {
+                state.status_message = Some("No recently viewed views".into());
+            }
+            vec![]
+        }
+        Action::OpenLabelMenu => {
+            state.chooser = state.label_menu();
+            if state.chooser.is_none() {
+                state.status_message = Some("No labels to filter by".into());
+            }
+            vec![]
+        }
+        Action::ChooserUp => {
+            if let Some(chooser) = &mut state.chooser {
+                chooser.move_up();
+            }
+            vec![]
+        }
+        Action::ChooserDown => {
+            if let Some(chooser) = &mut state.chooser {
+                chooser.move_down();
+            }
+            vec![]
+        }
+        Action::ChooserConfirm => match state.chooser.take().and_then(|c| c.into_selected()) {
+            Some(ChooserChoice::OpenUrl(url)) => vec![SideEffect::OpenUrl(url)],
+            Some(ChooserChoice::OpenUnread(count)) => {
+                let mut prs = state.unread_inbox();
+                prs.truncate(count);
+                open_prs(state, prs)
+            }
+            Some(ChooserChoice::FilterLabel(label)) => {
+                state.label_filter.push(label);
+                state.content_cursor = 0;
+                vec![]
+            }
+            Some(ChooserChoice::SwitchView(view)) => {
+                state.content_view = view;
+                state.content_cursor = 0;
+                state.pr_state_filter = PrStateFilter::Open;
+                state.remember_view();
+                if state.content_view == ContentView::Watched {
+                    return watched_fetches(state, false);
+                }
+                vec![]
+            }
+            None => vec![],
+        },
+        Action::CloseChooser => {
+            state.chooser = None;
+            vec![]
+        }
+        Action::CopyPrLink => match state.selected_pr() {
+            Some(pr) => {
+                state.status_message = Some(format!("Copied {}#{}", pr.repo_name, pr.number));
+                vec![SideEffect::CopyToClipboard(export::pr_markdown_link(&pr))]
+            }
+            None => vec![],
+        },
+        Action::CopyPrList => {
+            let prs = state.current_pr_list();
+            if prs.is_empty() {
+                return vec![];
+            }
+            state.status_message = Some(format!("Copied {} PRs as a Markdown table", prs.len()));
+            vec![SideEffect::CopyToClipboard(export::prs_markdown_table(
+                &prs,
+            ))]
+        }
+        Action::TogglePrInfo => {
+            state.pr_info_open = !state.pr_info_open && state.selected_pr().is_some();
+            vec![]
+        }
+        Action::NextMatch | Action::PrevMatch => {
+            match state.next_match(matches!(action, Action::NextMatch)) {
+                Some(row) => state.content_cursor = row,
+                None => {
+                    let what = state.match_category().label().unwrap_or_default();
+                    state.status_message = Some(format!("No matching PRs ({})", what));
+                }
+            }
+            vec![]
+        }
+        Action::FilterByAuthor => {
+            // Toggles just the `author:` term; the rest of the search stays.
+            let selected = state.selected_pr();
+            let mut terms: Vec<String> = state
+                .search_query
+                .split_whitespace()
+                .map(str::to_string)
+                .collect();
+            if terms.iter().any(|t| t.starts_with("author:")) {
+                terms.retain(|t| !t.starts_with("author:"));
+            } else if let Some(pr) = &selected {
+                terms.push(format!("author:{}", pr.author));
+            } else {
+                return vec![];
+            }
+            state.search_query = terms.join(" ");
+            // Keep the highlighted PR under the cursor when it is still listed.
+            state.content_cursor = selected
+                .and_then(|sel| {
+                    state
+                        .current_pr_list()
+                        .iter()
+                        .position(|pr| pr.url == sel.url)
+                })
+                .unwrap_or(0);
+            vec![]
+        }
+        Action::ToggleWatch => {
+            let Some(pr) = state.selected_pr() else {
+                return vec![];
+            };
+            let verb = if state.toggle_watch(&pr) {
+                "Watching"
+            } else {
+                "Stopped watching"
+            };
+            state.status_message = Some(format!("{} {}#{}", verb, pr.repo_name, pr.number));
+            // Unwatching in the Watched view shrinks the list under the cursor.
+            let len = state.current_pr_list().len();
+            state.content_cursor = state.content_cursor.min(len.saturating_sub(1));
+            vec![SideEffect::SaveWatchlist(state.watched.clone())]
+        }
         Action::SearchInput(ch) => {
             if state.search_active {
                 state.search_query.push(ch);
@@ -199,6 +710,18 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
             vec![]
         }
         Action::DataLoaded(payload) => {
+            let mut effects = Vec::new();
+            // The other payloads aren't part of a full refresh and return early.
+            let section = match &payload {
+                DataPayload::OrgRepos { org, .. } => Some(ContentView::OrgOverview(org.clone())),
+                DataPayload::InboxPrs { .. } => Some(ContentView::Inbox),
+                DataPayload::AllOpenPrs { .. } => Some(ContentView::AllOpenPrs),
+                DataPayload::PrHistory { kind, .. } => Some(match kind {
+                    PrHistoryKind::Merged => ContentView::RecentlyMerged,
+                    PrHistoryKind::Closed => ContentView::RecentlyClosed,
+                }),
+                _ => None,
+            };
             match payload {
                 DataPayload::OrgRepos {
                     org,
@@ -206,24 +729,207 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
                     rate_limit,
                 } => {
                     state.loading_orgs.remove(&org);
-                    state.rate_limit = rate_limit;
-                    state.orgs.insert(org.clone(), OrgData { name: org, repos });
+                    state.unknown_owners.remove(&org);
+                    state
+                        .failed_views
+                        .remove(&ContentView::OrgOverview(org.clone()));
+                    record_success(state, ContentView::OrgOverview(org.clone()));
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
+                    // Offline data is old; sampling it would skew the trend.
+                    if !state.offline {
+                        let now = time::now();
+                        let mut changed = false;
+                        for repo in repos.iter().filter(|r| !r.is_archived) {
+                            changed |=
+                                state
+                                    .pr_history
+                                    .append(&repo.full_name(), now, repo.open_pr_count);
+                        }
+                        changed |= state.pr_history.prune(now);
+                        if changed {
+                            effects.push(SideEffect::SavePrHistory(state.pr_history.clone()));
+                        }
+                    }
+                    state.orgs.insert(
+                        org.clone(),
+                        OrgData {
+                            name: org.clone(),
+                            repos,
+                        },
+                    );
                     state.rebuild_nav_tree();
+                    // Orgs already expanded (restored, or refreshed while
+                    // open) get the same prefetch as expanding them.
+                    if state.nav_expanded.contains(&org) {
+                        effects.extend(org_prefetches(state, &org));
+                    }
+                }
+                DataPayload::OwnerNotFound { owner } => {
+                    // Shown on the owner's nav row, not as a modal.
+                    let view = ContentView::OrgOverview(owner.clone());
+                    state.loading_orgs.remove(&owner);
+                    state.record_fetch(view.clone(), false);
+                    effects.extend(refresh_section_done(state, &view, false));
+                    state.unknown_owners.insert(owner);
                 }
                 DataPayload::InboxPrs { prs, rate_limit } => {
-                    state.rate_limit = rate_limit;
+                    state.inbox_loading = false;
+                    state.failed_views.remove(&ContentView::Inbox);
+                    record_success(state, ContentView::Inbox);
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
                     state.inbox = prs;
+                    state.sort_inbox();
+                    state.rebuild_inbox_keys();
                 }
                 DataPayload::AllOpenPrs { prs, rate_limit } => {
-                    state.rate_limit = rate_limit;
+                    state.failed_views.remove(&ContentView::AllOpenPrs);
+                    record_success(state, ContentView::AllOpenPrs);
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
                     state.all_open_prs = prs;
+                    state.rebuild_inbox_keys();
+                    if !state.standalone_repos.is_empty() {
+                        state.rebuild_nav_tree();
+                    }
+                }
+                DataPayload::PrHistory {
+                    kind,
+                    prs,
+                    rate_limit,
+                } => {
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
+                    match kind {
+                        PrHistoryKind::Merged => {
+                            state.failed_views.remove(&ContentView::RecentlyMerged);
+                            record_success(state, ContentView::RecentlyMerged);
+                            state.recently_merged = prs;
+                        }
+                        PrHistoryKind::Closed => {
+                            state.failed_views.remove(&ContentView::RecentlyClosed);
+                            record_success(state, ContentView::RecentlyClosed);
+                            state.recently_closed = prs;
+                        }
+                    }
+                }
+                DataPayload::RepoPrs {
+                    owner,
+                    name,
+                    prs,
+                    rate_limit,
+                    partial,
+                    cancel,
+                } => {
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
+                    let full_name = format!("{}/{}", owner, name);
+                    let standalone = state.standalone_repos.contains(&full_name);
+                    let view = ContentView::RepoPrList { owner, name };
+                    state.end_view_fetch(&view, &cancel);
+                    // A cut-short list only stands in until a whole one is
+                    // fetched; it never replaces one.
+                    if partial {
+                        state.repo_prs.entry(full_name).or_insert(prs);
+                    } else {
+                        state.repo_prs.insert(full_name, prs);
+                        record_success(state, view);
+                    }
+                    if standalone {
+                        state.rebuild_nav_tree();
+                    }
+                    // Background prefetch; not part of the initial load.
+                    return vec![];
+                }
+                DataPayload::RepoPrsFailed {
+                    owner,
+                    name,
+                    cancel,
+                } => {
+                    state.end_view_fetch(&ContentView::RepoPrList { owner, name }, &cancel);
+                    return vec![];
+                }
+                DataPayload::RepoFinishedPrs {
+                    owner,
+                    name,
+                    state: pr_state,
+                    prs,
+                    rate_limit,
+                    cancel,
+                } => {
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
+                    let view = ContentView::RepoPrList {
+                        owner: owner.clone(),
+                        name: name.clone(),
+                    };
+                    state.end_view_fetch(&view, &cancel);
+                    let key = (format!("{}/{}", owner, name), pr_state);
+                    state.failed_finished_prs.remove(&key);
+                    state.repo_finished_prs.insert(key, prs);
+                    return vec![];
+                }
+                DataPayload::RepoFinishedPrsFailed {
+                    owner,
+                    name,
+                    state: pr_state,
+                    msg,
+                    cancel,
+                } => {
+                    let view = ContentView::RepoPrList {
+                        owner: owner.clone(),
+                        name: name.clone(),
+                    };
+                    state.end_view_fetch(&view, &cancel);
+                    state
+                        .failed_finished_prs
+                        .insert((format!("{}/{}", owner, name), pr_state));
+                    // Not part of a refresh, so it leaves `loading` alone.
+                    state.error_message = Some(msg);
+                    state.error_scroll = 0;
+                    return vec![];
+                }
+                DataPayload::InvolvedPrs { prs, rate_limit } => {
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
+                    state.involved_urls = Some(prs.into_iter().map(|pr| pr.url).collect());
+                    return vec![];
+                }
+                DataPayload::InvolvedPrsFailed { msg } => {
+                    state.status_message = Some(format!(
+                        "{}; matching on author and review requests only",
+                        msg
+                    ));
+                    return vec![];
+                }
+                DataPayload::WatchedPr {
+                    watched,
+                    pr,
+                    rate_limit,
+                } => {
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
+                    state.failed_views.remove(&ContentView::Watched);
+                    state.watched_prs.insert(watched, *pr);
+                    return vec![];
                 }
                 DataPayload::PrDetailLoaded {
                     key,
                     detail,
                     rate_limit,
                 } => {
-                    state.rate_limit = rate_limit;
+                    if let Some(rl) = rate_limit {
+                        state.rate_limit.observe(rl);
+                    }
                     // Upgrade the list column to the freshly computed merge state.
                     state.apply_fresh_merge_state(
                         &key,
@@ -249,24 +955,236 @@ pub fn update(state: &mut AppState, action: Action) -> Vec<SideEffect> {
                 }
             }
 
+            if let Some(section) = section {
+                effects.extend(refresh_section_done(state, &section, true));
+            }
+
             // Check if all loading complete
             if state.loading_orgs.is_empty() {
                 state.loading = false;
-                state.last_refresh = Some(chrono::Utc::now());
+                state.stale = false;
+                state.last_refresh = Some(time::now());
+                // Whatever the refreshed lists no longer carry is fetched on
+                // its own.
+                if state.content_view == ContentView::Watched {
+                    effects.extend(watched_fetches(state, false));
+                }
             }
 
-            vec![]
+            effects
+        }
+        Action::FetchFailed { view, msg } => {
+            match &view {
+                ContentView::Inbox => state.inbox_loading = false,
+                ContentView::OrgOverview(owner) => {
+                    state.loading_orgs.remove(owner);
+                }
+                _ => {}
+            }
+            let mut effects = refresh_section_done(state, &view, false);
+            state.record_fetch(view.clone(), false);
+            state.failed_views.insert(view);
+            // Only the first failure of a streak pops the modal; after that
+            // the status bar says when the next retry is. A rejected token has
+            // its own prompt.
+            if state.token_rejected || state.refresh_retry_at.is_some() {
+                state.loading = false;
+                state.loading_orgs.clear();
+                return effects;
+            }
+            effects.extend(update(state, Action::LoadError(msg)));
+            effects
         }
         Action::LoadError(msg) => {
             state.loading = false;
             state.loading_orgs.clear();
+            state.inbox_loading = false;
             state.error_message = Some(msg);
+            state.error_scroll = 0;
+            vec![]
+        }
+        Action::RefreshSummary(summary) => {
+            state.status_message = Some(summary.message());
             vec![]
         }
         Action::DismissError => {
             state.error_message = None;
             vec![]
         }
+        Action::CopyError => match &state.error_message {
+            Some(msg) => {
+                let effect = SideEffect::CopyToClipboard(msg.clone());
+                state.status_message = Some("Copied the error text".to_string());
+                vec![effect]
+            }
+            None => vec![],
+        },
         Action::Tick => vec![],
     }
 }
+
+/// Start a full refresh: everything is fetched again and per-PR data is
+/// dropped.
+fn refresh(state: &mut AppState) -> Vec<SideEffect> {
+    state.loading = true;
+    // The refresh is done, and the data no longer stale, once every owner's
+    // repos are back.
+    state.loading_orgs = state.orgs.keys().cloned().collect();
+    state.rate_limit.begin_cycle();
+    state.error_message = None;
+    // Drop cached PR details / diffs / prefetched repo lists so they are
+    // re-fetched fresh.
+    state.pr_details.clear();
+    state.pr_diffs.clear();
+    state.repo_prs.clear();
+    state.repo_finished_prs.clear();
+    state.watched_prs.clear();
+    state.watched_requested.clear();
+    state.involved_urls = None;
+    state.begin_refresh_summary();
+    let mut effects = vec![SideEffect::RefreshAll];
+    effects.extend(standalone_prefetches(state));
+    if state.involves_me_only {
+        effects.push(SideEffect::FetchInvolvedPrs);
+    }
+    effects
+}
+
+/// Fetches for the standalone repos' PR lists, which get the per-repo query
+/// rather than an owner listing.
+pub fn standalone_prefetches(state: &mut AppState) -> Vec<SideEffect> {
+    let repos = state.standalone_repos.clone();
+    repos
+        .iter()
+        .filter_map(|full_name| full_name.split_once('/'))
+        .map(|(owner, name)| prefetch_repo(state, owner.to_string(), name.to_string()))
+        .collect()
+}
+
+/// Prefetches for the PR lists of `org`'s busiest repos.
+fn org_prefetches(state: &mut AppState, org: &str) -> Vec<SideEffect> {
+    state
+        .repos_to_prefetch(org, PREFETCH_TOP_REPOS)
+        .into_iter()
+        .map(|(owner, name)| prefetch_repo(state, owner, name))
+        .collect()
+}
+
+/// Fetch `owner/name`'s PR list under its view's token, so leaving the view or
+/// starting a newer fetch for it stops this one.
+fn prefetch_repo(state: &mut AppState, owner: String, name: String) -> SideEffect {
+    let cancel = state.begin_view_fetch(ContentView::RepoPrList {
+        owner: owner.clone(),
+        name: name.clone(),
+    });
+    SideEffect::PrefetchRepoPrs {
+        owner,
+        name,
+        cancel,
+    }
+}
+
+/// Note a section's successful fetch. Offline results come from the cache, so
+/// they say nothing about when the data was fetched.
+fn record_success(state: &mut AppState, section: ContentView) {
+    if !state.offline {
+        state.record_fetch(section, true);
+    }
+}
+
+/// Show the overview of the owner after (or before) the one in view, in nav
+/// order and wrapping around, and move the nav cursor onto it. From a view
+/// that isn't an owner's, start at the first (or last).
+fn cycle_owner(state: &mut AppState, forward: bool) -> Vec<SideEffect> {
+    let owners: Vec<(usize, String)> = state
+        .nav_nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(row, node)| match node {
+            NavNode::Org(owner) => Some((row, owner.clone())),
+            _ => None,
+        })
+        .collect();
+    if owners.is_empty() {
+        return vec![];
+    }
+    let current = match &state.content_view {
+        ContentView::OrgOverview(owner) | ContentView::RepoPrList { owner, .. } => {
+            owners.iter().position(|(_, o)| o == owner)
+        }
+        _ => None,
+    };
+    let last = owners.len() - 1;
+    let next = match (current, forward) {
+        (Some(i), true) if i == last => 0,
+        (Some(i), true) => i + 1,
+        (Some(0), false) => last,
+        (Some(i), false) => i - 1,
+        (None, true) => 0,
+        (None, false) => last,
+    };
+    let (row, owner) = owners[next].clone();
+    state.content_view = ContentView::OrgOverview(owner);
+    state.content_cursor = 0;
+    state.nav_cursor = row;
+    state.remember_view();
+    vec![]
+}
+
+/// Open each of `prs` in the browser, marking them seen.
+fn open_prs(state: &mut AppState, prs: Vec<PullRequest>) -> Vec<SideEffect> {
+    state.status_message = Some(format!(
+        "Opened {} PR{}",
+        prs.len(),
+        if prs.len() == 1 { "" } else { "s" }
+    ));
+    prs.into_iter()
+        .map(|pr| {
+            state.mark_seen(&pr);
+            SideEffect::OpenUrl(pr.url)
+        })
+        .collect()
+}
+
+/// Note a full refresh's section reporting back; after the last one, show
+/// the summary.
+fn refresh_section_done(state: &mut AppState, section: &ContentView, ok: bool) -> Vec<SideEffect> {
+    match state.refresh_section_done(section, ok) {
+        Some(summary) => update(state, Action::RefreshSummary(summary)),
+        None => vec![],
+    }
+}
+
+/// Fetches for the watched PRs picked by [`AppState::watched_to_fetch`].
+/// Offline there is nothing to fetch them from.
+fn watched_fetches(state: &mut AppState, all: bool) -> Vec<SideEffect> {
+    if state.offline {
+        return vec![];
+    }
+    state
+        .watched_to_fetch(all)
+        .into_iter()
+        .map(SideEffect::FetchWatchedPr)
+        .collect()
+}
+
+/// Cursor position in the focused pane.
+fn cursor_row(state: &AppState) -> usize {
+    match state.focused_pane {
+        FocusedPane::Navigation => state.nav_cursor,
+        FocusedPane::Content => state.content_cursor,
+    }
+}
+
+/// Move the focused pane's cursor to `row`, clamped to the last row.
+fn set_cursor_row(state: &mut AppState, row: usize) {
+    match state.focused_pane {
+        FocusedPane::Navigation => {
+            state.nav_cursor = row.min(state.nav_nodes.len().saturating_sub(1));
+        }
+        FocusedPane::Content => {
+            let len = state.current_pr_list().len();
+            state.content_cursor = row.min(len.saturating_sub(1));
+        }
+    }
+}
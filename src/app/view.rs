@@ -1,17 +1,18 @@
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
 };
 
 use crate::app::state::AppState;
-use crate::ui::widgets;
+use crate::ui::{chooser, widgets};
 
-pub fn render(f: &mut Frame, state: &AppState) {
+/// Split the screen into the nav pane, content pane and status bar.
+pub fn layout(area: Rect) -> (Rect, Rect, Rect) {
     // Main layout: body + status bar
     let vertical = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(1), Constraint::Length(1)])
-        .split(f.area());
+        .split(area);
 
     let body_area = vertical[0];
     let status_area = vertical[1];
@@ -22,8 +23,11 @@ pub fn render(f: &mut Frame, state: &AppState) {
         .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
         .split(body_area);
 
-    let nav_area = horizontal[0];
-    let content_area = horizontal[1];
+    (horizontal[0], horizontal[1], status_area)
+}
+
+pub fn render(f: &mut Frame, state: &AppState) {
+    let (nav_area, content_area, status_area) = layout(f.area());
 
     widgets::render_nav_pane(f, nav_area, state);
     widgets::render_content_pane(f, content_area, state);
@@ -31,8 +35,12 @@ pub fn render(f: &mut Frame, state: &AppState) {
 
     // Overlays
     widgets::render_pr_overlay(f, state);
+    widgets::render_pr_info_popup(f, state);
     widgets::render_help_overlay(f, state);
     widgets::render_search_overlay(f, state);
+    if let Some(chooser) = &state.chooser {
+        chooser::render_chooser(f, chooser, state.theme().glyphs);
+    }
     if state.error_message.is_some() {
         widgets::render_error_modal(f, f.area(), state);
     }
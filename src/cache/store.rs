@@ -1,23 +1,78 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use tracing::{debug, warn};
 
+use crate::util::time;
+
 #[derive(Debug, Clone)]
 pub struct CacheStore {
     dir: PathBuf,
     ttl_secs: u64,
+    negative_ttl_secs: u64,
+    /// Data written before these reads as expired (see
+    /// [`expire`](CacheStore::expire)). Shared between clones.
+    expired: Arc<Mutex<Expired>>,
+}
+
+/// When the whole store, and individual keys, were last expired.
+#[derive(Debug, Default)]
+struct Expired {
+    all: Option<chrono::DateTime<chrono::Utc>>,
+    keys: HashMap<String, chrono::DateTime<chrono::Utc>>,
+}
+
+/// Why a fetch was cached as having nothing to show (see
+/// [`CacheStore::set_negative`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Negative {
+    /// The fetch succeeded with nothing in it.
+    Empty,
+    /// GitHub refused: the token can't see the owner, or it doesn't exist.
+    NoAccess,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct CacheEntry<T> {
     timestamp: chrono::DateTime<chrono::Utc>,
-    data: T,
+    /// Set on negative entries, which carry no data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    negative: Option<Negative>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<T>,
 }
 
+/// Default lifetime of negative entries: shorter than real data, so a newly
+/// granted permission shows up soon.
+pub const DEFAULT_NEGATIVE_TTL_SECS: u64 = 300;
+
 impl CacheStore {
     pub fn new(dir: PathBuf, ttl_secs: u64) -> Self {
-        Self { dir, ttl_secs }
+        Self {
+            dir,
+            ttl_secs,
+            negative_ttl_secs: DEFAULT_NEGATIVE_TTL_SECS,
+            expired: Arc::default(),
+        }
+    }
+
+    /// How long negative entries are honoured.
+    pub fn with_negative_ttl(mut self, secs: u64) -> Self {
+        self.negative_ttl_secs = secs;
+        self
+    }
+
+    pub fn negative_ttl_secs(&self) -> u64 {
+        self.negative_ttl_secs
+    }
+
+    fn age_secs(entry_timestamp: chrono::DateTime<chrono::Utc>) -> i64 {
+        time::now()
+            .signed_duration_since(entry_timestamp)
+            .num_seconds()
     }
 
     fn path_for_key(&self, key: &str) -> PathBuf {
@@ -26,56 +81,125 @@ impl CacheStore {
         self.dir.join(format!("{safe_key}.json"))
     }
 
-    pub fn get<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Option<T> {
+    fn read_entry<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Option<CacheEntry<T>> {
         let path = self.path_for_key(key);
-        let content = match std::fs::read_to_string(&path) {
-            Ok(c) => c,
-            Err(_) => return None,
-        };
-
-        let entry: CacheEntry<T> = match serde_json::from_str(&content) {
-            Ok(e) => e,
+        let content = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(e) => Some(e),
             Err(e) => {
                 warn!(key = key, error = %e, "Failed to parse cache entry");
-                return None;
+                None
             }
-        };
+        }
+    }
 
-        let age = chrono::Utc::now()
-            .signed_duration_since(entry.timestamp)
-            .num_seconds();
+    /// The data cached under `key`, if it hasn't expired. Negative entries
+    /// read as missing; see [`get_negative`](Self::get_negative).
+    pub fn get<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Option<T> {
+        let entry: CacheEntry<T> = self.read_entry(key)?;
+        if entry.negative.is_some() {
+            debug!(key = key, "Negative cache entry");
+            return None;
+        }
 
+        let age = Self::age_secs(entry.timestamp);
         if age < 0 || age as u64 > self.ttl_secs {
             debug!(key = key, age = age, "Cache entry expired");
             return None;
         }
+        if self
+            .expired_before(key)
+            .is_some_and(|at| entry.timestamp < at)
+        {
+            debug!(key = key, age = age, "Cache entry predates refresh");
+            return None;
+        }
 
         debug!(key = key, age = age, "Cache hit");
-        Some(entry.data)
+        entry.data
+    }
+
+    /// The negative marker under `key`, if there is one and it is younger
+    /// than the negative TTL.
+    pub fn get_negative(&self, key: &str) -> Option<Negative> {
+        let entry: CacheEntry<serde::de::IgnoredAny> = self.read_entry(key)?;
+        let negative = entry.negative?;
+        let age = Self::age_secs(entry.timestamp);
+        if age < 0 || age as u64 > self.negative_ttl_secs {
+            debug!(key = key, age = age, "Negative cache entry expired");
+            return None;
+        }
+        debug!(key = key, age = age, ?negative, "Negative cache hit");
+        Some(negative)
+    }
+
+    /// Remember that the fetch behind `key` came back empty or was refused,
+    /// so it isn't repeated for a while. Unlike data, these markers survive
+    /// [`expire`](Self::expire), [`expire_all`](Self::expire_all) and
+    /// [`invalidate`](Self::invalidate), so refreshes skip them until they
+    /// expire.
+    pub fn set_negative(&self, key: &str, negative: Negative) -> Result<()> {
+        self.write_entry(
+            key,
+            &CacheEntry::<()> {
+                timestamp: time::now(),
+                negative: Some(negative),
+                data: None,
+            },
+        )
+    }
+
+    /// Read an entry however old it is, along with when it was written. Used in
+    /// offline mode, where stale data beats no data.
+    pub fn get_ignoring_ttl<T: for<'de> Deserialize<'de>>(
+        &self,
+        key: &str,
+    ) -> Option<(T, chrono::DateTime<chrono::Utc>)> {
+        let entry: CacheEntry<T> = self.read_entry(key)?;
+        debug!(key = key, "Cache read ignoring TTL");
+        Some((entry.data?, entry.timestamp))
     }
 
     pub fn set<T: Serialize>(&self, key: &str, data: &T) -> Result<()> {
+        self.write_entry(
+            key,
+            &CacheEntry {
+                timestamp: time::now(),
+                negative: None,
+                data: Some(data),
+            },
+        )?;
+        debug!(key = key, "Cache set");
+        Ok(())
+    }
+
+    fn write_entry<T: Serialize>(&self, key: &str, entry: &CacheEntry<T>) -> Result<()> {
         std::fs::create_dir_all(&self.dir)
             .with_context(|| format!("Failed to create cache directory: {}", self.dir.display()))?;
 
-        let entry = CacheEntry {
-            timestamp: chrono::Utc::now(),
-            data,
-        };
-
-        let content = serde_json::to_string(&entry).context("Failed to serialize cache entry")?;
+        let content = serde_json::to_string(entry).context("Failed to serialize cache entry")?;
         let path = self.path_for_key(key);
         std::fs::write(&path, content)
-            .with_context(|| format!("Failed to write cache file: {}", path.display()))?;
+            .with_context(|| format!("Failed to write cache file: {}", path.display()))
+    }
 
-        debug!(key = key, "Cache set");
-        Ok(())
+    /// Whether the file at `path` holds a negative entry.
+    fn is_negative(path: &std::path::Path) -> bool {
+        #[derive(Deserialize)]
+        struct Marker {
+            #[serde(default)]
+            negative: Option<Negative>,
+        }
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Marker>(&content).ok())
+            .is_some_and(|m| m.negative.is_some())
     }
 
-    #[allow(dead_code)]
+    /// Drop the data cached under `key`. A negative marker is kept.
     pub fn invalidate(&self, key: &str) -> Result<()> {
         let path = self.path_for_key(key);
-        if path.exists() {
+        if path.exists() && !Self::is_negative(&path) {
             std::fs::remove_file(&path)
                 .with_context(|| format!("Failed to remove cache file: {}", path.display()))?;
             debug!(key = key, "Cache invalidated");
@@ -83,17 +207,29 @@ impl CacheStore {
         Ok(())
     }
 
-    pub fn invalidate_all(&self) -> Result<()> {
-        if self.dir.exists() {
-            for entry in std::fs::read_dir(&self.dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.extension().is_some_and(|ext| ext == "json") {
-                    std::fs::remove_file(&path)?;
-                }
-            }
-            debug!("All cache entries invalidated");
-        }
-        Ok(())
+    /// Treat all data cached so far as expired without deleting it: reads
+    /// miss until a fetch writes the entry again, but whatever a failed fetch
+    /// didn't replace is still there for offline mode and the next start.
+    /// Applies to every clone of this store. Negative markers are unaffected.
+    pub fn expire_all(&self) {
+        let mut expired = self.expired.lock().unwrap();
+        expired.all = Some(time::now());
+        expired.keys.clear();
+        debug!("Cache expired");
+    }
+
+    /// [`expire_all`](Self::expire_all) for just the data under `key`.
+    pub fn expire(&self, key: &str) {
+        self.expired
+            .lock()
+            .unwrap()
+            .keys
+            .insert(key.to_string(), time::now());
+        debug!(key = key, "Cache entry marked expired");
+    }
+
+    fn expired_before(&self, key: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        let expired = self.expired.lock().unwrap();
+        expired.all.max(expired.keys.get(key).copied())
     }
 }
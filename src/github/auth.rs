@@ -1,44 +1,484 @@
-use anyhow::{Result, bail};
+use std::io::Read;
+use std::path::PathBuf;
 use std::process::Command;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Duration, Utc};
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use ring::rand::SystemRandom;
+use ring::signature::{RSA_PKCS1_SHA256, RsaKeyPair};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::debug;
 
-/// Resolve GitHub token using multiple strategies:
-/// 1. `gh auth token` subprocess
-/// 2. `GITHUB_TOKEN` environment variable
-/// 3. `GH_TOKEN` environment variable
-pub fn resolve_token() -> Result<String> {
-    // Try `gh auth token` first
-    debug!("Attempting to resolve token via `gh auth token`");
-    if let Ok(output) = Command::new("gh").args(["auth", "token"]).output()
-        && output.status.success()
-    {
-        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !token.is_empty() {
-            debug!("Token resolved via gh CLI");
-            return Ok(token);
+use super::graphql::{GithubClient, rest_base};
+use crate::util::config::{GithubAppConfig, GithubConfig};
+use crate::util::secret::SecretString;
+use crate::util::time;
+
+/// A way of obtaining a GitHub token. Tried in the order given by
+/// `github.auth_order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMethod {
+    /// `GITHUB_TOKEN`, then `GH_TOKEN`.
+    Env,
+    /// `gh auth token`.
+    Gh,
+    /// OS keychain entry for service `ghdash` (`security` on macOS,
+    /// `secret-tool` elsewhere).
+    Keyring,
+    /// A GitHub App installation token, minted from `[github.app]` and
+    /// refreshed before it expires.
+    App,
+    /// Piped in with `--token-stdin`; never part of `auth_order`.
+    Stdin,
+}
+
+impl AuthMethod {
+    pub fn label(self) -> &'static str {
+        match self {
+            AuthMethod::Env => "environment variable",
+            AuthMethod::Gh => "gh CLI",
+            AuthMethod::Keyring => "keyring",
+            AuthMethod::App => "GitHub App",
+            AuthMethod::Stdin => "stdin",
         }
     }
+}
 
-    // Try GITHUB_TOKEN env
-    if let Ok(token) = std::env::var("GITHUB_TOKEN")
-        && !token.is_empty()
-    {
-        debug!("Token resolved via GITHUB_TOKEN env var");
-        return Ok(token);
+/// The order used when `github.auth_order` isn't set.
+pub fn default_auth_order() -> Vec<AuthMethod> {
+    vec![AuthMethod::Gh, AuthMethod::Env]
+}
+
+/// Process environment as seen by token resolution, so tests can swap in
+/// fixed variables and command output.
+pub trait Environment {
+    fn var(&self, key: &str) -> Option<String>;
+    /// Stdout of a successful command run, `None` if it fails or can't start.
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<String>;
+}
+
+/// The real process environment.
+pub struct SystemEnv;
+
+impl Environment for SystemEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
     }
 
-    // Try GH_TOKEN env
-    if let Ok(token) = std::env::var("GH_TOKEN")
-        && !token.is_empty()
-    {
-        debug!("Token resolved via GH_TOKEN env var");
-        return Ok(token);
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
     }
+}
+
+/// A resolved token, and for tokens that expire, what keeps them fresh.
+pub struct ResolvedToken {
+    pub token: String,
+    pub method: AuthMethod,
+    /// Set for GitHub App tokens; the client asks it for the current token
+    /// before each request.
+    pub refresher: Option<Arc<TokenRefresher>>,
+}
+
+/// Resolve a GitHub token by trying each method in `github.auth_order`. The
+/// `app` method talks to GitHub to mint an installation token; the others
+/// are as in [`resolve_token_with`].
+pub async fn resolve_token(config: &GithubConfig) -> Result<ResolvedToken> {
+    let order = config.auth_order.clone();
+    let app_config = config.app.clone();
+    // Env lookups, credential helpers and the app key file all block, so
+    // they run off the async runtime; only minting an app token awaits.
+    let local = tokio::task::spawn_blocking(move || -> Result<LocalAuth> {
+        let Some(app_at) = order.iter().position(|m| *m == AuthMethod::App) else {
+            return resolve_token_with(&SystemEnv, &order).map(LocalAuth::Token);
+        };
+        if let Ok(resolved) = resolve_token_with(&SystemEnv, &order[..app_at]) {
+            return Ok(LocalAuth::Token(resolved));
+        }
+        debug!(
+            method = AuthMethod::App.label(),
+            "Attempting to resolve token"
+        );
+        if let Some(app) = GithubApp::from_sources(app_config.as_ref(), &SystemEnv)? {
+            return Ok(LocalAuth::App(Box::new(app)));
+        }
+        resolve_token_with(&SystemEnv, &order[app_at + 1..]).map(LocalAuth::Token)
+    })
+    .await??;
+    match local {
+        LocalAuth::Token((token, method)) => Ok(ResolvedToken {
+            token,
+            method,
+            refresher: None,
+        }),
+        LocalAuth::App(app) => app_token(*app, config).await,
+    }
+}
+
+/// What [`resolve_token`] finds without the network: a token, or the app
+/// to mint one for.
+enum LocalAuth {
+    Token((String, AuthMethod)),
+    App(Box<GithubApp>),
+}
 
-    bail!(
+/// Resolve a token from the methods that need no network, trying each in
+/// `order`; `app` is skipped. Returns the token and the method that produced
+/// it.
+pub fn resolve_token_with(
+    env: &dyn Environment,
+    order: &[AuthMethod],
+) -> Result<(String, AuthMethod)> {
+    for &method in order {
+        debug!(method = method.label(), "Attempting to resolve token");
+        if let Some(token) = try_method(env, method) {
+            debug!(method = method.label(), "Token resolved");
+            return Ok((token, method));
+        }
+    }
+    Err(no_token_error())
+}
+
+fn no_token_error() -> anyhow::Error {
+    anyhow!(
         "Could not resolve GitHub token. Please either:\n\
          - Run `gh auth login` to authenticate with the GitHub CLI\n\
          - Set the GITHUB_TOKEN environment variable\n\
-         - Set the GH_TOKEN environment variable"
+         - Set the GH_TOKEN environment variable\n\
+         - Configure [github.app] and add \"app\" to github.auth_order\n\
+         - Pipe a token in with --token-stdin"
     )
 }
+
+fn try_method(env: &dyn Environment, method: AuthMethod) -> Option<String> {
+    let token = match method {
+        AuthMethod::Env => {
+            non_empty(env.var("GITHUB_TOKEN")).or_else(|| non_empty(env.var("GH_TOKEN")))?
+        }
+        // Some Windows shells only find the CLI by its full name.
+        AuthMethod::Gh => env.command_output("gh", &["auth", "token"]).or_else(|| {
+            if cfg!(windows) {
+                env.command_output("gh.exe", &["auth", "token"])
+            } else {
+                None
+            }
+        })?,
+        AuthMethod::Keyring => {
+            if cfg!(target_os = "macos") {
+                env.command_output("security", &["find-generic-password", "-s", "ghdash", "-w"])?
+            } else {
+                env.command_output("secret-tool", &["lookup", "service", "ghdash"])?
+            }
+        }
+        AuthMethod::App | AuthMethod::Stdin => return None,
+    };
+    non_empty(Some(token))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Cache key for the login `token` signs in as on `api_url`. Only a digest of
+/// the token goes into the key.
+pub fn viewer_cache_key(api_url: &str, token: &str) -> String {
+    let digest = ring::digest::digest(
+        &ring::digest::SHA256,
+        format!("{}\n{}", api_url, token).as_bytes(),
+    );
+    let hex: String = digest.as_ref()[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("viewer_{}", hex)
+}
+
+/// Read a token piped in on `--token-stdin`.
+pub fn read_token(mut reader: impl Read) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let token = input.trim();
+    if token.is_empty() {
+        bail!("--token-stdin was given but no token was read from stdin");
+    }
+    Ok(token.to_string())
+}
+
+/// Env vars that override the matching `[github.app]` fields.
+pub const APP_ID_VAR: &str = "GHDASH_APP_ID";
+pub const APP_INSTALLATION_ID_VAR: &str = "GHDASH_APP_INSTALLATION_ID";
+pub const APP_PRIVATE_KEY_VAR: &str = "GHDASH_APP_PRIVATE_KEY_PATH";
+
+/// Installation tokens are minted again this long before they expire, so a
+/// request never goes out with one about to lapse.
+pub const REFRESH_MARGIN_SECS: i64 = 5 * 60;
+
+/// A GitHub App installation to authenticate as: signs JWTs with the App's
+/// private key and exchanges them for installation tokens.
+pub struct GithubApp {
+    app_id: u64,
+    installation_id: u64,
+    key: RsaKeyPair,
+}
+
+impl GithubApp {
+    /// The App configured by env vars and `[github.app]`, env first. `None`
+    /// when neither sets anything; an error when only some fields are set or
+    /// the key can't be read.
+    pub fn from_sources(
+        config: Option<&GithubAppConfig>,
+        env: &dyn Environment,
+    ) -> Result<Option<Self>> {
+        let config = config.cloned().unwrap_or_default();
+        let number = |var: &str| -> Result<Option<u64>> {
+            non_empty(env.var(var))
+                .map(|v| v.parse().with_context(|| format!("{var} must be a number")))
+                .transpose()
+        };
+        let app_id = number(APP_ID_VAR)?.or(config.app_id);
+        let installation_id = number(APP_INSTALLATION_ID_VAR)?.or(config.installation_id);
+        let key_path = non_empty(env.var(APP_PRIVATE_KEY_VAR))
+            .map(PathBuf::from)
+            .or(config.private_key_path);
+
+        match (app_id, installation_id, key_path) {
+            (None, None, None) => Ok(None),
+            (Some(app_id), Some(installation_id), Some(key_path)) => {
+                let pem = std::fs::read_to_string(&key_path).with_context(|| {
+                    format!("Failed to read GitHub App key: {}", key_path.display())
+                })?;
+                Self::from_pem(app_id, installation_id, &pem).map(Some)
+            }
+            _ => bail!(
+                "GitHub App auth needs app_id, installation_id and private_key_path \
+                 (in [github.app] or {APP_ID_VAR}, {APP_INSTALLATION_ID_VAR} and \
+                 {APP_PRIVATE_KEY_VAR})"
+            ),
+        }
+    }
+
+    /// An App whose private key is `pem`, either PKCS#1 (`BEGIN RSA PRIVATE
+    /// KEY`, as GitHub issues them) or PKCS#8 (`BEGIN PRIVATE KEY`).
+    pub fn from_pem(app_id: u64, installation_id: u64, pem: &str) -> Result<Self> {
+        let (label, der) = decode_pem(pem)?;
+        let key = match label.as_str() {
+            "RSA PRIVATE KEY" => RsaKeyPair::from_der(&der),
+            "PRIVATE KEY" => RsaKeyPair::from_pkcs8(&der),
+            other => bail!("GitHub App key is a {other:?}, not an RSA private key"),
+        }
+        .map_err(|e| anyhow!("Invalid GitHub App private key: {e}"))?;
+        Ok(Self {
+            app_id,
+            installation_id,
+            key,
+        })
+    }
+
+    /// Whether `other` is the same installation signing with the same key.
+    pub fn same_credentials(&self, other: &GithubApp) -> bool {
+        self.app_id == other.app_id
+            && self.installation_id == other.installation_id
+            && self.key.public().as_ref() == other.key.public().as_ref()
+    }
+
+    /// A JWT identifying the App, valid for nine minutes from `now`. Issued a
+    /// minute in the past to allow for clock drift, as GitHub recommends.
+    pub fn jwt(&self, now: DateTime<Utc>) -> Result<String> {
+        let header = json!({ "alg": "RS256", "typ": "JWT" });
+        let claims = json!({
+            "iat": (now - Duration::seconds(60)).timestamp(),
+            "exp": (now + Duration::minutes(9)).timestamp(),
+            "iss": self.app_id,
+        });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let mut signature = vec![0; self.key.public().modulus_len()];
+        self.key
+            .sign(
+                &RSA_PKCS1_SHA256,
+                &SystemRandom::new(),
+                signing_input.as_bytes(),
+                &mut signature,
+            )
+            .map_err(|_| anyhow!("Failed to sign GitHub App JWT"))?;
+        Ok(format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+
+    /// Exchange a fresh JWT for an installation token via the REST API.
+    pub async fn installation_token(
+        &self,
+        http: &reqwest::Client,
+        api_url: &str,
+    ) -> Result<ExpiringToken> {
+        #[derive(Deserialize)]
+        struct Response {
+            token: String,
+            expires_at: DateTime<Utc>,
+        }
+
+        let url = format!(
+            "{}/app/installations/{}/access_tokens",
+            rest_base(api_url),
+            self.installation_id
+        );
+        let resp = http
+            .post(&url)
+            .bearer_auth(self.jwt(time::now())?)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| e.without_url())
+            .context("GitHub App token request failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            bail!(
+                "GitHub rejected the App token request ({}): {}",
+                status,
+                crate::util::secret::redact(&text)
+            );
+        }
+        let body: Response = resp
+            .json()
+            .await
+            .context("Failed to parse GitHub App token response")?;
+        debug!(expires_at = %body.expires_at, "Minted GitHub App installation token");
+        Ok(ExpiringToken {
+            token: SecretString::new(body.token),
+            expires_at: body.expires_at,
+        })
+    }
+}
+
+/// The label and DER bytes of the first PEM block in `pem`.
+fn decode_pem(pem: &str) -> Result<(String, Vec<u8>)> {
+    let invalid = || anyhow!("GitHub App key is not a PEM file");
+    let start = pem.find("-----BEGIN ").ok_or_else(invalid)?;
+    let rest = &pem[start + "-----BEGIN ".len()..];
+    let label_end = rest.find("-----").ok_or_else(invalid)?;
+    let label = rest[..label_end].to_string();
+    let body = &rest[label_end + "-----".len()..];
+    let end = body
+        .find(&format!("-----END {label}-----"))
+        .ok_or_else(invalid)?;
+    let base64: String = body[..end].split_whitespace().collect();
+    let der = base64::engine::general_purpose::STANDARD
+        .decode(base64)
+        .map_err(|_| invalid())?;
+    Ok((label, der))
+}
+
+/// A token and when GitHub stops accepting it.
+#[derive(Clone)]
+pub struct ExpiringToken {
+    pub token: SecretString,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Mints a replacement for an expiring token.
+pub type RefreshFn = Arc<dyn Fn() -> BoxFuture<'static, Result<ExpiringToken>> + Send + Sync>;
+
+/// Hands out a token, minting a new one with `refresh` whenever the current
+/// one is within [`REFRESH_MARGIN_SECS`] of expiring. Shared by every clone of
+/// a client; concurrent requests wait for a single refresh.
+pub struct TokenRefresher {
+    refresh: RefreshFn,
+    current: tokio::sync::Mutex<ExpiringToken>,
+    /// The App `refresh` mints tokens for, if it is one.
+    app: Option<Arc<GithubApp>>,
+}
+
+impl TokenRefresher {
+    pub fn new(token: ExpiringToken, refresh: RefreshFn) -> Self {
+        Self {
+            refresh,
+            current: tokio::sync::Mutex::new(token),
+            app: None,
+        }
+    }
+
+    /// A refresher minting `app`'s installation tokens over the same HTTP
+    /// settings as the API client. It holds no token yet, so the first
+    /// [`token`](Self::token) call mints one.
+    pub fn for_app(app: GithubApp, config: &GithubConfig) -> Result<Self> {
+        let http = GithubClient::http_client(config)?;
+        let api_url = config.api_url.clone();
+        let app = Arc::new(app);
+        let minting = app.clone();
+        let refresh: RefreshFn = Arc::new(move || {
+            let (app, http, api_url) = (minting.clone(), http.clone(), api_url.clone());
+            async move { app.installation_token(&http, &api_url).await }.boxed()
+        });
+        let unminted = ExpiringToken {
+            token: SecretString::new(""),
+            expires_at: DateTime::UNIX_EPOCH,
+        };
+        Ok(Self {
+            app: Some(app),
+            ..Self::new(unminted, refresh)
+        })
+    }
+
+    /// The refresher to use once `[github]` is reloaded as `config`: this one
+    /// while the App's id, installation and key are unchanged, a new one for
+    /// the reloaded App otherwise. Refreshers that aren't for an App, or
+    /// configs that no longer name one, keep this one.
+    pub fn reconfigured(self: &Arc<Self>, config: &GithubConfig) -> Result<Arc<Self>> {
+        let Some(current) = &self.app else {
+            return Ok(self.clone());
+        };
+        match GithubApp::from_sources(config.app.as_ref(), &SystemEnv)? {
+            Some(app) if !app.same_credentials(current) => {
+                Ok(Arc::new(Self::for_app(app, config)?))
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+
+    pub async fn token(&self) -> Result<SecretString> {
+        let mut current = self.current.lock().await;
+        if current.expires_at - Duration::seconds(REFRESH_MARGIN_SECS) <= time::now() {
+            *current = (self.refresh)()
+                .await
+                .context("Failed to refresh GitHub token")?;
+        }
+        Ok(current.token.clone())
+    }
+}
+
+impl std::fmt::Debug for TokenRefresher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenRefresher").finish_non_exhaustive()
+    }
+}
+
+/// Mint `app`'s first installation token, with a refresher that mints the
+/// next ones.
+async fn app_token(app: GithubApp, config: &GithubConfig) -> Result<ResolvedToken> {
+    let refresher = TokenRefresher::for_app(app, config)?;
+    let first = refresher.token().await?;
+    Ok(ResolvedToken {
+        token: first.expose().to_string(),
+        method: AuthMethod::App,
+        refresher: Some(Arc::new(refresher)),
+    })
+}
@@ -0,0 +1,353 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, info};
+
+use super::cancel::CancelToken;
+use super::graphql::GithubClient;
+use super::models::*;
+use super::source::{DataSource, Fetch};
+use crate::util::config::{AppConfig, OwnerKind};
+
+/// Every repo to list, as `Vec<Repo>`.
+pub const REPOS_FILE: &str = "repos.json";
+/// The inbox, as `Vec<PullRequest>`, served as-is.
+pub const INBOX_FILE: &str = "inbox.json";
+/// PRs in any state, as `Vec<PullRequest>`; every PR list is cut from these.
+pub const PRS_FILE: &str = "prs.json";
+/// Optional: the login to show as the viewer, as a JSON string.
+pub const VIEWER_FILE: &str = "viewer.json";
+/// Optional: PR detail panes, keyed by `owner/name#number`.
+pub const DETAILS_FILE: &str = "details.json";
+
+/// Login used when a fixture set has no `viewer.json`.
+const DEFAULT_VIEWER: &str = "octocat";
+
+/// A [`DataSource`] serving canned JSON from a directory instead of GitHub
+/// (`--fixtures <dir>`), for working on the UI without a token or network.
+///
+/// History views aren't cut to the configured window, so a fixture set
+/// keeps showing its merged and closed PRs however old it gets. Diffs are
+/// never available.
+#[derive(Debug, Clone)]
+pub struct FixtureSource {
+    viewer: String,
+    repos: Vec<Repo>,
+    inbox: Vec<PullRequest>,
+    prs: Vec<PullRequest>,
+    details: HashMap<String, PrDetail>,
+}
+
+impl FixtureSource {
+    /// A source serving the given data, with no PR details. Tests use this to
+    /// drive the event loop without files or network.
+    #[allow(dead_code)]
+    pub fn new(
+        viewer: &str,
+        repos: Vec<Repo>,
+        inbox: Vec<PullRequest>,
+        prs: Vec<PullRequest>,
+    ) -> Self {
+        Self {
+            viewer: viewer.to_string(),
+            repos,
+            inbox,
+            prs,
+            details: HashMap::new(),
+        }
+    }
+
+    pub fn load(dir: &Path) -> Result<Self> {
+        let viewer = match read_optional(&dir.join(VIEWER_FILE))? {
+            Some(viewer) => viewer,
+            None => DEFAULT_VIEWER.to_string(),
+        };
+        let source = Self {
+            viewer,
+            repos: read(&dir.join(REPOS_FILE))?,
+            inbox: read(&dir.join(INBOX_FILE))?,
+            prs: read(&dir.join(PRS_FILE))?,
+            details: read_optional(&dir.join(DETAILS_FILE))?.unwrap_or_default(),
+        };
+        debug!(
+            dir = %dir.display(),
+            repos = source.repos.len(),
+            prs = source.prs.len(),
+            "Loaded fixtures"
+        );
+        Ok(source)
+    }
+
+    /// Distinct owners of the fixture repos, sorted.
+    pub fn owners(&self) -> Vec<String> {
+        self.repos
+            .iter()
+            .map(|r| r.owner.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn repos_of(&self, owner: &str) -> Vec<Repo> {
+        self.repos
+            .iter()
+            .filter(|r| r.owner.eq_ignore_ascii_case(owner))
+            .cloned()
+            .collect()
+    }
+
+    fn prs_where(&self, keep: impl Fn(&PullRequest) -> bool) -> Vec<PullRequest> {
+        self.prs.iter().filter(|pr| keep(pr)).cloned().collect()
+    }
+}
+
+/// Key of a PR in `details.json`.
+pub fn detail_key(owner: &str, name: &str, number: u32) -> String {
+    format!("{owner}/{name}#{number}")
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    read_optional(path)?.ok_or_else(|| anyhow!("Missing fixture file: {}", path.display()))
+}
+
+fn read_optional<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read fixture file: {}", path.display()));
+        }
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .with_context(|| format!("Failed to parse fixture file: {}", path.display()))
+}
+
+fn ready<'a, T: Send + 'a>(value: T) -> Fetch<'a, T> {
+    futures::future::ready(Ok((value, None))).boxed()
+}
+
+fn owned_by(pr: &PullRequest, orgs: &[String], users: &[String]) -> bool {
+    orgs.iter()
+        .chain(users)
+        .any(|o| o.eq_ignore_ascii_case(&pr.repo_owner))
+}
+
+/// Whether `pr` is in a search over the owners plus the standalone `repos`.
+fn in_scope(pr: &PullRequest, orgs: &[String], users: &[String], repos: &[String]) -> bool {
+    owned_by(pr, orgs, users)
+        || repos
+            .iter()
+            .any(|r| r.eq_ignore_ascii_case(&pr.repo_full_name()))
+}
+
+impl DataSource for FixtureSource {
+    fn is_fixture(&self) -> bool {
+        true
+    }
+
+    fn fetch_viewer(&self) -> BoxFuture<'_, Result<String>> {
+        futures::future::ready(Ok(self.viewer.clone())).boxed()
+    }
+
+    fn fetch_org_repos<'a>(&'a self, org: &'a str) -> Fetch<'a, Vec<Repo>> {
+        ready(self.repos_of(org))
+    }
+
+    fn fetch_orgs_batched<'a>(&'a self, orgs: &'a [String]) -> Fetch<'a, Vec<Vec<Repo>>> {
+        ready(orgs.iter().map(|org| self.repos_of(org)).collect())
+    }
+
+    fn fetch_user_repos<'a>(&'a self, user: &'a str) -> Fetch<'a, Vec<Repo>> {
+        ready(self.repos_of(user))
+    }
+
+    /// Fixtures don't say which owners are users, so every owner with repos
+    /// is taken for an organization.
+    fn fetch_owner_kind<'a>(&'a self, login: &'a str) -> Fetch<'a, Option<OwnerKind>> {
+        ready((!self.repos_of(login).is_empty()).then_some(OwnerKind::Org))
+    }
+
+    fn fetch_inbox<'a>(
+        &'a self,
+        _viewer_login: &'a str,
+        _teams: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        ready(self.inbox.clone())
+    }
+
+    fn fetch_all_open_prs<'a>(
+        &'a self,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        ready(self.prs_where(|pr| pr.state == PrState::Open && in_scope(pr, orgs, users, repos)))
+    }
+
+    fn fetch_involved_prs<'a>(
+        &'a self,
+        login: &'a str,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        ready(self.prs_where(|pr| {
+            pr.state == PrState::Open
+                && in_scope(pr, orgs, users, repos)
+                && (pr.author.eq_ignore_ascii_case(login)
+                    || self.inbox.iter().any(|i| i.url == pr.url))
+        }))
+    }
+
+    fn fetch_pr_history<'a>(
+        &'a self,
+        kind: PrHistoryKind,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+        _window_days: u32,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        let state = match kind {
+            PrHistoryKind::Merged => PrState::Merged,
+            PrHistoryKind::Closed => PrState::Closed,
+        };
+        let mut prs = self.prs_where(|pr| pr.state == state && in_scope(pr, orgs, users, repos));
+        prs.sort_by_key(|pr| std::cmp::Reverse(kind.finished_at(pr)));
+        ready(prs)
+    }
+
+    fn fetch_repo_prs_in_state<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        state: PrStateFilter,
+        _cancel: &'a CancelToken,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        let state = match state {
+            PrStateFilter::Open => PrState::Open,
+            PrStateFilter::Merged => PrState::Merged,
+            PrStateFilter::Closed => PrState::Closed,
+        };
+        let mut prs = self
+            .prs_where(|pr| pr.state == state && pr.repo_owner == owner && pr.repo_name == name);
+        prs.sort_by_key(|pr| std::cmp::Reverse(pr.updated_at));
+        ready(prs)
+    }
+
+    fn fetch_pr<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PullRequest> {
+        let pr = self
+            .prs
+            .iter()
+            .chain(&self.inbox)
+            .find(|pr| pr.repo_owner == owner && pr.repo_name == name && pr.number == number);
+        let result = match pr {
+            Some(pr) => Ok((pr.clone(), None)),
+            None => Err(anyhow!(
+                "Pull request {}/{}#{} not found",
+                owner,
+                name,
+                number
+            )),
+        };
+        futures::future::ready(result).boxed()
+    }
+
+    fn fetch_pr_detail<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PrDetail> {
+        let key = detail_key(owner, name, number);
+        let result = match self.details.get(&key) {
+            Some(detail) => Ok((detail.clone(), None)),
+            None => Err(anyhow!("No detail fixture for {}", key)),
+        };
+        futures::future::ready(result).boxed()
+    }
+
+    fn fetch_pr_diff<'a>(
+        &'a self,
+        _owner: &'a str,
+        _name: &'a str,
+        _number: u32,
+    ) -> BoxFuture<'a, Result<String>> {
+        futures::future::ready(Err(anyhow!("fixtures: diffs are not available"))).boxed()
+    }
+}
+
+/// Fetch the configured owners' live data with `client` and write it to
+/// `dir` as a fixture set (`--dump-fixtures <dir>`). PR details aren't
+/// dumped; fetching one per PR would be slow and burn rate limit.
+pub async fn dump(
+    client: &GithubClient,
+    config: &AppConfig,
+    viewer: &str,
+    dir: &Path,
+) -> Result<()> {
+    let github = &config.github;
+    let dashboard = &config.dashboard;
+
+    let mut repos = Vec::new();
+    for owner in github.owners() {
+        let login = owner.login.as_str();
+        let kind = match owner.kind {
+            OwnerKind::Unknown => client
+                .fetch_owner_kind(login)
+                .await?
+                .0
+                .ok_or_else(|| anyhow!("No GitHub user or organization named {}", login))?,
+            kind => kind,
+        };
+        let (owner_repos, _) = match kind {
+            OwnerKind::User => client.fetch_user_repos(login).await?,
+            _ => client.fetch_org_repos(login).await?,
+        };
+        repos.extend(owner_repos);
+    }
+    let (inbox, _) = client.fetch_inbox(viewer, &github.teams).await?;
+    let (orgs, users) = github.search_scope();
+    let repos = github.standalone_repos();
+    let (mut prs, _) = client.fetch_all_open_prs(&orgs, &users, &repos).await?;
+    for (kind, window_days) in [
+        (PrHistoryKind::Merged, dashboard.merged_window_days),
+        (PrHistoryKind::Closed, dashboard.closed_window_days),
+    ] {
+        let (history, _) = client
+            .fetch_pr_history(kind, &orgs, &users, &repos, window_days)
+            .await?;
+        prs.extend(history);
+    }
+
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create fixture directory: {}", dir.display()))?;
+    write(&dir.join(VIEWER_FILE), &viewer)?;
+    write(&dir.join(REPOS_FILE), &repos)?;
+    write(&dir.join(INBOX_FILE), &inbox)?;
+    write(&dir.join(PRS_FILE), &prs)?;
+    info!(
+        dir = %dir.display(),
+        repos = repos.len(),
+        prs = prs.len(),
+        "Dumped fixtures"
+    );
+    Ok(())
+}
+
+fn write<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let content = serde_json::to_string_pretty(value).context("Failed to serialize fixture")?;
+    std::fs::write(path, content + "\n")
+        .with_context(|| format!("Failed to write fixture file: {}", path.display()))
+}
@@ -1,37 +1,158 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use anyhow::{Context, Result, bail};
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Certificate, Client, ClientBuilder, Identity, StatusCode};
 use serde_json::{Value, json};
-use tracing::debug;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use tracing::{debug, warn};
 
+use super::auth::TokenRefresher;
+use super::cancel::CancelToken;
 use super::models::*;
 use super::queries;
+use crate::util::config::{GithubConfig, OwnerKind};
+use crate::util::secret::{SecretString, redact};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct GithubClient {
     client: Client,
     api_url: String,
-    token: String,
+    token: SecretString,
+    /// Supplies the token instead of `token` when it expires and is renewed
+    /// (GitHub App auth).
+    refresher: Option<Arc<TokenRefresher>>,
+    /// Offline clients fail every request without touching the network.
+    offline: bool,
+    /// Set while a secondary rate limit is being waited out; shared between
+    /// clones so every in-flight request holds off, and the UI can show it.
+    backoff_until: Arc<Mutex<Option<Instant>>>,
+    /// Woken when a backoff starts, so the UI shows it without waiting for
+    /// some other event to redraw.
+    backoff_started: Arc<Notify>,
+    /// Set once GitHub answers 401, so the UI can ask for a fresh token
+    /// instead of reporting every failed fetch. A new client starts clear.
+    token_rejected: Arc<AtomicBool>,
 }
 
+/// Wait used when a secondary rate limit response carries no `Retry-After`.
+const DEFAULT_SECONDARY_BACKOFF: Duration = Duration::from_secs(60);
+
 impl GithubClient {
-    pub fn new(token: &str, api_url: &str) -> Result<Self> {
+    pub fn new(token: &str, config: &GithubConfig) -> Result<Self> {
+        let api_url = &config.api_url;
         if !api_url.starts_with("https://") {
             bail!("GitHub API URL must use HTTPS: {}", api_url);
         }
 
-        let client = Client::builder()
-            .user_agent("ghdash")
-            .build()
-            .context("Failed to create HTTP client")?;
-
         Ok(Self {
-            client,
+            client: Self::http_client(config)?,
             api_url: api_url.to_string(),
-            token: token.to_string(),
+            token: SecretString::new(token),
+            refresher: None,
+            offline: false,
+            backoff_until: Arc::default(),
+            backoff_started: Arc::default(),
+            token_rejected: Arc::default(),
         })
     }
 
+    /// An HTTP client sending `github.user_agent` and `github.extra_headers`.
+    pub fn http_client(config: &GithubConfig) -> Result<Client> {
+        let (user_agent, headers) = request_headers(config)?;
+        let builder = Client::builder()
+            .user_agent(user_agent)
+            .default_headers(headers);
+        with_tls_options(builder, config)?
+            .build()
+            .context("Failed to create HTTP client")
+    }
+
+    /// Have requests take their token from `refresher` (GitHub App tokens,
+    /// which expire) instead of the fixed one.
+    pub fn with_refresher(mut self, refresher: Option<Arc<TokenRefresher>>) -> Self {
+        self.refresher = refresher;
+        self
+    }
+
+    /// The token to send: the refresher's, if there is one.
+    async fn current_token(&self) -> Result<SecretString> {
+        match &self.refresher {
+            Some(refresher) => refresher.token().await,
+            None => Ok(self.token.clone()),
+        }
+    }
+
+    /// A client for `--offline` sessions: no token, and every request fails
+    /// immediately so only cached data is shown.
+    pub fn offline(config: &GithubConfig) -> Self {
+        Self {
+            client: Client::new(),
+            api_url: config.api_url.clone(),
+            token: SecretString::new(""),
+            refresher: None,
+            offline: true,
+            backoff_until: Arc::default(),
+            backoff_started: Arc::default(),
+            token_rejected: Arc::default(),
+        }
+    }
+
+    /// A client with the same token for an updated `[github]` config, after the
+    /// config file is reloaded. Offline clients stay offline; App tokens are
+    /// minted afresh if the reload changed the App's credentials.
+    pub fn reconfigured(&self, config: &GithubConfig) -> Result<Self> {
+        if self.offline {
+            return Ok(Self::offline(config));
+        }
+        let refresher = self
+            .refresher
+            .as_ref()
+            .map(|refresher| refresher.reconfigured(config))
+            .transpose()?;
+        Ok(Self::new(self.token.expose(), config)?.with_refresher(refresher))
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Time left before requests resume after a secondary rate limit, if any.
+    pub fn backoff_remaining(&self) -> Option<Duration> {
+        let until = (*self.backoff_until.lock().unwrap())?;
+        let now = Instant::now();
+        (until > now).then(|| until - now)
+    }
+
+    /// Resolves once a secondary rate limit backoff starts, or right away if
+    /// one started since the last call.
+    pub async fn backoff_started(&self) {
+        self.backoff_started.notified().await
+    }
+
+    /// Whether GitHub has rejected this client's token (expired or revoked).
+    pub fn token_rejected(&self) -> bool {
+        self.token_rejected.load(Ordering::Relaxed)
+    }
+
+    fn start_backoff(&self, wait: Duration) {
+        let until = Instant::now() + wait;
+        let mut guard = self.backoff_until.lock().unwrap();
+        // Concurrent responses may report different waits; honour the longest.
+        if guard.is_none_or(|current| current < until) {
+            *guard = Some(until);
+        }
+        self.backoff_started.notify_one();
+    }
+
     async fn query(&self, query: &str, variables: Value) -> Result<Value> {
+        if self.offline {
+            bail!("offline: no cached data available");
+        }
         let body = json!({
             "query": query,
             "variables": variables,
@@ -41,16 +162,25 @@ impl GithubClient {
         // under load, especially for broad searches over many repos. Retry a few
         // times with backoff before surfacing the error.
         const MAX_ATTEMPTS: u32 = 3;
+        // Secondary rate limits are waited out rather than reported, up to a point.
+        const MAX_BACKOFFS: u32 = 3;
+        let token = self.current_token().await?;
         let mut attempt = 0;
+        let mut backoffs = 0;
         let resp = loop {
+            if let Some(wait) = self.backoff_remaining() {
+                tokio::time::sleep(wait).await;
+            }
             attempt += 1;
             let resp = self
                 .client
                 .post(&self.api_url)
-                .bearer_auth(&self.token)
+                .bearer_auth(token.expose())
                 .json(&body)
                 .send()
                 .await
+                // Proxy URLs can carry credentials; keep them out of the error.
+                .map_err(|e| e.without_url())
                 .context("GitHub API request failed")?;
 
             let status = resp.status();
@@ -65,8 +195,28 @@ impl GithubClient {
                 continue;
             }
 
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string());
             let text = resp.text().await.unwrap_or_default();
-            bail!("GitHub API returned {}: {}", status, text);
+            if status == StatusCode::UNAUTHORIZED {
+                self.token_rejected.store(true, Ordering::Relaxed);
+            }
+            if let Some(wait) = secondary_rate_limit_wait(status, retry_after.as_deref(), &text)
+                && backoffs < MAX_BACKOFFS
+            {
+                backoffs += 1;
+                debug!(%status, secs = wait.as_secs(), "Secondary rate limit, backing off");
+                self.start_backoff(wait);
+                continue;
+            }
+            return Err(ApiStatusError {
+                status,
+                body: redact(&text).into_owned(),
+            }
+            .into());
         };
 
         let data: Value = resp
@@ -75,25 +225,47 @@ impl GithubClient {
             .context("Failed to parse GitHub response")?;
 
         if let Some(errors) = data.get("errors") {
-            let error_msg = errors
-                .as_array()
-                .and_then(|arr| arr.first())
+            let first = errors.as_array().and_then(|arr| arr.first());
+            let error_msg = first
                 .and_then(|e| e.get("message"))
                 .and_then(|m| m.as_str())
                 .unwrap_or("Unknown GraphQL error");
-            bail!("GraphQL error: {}", error_msg);
+            return Err(GraphqlError {
+                kind: first.and_then(|e| e["type"].as_str()).map(str::to_string),
+                message: redact(error_msg).into_owned(),
+            }
+            .into());
         }
 
         Ok(data)
     }
 
-    fn extract_rate_limit(data: &Value) -> RateLimit {
-        let rl = &data["data"]["rateLimit"];
-        RateLimit {
-            remaining: rl["remaining"].as_u64().unwrap_or(0) as u32,
-            limit: rl["limit"].as_u64().unwrap_or(0) as u32,
-            reset_at: rl["resetAt"].as_str().and_then(|s| s.parse().ok()),
-        }
+    /// Parse the `rateLimit` block of a response. GitHub App installation tokens
+    /// get `null` here (their limits aren't exposed via GraphQL), so absence is
+    /// reported as `None` rather than a misleading 0/0.
+    pub fn extract_rate_limit(data: &Value) -> Option<RateLimit> {
+        let rl = data["data"]["rateLimit"].as_object()?;
+        Some(RateLimit {
+            remaining: rl.get("remaining")?.as_u64()? as u32,
+            limit: rl.get("limit")?.as_u64()? as u32,
+            reset_at: rl
+                .get("resetAt")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse().ok()),
+        })
+    }
+
+    /// Topic names from a repository node's `repositoryTopics` connection.
+    pub fn parse_topics(node: &Value) -> Vec<String> {
+        node["repositoryTopics"]["nodes"]
+            .as_array()
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .filter_map(|n| n["topic"]["name"].as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub async fn fetch_viewer(&self) -> Result<String> {
@@ -106,10 +278,33 @@ impl GithubClient {
         Ok(login)
     }
 
-    pub async fn fetch_org_repos(&self, org: &str) -> Result<(Vec<Repo>, RateLimit)> {
+    /// Whether `login` is an organization or a user; `None` when GitHub
+    /// has neither by that name.
+    pub async fn fetch_owner_kind(
+        &self,
+        login: &str,
+    ) -> Result<(Option<OwnerKind>, Option<RateLimit>)> {
+        let data = self
+            .query(queries::OWNER_TYPE_QUERY, json!({ "login": login }))
+            .await?;
+        let kind = Self::parse_owner_kind(&data);
+        debug!(login = login, kind = ?kind, "Resolved owner type");
+        Ok((kind, Self::extract_rate_limit(&data)))
+    }
+
+    /// The owner type in an `OWNER_TYPE_QUERY` response.
+    pub fn parse_owner_kind(data: &Value) -> Option<OwnerKind> {
+        match data["data"]["repositoryOwner"]["__typename"].as_str()? {
+            "Organization" => Some(OwnerKind::Org),
+            "User" => Some(OwnerKind::User),
+            _ => None,
+        }
+    }
+
+    pub async fn fetch_org_repos(&self, org: &str) -> Result<(Vec<Repo>, Option<RateLimit>)> {
         let mut all_repos = Vec::new();
         let mut cursor: Option<String> = None;
-        let mut rate_limit;
+        let mut rate_limit = None;
 
         loop {
             let variables = json!({
@@ -118,24 +313,14 @@ impl GithubClient {
             });
 
             let data = self.query(queries::ORG_REPOS_QUERY, variables).await?;
-            rate_limit = Self::extract_rate_limit(&data);
+            rate_limit = Self::extract_rate_limit(&data).or(rate_limit);
 
             let repos_data = &data["data"]["organization"]["repositories"];
             let nodes = repos_data["nodes"]
                 .as_array()
                 .context("Missing repository nodes")?;
 
-            for node in nodes {
-                let repo = Repo {
-                    name: node["name"].as_str().unwrap_or("").to_string(),
-                    owner: node["owner"]["login"].as_str().unwrap_or("").to_string(),
-                    url: node["url"].as_str().unwrap_or("").to_string(),
-                    description: node["description"].as_str().map(|s| s.to_string()),
-                    open_pr_count: node["pullRequests"]["totalCount"].as_u64().unwrap_or(0) as u32,
-                    is_archived: node["isArchived"].as_bool().unwrap_or(false),
-                };
-                all_repos.push(repo);
-            }
+            all_repos.extend(nodes.iter().map(Self::parse_repo));
 
             let page_info = &repos_data["pageInfo"];
             if page_info["hasNextPage"].as_bool().unwrap_or(false) {
@@ -149,10 +334,81 @@ impl GithubClient {
         Ok((all_repos, rate_limit))
     }
 
-    pub async fn fetch_user_repos(&self, user: &str) -> Result<(Vec<Repo>, RateLimit)> {
+    /// Repo lists for several orgs, fetched together with one aliased query
+    /// per page instead of one request per org. Results are in `orgs` order.
+    /// Any error (one org not existing, say) fails the whole batch.
+    pub async fn fetch_orgs_batched(
+        &self,
+        orgs: &[String],
+    ) -> Result<(Vec<Vec<Repo>>, Option<RateLimit>)> {
+        let mut all_repos: Vec<Vec<Repo>> = vec![Vec::new(); orgs.len()];
+        // Orgs still to fetch, with the cursor of their next page.
+        let mut pending: Vec<(usize, Option<String>)> =
+            (0..orgs.len()).map(|i| (i, None)).collect();
+        let mut rate_limit = None;
+
+        while !pending.is_empty() {
+            let mut variables = serde_json::Map::new();
+            for (alias, (org, cursor)) in pending.iter().enumerate() {
+                variables.insert(format!("org{alias}"), json!(orgs[*org]));
+                variables.insert(format!("cursor{alias}"), json!(cursor));
+            }
+            let query = queries::org_repos_batch_query(pending.len());
+            let data = self.query(&query, Value::Object(variables)).await?;
+            rate_limit = Self::extract_rate_limit(&data).or(rate_limit);
+
+            let pages = Self::parse_org_batch(&data, pending.len())?;
+            let mut next = Vec::new();
+            for ((org, _), (repos, cursor)) in pending.into_iter().zip(pages) {
+                all_repos[org].extend(repos);
+                if cursor.is_some() {
+                    next.push((org, cursor));
+                }
+            }
+            pending = next;
+        }
+
+        debug!(orgs = orgs.len(), "Fetched org repos in a batch");
+        Ok((all_repos, rate_limit))
+    }
+
+    /// One page per alias (`org0`, `org1`, ...) of a batched org repos
+    /// response: the repos, and the cursor of the next page if there is one.
+    pub fn parse_org_batch(data: &Value, count: usize) -> Result<Vec<(Vec<Repo>, Option<String>)>> {
+        (0..count)
+            .map(|i| {
+                let repos_data = &data["data"][format!("org{i}")]["repositories"];
+                let nodes = repos_data["nodes"]
+                    .as_array()
+                    .with_context(|| format!("Missing repository nodes for org{i}"))?;
+                let page_info = &repos_data["pageInfo"];
+                let cursor = page_info["hasNextPage"]
+                    .as_bool()
+                    .unwrap_or(false)
+                    .then(|| page_info["endCursor"].as_str().map(|s| s.to_string()))
+                    .flatten();
+                Ok((nodes.iter().map(Self::parse_repo).collect(), cursor))
+            })
+            .collect()
+    }
+
+    /// A repository node from the org or user repo queries.
+    pub fn parse_repo(node: &Value) -> Repo {
+        Repo {
+            name: node["name"].as_str().unwrap_or("").to_string(),
+            owner: node["owner"]["login"].as_str().unwrap_or("").to_string(),
+            url: node["url"].as_str().unwrap_or("").to_string(),
+            description: node["description"].as_str().map(|s| s.to_string()),
+            open_pr_count: node["pullRequests"]["totalCount"].as_u64().unwrap_or(0) as u32,
+            is_archived: node["isArchived"].as_bool().unwrap_or(false),
+            topics: Self::parse_topics(node),
+        }
+    }
+
+    pub async fn fetch_user_repos(&self, user: &str) -> Result<(Vec<Repo>, Option<RateLimit>)> {
         let mut all_repos = Vec::new();
         let mut cursor: Option<String> = None;
-        let mut rate_limit;
+        let mut rate_limit = None;
 
         loop {
             let variables = json!({
@@ -161,24 +417,14 @@ impl GithubClient {
             });
 
             let data = self.query(queries::USER_REPOS_QUERY, variables).await?;
-            rate_limit = Self::extract_rate_limit(&data);
+            rate_limit = Self::extract_rate_limit(&data).or(rate_limit);
 
             let repos_data = &data["data"]["user"]["repositories"];
             let nodes = repos_data["nodes"]
                 .as_array()
                 .context("Missing repository nodes")?;
 
-            for node in nodes {
-                let repo = Repo {
-                    name: node["name"].as_str().unwrap_or("").to_string(),
-                    owner: node["owner"]["login"].as_str().unwrap_or("").to_string(),
-                    url: node["url"].as_str().unwrap_or("").to_string(),
-                    description: node["description"].as_str().map(|s| s.to_string()),
-                    open_pr_count: node["pullRequests"]["totalCount"].as_u64().unwrap_or(0) as u32,
-                    is_archived: node["isArchived"].as_bool().unwrap_or(false),
-                };
-                all_repos.push(repo);
-            }
+            all_repos.extend(nodes.iter().map(Self::parse_repo));
 
             let page_info = &repos_data["pageInfo"];
             if page_info["hasNextPage"].as_bool().unwrap_or(false) {
@@ -192,19 +438,34 @@ impl GithubClient {
         Ok((all_repos, rate_limit))
     }
 
-    pub async fn search_prs(&self, query_string: &str) -> Result<(Vec<PullRequest>, RateLimit)> {
+    pub async fn search_prs(
+        &self,
+        query_string: &str,
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
+        self.search_prs_as(query_string, None).await
+    }
+
+    /// [`Self::search_prs`], also fetching `viewer`'s latest review of each
+    /// PR into `my_review_state` when given.
+    pub async fn search_prs_as(
+        &self,
+        query_string: &str,
+        viewer: Option<&str>,
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
         let mut all_prs = Vec::new();
         let mut cursor: Option<String> = None;
-        let mut rate_limit;
+        let mut rate_limit = None;
 
         loop {
             let variables = json!({
                 "query": query_string,
                 "cursor": cursor,
+                "viewer": viewer,
+                "mine": viewer.is_some(),
             });
 
             let data = self.query(queries::SEARCH_PRS_QUERY, variables).await?;
-            rate_limit = Self::extract_rate_limit(&data);
+            rate_limit = Self::extract_rate_limit(&data).or(rate_limit);
 
             let search_data = &data["data"]["search"];
             let nodes = search_data["nodes"]
@@ -235,26 +496,51 @@ impl GithubClient {
         Ok((all_prs, rate_limit))
     }
 
-    pub async fn fetch_inbox(&self, viewer_login: &str) -> Result<(Vec<PullRequest>, RateLimit)> {
+    /// Search string for open PRs awaiting review from `team` (`org/team-slug`).
+    pub fn team_review_query(team: &str) -> String {
+        format!(
+            "is:open is:pr team-review-requested:{} archived:false",
+            team
+        )
+    }
+
+    pub async fn fetch_inbox(
+        &self,
+        viewer_login: &str,
+        teams: &[String],
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
         let review_query = format!(
             "is:open is:pr review-requested:{} archived:false",
             viewer_login
         );
         let assigned_query = format!("is:open is:pr assignee:{} archived:false", viewer_login);
+        let team_queries: Vec<String> = teams.iter().map(|t| Self::team_review_query(t)).collect();
 
-        let (review_result, assigned_result) = tokio::join!(
-            self.search_prs(&review_query),
-            self.search_prs(&assigned_query),
+        let viewer = Some(viewer_login);
+        let (review_result, assigned_result, team_results) = tokio::join!(
+            self.search_prs_as(&review_query, viewer),
+            self.search_prs_as(&assigned_query, viewer),
+            futures::future::join_all(team_queries.iter().map(|q| self.search_prs_as(q, viewer))),
         );
 
-        let (review_prs, _) = review_result.context("Failed to fetch review-requested PRs")?;
+        let (review_prs, review_rate_limit) =
+            review_result.context("Failed to fetch review-requested PRs")?;
         let (assigned_prs, rate_limit) = assigned_result.context("Failed to fetch assigned PRs")?;
+        let mut rate_limit = rate_limit.or(review_rate_limit);
+
+        let mut team_prs = Vec::new();
+        for (team, result) in teams.iter().zip(team_results) {
+            let (prs, team_rate_limit) = result
+                .with_context(|| format!("Failed to fetch PRs requesting review from {}", team))?;
+            rate_limit = rate_limit.or(team_rate_limit);
+            team_prs.extend(prs);
+        }
 
         // Deduplicate by (repo, number)
         let mut seen = std::collections::HashSet::new();
         let mut inbox = Vec::new();
 
-        for pr in review_prs.into_iter().chain(assigned_prs) {
+        for pr in review_prs.into_iter().chain(assigned_prs).chain(team_prs) {
             let key = (pr.repo_full_name(), pr.number);
             if seen.insert(key) {
                 inbox.push(pr);
@@ -268,21 +554,171 @@ impl GithubClient {
         Ok((inbox, rate_limit))
     }
 
+    /// Search string for every open PR of the configured owners, plus the
+    /// standalone `repos` (`owner/name`).
+    pub fn all_open_prs_query(orgs: &[String], users: &[String], repos: &[String]) -> String {
+        format!(
+            "is:open is:pr archived:false {}",
+            scope_qualifiers(orgs, users, repos)
+        )
+    }
+
     pub async fn fetch_all_open_prs(
         &self,
         orgs: &[String],
         users: &[String],
-    ) -> Result<(Vec<PullRequest>, RateLimit)> {
-        let mut owner_filters: Vec<String> = Vec::new();
-        for o in orgs {
-            owner_filters.push(format!("org:{}", o));
-        }
-        for u in users {
-            owner_filters.push(format!("user:{}", u));
+        repos: &[String],
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
+        self.search_prs(&Self::all_open_prs_query(orgs, users, repos))
+            .await
+    }
+
+    /// Search string for open PRs across the configured owners and standalone
+    /// `repos` that `login` authored, was asked to review, is assigned to, or
+    /// commented on.
+    pub fn involves_search_query(
+        login: &str,
+        orgs: &[String],
+        users: &[String],
+        repos: &[String],
+    ) -> String {
+        format!(
+            "is:open is:pr archived:false involves:{} {}",
+            login,
+            scope_qualifiers(orgs, users, repos)
+        )
+    }
+
+    /// Open PRs involving `login`, for the All PRs "involving me" filter.
+    pub async fn fetch_involved_prs(
+        &self,
+        login: &str,
+        orgs: &[String],
+        users: &[String],
+        repos: &[String],
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
+        self.search_prs(&Self::involves_search_query(login, orgs, users, repos))
+            .await
+    }
+
+    /// Fetch a repository's PRs in `state`, most recently updated first. Merged
+    /// and closed PRs are only for context, so just the first page is fetched.
+    /// Once `cancel` fires, returns the pages fetched so far.
+    pub async fn fetch_repo_prs_in_state(
+        &self,
+        owner: &str,
+        name: &str,
+        state: PrStateFilter,
+        cancel: &CancelToken,
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
+        let (all_prs, rate_limit) = paginate(cancel, |cursor| async move {
+            let variables = json!({
+                "owner": owner,
+                "name": name,
+                "states": [state.graphql_state()],
+                "cursor": cursor,
+            });
+
+            let data = self.query(queries::REPO_PRS_QUERY, variables).await?;
+            let prs_data = &data["data"]["repository"]["pullRequests"];
+            let nodes = prs_data["nodes"]
+                .as_array()
+                .context("Missing pull request nodes")?;
+
+            let items = nodes
+                .iter()
+                .map(|node| {
+                    // The repository is implied by the query rather than selected per node.
+                    let mut pr = parse_search_pr(node);
+                    pr.repo_owner = owner.to_string();
+                    pr.repo_name = name.to_string();
+                    pr
+                })
+                .collect();
+
+            let page_info = &prs_data["pageInfo"];
+            let next_cursor = (state == PrStateFilter::Open
+                && page_info["hasNextPage"].as_bool().unwrap_or(false))
+            .then(|| page_info["endCursor"].as_str().map(|s| s.to_string()))
+            .flatten();
+            Ok(Page {
+                items,
+                next_cursor,
+                rate_limit: Self::extract_rate_limit(&data),
+            })
+        })
+        .await?;
+
+        debug!(
+            owner,
+            name,
+            state = state.graphql_state(),
+            count = all_prs.len(),
+            "Fetched repo PRs"
+        );
+        Ok((all_prs, rate_limit))
+    }
+
+    /// Search string for PRs of `kind` finished on or after `since` across the
+    /// configured owners.
+    pub fn history_search_query(
+        kind: PrHistoryKind,
+        orgs: &[String],
+        users: &[String],
+        repos: &[String],
+        since: chrono::NaiveDate,
+    ) -> String {
+        format!(
+            "is:pr {} {}:>={} {}",
+            kind.qualifiers(),
+            kind.date_field(),
+            since.format("%Y-%m-%d"),
+            scope_qualifiers(orgs, users, repos)
+        )
+    }
+
+    /// PRs of `kind` finished within the last `window_days` days, for the
+    /// history views. Most recently finished first.
+    pub async fn fetch_pr_history(
+        &self,
+        kind: PrHistoryKind,
+        orgs: &[String],
+        users: &[String],
+        repos: &[String],
+        window_days: u32,
+    ) -> Result<(Vec<PullRequest>, Option<RateLimit>)> {
+        let since = (crate::util::time::now() - chrono::Duration::days(i64::from(window_days)))
+            .date_naive();
+        let query_string = Self::history_search_query(kind, orgs, users, repos, since);
+        let (mut prs, rate_limit) = self.search_prs(&query_string).await?;
+        prs.sort_by_key(|pr| std::cmp::Reverse(kind.finished_at(pr)));
+        Ok((prs, rate_limit))
+    }
+
+    /// Fetch a single PR by number, in whatever state it is in. Used for
+    /// watched PRs missing from the loaded lists.
+    pub async fn fetch_pr(
+        &self,
+        owner: &str,
+        name: &str,
+        number: u32,
+    ) -> Result<(PullRequest, Option<RateLimit>)> {
+        let variables = json!({
+            "owner": owner,
+            "name": name,
+            "number": number,
+        });
+
+        let data = self.query(queries::PR_QUERY, variables).await?;
+        let rate_limit = Self::extract_rate_limit(&data);
+
+        let pr_node = &data["data"]["repository"]["pullRequest"];
+        if pr_node.is_null() {
+            bail!("Pull request {}/{}#{} not found", owner, name, number);
         }
-        let filter = owner_filters.join(" ");
-        let query_string = format!("is:open is:pr archived:false {}", filter);
-        self.search_prs(&query_string).await
+
+        debug!(owner, name, number, "Fetched PR");
+        Ok((parse_search_pr(pr_node), rate_limit))
     }
 
     /// Fetch on-demand detail for a single PR (fresh merge state, recent commits,
@@ -292,7 +728,7 @@ impl GithubClient {
         owner: &str,
         name: &str,
         number: u32,
-    ) -> Result<(PrDetail, RateLimit)> {
+    ) -> Result<(PrDetail, Option<RateLimit>)> {
         let variables = json!({
             "owner": owner,
             "name": name,
@@ -310,23 +746,15 @@ impl GithubClient {
         Ok((parse_pr_detail(pr_node), rate_limit))
     }
 
-    /// REST v3 base URL, derived from the configured GraphQL `api_url`.
-    /// `https://api.github.com/graphql` → `https://api.github.com`;
-    /// Enterprise `https://host/api/graphql` → `https://host/api/v3`.
-    fn rest_base(&self) -> String {
-        match self.api_url.strip_suffix("/graphql") {
-            Some(base) if base.ends_with("/api") => format!("{}/v3", base),
-            Some(base) => base.to_string(),
-            None => "https://api.github.com".to_string(),
-        }
-    }
-
     /// Fetch the full unified diff for a single PR via the REST API
     /// (`Accept: application/vnd.github.v3.diff`), used by the diff overlay.
     pub async fn fetch_pr_diff(&self, owner: &str, name: &str, number: u32) -> Result<String> {
+        if self.offline {
+            bail!("offline: diffs are not cached");
+        }
         let url = format!(
             "{}/repos/{}/{}/pulls/{}",
-            self.rest_base(),
+            rest_base(&self.api_url),
             owner,
             name,
             number
@@ -335,16 +763,24 @@ impl GithubClient {
         let resp = self
             .client
             .get(&url)
-            .bearer_auth(&self.token)
+            .bearer_auth(self.current_token().await?.expose())
             .header(reqwest::header::ACCEPT, "application/vnd.github.v3.diff")
             .send()
             .await
+            .map_err(|e| e.without_url())
             .context("GitHub diff request failed")?;
 
         let status = resp.status();
         if !status.is_success() {
+            if status == StatusCode::UNAUTHORIZED {
+                self.token_rejected.store(true, Ordering::Relaxed);
+            }
             let text = resp.text().await.unwrap_or_default();
-            bail!("GitHub API returned {}: {}", status, text);
+            return Err(ApiStatusError {
+                status,
+                body: redact(&text).into_owned(),
+            }
+            .into());
         }
 
         let diff = resp.text().await.context("Failed to read PR diff")?;
@@ -353,6 +789,282 @@ impl GithubClient {
     }
 }
 
+/// REST v3 base URL, derived from the configured GraphQL `api_url`.
+/// `https://api.github.com/graphql` → `https://api.github.com`;
+/// Enterprise `https://host/api/graphql` → `https://host/api/v3`.
+pub fn rest_base(api_url: &str) -> String {
+    match api_url.strip_suffix("/graphql") {
+        Some(base) if base.ends_with("/api") => format!("{}/v3", base),
+        Some(base) => base.to_string(),
+        None => "https://api.github.com".to_string(),
+    }
+}
+
+/// One page of a cursor-paginated connection.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor of the next page; `None` on the last one.
+    pub next_cursor: Option<String>,
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// Fetch pages with `fetch_page(cursor)` until the last one. `cancel` is
+/// checked between pages: once it fires, the items gathered so far are
+/// returned without fetching the rest.
+pub async fn paginate<T, F, Fut>(
+    cancel: &CancelToken,
+    mut fetch_page: F,
+) -> Result<(Vec<T>, Option<RateLimit>)>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Page<T>>>,
+{
+    let mut items = Vec::new();
+    let mut rate_limit = None;
+    let mut cursor = None;
+    loop {
+        let page = fetch_page(cursor).await?;
+        items.extend(page.items);
+        rate_limit = page.rate_limit.or(rate_limit);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+        if cancel.is_cancelled() {
+            debug!(count = items.len(), "Pagination cancelled");
+            break;
+        }
+    }
+    Ok((items, rate_limit))
+}
+
+/// How long to wait if a failed response is a secondary ("abuse") rate limit
+/// rather than an ordinary error: a 403/429 whose body mentions the secondary
+/// rate limit, or that carries `Retry-After`. Distinct from running out of
+/// quota, which `rateLimit` reports.
+pub fn secondary_rate_limit_wait(
+    status: StatusCode,
+    retry_after: Option<&str>,
+    body: &str,
+) -> Option<Duration> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = retry_after.and_then(|v| v.trim().parse::<u64>().ok());
+    let mentioned = body.to_lowercase().contains("secondary rate limit");
+    if !mentioned && retry_after.is_none() {
+        return None;
+    }
+    Some(retry_after.map_or(DEFAULT_SECONDARY_BACKOFF, Duration::from_secs))
+}
+
+/// A non-success HTTP response from the API. Typed so callers can tell a
+/// rejected token from other failures.
+#[derive(Debug, thiserror::Error)]
+#[error("GitHub API returned {status}: {body}")]
+pub struct ApiStatusError {
+    pub status: StatusCode,
+    /// Response body, with anything token-like redacted.
+    pub body: String,
+}
+
+/// The first entry of a GraphQL response's `errors`. Typed so callers can
+/// tell a refusal from other failures.
+#[derive(Debug, thiserror::Error)]
+#[error("GraphQL error: {message}")]
+pub struct GraphqlError {
+    /// GitHub's error `type`, e.g. `NOT_FOUND` or `FORBIDDEN`.
+    pub kind: Option<String>,
+    /// The message, with anything token-like redacted.
+    pub message: String,
+}
+
+/// Whether `err` means the token can't see what was asked for: a 403 that
+/// isn't a rate limit, or a GraphQL `FORBIDDEN` / `NOT_FOUND` error (GitHub
+/// reports owners hidden from the token as not found).
+pub fn is_no_access(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(e) = cause.downcast_ref::<ApiStatusError>() {
+            return e.status == StatusCode::FORBIDDEN
+                && !e.body.to_lowercase().contains("rate limit");
+        }
+        cause
+            .downcast_ref::<GraphqlError>()
+            .is_some_and(|e| matches!(e.kind.as_deref(), Some("FORBIDDEN" | "NOT_FOUND")))
+    })
+}
+
+/// Search qualifiers for the configured owners. A login listed as both an
+/// org and a user only gets `user:`, which matches organizations too, where
+/// `org:` would fail the whole search for a user account.
+fn owner_qualifiers(orgs: &[String], users: &[String]) -> String {
+    let mut owner_filters: Vec<String> = Vec::new();
+    for o in orgs {
+        if !users.iter().any(|u| u.eq_ignore_ascii_case(o)) {
+            owner_filters.push(format!("org:{}", o));
+        }
+    }
+    for u in users {
+        owner_filters.push(format!("user:{}", u));
+    }
+    owner_filters.join(" ")
+}
+
+/// [`owner_qualifiers`] plus a `repo:` qualifier per standalone repo.
+fn scope_qualifiers(orgs: &[String], users: &[String], repos: &[String]) -> String {
+    let mut qualifiers = owner_qualifiers(orgs, users);
+    for repo in repos {
+        if !qualifiers.is_empty() {
+            qualifiers.push(' ');
+        }
+        qualifiers.push_str(&format!("repo:{}", repo));
+    }
+    qualifiers
+}
+
+/// Broad cause of a failed request, for picking the guidance shown to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// GitHub could not be reached (see [`is_network_error`]).
+    Network,
+    /// GitHub answered 401: the token is invalid, expired or revoked.
+    Unauthorized,
+    Other,
+}
+
+pub fn failure_kind(err: &anyhow::Error) -> FailureKind {
+    if is_network_error(err) {
+        return FailureKind::Network;
+    }
+    let unauthorized = err.chain().any(|cause| {
+        cause
+            .downcast_ref::<ApiStatusError>()
+            .is_some_and(|e| e.status == StatusCode::UNAUTHORIZED)
+    });
+    if unauthorized {
+        FailureKind::Unauthorized
+    } else {
+        FailureKind::Other
+    }
+}
+
+/// Whether `err` came from failing to reach GitHub at all (no route, DNS,
+/// timeout) rather than from a response.
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout())
+    })
+}
+
+/// The `User-Agent` and extra headers sent with every request, validated.
+/// Config load calls this too, so a bad header fails at startup with a clear
+/// error rather than on the first request.
+pub fn request_headers(config: &GithubConfig) -> Result<(HeaderValue, HeaderMap)> {
+    let user_agent = HeaderValue::from_str(&config.user_agent)
+        .with_context(|| format!("Invalid github.user_agent: {:?}", config.user_agent))?;
+    let headers = build_extra_headers(&config.extra_headers, &|name| std::env::var(name).ok())?;
+    Ok((user_agent, headers))
+}
+
+/// Apply `github.ca_bundle`, `client_cert`/`client_key` and
+/// `danger_accept_invalid_certs` to `builder`.
+fn with_tls_options(mut builder: ClientBuilder, config: &GithubConfig) -> Result<ClientBuilder> {
+    if let Some(path) = &config.ca_bundle {
+        let pem = read_pem(path, "github.ca_bundle")?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Failed to parse github.ca_bundle {}", path.display()))?;
+        if certs.is_empty() {
+            bail!(
+                "Failed to parse github.ca_bundle {}: no PEM certificates found",
+                path.display()
+            );
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    match (&config.client_cert, &config.client_key) {
+        (Some(cert_path), key_path) => {
+            let mut pem = read_pem(cert_path, "github.client_cert")?;
+            if let Some(key_path) = key_path {
+                pem.push(b'\n');
+                pem.extend(read_pem(key_path, "github.client_key")?);
+            }
+            let identity = Identity::from_pem(&pem).with_context(|| match key_path {
+                Some(key_path) => format!(
+                    "Failed to load client certificate {} with key {}",
+                    cert_path.display(),
+                    key_path.display()
+                ),
+                None => format!(
+                    "Failed to load client certificate {} (set github.client_key if the key is in a separate file)",
+                    cert_path.display()
+                ),
+            })?;
+            builder = builder.identity(identity);
+        }
+        (None, Some(_)) => bail!("github.client_key is set without github.client_cert"),
+        (None, None) => {}
+    }
+    if config.danger_accept_invalid_certs {
+        warn!(
+            "github.danger_accept_invalid_certs is on: TLS certificates are NOT verified, \
+             so the token can be intercepted"
+        );
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
+
+fn read_pem(path: &Path, setting: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).with_context(|| format!("Failed to read {} {}", setting, path.display()))
+}
+
+/// Prefix of an extra header value naming the environment variable that holds
+/// it, so secrets needn't live in the config file.
+pub const ENV_VALUE_PREFIX: &str = "env:";
+
+/// Validate and convert `github.extra_headers` into a header map, reading
+/// `env:VAR_NAME` values from `lookup`. `Authorization` is rejected since the
+/// token is always sent as a bearer credential.
+pub fn build_extra_headers(
+    extra: &std::collections::HashMap<String, String>,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in extra {
+        let value = match value.strip_prefix(ENV_VALUE_PREFIX) {
+            Some(var) => lookup(var).with_context(|| {
+                format!(
+                    "Header {:?} in github.extra_headers reads undefined environment variable {}",
+                    name, var
+                )
+            })?,
+            None => value.clone(),
+        };
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|| format!("Invalid header name in github.extra_headers: {:?}", name))?;
+        if header_name == reqwest::header::AUTHORIZATION {
+            bail!(
+                "github.extra_headers must not set Authorization; the token is sent automatically"
+            );
+        }
+        let mut header_value = HeaderValue::from_str(&value).with_context(|| {
+            format!(
+                "Invalid value for header {:?} in github.extra_headers",
+                name
+            )
+        })?;
+        // Keep gateway credentials out of debug output.
+        header_value.set_sensitive(true);
+        headers.insert(header_name, header_value);
+    }
+    Ok(headers)
+}
+
 fn parse_pr_detail(node: &Value) -> PrDetail {
     let commit_nodes = node["commits"]["nodes"].as_array();
 
@@ -383,15 +1095,32 @@ fn parse_pr_detail(node: &Value) -> PrDetail {
         .and_then(|n| n["commit"]["statusCheckRollup"]["state"].as_str())
         .map(|s| s.to_string());
 
+    let closes_issues = node["closingIssuesReferences"]["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|n| {
+                    let number = n["number"].as_u64()? as u32;
+                    let title = n["title"].as_str().unwrap_or("").to_string();
+                    Some((number, title))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
     PrDetail {
         mergeable: node["mergeable"].as_str().map(|s| s.to_string()),
         merge_state_status: node["mergeStateStatus"].as_str().map(|s| s.to_string()),
         checks_status,
         commits,
+        closes_issues,
+        body: node["body"].as_str().unwrap_or("").to_string(),
     }
 }
 
-fn parse_search_pr(node: &Value) -> PullRequest {
+/// A `PullRequest` node from a search or repository query. Missing fields get
+/// their defaults.
+pub fn parse_search_pr(node: &Value) -> PullRequest {
     let labels = node["labels"]["nodes"]
         .as_array()
         .map(|arr| {
@@ -400,6 +1129,14 @@ fn parse_search_pr(node: &Value) -> PullRequest {
                 .collect()
         })
         .unwrap_or_default();
+    let assignees = node["assignees"]["nodes"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|a| a["login"].as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
 
     PullRequest {
         number: node["number"].as_u64().unwrap_or(0) as u32,
@@ -426,16 +1163,33 @@ fn parse_search_pr(node: &Value) -> PullRequest {
             .and_then(|s| s.parse().ok())
             .unwrap_or_default(),
         is_draft: node["isDraft"].as_bool().unwrap_or(false),
+        state: node["state"]
+            .as_str()
+            .map(PrState::from_graphql)
+            .unwrap_or_default(),
         additions: node["additions"].as_u64().unwrap_or(0) as u32,
         deletions: node["deletions"].as_u64().unwrap_or(0) as u32,
         review_decision: node["reviewDecision"].as_str().map(|s| s.to_string()),
         mergeable: node["mergeable"].as_str().map(|s| s.to_string()),
         merge_state_status: node["mergeStateStatus"].as_str().map(|s| s.to_string()),
+        is_behind: node["mergeStateStatus"].as_str() == Some("BEHIND"),
         checks_status: node["commits"]["nodes"]
             .as_array()
             .and_then(|arr| arr.last())
             .and_then(|n| n["commit"]["statusCheckRollup"]["state"].as_str())
             .map(|s| s.to_string()),
+        merged_at: node["mergedAt"].as_str().and_then(|s| s.parse().ok()),
+        closed_at: node["closedAt"].as_str().and_then(|s| s.parse().ok()),
+        head_ref: node["headRefName"].as_str().map(|s| s.to_string()),
+        base_ref: node["baseRefName"].as_str().map(|s| s.to_string()),
+        comment_count: node["comments"]["totalCount"].as_u64().unwrap_or(0) as u32,
+        review_count: node["reviews"]["totalCount"].as_u64().unwrap_or(0) as u32,
+        my_review_state: node["myReviews"]["nodes"]
+            .as_array()
+            .and_then(|arr| arr.last())
+            .and_then(|n| n["state"].as_str())
+            .map(|s| s.to_string()),
         labels,
+        assignees,
     }
 }
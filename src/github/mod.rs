@@ -1,7 +1,12 @@
 pub mod auth;
+pub mod cancel;
+pub mod fixtures;
 pub mod graphql;
 pub mod models;
 pub mod queries;
+pub mod source;
 
+pub use cancel::CancelToken;
 pub use graphql::GithubClient;
 pub use models::*;
+pub use source::DataSource;
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +15,8 @@ pub struct Repo {
     pub description: Option<String>,
     pub open_pr_count: u32,
     pub is_archived: bool,
+    #[serde(default)]
+    pub topics: Vec<String>,
 }
 
 impl Repo {
@@ -24,6 +26,7 @@ impl Repo {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct PullRequest {
     pub number: u32,
     pub title: String,
@@ -34,6 +37,10 @@ pub struct PullRequest {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub is_draft: bool,
+    /// Open, merged or closed. Older cache entries don't have it; it is
+    /// worked out from `merged_at` and `closed_at` for them.
+    #[serde(default)]
+    pub state: PrState,
     pub additions: u32,
     pub deletions: u32,
     pub review_decision: Option<String>,
@@ -48,11 +55,87 @@ pub struct PullRequest {
     /// same lazy-compute caveat.
     #[serde(default)]
     pub merge_state_status: Option<String>,
+    /// The head branch is behind its base (`mergeStateStatus == BEHIND`), so
+    /// "Update branch" is needed before merging. `false` when unknown.
+    #[serde(default)]
+    pub is_behind: bool,
     /// `statusCheckRollup.state` of the PR's latest commit: `SUCCESS` / `FAILURE` /
     /// `PENDING` / `ERROR` / `EXPECTED`. Unlike `mergeable`, this is not computed
     /// lazily, so the search API returns real values. `None` = no checks / absent.
     #[serde(default)]
     pub checks_status: Option<String>,
+    /// When the PR was merged; `None` for open PRs and older cache entries.
+    #[serde(default)]
+    pub merged_at: Option<DateTime<Utc>>,
+    /// When the PR was closed (merged or not); `None` for open PRs.
+    #[serde(default)]
+    pub closed_at: Option<DateTime<Utc>>,
+    /// Source branch name; `None` in older cache entries.
+    #[serde(default)]
+    pub head_ref: Option<String>,
+    /// Target branch name; `None` in older cache entries.
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    /// Conversation comments; zero in older cache entries.
+    #[serde(default)]
+    pub comment_count: u32,
+    /// Submitted reviews; zero in older cache entries.
+    #[serde(default)]
+    pub review_count: u32,
+    /// State of the viewer's latest review (`APPROVED`, `CHANGES_REQUESTED`,
+    /// `COMMENTED`, ...). Only fetched for the inbox; `None` elsewhere and
+    /// when the viewer hasn't reviewed.
+    #[serde(default)]
+    pub my_review_state: Option<String>,
+    /// Logins of the first few assignees; empty when nobody is assigned and
+    /// in older cache entries.
+    #[serde(default)]
+    pub assignees: Vec<String>,
+}
+
+impl Serialize for PullRequest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PullRequest::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PullRequest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let has_state = value.get("state").is_some();
+        let mut pr = PullRequest::deserialize(value).map_err(serde::de::Error::custom)?;
+        if !has_state {
+            pr.state = if pr.merged_at.is_some() {
+                PrState::Merged
+            } else if pr.closed_at.is_some() {
+                PrState::Closed
+            } else {
+                PrState::Open
+            };
+        }
+        Ok(pr)
+    }
+}
+
+/// Lifecycle state of a PR (GraphQL `PullRequestState`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PrState {
+    #[default]
+    Open,
+    /// Closed without merging.
+    Closed,
+    Merged,
+}
+
+impl PrState {
+    /// Map the GraphQL enum; anything unrecognised counts as open.
+    pub fn from_graphql(state: &str) -> Self {
+        match state {
+            "MERGED" => PrState::Merged,
+            "CLOSED" => PrState::Closed,
+            _ => PrState::Open,
+        }
+    }
 }
 
 /// Coarse CI outcome derived from `checks_status`, decoupled from the raw GitHub
@@ -65,11 +148,101 @@ pub enum CiStatus {
     None,
 }
 
+/// Which finished PRs a history view lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrHistoryKind {
+    Merged,
+    /// Closed without merging.
+    Closed,
+}
+
+impl PrHistoryKind {
+    /// Search qualifiers selecting this kind of PR.
+    pub fn qualifiers(self) -> &'static str {
+        match self {
+            PrHistoryKind::Merged => "is:merged",
+            PrHistoryKind::Closed => "is:closed is:unmerged",
+        }
+    }
+
+    /// Search field the window's date bound applies to.
+    pub fn date_field(self) -> &'static str {
+        match self {
+            PrHistoryKind::Merged => "merged",
+            PrHistoryKind::Closed => "closed",
+        }
+    }
+
+    /// Short lowercase name, used in cache keys and messages.
+    pub fn as_str(self) -> &'static str {
+        self.date_field()
+    }
+
+    /// When the PR reached this state.
+    pub fn finished_at(self, pr: &PullRequest) -> Option<DateTime<Utc>> {
+        match self {
+            PrHistoryKind::Merged => pr.merged_at,
+            PrHistoryKind::Closed => pr.closed_at,
+        }
+    }
+}
+
+/// Which PRs a repo view lists. Cycled with the state key in repo views.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrStateFilter {
+    Open,
+    Merged,
+    /// Closed without merging.
+    Closed,
+}
+
+impl PrStateFilter {
+    /// Cycle Open -> Merged -> Closed -> Open.
+    pub fn next(self) -> Self {
+        match self {
+            PrStateFilter::Open => PrStateFilter::Merged,
+            PrStateFilter::Merged => PrStateFilter::Closed,
+            PrStateFilter::Closed => PrStateFilter::Open,
+        }
+    }
+
+    /// Short label for the table title; `None` for open PRs.
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            PrStateFilter::Open => None,
+            PrStateFilter::Merged => Some("merged"),
+            PrStateFilter::Closed => Some("closed"),
+        }
+    }
+
+    /// GraphQL `PullRequestState` value.
+    pub fn graphql_state(self) -> &'static str {
+        match self {
+            PrStateFilter::Open => "OPEN",
+            PrStateFilter::Merged => "MERGED",
+            PrStateFilter::Closed => "CLOSED",
+        }
+    }
+}
+
 impl PullRequest {
+    /// Whether the viewer already submitted a review (a pending draft
+    /// doesn't count).
+    pub fn reviewed_by_me(&self) -> bool {
+        self.my_review_state
+            .as_deref()
+            .is_some_and(|state| state != "PENDING")
+    }
+
     pub fn repo_full_name(&self) -> String {
         format!("{}/{}", self.repo_owner, self.repo_name)
     }
 
+    /// Total changed lines, `additions + deletions`.
+    pub fn diff_size(&self) -> u32 {
+        self.additions.saturating_add(self.deletions)
+    }
+
     /// Classify the CI check rollup into a coarse outcome for display.
     pub fn ci_status(&self) -> CiStatus {
         match self.checks_status.as_deref() {
@@ -108,6 +281,13 @@ pub struct PrDetail {
     pub checks_status: Option<String>,
     /// Recent commits, oldest-first as returned by GitHub (`commits(last: N)`).
     pub commits: Vec<CommitInfo>,
+    /// Issues this PR will close on merge (`closingIssuesReferences`), as
+    /// `(number, title)`. Only fetched here, never in the list queries.
+    #[serde(default)]
+    pub closes_issues: Vec<(u32, String)>,
+    /// The PR description, as markdown.
+    #[serde(default)]
+    pub body: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
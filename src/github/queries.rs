@@ -11,6 +11,19 @@ query {
 }
 "#;
 
+pub const OWNER_TYPE_QUERY: &str = r#"
+query($login: String!) {
+  repositoryOwner(login: $login) {
+    __typename
+  }
+  rateLimit {
+    remaining
+    limit
+    resetAt
+  }
+}
+"#;
+
 pub const ORG_REPOS_QUERY: &str = r#"
 query($org: String!, $cursor: String) {
   organization(login: $org) {
@@ -28,6 +41,9 @@ query($org: String!, $cursor: String) {
         pullRequests(states: OPEN) {
           totalCount
         }
+        repositoryTopics(first: 10) {
+          nodes { topic { name } }
+        }
       }
     }
   }
@@ -39,6 +55,51 @@ query($org: String!, $cursor: String) {
 }
 "#;
 
+/// Several orgs' repo lists in one request: org `i` is aliased `org{i}` and
+/// takes `$org{i}` / `$cursor{i}`, with the same fields as [`ORG_REPOS_QUERY`].
+pub fn org_repos_batch_query(count: usize) -> String {
+    let params: Vec<String> = (0..count)
+        .map(|i| format!("$org{i}: String!, $cursor{i}: String"))
+        .collect();
+    let mut query = format!("query({}) {{\n", params.join(", "));
+    for i in 0..count {
+        query.push_str(&format!(
+            r#"  org{i}: organization(login: $org{i}) {{
+    repositories(first: 100, after: $cursor{i}, orderBy: {{field: UPDATED_AT, direction: DESC}}) {{
+      pageInfo {{
+        hasNextPage
+        endCursor
+      }}
+      nodes {{
+        name
+        owner {{ login }}
+        url
+        description
+        isArchived
+        pullRequests(states: OPEN) {{
+          totalCount
+        }}
+        repositoryTopics(first: 10) {{
+          nodes {{ topic {{ name }} }}
+        }}
+      }}
+    }}
+  }}
+"#
+        ));
+    }
+    query.push_str(
+        r#"  rateLimit {
+    remaining
+    limit
+    resetAt
+  }
+}
+"#,
+    );
+    query
+}
+
 pub const USER_REPOS_QUERY: &str = r#"
 query($user: String!, $cursor: String) {
   user(login: $user) {
@@ -56,6 +117,9 @@ query($user: String!, $cursor: String) {
         pullRequests(states: OPEN) {
           totalCount
         }
+        repositoryTopics(first: 10) {
+          nodes { topic { name } }
+        }
       }
     }
   }
@@ -67,11 +131,10 @@ query($user: String!, $cursor: String) {
 }
 "#;
 
-#[allow(dead_code)]
 pub const REPO_PRS_QUERY: &str = r#"
-query($owner: String!, $name: String!, $cursor: String) {
+query($owner: String!, $name: String!, $states: [PullRequestState!], $cursor: String) {
   repository(owner: $owner, name: $name) {
-    pullRequests(first: 100, after: $cursor, states: OPEN, orderBy: {field: UPDATED_AT, direction: DESC}) {
+    pullRequests(first: 100, after: $cursor, states: $states, orderBy: {field: UPDATED_AT, direction: DESC}) {
       pageInfo {
         hasNextPage
         endCursor
@@ -84,10 +147,18 @@ query($owner: String!, $name: String!, $cursor: String) {
         createdAt
         updatedAt
         isDraft
+        state
         additions
         deletions
         reviewDecision
         mergeable
+        mergeStateStatus
+        mergedAt
+        closedAt
+        headRefName
+        baseRefName
+        comments { totalCount }
+        reviews { totalCount }
         commits(last: 1) {
           nodes {
             commit {
@@ -98,6 +169,9 @@ query($owner: String!, $name: String!, $cursor: String) {
         labels(first: 10) {
           nodes { name }
         }
+        assignees(first: 5) {
+          nodes { login }
+        }
       }
     }
   }
@@ -109,8 +183,9 @@ query($owner: String!, $name: String!, $cursor: String) {
 }
 "#;
 
+/// `$viewer`'s latest review of each PR is only fetched when `$mine` is set.
 pub const SEARCH_PRS_QUERY: &str = r#"
-query($query: String!, $cursor: String) {
+query($query: String!, $cursor: String, $viewer: String, $mine: Boolean! = false) {
   search(query: $query, type: ISSUE, first: 50, after: $cursor) {
     pageInfo {
       hasNextPage
@@ -129,10 +204,21 @@ query($query: String!, $cursor: String) {
         createdAt
         updatedAt
         isDraft
+        state
         additions
         deletions
         reviewDecision
         mergeable
+        mergeStateStatus
+        mergedAt
+        closedAt
+        headRefName
+        baseRefName
+        comments { totalCount }
+        reviews { totalCount }
+        myReviews: reviews(last: 1, author: $viewer) @include(if: $mine) {
+          nodes { state }
+        }
         commits(last: 1) {
           nodes {
             commit {
@@ -143,6 +229,61 @@ query($query: String!, $cursor: String) {
         labels(first: 10) {
           nodes { name }
         }
+        assignees(first: 5) {
+          nodes { login }
+        }
+      }
+    }
+  }
+  rateLimit {
+    remaining
+    limit
+    resetAt
+  }
+}
+"#;
+
+/// A single PR with the same fields as the list queries, for watched PRs that
+/// aren't in any loaded list (e.g. outside the configured owners, or merged).
+pub const PR_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      number
+      title
+      author { login }
+      repository {
+        name
+        owner { login }
+      }
+      url
+      createdAt
+      updatedAt
+      isDraft
+      state
+      additions
+      deletions
+      reviewDecision
+      mergeable
+      mergeStateStatus
+      mergedAt
+      closedAt
+      headRefName
+      baseRefName
+      comments { totalCount }
+      reviews { totalCount }
+      commits(last: 1) {
+        nodes {
+          commit {
+            statusCheckRollup { state }
+          }
+        }
+      }
+      labels(first: 10) {
+        nodes { name }
+      }
+      assignees(first: 5) {
+        nodes { login }
       }
     }
   }
@@ -161,8 +302,15 @@ pub const PR_DETAIL_QUERY: &str = r#"
 query($owner: String!, $name: String!, $number: Int!) {
   repository(owner: $owner, name: $name) {
     pullRequest(number: $number) {
+      body
       mergeable
       mergeStateStatus
+      closingIssuesReferences(first: 5) {
+        nodes {
+          number
+          title
+        }
+      }
       commits(last: 5) {
         nodes {
           commit {
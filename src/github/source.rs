@@ -0,0 +1,212 @@
+use anyhow::Result;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+
+use super::cancel::CancelToken;
+use super::graphql::GithubClient;
+use super::models::*;
+use crate::util::config::OwnerKind;
+
+/// A fetch in flight: its data, plus the rate limit reported alongside.
+pub type Fetch<'a, T> = BoxFuture<'a, Result<(T, Option<RateLimit>)>>;
+
+/// Where the dashboard's data comes from: GitHub itself, or canned JSON
+/// files (`--fixtures`) when working on the UI. Mirrors the fetches of
+/// [`GithubClient`] the event loop makes.
+pub trait DataSource: Send + Sync {
+    /// Whether this source only serves cached data (`--offline`).
+    fn is_offline(&self) -> bool {
+        false
+    }
+
+    /// Whether this source serves a fixture set (`--fixtures`). Nothing is
+    /// read from or saved to the data dir then.
+    fn is_fixture(&self) -> bool {
+        false
+    }
+
+    /// Login of the authenticated user.
+    fn fetch_viewer(&self) -> BoxFuture<'_, Result<String>>;
+
+    fn fetch_org_repos<'a>(&'a self, org: &'a str) -> Fetch<'a, Vec<Repo>>;
+
+    /// Repo lists for several orgs at once, in `orgs` order.
+    fn fetch_orgs_batched<'a>(&'a self, orgs: &'a [String]) -> Fetch<'a, Vec<Vec<Repo>>>;
+
+    fn fetch_user_repos<'a>(&'a self, user: &'a str) -> Fetch<'a, Vec<Repo>>;
+
+    /// Whether `login` is an organization or a user; `None` when there's
+    /// no such owner.
+    fn fetch_owner_kind<'a>(&'a self, login: &'a str) -> Fetch<'a, Option<OwnerKind>>;
+
+    fn fetch_inbox<'a>(
+        &'a self,
+        viewer_login: &'a str,
+        teams: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>>;
+
+    /// Open PRs across the owners, plus the standalone `repos`
+    /// (`owner/name`).
+    fn fetch_all_open_prs<'a>(
+        &'a self,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>>;
+
+    /// Open PRs across the owners and standalone `repos` that `login` is
+    /// involved in: authored, review requested, assigned or commented on.
+    fn fetch_involved_prs<'a>(
+        &'a self,
+        login: &'a str,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>>;
+
+    fn fetch_pr_history<'a>(
+        &'a self,
+        kind: PrHistoryKind,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+        window_days: u32,
+    ) -> Fetch<'a, Vec<PullRequest>>;
+
+    fn fetch_repo_prs_in_state<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        state: PrStateFilter,
+        cancel: &'a CancelToken,
+    ) -> Fetch<'a, Vec<PullRequest>>;
+
+    /// Every open PR in a single repository. Used to prefetch repo views so
+    /// they don't depend on the (possibly truncated) cross-org search.
+    fn fetch_repo_prs<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        cancel: &'a CancelToken,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        self.fetch_repo_prs_in_state(owner, name, PrStateFilter::Open, cancel)
+    }
+
+    fn fetch_pr<'a>(&'a self, owner: &'a str, name: &'a str, number: u32)
+    -> Fetch<'a, PullRequest>;
+
+    fn fetch_pr_detail<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PrDetail>;
+
+    fn fetch_pr_diff<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> BoxFuture<'a, Result<String>>;
+}
+
+impl DataSource for GithubClient {
+    fn is_offline(&self) -> bool {
+        GithubClient::is_offline(self)
+    }
+
+    fn fetch_viewer(&self) -> BoxFuture<'_, Result<String>> {
+        GithubClient::fetch_viewer(self).boxed()
+    }
+
+    fn fetch_org_repos<'a>(&'a self, org: &'a str) -> Fetch<'a, Vec<Repo>> {
+        GithubClient::fetch_org_repos(self, org).boxed()
+    }
+
+    fn fetch_orgs_batched<'a>(&'a self, orgs: &'a [String]) -> Fetch<'a, Vec<Vec<Repo>>> {
+        GithubClient::fetch_orgs_batched(self, orgs).boxed()
+    }
+
+    fn fetch_user_repos<'a>(&'a self, user: &'a str) -> Fetch<'a, Vec<Repo>> {
+        GithubClient::fetch_user_repos(self, user).boxed()
+    }
+
+    fn fetch_owner_kind<'a>(&'a self, login: &'a str) -> Fetch<'a, Option<OwnerKind>> {
+        GithubClient::fetch_owner_kind(self, login).boxed()
+    }
+
+    fn fetch_inbox<'a>(
+        &'a self,
+        viewer_login: &'a str,
+        teams: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        GithubClient::fetch_inbox(self, viewer_login, teams).boxed()
+    }
+
+    fn fetch_all_open_prs<'a>(
+        &'a self,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        GithubClient::fetch_all_open_prs(self, orgs, users, repos).boxed()
+    }
+
+    fn fetch_involved_prs<'a>(
+        &'a self,
+        login: &'a str,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        GithubClient::fetch_involved_prs(self, login, orgs, users, repos).boxed()
+    }
+
+    fn fetch_pr_history<'a>(
+        &'a self,
+        kind: PrHistoryKind,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+        window_days: u32,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        GithubClient::fetch_pr_history(self, kind, orgs, users, repos, window_days).boxed()
+    }
+
+    fn fetch_repo_prs_in_state<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        state: PrStateFilter,
+        cancel: &'a CancelToken,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        GithubClient::fetch_repo_prs_in_state(self, owner, name, state, cancel).boxed()
+    }
+
+    fn fetch_pr<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PullRequest> {
+        GithubClient::fetch_pr(self, owner, name, number).boxed()
+    }
+
+    fn fetch_pr_detail<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PrDetail> {
+        GithubClient::fetch_pr_detail(self, owner, name, number).boxed()
+    }
+
+    fn fetch_pr_diff<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> BoxFuture<'a, Result<String>> {
+        GithubClient::fetch_pr_diff(self, owner, name, number).boxed()
+    }
+}
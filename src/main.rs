@@ -1,13 +1,10 @@
-mod app;
-mod cache;
-mod github;
-mod ui;
-mod util;
+use ghdash::{app, cache, github, ui, util};
 
 use anyhow::Result;
-use clap::Parser;
+use app::event_loop::{VIEWER_CACHE_KEY, Viewer};
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Parser, Debug)]
 #[command(name = "ghdash", version, about = "TUI GitHub Dashboard")]
@@ -27,6 +24,49 @@ struct Cli {
     /// Enable debug logging to file
     #[arg(short, long)]
     debug: bool,
+
+    /// Log at this level instead of debug (implies logging)
+    #[arg(long, value_enum, value_name = "LEVEL")]
+    log_level: Option<LogLevel>,
+
+    /// Log to stderr instead of the daily log file (implies logging). Only
+    /// with --dump-fixtures: the dashboard draws over stderr
+    #[arg(long, requires = "dump_fixtures")]
+    log_stderr: bool,
+
+    /// Read the GitHub token from stdin instead of `github.auth_order`
+    #[arg(long)]
+    token_stdin: bool,
+
+    /// Don't touch the network: show cached data only
+    #[arg(long, conflicts_with_all = ["no_cache", "refresh"])]
+    offline: bool,
+
+    /// Serve canned JSON from a fixture directory instead of GitHub
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["offline", "token_stdin"])]
+    fixtures: Option<PathBuf>,
+
+    /// Write live data to a fixture directory for --fixtures, then exit
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["offline", "fixtures"])]
+    dump_fixtures: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+}
+
+impl LogLevel {
+    /// `EnvFilter` directive limiting output to ghdash at this level.
+    fn filter(self) -> &'static str {
+        match self {
+            LogLevel::Trace => "ghdash=trace",
+            LogLevel::Debug => "ghdash=debug",
+            LogLevel::Info => "ghdash=info",
+        }
+    }
 }
 
 #[tokio::main]
@@ -34,66 +74,221 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     let config = util::config::AppConfig::load(cli.config.as_deref())?;
+    ui::theme::check_org_colors(&config.github.org_color)?;
 
-    // Setup logging
-    let _guard = setup_logging(&config, cli.debug)?;
+    // Setup logging, then report what loading the config found
+    let _guard = setup_logging(&config, &cli)?;
+    config.log_warnings();
 
     info!("ghdash starting");
 
-    // Resolve auth token before starting TUI
-    let token = match github::auth::resolve_token() {
-        Ok(t) => t,
-        Err(e) => {
-            eprintln!("Authentication error: {e}");
-            std::process::exit(1);
-        }
-    };
-
-    let client = github::GithubClient::new(&token, &config.github.api_url)?;
-
-    // Verify auth by fetching viewer
-    let viewer = match client.fetch_viewer().await {
-        Ok(v) => v,
-        Err(e) => {
-            eprintln!("Failed to authenticate with GitHub: {e}");
-            eprintln!("Please check your token and try again.");
-            std::process::exit(1);
-        }
-    };
-
-    info!(login = %viewer, "Authenticated as {}", viewer);
+    if let Some(dir) = &cli.fixtures {
+        return run_fixtures(config, cli.config, dir).await;
+    }
 
-    if config.github.orgs.is_empty() && config.github.users.is_empty() {
+    if config.github.owners().is_empty() && config.github.standalone_repos().is_empty() {
         eprintln!(
-            "No organizations or users configured. Please add orgs or users to your config file.\n\
+            "No organizations, users or repos configured. Please add owners or repos to your \
+             config file.\n\
              Example config (~/.config/ghdash/config.toml):\n\n\
              [github]\n\
-             orgs = [\"my-org\"]\n\
-             users = [\"my-username\"]"
+             owners = [\"my-org\", \"my-username\"]\n\
+             repos = [\"big-org/payment-service\"]"
         );
         std::process::exit(1);
     }
 
+    if config.github.danger_accept_invalid_certs {
+        eprintln!(
+            "WARNING: github.danger_accept_invalid_certs is set; TLS certificates are not \
+             verified and your token can be intercepted."
+        );
+    }
+
     // Build cache store
     let cache_store = if cli.no_cache {
         None
     } else {
-        let store = cache::CacheStore::new(config.cache_dir(), config.cache.ttl_secs);
-        if cli.refresh {
-            store.invalidate_all()?;
+        Some(
+            cache::CacheStore::new(config.cache_dir(), config.cache.ttl_secs)
+                .with_negative_ttl(config.cache.negative_ttl_secs),
+        )
+    };
+    let cached_login = |key: &str| {
+        cache_store
+            .as_ref()
+            .and_then(|c| c.get_ignoring_ttl::<String>(key))
+            .map(|(login, _)| login)
+    };
+    let cached_viewer = || cached_login(VIEWER_CACHE_KEY);
+
+    let (client, viewer) = if cli.offline {
+        let Some(viewer) = cached_viewer() else {
+            eprintln!("Offline mode needs data cached by a previous online run.");
+            std::process::exit(1);
+        };
+        info!(login = %viewer, "Starting offline");
+        (
+            github::GithubClient::offline(&config.github),
+            Viewer::verified(viewer),
+        )
+    } else {
+        // Resolve auth token before starting TUI
+        let resolved = if cli.token_stdin {
+            github::auth::read_token(std::io::stdin().lock()).map(|token| {
+                github::auth::ResolvedToken {
+                    token,
+                    method: github::auth::AuthMethod::Stdin,
+                    refresher: None,
+                }
+            })
+        } else {
+            github::auth::resolve_token(&config.github).await
+        };
+        let resolved = match resolved {
+            Ok(resolved) => {
+                info!(method = resolved.method.label(), "Resolved GitHub token");
+                resolved
+            }
+            Err(e) => {
+                eprintln!("Authentication error: {e:#}");
+                std::process::exit(1);
+            }
+        };
+
+        let client = github::GithubClient::new(&resolved.token, &config.github)?
+            .with_refresher(resolved.refresher);
+        let viewer_key = github::auth::viewer_cache_key(&config.github.api_url, &resolved.token);
+
+        // With this token's login cached, start right away and verify it in
+        // the background, which switches to offline mode if GitHub turns out
+        // to be unreachable. Otherwise verify auth by fetching the viewer
+        // first; if GitHub is unreachable and we have cached data, fall back
+        // to offline mode instead of exiting.
+        match cached_login(&viewer_key).filter(|_| cli.dump_fixtures.is_none()) {
+            Some(login) => {
+                info!(login = %login, "Using cached viewer login");
+                (
+                    client,
+                    Viewer {
+                        login,
+                        unverified_key: Some(viewer_key),
+                    },
+                )
+            }
+            None => match client.fetch_viewer().await {
+                Ok(v) => {
+                    if let Some(ref store) = cache_store {
+                        for key in [viewer_key.as_str(), VIEWER_CACHE_KEY] {
+                            if let Err(e) = store.set(key, &v) {
+                                warn!(error = %e, "Failed to cache viewer login");
+                            }
+                        }
+                    }
+                    (client, Viewer::verified(v))
+                }
+                Err(e) if github::graphql::is_network_error(&e) && cached_viewer().is_some() => {
+                    warn!(error = %e, "GitHub unreachable, falling back to offline mode");
+                    (
+                        github::GithubClient::offline(&config.github),
+                        Viewer::verified(cached_viewer().unwrap_or_default()),
+                    )
+                }
+                Err(e) => {
+                    let host = reqwest::Url::parse(&config.github.api_url)
+                        .ok()
+                        .and_then(|url| url.host_str().map(str::to_string))
+                        .unwrap_or_else(|| config.github.api_url.clone());
+                    match github::graphql::failure_kind(&e) {
+                        github::graphql::FailureKind::Network => {
+                            eprintln!("Can't reach {host}: {e:#}");
+                            eprintln!(
+                                "Check your network connection, proxy settings \
+                             (HTTPS_PROXY) and github.api_url."
+                            );
+                        }
+                        github::graphql::FailureKind::Unauthorized => {
+                            eprintln!("Token rejected by {host}.");
+                            eprintln!(
+                                "It may be invalid, expired or revoked; check github.auth_order \
+                             or pass a fresh one with --token-stdin."
+                            );
+                        }
+                        github::graphql::FailureKind::Other => {
+                            eprintln!("Failed to authenticate with GitHub: {e:#}");
+                            eprintln!("Please check your token and try again.");
+                        }
+                    }
+                    std::process::exit(1);
+                }
+            },
         }
-        Some(store)
     };
 
+    info!(login = %viewer.login, "Authenticated as {}", viewer.login);
+
+    if let Some(dir) = &cli.dump_fixtures {
+        github::fixtures::dump(&client, &config, &viewer.login, dir).await?;
+        println!("Wrote fixtures to {}", dir.display());
+        return Ok(());
+    }
+
+    // Bypass everything cached before this start rather than deleting it, so
+    // a refresh that fails on a flaky network still leaves the old data.
+    if cli.refresh
+        && let Some(ref store) = cache_store
+    {
+        store.expire_all();
+    }
+
     // Run the TUI event loop
-    app::event_loop::run(config, client, viewer, cache_store).await
+    app::event_loop::run(config, cli.config, client, None, viewer, cache_store).await
 }
 
+/// `--fixtures`: run the dashboard on a fixture set, with no token, network
+/// or disk cache. Without configured owners, every owner in the fixtures is
+/// shown.
+async fn run_fixtures(
+    mut config: util::config::AppConfig,
+    config_path: Option<PathBuf>,
+    dir: &std::path::Path,
+) -> Result<()> {
+    let fixtures = github::fixtures::FixtureSource::load(dir)?;
+    if config.github.owners().is_empty() && config.github.standalone_repos().is_empty() {
+        config.github.orgs = fixtures.owners();
+    }
+    let viewer = github::DataSource::fetch_viewer(&fixtures).await?;
+    info!(dir = %dir.display(), login = %viewer, "Starting on fixtures");
+    let client = github::GithubClient::offline(&config.github);
+    app::event_loop::run(
+        config,
+        config_path,
+        client,
+        Some(fixtures),
+        Viewer::verified(viewer),
+        None,
+    )
+    .await
+}
+
+/// Logging is off unless `--debug`, `--log-level` or `--log-stderr` is
+/// given. It goes to a daily file in the log directory, or to stderr with
+/// `--log-stderr`.
 fn setup_logging(
     config: &util::config::AppConfig,
-    debug: bool,
+    cli: &Cli,
 ) -> Result<Option<tracing_appender::non_blocking::WorkerGuard>> {
-    if !debug {
+    if !cli.debug && cli.log_level.is_none() && !cli.log_stderr {
+        return Ok(None);
+    }
+    let filter = cli.log_level.unwrap_or(LogLevel::Debug).filter();
+
+    if cli.log_stderr {
+        tracing_subscriber::fmt()
+            .with_writer(util::secret::RedactingMakeWriter::new(std::io::stderr))
+            .with_env_filter(filter)
+            .with_ansi(false)
+            .init();
         return Ok(None);
     }
 
@@ -104,8 +299,8 @@ fn setup_logging(
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
     tracing_subscriber::fmt()
-        .with_writer(non_blocking)
-        .with_env_filter("ghdash=debug")
+        .with_writer(util::secret::RedactingMakeWriter::new(non_blocking))
+        .with_env_filter(filter)
         .with_ansi(false)
         .init();
 
@@ -0,0 +1,101 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::ui::glyphs::Glyphs;
+use crate::ui::theme;
+
+/// A small modal list for picking one of a few options: j/k move, Enter
+/// picks, Esc cancels. `T` is what each option stands for, so any feature can
+/// reuse it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chooser<T> {
+    pub title: String,
+    items: Vec<(String, T)>,
+    cursor: usize,
+}
+
+impl<T> Chooser<T> {
+    pub fn new(title: impl Into<String>, items: Vec<(String, T)>) -> Self {
+        Self {
+            title: title.into(),
+            items,
+            cursor: 0,
+        }
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(|(label, _)| label.as_str())
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.items.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Consume the chooser, returning the highlighted option (`None` only when
+    /// there are no options).
+    pub fn into_selected(mut self) -> Option<T> {
+        if self.cursor < self.items.len() {
+            Some(self.items.swap_remove(self.cursor).1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Draw `chooser` centered over whatever is on screen.
+pub fn render_chooser<T>(f: &mut Frame, chooser: &Chooser<T>, glyphs: &Glyphs) {
+    const HINT: &str = " Enter: pick  Esc: cancel ";
+    let area = f.area();
+    let widest = chooser
+        .labels()
+        .map(UnicodeWidthStr::width)
+        .chain([chooser.title.width() + 2, HINT.width()])
+        .max()
+        .unwrap_or(0) as u16;
+    let width = (widest + 6).min(area.width.saturating_sub(4));
+    let height = (chooser.items.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(format!(" {} ", chooser.title))
+        .title_bottom(Line::from(Span::styled(HINT, theme::DIM)))
+        .borders(Borders::ALL)
+        .border_set(glyphs.border)
+        .border_style(theme::BORDER_FOCUSED);
+
+    let items: Vec<ListItem> = chooser
+        .labels()
+        .enumerate()
+        .map(|(i, label)| {
+            let style = if i == chooser.cursor() {
+                theme::HIGHLIGHT
+            } else {
+                ratatui::style::Style::default()
+            };
+            ListItem::new(Line::from(Span::styled(format!("  {}  ", label), style)))
+        })
+        .collect();
+
+    f.render_widget(Clear, modal_area);
+    f.render_widget(List::new(items).block(block), modal_area);
+}
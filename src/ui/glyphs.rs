@@ -0,0 +1,123 @@
+use ratatui::symbols::border;
+
+/// How long each spinner frame shows.
+pub const SPINNER_FRAME_MS: u64 = 100;
+
+/// Every symbol the dashboard draws outside of plain text, so a terminal that
+/// can't render Unicode gets a readable ASCII stand-in instead of tofu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Nav: an expanded / collapsed org.
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    /// Trailing "more to come" marker, as in "… and 3 more", and the end of
+    /// truncated text.
+    pub ellipsis: &'static str,
+    /// Between the bounds of an age bucket, as in "1–3d".
+    pub range: &'static str,
+    /// Sign of the API points a refresh used.
+    pub minus: &'static str,
+    /// Comments column header.
+    pub comments: &'static str,
+    /// Inbox title prefixes for an unread PR and one I've already reviewed.
+    pub unread: &'static str,
+    pub reviewed: &'static str,
+    /// Merge-state column.
+    pub merged: &'static str,
+    pub closed: &'static str,
+    pub mergeable: &'static str,
+    pub behind: &'static str,
+    pub conflicting: &'static str,
+    /// CI column.
+    pub ci_passing: &'static str,
+    pub ci_failing: &'static str,
+    pub ci_pending: &'static str,
+    pub ci_none: &'static str,
+    /// Horizontal scroll hints in the table title.
+    pub scroll_left: &'static str,
+    pub scroll_right: &'static str,
+    /// Color swatch in the All PRs legend.
+    pub swatch: &'static str,
+    /// Separator between the age histogram buckets.
+    pub separator: &'static str,
+    /// Stale-data marker after "(fetched 43m ago)".
+    pub warning: &'static str,
+    /// Status bar prefixes for the refresh lock and paused auto-refresh.
+    pub locked: &'static str,
+    pub paused: &'static str,
+    /// Repo sparkline bars, lowest to highest.
+    pub bars: [char; 8],
+    /// Loading spinner, one frame per [`SPINNER_FRAME_MS`].
+    pub spinner: &'static [&'static str],
+    /// Pane and popup borders.
+    pub border: border::Set<'static>,
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    expanded: "▼",
+    collapsed: "▶",
+    ellipsis: "…",
+    range: "–",
+    minus: "−",
+    comments: "💬",
+    unread: "●",
+    reviewed: "↺",
+    merged: "⇒ mg",
+    closed: "⊘ cl",
+    mergeable: "✓ ok",
+    behind: "✓ ok↓",
+    conflicting: "✗ cf",
+    ci_passing: "✓",
+    ci_failing: "✗",
+    ci_pending: "…",
+    ci_none: "·",
+    scroll_left: "◀",
+    scroll_right: "▶",
+    swatch: "■",
+    separator: "│",
+    warning: "⚠",
+    locked: "🔒",
+    paused: "⏸",
+    bars: ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+    spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+    border: border::PLAIN,
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    expanded: "v",
+    collapsed: ">",
+    ellipsis: "...",
+    range: "-",
+    minus: "-",
+    comments: "#c",
+    unread: "*",
+    reviewed: "~",
+    merged: "=> mg",
+    closed: "/ cl",
+    mergeable: "+ ok",
+    behind: "+ ok-",
+    conflicting: "x cf",
+    ci_passing: "+",
+    ci_failing: "x",
+    ci_pending: "~",
+    ci_none: ".",
+    scroll_left: "<",
+    scroll_right: ">",
+    swatch: "#",
+    separator: "|",
+    warning: "!",
+    locked: "[L]",
+    paused: "[P]",
+    bars: ['_', '.', ':', '-', '=', '+', '*', '#'],
+    spinner: &["|", "/", "-", "\\"],
+    border: border::Set {
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        vertical_left: "|",
+        vertical_right: "|",
+        horizontal_top: "-",
+        horizontal_bottom: "-",
+    },
+};
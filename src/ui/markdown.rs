@@ -0,0 +1,151 @@
+use ratatui::{
+    style::Modifier,
+    text::{Line, Span, Text},
+};
+
+use crate::ui::theme;
+
+/// Render a PR description for the terminal: headings, bullets, quotes,
+/// fenced code and inline `code`, **bold** and links (as `text (url)`). Not
+/// CommonMark, just enough to read a typical PR body. HTML comments (PR
+/// template hints) are dropped and runs of blank lines collapse to one.
+pub fn to_text(markdown: &str) -> Text<'static> {
+    let markdown = strip_html_comments(markdown);
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut in_code = false;
+
+    for raw in markdown.lines() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(Line::from(Span::styled(format!("  {}", raw), theme::CODE)));
+            continue;
+        }
+        if trimmed.is_empty() {
+            if lines.last().is_some_and(|l| l.width() > 0) {
+                lines.push(Line::from(""));
+            }
+            continue;
+        }
+
+        let indent = &raw[..raw.len() - trimmed.len()];
+        if let Some(heading) = heading_text(trimmed) {
+            lines.push(Line::from(Span::styled(heading.to_string(), theme::HEADER)));
+        } else if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            let mut spans = vec![Span::raw(format!("{}• ", indent))];
+            spans.extend(inline_spans(item));
+            lines.push(Line::from(spans));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let mut spans = vec![Span::styled("│ ", theme::DIM)];
+            spans.extend(inline_spans(quote.trim_start()));
+            lines.push(Line::from(spans));
+        } else {
+            let mut spans = Vec::new();
+            if !indent.is_empty() {
+                spans.push(Span::raw(indent.to_string()));
+            }
+            spans.extend(inline_spans(trimmed));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    while lines.last().is_some_and(|l| l.width() == 0) {
+        lines.pop();
+    }
+    Text::from(lines)
+}
+
+/// `## Title` -> `Title`; `None` if the line isn't an ATX heading.
+fn heading_text(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some(rest.trim().trim_end_matches('#').trim_end())
+}
+
+/// Split one line into styled spans. Unclosed markers are kept as text.
+fn inline_spans(text: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => rest[1..].find('`').map(|end| {
+                let span = Span::styled(rest[1..1 + end].to_string(), theme::CODE);
+                (vec![span], 2 + end)
+            }),
+            '*' if rest.starts_with("**") => rest[2..].find("**").map(|end| {
+                let span = Span::styled(
+                    rest[2..2 + end].to_string(),
+                    ratatui::style::Style::new().add_modifier(Modifier::BOLD),
+                );
+                (vec![span], 4 + end)
+            }),
+            '[' => link(rest),
+            _ => None,
+        };
+        match styled {
+            Some((new_spans, consumed)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::raw(std::mem::take(&mut plain)));
+                }
+                spans.extend(new_spans);
+                rest = &rest[consumed..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+/// `[text](url)` at the start of `s`, as spans plus the bytes consumed.
+fn link(s: &str) -> Option<(Vec<Span<'static>>, usize)> {
+    let close = s.find("](")?;
+    let end = close + 2 + s[close + 2..].find(')')?;
+    let text = &s[1..close];
+    let url = &s[close + 2..end];
+    if text.contains(['[', ']']) {
+        return None;
+    }
+    let spans = if text.is_empty() || text == url {
+        vec![Span::styled(url.to_string(), theme::PR_NUMBER)]
+    } else {
+        vec![
+            Span::raw(text.to_string()),
+            Span::styled(format!(" ({})", url), theme::DIM),
+        ]
+    };
+    Some((spans, end + 1))
+}
+
+fn strip_html_comments(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -1,2 +1,6 @@
+pub mod chooser;
+pub mod glyphs;
+pub mod markdown;
+pub mod sparkline;
 pub mod theme;
 pub mod widgets;
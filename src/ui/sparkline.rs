@@ -0,0 +1,14 @@
+/// One bar per value, drawn with `bars` (lowest to highest) and scaled
+/// between the smallest and largest value so small swings in a big backlog
+/// still show. A flat series is all low bars; an empty one is an empty
+/// string.
+pub fn sparkline(values: &[u32], bars: &[char; 8]) -> String {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min).max(1) as u64;
+    values
+        .iter()
+        .map(|&v| bars[((v - min) as u64 * (bars.len() as u64 - 1) / range) as usize])
+        .collect()
+}
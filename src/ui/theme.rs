@@ -1,5 +1,11 @@
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::ui::glyphs::{ASCII, Glyphs, UNICODE};
+use crate::util::config::OrgColor;
+
 pub const HIGHLIGHT: Style = Style::new()
     .fg(Color::Black)
     .bg(Color::Cyan)
@@ -13,6 +19,18 @@ pub const ERROR: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD
 
 pub const DRAFT: Style = Style::new().fg(Color::DarkGray);
 
+pub const MERGED: Style = Style::new().fg(Color::Magenta);
+
+/// Closed without merging.
+pub const CLOSED: Style = Style::new()
+    .fg(Color::DarkGray)
+    .add_modifier(Modifier::CROSSED_OUT);
+
+// All PRs row tints: PRs I wrote, and PRs waiting on my review.
+pub const MINE: Style = Style::new().fg(Color::Green);
+
+pub const REVIEW_REQUESTED: Style = Style::new().fg(Color::Yellow);
+
 #[allow(dead_code)]
 pub const SUCCESS: Style = Style::new().fg(Color::Green);
 
@@ -33,6 +51,9 @@ pub const NAV_VIRTUAL: Style = Style::new().fg(Color::Magenta).add_modifier(Modi
 
 pub const PR_NUMBER: Style = Style::new().fg(Color::Cyan);
 
+/// Inline and fenced code in a rendered PR description.
+pub const CODE: Style = Style::new().fg(Color::LightYellow);
+
 pub const PR_AUTHOR: Style = Style::new().fg(Color::Yellow);
 
 // Merge-state column. Color is paired with a distinct glyph in the widget so the
@@ -40,3 +61,208 @@ pub const PR_AUTHOR: Style = Style::new().fg(Color::Yellow);
 pub const MERGE_CLEAN: Style = Style::new().fg(Color::Green);
 
 pub const MERGE_CONFLICT: Style = Style::new().fg(Color::Red).add_modifier(Modifier::BOLD);
+
+/// Parse a color name (`red`, `light-blue`), `#rrggbb` or palette index.
+pub fn parse_color(s: &str) -> Option<Color> {
+    Color::from_str(s.trim()).ok()
+}
+
+/// Fail on the first `github.org_color` entry whose color doesn't parse.
+pub fn check_org_colors(entries: &[OrgColor]) -> Result<()> {
+    for entry in entries {
+        if parse_color(&entry.color).is_none() {
+            bail!(
+                "Unknown color {:?} for org {:?} in github.org_color",
+                entry.color,
+                entry.org
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Nav style for an org heading, tinted with its `org_color` if it has one.
+pub fn nav_org(color: Option<Color>) -> Style {
+    match color {
+        Some(c) => NAV_ORG.fg(c),
+        None => NAV_ORG,
+    }
+}
+
+/// Nav style for a repo row, tinted with its org's `org_color` if it has one.
+pub fn nav_repo(color: Option<Color>) -> Style {
+    match color {
+        Some(c) => NAV_REPO.fg(c),
+        None => NAV_REPO,
+    }
+}
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+/// What the terminal can draw, sniffed from the environment at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalCaps {
+    pub unicode: bool,
+    pub colors: ColorDepth,
+}
+
+impl TerminalCaps {
+    pub const FULL: TerminalCaps = TerminalCaps {
+        unicode: true,
+        colors: ColorDepth::TrueColor,
+    };
+
+    /// Read `COLORTERM`, `TERM` and the locale (`LC_ALL`, `LC_CTYPE`, `LANG`)
+    /// through `lookup`. Without any of them we assume a modern terminal;
+    /// `dumb`, the Linux console and `vt*` get ASCII and 16 colors.
+    pub fn detect(lookup: &dyn Fn(&str) -> Option<String>) -> TerminalCaps {
+        let term = lookup("TERM").unwrap_or_default().to_ascii_lowercase();
+        let colorterm = lookup("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        let basic = term == "dumb" || term == "linux" || term.starts_with("vt");
+
+        let colors = if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if basic {
+            ColorDepth::Ansi16
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if term.is_empty() {
+            ColorDepth::TrueColor
+        } else {
+            ColorDepth::Ansi16
+        };
+
+        // The first locale variable that is set wins, as in setlocale(3).
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|v| lookup(v))
+            .find(|v| !v.is_empty());
+        let utf8_locale = locale.is_none_or(|l| {
+            let l = l.to_ascii_lowercase();
+            l.contains("utf-8") || l.contains("utf8")
+        });
+
+        TerminalCaps {
+            unicode: utf8_locale && !basic,
+            colors,
+        }
+    }
+}
+
+/// Glyphs plus the color depth to draw with. `[ui] ascii` overrides what was
+/// detected either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub glyphs: &'static Glyphs,
+    pub colors: ColorDepth,
+}
+
+impl Theme {
+    pub fn new(caps: TerminalCaps, ascii: Option<bool>) -> Theme {
+        let glyphs = if ascii.unwrap_or(!caps.unicode) {
+            &ASCII
+        } else {
+            &UNICODE
+        };
+        Theme {
+            glyphs,
+            colors: caps.colors,
+        }
+    }
+
+    /// `color` as the terminal can show it: RGB and palette colors are mapped
+    /// to the nearest one the color depth allows.
+    pub fn color(&self, color: Color) -> Color {
+        match (self.colors, color) {
+            (ColorDepth::TrueColor, c) => c,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(i)) => {
+                let (r, g, b) = indexed_rgb(i);
+                nearest_16(r, g, b)
+            }
+            (_, c) => c,
+        }
+    }
+}
+
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(c, _)| *c)
+        .unwrap_or(Color::White)
+}
+
+/// The xterm palette entry closest to `(r, g, b)`, from the 6×6×6 cube or the
+/// grayscale ramp.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let step = (avg.saturating_sub(8) / 10).min(23) as u8;
+    let gray = 8 + step * 10;
+
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube_rgb, (r, g, b)) {
+        232 + step
+    } else {
+        cube as u8
+    }
+}
+
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    match i {
+        0..=15 => ANSI_16[i as usize].1,
+        16..=231 => {
+            let i = (i - 16) as usize;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
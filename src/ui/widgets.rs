@@ -1,16 +1,25 @@
+use std::borrow::Cow;
+
 use ratatui::{
     Frame,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Flex, Layout, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState, Wrap,
+    },
 };
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::app::state::{
-    AppState, ContentView, DiffEntry, FocusedPane, NavNode, Overlay, PrDetailEntry,
+    AppState, ContentView, DiffEntry, FocusedPane, MergeFilter, NavNode, Overlay, PrDetailEntry,
+    PrRelation, RateLimitTracker, ReviewFilter, org_activity, scroll_offset,
 };
-use crate::github::models::{CiStatus, PrDetail, PullRequest};
-use crate::ui::theme;
-use crate::util::time::relative_time;
+use crate::github::models::{CiStatus, PrDetail, PrHistoryKind, PrState, PullRequest};
+use crate::ui::glyphs::{Glyphs, SPINNER_FRAME_MS};
+use crate::ui::sparkline::sparkline;
+use crate::ui::{markdown, theme};
+use crate::util::config::PrColumn;
+use crate::util::time::{self, relative_time};
 
 pub fn render_nav_pane(f: &mut Frame, area: Rect, state: &AppState) {
     let border_style = if state.focused_pane == FocusedPane::Navigation {
@@ -19,11 +28,25 @@ pub fn render_nav_pane(f: &mut Frame, area: Rect, state: &AppState) {
         theme::BORDER_UNFOCUSED
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .title(" Navigation ")
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(border_style);
+    // Auto-refresh is paused until the window resets; say so, or the app
+    // looks stuck.
+    if let Some(reset_at) = state.rate_limit.throttled_until(time::now()) {
+        block = block.title_bottom(Span::styled(
+            format!(
+                " Rate limit: {} remaining, resets in {} ",
+                state.rate_limit.current().remaining,
+                time::countdown(&reset_at)
+            ),
+            theme::WARNING,
+        ));
+    }
 
+    let glyphs = state.theme().glyphs;
     let items: Vec<ListItem> = state
         .nav_nodes
         .iter()
@@ -31,28 +54,60 @@ pub fn render_nav_pane(f: &mut Frame, area: Rect, state: &AppState) {
         .map(|(i, node)| {
             let (text, style) = match node {
                 NavNode::MyInbox => {
-                    let count = state.inbox.len();
-                    let label = if count > 0 {
-                        format!("  Inbox ({})", count)
-                    } else {
-                        "  Inbox".to_string()
+                    let mut label = match open_nav_count(state, &state.without_muted(&state.inbox))
+                    {
+                        Some(count) => format!("  Inbox ({})", count),
+                        None => "  Inbox".to_string(),
                     };
+                    if state.inbox_loading && state.reduce_motion {
+                        label.push_str(" (loading)");
+                    } else if state.inbox_loading {
+                        label.push(' ');
+                        label.push_str(spinner_frame(glyphs, time::now()));
+                    }
                     (label, theme::NAV_VIRTUAL)
                 }
                 NavNode::AllPrs => {
-                    let count = state.all_open_prs.len();
-                    let label = if count > 0 {
-                        format!("  All PRs ({})", count)
-                    } else {
-                        "  All PRs".to_string()
+                    let label =
+                        match open_nav_count(state, &state.without_muted(&state.all_open_prs)) {
+                            Some(count) => format!("  All PRs ({})", count),
+                            None => "  All PRs".to_string(),
+                        };
+                    (label, theme::NAV_VIRTUAL)
+                }
+                NavNode::RecentlyMerged => {
+                    let label = match nav_count(state, &state.recently_merged) {
+                        Some(count) => format!("  Recently merged ({})", count),
+                        None => "  Recently merged".to_string(),
+                    };
+                    (label, theme::NAV_VIRTUAL)
+                }
+                NavNode::RecentlyClosed => {
+                    let label = match nav_count(state, &state.recently_closed) {
+                        Some(count) => format!("  Recently closed ({})", count),
+                        None => "  Recently closed".to_string(),
+                    };
+                    (label, theme::NAV_VIRTUAL)
+                }
+                NavNode::FailingChecks => {
+                    let label = match open_nav_count(state, &state.failing_check_prs()) {
+                        Some(count) => format!("  Failing checks ({})", count),
+                        None => "  Failing checks".to_string(),
+                    };
+                    (label, theme::NAV_VIRTUAL)
+                }
+                NavNode::Watched => {
+                    let label = match open_nav_count(state, &state.watched_pr_list()) {
+                        Some(count) => format!("  Watched ({})", count),
+                        None => "  Watched".to_string(),
                     };
                     (label, theme::NAV_VIRTUAL)
                 }
                 NavNode::Org(name) => {
                     let icon = if state.nav_expanded.contains(name) {
-                        "▼"
+                        glyphs.expanded
                     } else {
-                        "▶"
+                        glyphs.collapsed
                     };
                     let repo_count = state
                         .orgs
@@ -60,22 +115,60 @@ pub fn render_nav_pane(f: &mut Frame, area: Rect, state: &AppState) {
                         .map(|o| o.repos.iter().filter(|r| !r.is_archived).count())
                         .unwrap_or(0);
                     let loading = state.loading_orgs.contains(name);
-                    let suffix = if loading {
+                    let suffix = if state.unknown_owners.contains(name) {
+                        format!(" {} not found", glyphs.warning)
+                    } else if loading && state.reduce_motion {
+                        " (loading)".to_string()
+                    } else if loading {
                         " ...".to_string()
                     } else if repo_count > 0 {
                         format!(" ({})", repo_count)
                     } else {
                         String::new()
                     };
-                    (format!("{} {}{}", icon, name, suffix), theme::NAV_ORG)
+                    let style = if state.unknown_owners.contains(name) {
+                        theme::ERROR
+                    } else {
+                        theme::nav_org(org_color(state, name))
+                    };
+                    (format!("{} {}{}", icon, name, suffix), style)
                 }
-                NavNode::Repo { name, open_prs, .. } => {
+                NavNode::Topic(topic) => (format!("    # {}", topic), theme::DIM),
+                NavNode::ReposSection => ("  Repos".to_string(), theme::NAV_VIRTUAL),
+                NavNode::MoreRepos(_, hidden) => (
+                    format!("    {} and {} more", glyphs.ellipsis, hidden),
+                    theme::DIM,
+                ),
+                NavNode::Repo {
+                    owner,
+                    name,
+                    open_prs,
+                } => {
                     let pr_info = if *open_prs > 0 {
                         format!(" [{}]", open_prs)
                     } else {
                         String::new()
                     };
-                    (format!("    {}{}", name, pr_info), theme::NAV_REPO)
+                    let full_name = format!("{}/{}", owner, name);
+                    let standalone = state.standalone_repos.contains(&full_name);
+                    let indent = if state.group_by_topic && !standalone {
+                        "      "
+                    } else {
+                        "    "
+                    };
+                    // Repos outside any listed org need their owner to be told apart.
+                    let label = if standalone { &full_name } else { name };
+                    let color = org_color(state, owner);
+                    (
+                        format!(
+                            "{}{}{}{}",
+                            indent,
+                            label,
+                            pr_info,
+                            repo_sparkline(state, &format!("{}/{}", owner, name))
+                        ),
+                        theme::nav_repo(color),
+                    )
                 }
             };
 
@@ -93,6 +186,27 @@ pub fn render_nav_pane(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(list, area);
 }
 
+/// The spinner frame to show at `now`.
+pub fn spinner_frame(glyphs: &Glyphs, now: chrono::DateTime<chrono::Utc>) -> &'static str {
+    let tick = now.timestamp_millis().unsigned_abs() / SPINNER_FRAME_MS;
+    glyphs.spinner[tick as usize % glyphs.spinner.len()]
+}
+
+/// An org's `org_color`, brought down to what the terminal can show.
+fn org_color(state: &AppState, org: &str) -> Option<ratatui::style::Color> {
+    state.org_color(org).map(|c| state.theme().color(c))
+}
+
+/// " ▂▃▅▇" for a repo's open PR counts over time, once there are at least two
+/// samples to compare; empty before that.
+fn repo_sparkline(state: &AppState, full_name: &str) -> String {
+    let counts = state.pr_history.counts(full_name);
+    if counts.len() < 2 {
+        return String::new();
+    }
+    format!(" {}", sparkline(&counts, &state.theme().glyphs.bars))
+}
+
 pub fn render_content_pane(f: &mut Frame, area: Rect, state: &AppState) {
     let border_style = if state.focused_pane == FocusedPane::Content {
         theme::BORDER_FOCUSED
@@ -107,6 +221,18 @@ pub fn render_content_pane(f: &mut Frame, area: Rect, state: &AppState) {
         ContentView::AllOpenPrs => {
             render_pr_table(f, area, state, "All Open PRs", border_style);
         }
+        ContentView::RecentlyMerged => {
+            render_pr_table(f, area, state, "Recently Merged", border_style);
+        }
+        ContentView::RecentlyClosed => {
+            render_pr_table(f, area, state, "Recently Closed", border_style);
+        }
+        ContentView::FailingChecks => {
+            render_pr_table(f, area, state, "Failing Checks", border_style);
+        }
+        ContentView::Watched => {
+            render_pr_table(f, area, state, "Watched", border_style);
+        }
         ContentView::RepoPrList { owner, name } => {
             let title = format!("{}/{}", owner, name);
             render_pr_table(f, area, state, &title, border_style);
@@ -117,28 +243,334 @@ pub fn render_content_pane(f: &mut Frame, area: Rect, state: &AppState) {
     }
 }
 
+fn column_header(col: PrColumn, history: Option<PrHistoryKind>, glyphs: &Glyphs) -> &'static str {
+    match col {
+        PrColumn::Number => "#",
+        PrColumn::State => "State",
+        PrColumn::Checks => "CI",
+        PrColumn::Title => "Title",
+        PrColumn::Author => "Author",
+        PrColumn::Repo => "Repo",
+        PrColumn::Updated => match history {
+            Some(PrHistoryKind::Merged) => "Merged",
+            Some(PrHistoryKind::Closed) => "Closed",
+            None => "Updated",
+        },
+        PrColumn::Diffstat => "Diff",
+        PrColumn::Size => "Size",
+        PrColumn::Comments => glyphs.comments,
+        PrColumn::Branch => "Branch",
+        PrColumn::Base => "Base",
+        PrColumn::Assignees => "Assignees",
+    }
+}
+
+fn column_width(col: PrColumn) -> Constraint {
+    match col {
+        PrColumn::Number => Constraint::Length(7),
+        PrColumn::State => Constraint::Length(5),
+        PrColumn::Checks => Constraint::Length(3),
+        PrColumn::Title => Constraint::Min(20),
+        PrColumn::Author => Constraint::Length(16),
+        PrColumn::Repo => Constraint::Length(24),
+        PrColumn::Updated => Constraint::Length(10),
+        PrColumn::Diffstat => Constraint::Length(12),
+        PrColumn::Size => Constraint::Length(4),
+        PrColumn::Comments => Constraint::Length(5),
+        PrColumn::Branch => Constraint::Length(20),
+        PrColumn::Base => Constraint::Length(14),
+        PrColumn::Assignees => Constraint::Length(16),
+    }
+}
+
+/// Narrowest a column may get before it is dropped from the table.
+fn column_min_width(col: PrColumn) -> u16 {
+    match column_width(col) {
+        Constraint::Length(n) | Constraint::Min(n) => n,
+        _ => 0,
+    }
+}
+
+/// The PR table columns that fit, and whether any were scrolled out of view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnWindow {
+    pub columns: Vec<PrColumn>,
+    pub hidden_left: bool,
+    pub hidden_right: bool,
+}
+
+/// Pick which of `columns` render in `width` cells when scrolled `offset`
+/// columns to the right. Title is always kept. The other columns are skipped
+/// from the left by `offset`; of the rest, the highest in `priority` are taken
+/// first, so low-priority ones drop out as the table narrows and come back as
+/// it widens. Columns missing from `priority` rank last. Configured order is
+/// preserved.
+pub fn column_window(
+    columns: &[PrColumn],
+    priority: &[PrColumn],
+    width: u16,
+    offset: usize,
+) -> ColumnWindow {
+    let others: Vec<PrColumn> = columns
+        .iter()
+        .copied()
+        .filter(|c| *c != PrColumn::Title)
+        .collect();
+    let offset = offset.min(others.len().saturating_sub(1));
+
+    let mut candidates = others[offset..].to_vec();
+    // Stable, so unranked columns keep their configured order.
+    candidates.sort_by_key(|c| priority.iter().position(|p| p == c).unwrap_or(usize::MAX));
+
+    let mut used = if columns.contains(&PrColumn::Title) {
+        column_min_width(PrColumn::Title)
+    } else {
+        0
+    };
+    let mut shown = Vec::new();
+    for col in candidates {
+        // One cell of spacing between columns.
+        let cost = column_min_width(col) + u16::from(used > 0);
+        if used + cost <= width {
+            used += cost;
+            shown.push(col);
+        }
+    }
+
+    // Which side a hidden column is on goes by display order, whether it was
+    // scrolled past or dropped for width. One dropped from between shown
+    // columns comes back by scrolling right, so it counts as on the right.
+    let (hidden_left, hidden_right) = match others.iter().position(|c| shown.contains(c)) {
+        Some(first) => (first > 0, others.len() - first > shown.len()),
+        None => (offset > 0, offset < others.len()),
+    };
+
+    ColumnWindow {
+        hidden_left,
+        hidden_right,
+        columns: columns
+            .iter()
+            .copied()
+            .filter(|c| *c == PrColumn::Title || shown.contains(c))
+            .collect(),
+    }
+}
+
+/// One table cell. `style` is the row style; the highlight overrides each
+/// column's own color.
+fn pr_cell<'a>(
+    col: PrColumn,
+    pr: &'a PullRequest,
+    state: &AppState,
+    history: Option<PrHistoryKind>,
+    style: ratatui::style::Style,
+    width: usize,
+) -> Cell<'a> {
+    let ellipsis = state.theme().glyphs.ellipsis;
+    let colored = |own: ratatui::style::Style| {
+        if style == theme::HIGHLIGHT {
+            style
+        } else {
+            own
+        }
+    };
+    match col {
+        PrColumn::Number => Cell::from(format!("#{}", pr.number)).style(colored(theme::PR_NUMBER)),
+        PrColumn::State => {
+            let (label, own) = merge_state_display(pr, state.theme().glyphs);
+            Cell::from(label).style(colored(own))
+        }
+        PrColumn::Checks => {
+            let (label, own) = ci_display(pr, state.theme().glyphs);
+            Cell::from(label).style(colored(own))
+        }
+        PrColumn::Title => {
+            let review_icon = match pr.review_decision.as_deref() {
+                Some("APPROVED") => " +",
+                Some("CHANGES_REQUESTED") => " !",
+                _ => "",
+            };
+            let glyphs = state.theme().glyphs;
+            let unread = if state.is_unread(pr) {
+                format!("{} ", glyphs.unread)
+            } else {
+                String::new()
+            };
+            // Still requested, but already reviewed: awaiting the author.
+            let reviewed = match (
+                state.content_view == ContentView::Inbox,
+                pr.reviewed_by_me(),
+            ) {
+                (true, true) if state.reduce_motion => "[Reviewed] ".to_string(),
+                (true, true) => format!("{} ", glyphs.reviewed),
+                _ => String::new(),
+            };
+            let indent = if state.groups_by_repo() { "  " } else { "" };
+            // The All PRs row tints, spelled out.
+            let relation = if state.reduce_motion && state.content_view == ContentView::AllOpenPrs {
+                match state.pr_relation(pr) {
+                    PrRelation::Mine => "[Mine] ",
+                    PrRelation::ReviewRequested => "[Review] ",
+                    PrRelation::Other => "",
+                }
+            } else {
+                ""
+            };
+            let prefix = format!(
+                "{}{}{}{}{}",
+                indent,
+                unread,
+                reviewed,
+                relation,
+                if pr.is_draft { "[Draft] " } else { "" }
+            );
+            // Only the title itself gives way; the markers around it stay.
+            let title_width = width.saturating_sub(prefix.width() + review_icon.width());
+            Cell::from(format!(
+                "{}{}{}",
+                prefix,
+                truncate_with_ellipsis(
+                    &render_title(&pr.title, state.expand_shortcodes),
+                    title_width,
+                    ellipsis,
+                ),
+                review_icon,
+            ))
+            .style(style)
+        }
+        PrColumn::Author => Cell::from(truncate_with_ellipsis(&pr.author, width, ellipsis))
+            .style(colored(theme::PR_AUTHOR)),
+        PrColumn::Repo => {
+            Cell::from(truncate_with_ellipsis(&pr.repo_name, width, ellipsis)).style(style)
+        }
+        PrColumn::Updated => {
+            let when = match history {
+                Some(kind) => kind
+                    .finished_at(pr)
+                    .as_ref()
+                    .map(relative_time)
+                    .unwrap_or_default(),
+                None => relative_time(&pr.updated_at),
+            };
+            Cell::from(when).style(colored(theme::DIM))
+        }
+        PrColumn::Size => Cell::from(size_badge(pr.diff_size(), &state.size_thresholds))
+            .style(colored(theme::DIM)),
+        PrColumn::Diffstat => Cell::from(Line::from(vec![
+            Span::styled(format!("+{}", pr.additions), colored(theme::SUCCESS)),
+            Span::styled(format!(" -{}", pr.deletions), colored(theme::ERROR)),
+        ])),
+        PrColumn::Comments => Cell::from(comment_count_text(pr)).style(colored(theme::DIM)),
+        PrColumn::Branch => Cell::from(truncate_with_ellipsis(
+            pr.head_ref.as_deref().unwrap_or(""),
+            width,
+            ellipsis,
+        ))
+        .style(style),
+        PrColumn::Base => Cell::from(truncate_with_ellipsis(
+            pr.base_ref.as_deref().unwrap_or(""),
+            width,
+            ellipsis,
+        ))
+        .style(style),
+        PrColumn::Assignees => Cell::from(truncate_with_ellipsis(
+            &pr.assignees.join(", "),
+            width,
+            ellipsis,
+        ))
+        .style(colored(theme::PR_AUTHOR)),
+    }
+}
+
+/// Comments plus reviews, blank when there are none so busy PRs stand out.
+pub fn comment_count_text(pr: &PullRequest) -> String {
+    match pr.comment_count.saturating_add(pr.review_count) {
+        0 => String::new(),
+        n => n.to_string(),
+    }
+}
+
+/// T-shirt size for a diff of `size` changed lines. `thresholds` are the
+/// inclusive upper bounds for XS, S, M and L.
+pub fn size_badge(size: u32, thresholds: &[u32; 4]) -> &'static str {
+    const LABELS: [&str; 4] = ["XS", "S", "M", "L"];
+    thresholds
+        .iter()
+        .zip(LABELS)
+        .find(|(max, _)| size <= **max)
+        .map(|(_, label)| label)
+        .unwrap_or("XL")
+}
+
 /// Compact, colorblind-safe label + color for a PR's merge state.
 /// Driven by GitHub's `mergeable` enum; `UNKNOWN`/absent renders as a dim `?`
 /// because the search API computes `mergeable` lazily (often `UNKNOWN` at first).
-fn merge_state_display(pr: &PullRequest) -> (&'static str, ratatui::style::Style) {
+/// A mergeable PR whose head is behind its base gets a `↓` "update branch" hint.
+/// Merged and closed PRs say so instead, so finished watched PRs stand out.
+pub fn merge_state_display(
+    pr: &PullRequest,
+    glyphs: &Glyphs,
+) -> (&'static str, ratatui::style::Style) {
+    match pr.state {
+        PrState::Merged => return (glyphs.merged, theme::MERGED),
+        PrState::Closed => return (glyphs.closed, theme::CLOSED),
+        PrState::Open => {}
+    }
     match pr.mergeable.as_deref() {
-        Some("MERGEABLE") => ("✓ ok", theme::MERGE_CLEAN),
-        Some("CONFLICTING") => ("✗ cf", theme::MERGE_CONFLICT),
+        Some("MERGEABLE") if pr.is_behind => (glyphs.behind, theme::WARNING),
+        Some("MERGEABLE") => (glyphs.mergeable, theme::MERGE_CLEAN),
+        Some("CONFLICTING") => (glyphs.conflicting, theme::MERGE_CONFLICT),
         _ => ("?", theme::DIM),
     }
 }
 
 /// Single-glyph CI check indicator for the list column. `statusCheckRollup` is not
 /// lazily computed, so this is reliable straight from the search API.
-fn ci_display(pr: &PullRequest) -> (&'static str, ratatui::style::Style) {
+fn ci_display(pr: &PullRequest, glyphs: &Glyphs) -> (&'static str, ratatui::style::Style) {
     match pr.ci_status() {
-        CiStatus::Passing => ("✓", theme::MERGE_CLEAN),
-        CiStatus::Failing => ("✗", theme::MERGE_CONFLICT),
-        CiStatus::Pending => ("…", theme::WARNING),
-        CiStatus::None => ("·", theme::DIM),
+        CiStatus::Passing => (glyphs.ci_passing, theme::MERGE_CLEAN),
+        CiStatus::Failing => (glyphs.ci_failing, theme::MERGE_CONFLICT),
+        CiStatus::Pending => (glyphs.ci_pending, theme::WARNING),
+        CiStatus::None => (glyphs.ci_none, theme::DIM),
+    }
+}
+
+/// `"4/37"` while filters may hide PRs, otherwise just the total.
+pub fn count_text((visible, total): (usize, usize), filters_active: bool) -> String {
+    if filters_active {
+        format!("{}/{}", visible, total)
+    } else {
+        total.to_string()
     }
 }
 
+/// Table title count: `"5 of 12"` while filters may hide PRs, so the
+/// filter's impact shows, otherwise just the total.
+pub fn title_count_text((visible, total): (usize, usize), filters_active: bool) -> String {
+    if filters_active {
+        format!("{} of {}", visible, total)
+    } else {
+        total.to_string()
+    }
+}
+
+/// Badge for a virtual nav entry backed by `prs`; `None` when it has none.
+fn nav_count(state: &AppState, prs: &[PullRequest]) -> Option<String> {
+    let counts = state.filtered_counts(prs);
+    (counts.1 > 0).then(|| count_text(counts, state.shared_filters_active()))
+}
+
+/// [`nav_count`] for views about open PRs: merged and closed ones (a finished
+/// watched PR, say) don't count.
+fn open_nav_count(state: &AppState, prs: &[PullRequest]) -> Option<String> {
+    let open: Vec<PullRequest> = prs
+        .iter()
+        .filter(|pr| pr.state == PrState::Open)
+        .cloned()
+        .collect();
+    nav_count(state, &open)
+}
+
 fn render_pr_table(
     f: &mut Frame,
     area: Rect,
@@ -148,7 +580,7 @@ fn render_pr_table(
 ) {
     let prs = state.current_pr_list();
 
-    let search_suffix = if state.search_active && !state.search_query.is_empty() {
+    let search_suffix = if !state.search_query.is_empty() {
         format!(" [filter: {}]", state.search_query)
     } else {
         String::new()
@@ -157,120 +589,344 @@ fn render_pr_table(
         Some(l) => format!(" [state: {}]", l),
         None => String::new(),
     };
+    let review_suffix = match state.review_filter().label() {
+        Some(l) => format!(" [review: {}]", l),
+        None => String::new(),
+    };
+    let mut label_suffix: String = state
+        .label_filter
+        .iter()
+        .map(|l| format!(" [label: {}]", l))
+        .collect();
+    if state.involves_me_only && state.content_view == ContentView::AllOpenPrs {
+        label_suffix.push_str(" [involving me]");
+    }
+    let inbox_sort = match state.content_view {
+        ContentView::Inbox => state.inbox_sort.label(),
+        _ => None,
+    };
+    let sort_suffix = match state.sort.label().or(inbox_sort) {
+        Some(l) => format!(" [sort: {}]", l),
+        None => String::new(),
+    };
+    let showing_suffix = match (&state.content_view, state.pr_state_filter.label()) {
+        (ContentView::RepoPrList { .. }, Some(l)) => format!(" [showing: {}]", l),
+        _ => String::new(),
+    };
 
-    let title = format!(
-        " {} ({}){}{} ",
+    let inner_width = area.width.saturating_sub(2);
+    let window = column_window(
+        &state.columns,
+        &state.column_priority,
+        inner_width,
+        state.column_offset,
+    );
+    let glyphs = state.theme().glyphs;
+    let scroll_suffix = match (window.hidden_left, window.hidden_right) {
+        (true, true) => format!(" {} {}", glyphs.scroll_left, glyphs.scroll_right),
+        (true, false) => format!(" {}", glyphs.scroll_left),
+        (false, true) => format!(" {}", glyphs.scroll_right),
+        (false, false) => String::new(),
+    };
+
+    let mut title = vec![Span::raw(format!(
+        " {} ({})",
         title,
-        prs.len(),
+        title_count_text(state.view_counts(), state.filters_active())
+    ))];
+    title.extend(freshness_span(state, &state.content_view));
+    title.push(Span::raw(format!(
+        "{}{}{}{}{}{}{} ",
+        showing_suffix,
         merge_suffix,
-        search_suffix
-    );
+        review_suffix,
+        label_suffix,
+        sort_suffix,
+        search_suffix,
+        scroll_suffix
+    )));
 
     let block = Block::default()
-        .title(title)
+        .title(Line::from(title))
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(border_style);
 
+    // History views show when each PR was merged/closed instead of its last update.
+    let history = match state.content_view {
+        ContentView::RecentlyMerged => Some(PrHistoryKind::Merged),
+        ContentView::RecentlyClosed => Some(PrHistoryKind::Closed),
+        _ => None,
+    };
+
     if prs.is_empty() {
-        let msg = if state.loading {
-            "Loading..."
-        } else if state.search_active && !state.search_query.is_empty() {
-            "No matching pull requests"
-        } else {
-            "No open pull requests"
-        };
-        let para = Paragraph::new(msg).style(theme::DIM).block(block);
+        let para = Paragraph::new(empty_state_message(state))
+            .style(theme::DIM)
+            .block(block);
         f.render_widget(para, area);
         return;
     }
 
-    let header = Row::new(vec![
-        Cell::from("#").style(theme::HEADER),
-        Cell::from("State").style(theme::HEADER),
-        Cell::from("CI").style(theme::HEADER),
-        Cell::from("Title").style(theme::HEADER),
-        Cell::from("Author").style(theme::HEADER),
-        Cell::from("Repo").style(theme::HEADER),
-        Cell::from("Updated").style(theme::HEADER),
-    ])
+    let tinted = state.content_view == ContentView::AllOpenPrs;
+    let (summary_area, table_area, legend_area) = pr_table_areas(block.inner(area), state);
+
+    let header = Row::new(window.columns.iter().map(|col| {
+        Cell::from(column_header(*col, history, state.theme().glyphs)).style(theme::HEADER)
+    }))
     .height(1);
 
-    let rows: Vec<Row> = prs
+    let widths: Vec<Constraint> = window
+        .columns
         .iter()
-        .enumerate()
-        .map(|(i, pr)| {
-            let style = if i == state.content_cursor && state.focused_pane == FocusedPane::Content {
-                theme::HIGHLIGHT
-            } else if pr.is_draft {
-                theme::DRAFT
-            } else {
-                ratatui::style::Style::default()
-            };
-
-            let review_icon = match pr.review_decision.as_deref() {
-                Some("APPROVED") => " +",
-                Some("CHANGES_REQUESTED") => " !",
-                _ => "",
-            };
-
-            let (merge_label, merge_style) = merge_state_display(pr);
-            let (ci_label, ci_style) = ci_display(pr);
-
-            Row::new(vec![
-                Cell::from(format!("#{}", pr.number)).style(if style == theme::HIGHLIGHT {
-                    style
-                } else {
-                    theme::PR_NUMBER
-                }),
-                Cell::from(merge_label).style(if style == theme::HIGHLIGHT {
-                    style
-                } else {
-                    merge_style
-                }),
-                Cell::from(ci_label).style(if style == theme::HIGHLIGHT {
-                    style
-                } else {
-                    ci_style
-                }),
-                Cell::from(format!(
-                    "{}{}{}",
-                    if pr.is_draft { "[Draft] " } else { "" },
-                    pr.title.as_str(),
-                    review_icon,
-                ))
-                .style(style),
-                Cell::from(pr.author.as_str()).style(if style == theme::HIGHLIGHT {
-                    style
-                } else {
-                    theme::PR_AUTHOR
-                }),
-                Cell::from(pr.repo_name.as_str()).style(style),
-                Cell::from(relative_time(&pr.updated_at)).style(if style == theme::HIGHLIGHT {
-                    style
-                } else {
-                    theme::DIM
-                }),
-            ])
-            .height(1)
-        })
+        .map(|col| column_width(*col))
+        .collect();
+    // Lay the columns out the way `Table` will, so text is cut to the width it
+    // actually gets rather than chopped mid-character by the cell.
+    let col_widths: Vec<usize> = Layout::horizontal(widths.clone())
+        .flex(Flex::Start)
+        .spacing(1)
+        .split(table_area)
+        .iter()
+        .map(|r| r.width as usize)
         .collect();
 
-    let widths = [
-        Constraint::Length(7),
-        Constraint::Length(5),
-        Constraint::Length(3),
-        Constraint::Min(20),
-        Constraint::Length(16),
-        Constraint::Length(24),
-        Constraint::Length(10),
-    ];
+    // Tables have no column spans, so a repo header sits in the Title column
+    // (or the first one, if Title is hidden) above its indented PRs.
+    let header_col = window
+        .columns
+        .iter()
+        .position(|c| *c == PrColumn::Title)
+        .unwrap_or(0);
+    let mut rows: Vec<Row> = Vec::with_capacity(prs.len());
+    for (i, pr) in prs.iter().enumerate() {
+        let repo = pr.repo_full_name();
+        if state.groups_by_repo() && (i == 0 || prs[i - 1].repo_full_name() != repo) {
+            let cells = (0..window.columns.len()).map(|c| {
+                if c == header_col {
+                    Cell::from(truncate_with_ellipsis(
+                        &repo,
+                        col_widths[c],
+                        state.theme().glyphs.ellipsis,
+                    ))
+                } else {
+                    Cell::from("")
+                }
+            });
+            rows.push(Row::new(cells).style(theme::NAV_ORG));
+        }
+        let relation = if tinted {
+            state.pr_relation(pr)
+        } else {
+            PrRelation::Other
+        };
+        let style = if i == state.content_cursor && state.focused_pane == FocusedPane::Content {
+            theme::HIGHLIGHT
+        } else if relation == PrRelation::Mine {
+            theme::MINE
+        } else if relation == PrRelation::ReviewRequested {
+            theme::REVIEW_REQUESTED
+        } else if pr.is_draft {
+            theme::DRAFT
+        } else if pr.state == PrState::Merged {
+            theme::MERGED
+        } else if pr.state == PrState::Closed {
+            theme::CLOSED
+        } else {
+            ratatui::style::Style::default()
+        };
+        let cells = window
+            .columns
+            .iter()
+            .zip(&col_widths)
+            .map(|(col, width)| pr_cell(*col, pr, state, history, style, *width));
+        rows.push(Row::new(cells).height(1));
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
-        .block(block)
         .row_highlight_style(theme::HIGHLIGHT);
+    // The reducer keeps the offset near the cursor, but only knows the height
+    // of the previous frame; re-derive it for this one.
+    let (cursor, len) = state.cursor_table_position();
+    let offset = scroll_offset(
+        state.content_offset,
+        cursor,
+        len,
+        table_area.height.saturating_sub(1) as usize,
+        state.scrolloff,
+    );
+    let mut table_state = TableState::default().with_offset(offset);
+
+    f.render_widget(block, area);
+    if let Some(summary_area) = summary_area {
+        f.render_widget(Paragraph::new(age_summary_line(state)), summary_area);
+    }
+    f.render_stateful_widget(table, table_area, &mut table_state);
+
+    if let Some(legend_area) = legend_area {
+        let swatch = state.theme().glyphs.swatch;
+        let legend = Line::from(vec![
+            Span::styled(format!(" {} ", swatch), theme::MINE),
+            Span::styled("mine", theme::DIM),
+            Span::styled(format!("  {} ", swatch), theme::REVIEW_REQUESTED),
+            Span::styled("review requested", theme::DIM),
+        ]);
+        f.render_widget(Paragraph::new(legend), legend_area);
+    }
+}
+
+/// Split the inside of the PR table's border into the age summary line, the
+/// table itself and the All PRs legend line.
+fn pr_table_areas(mut inner: Rect, state: &AppState) -> (Option<Rect>, Rect, Option<Rect>) {
+    // The age summary goes on top, as long as a few rows are left for the table.
+    let summary_area = (inner.height > 4).then(|| {
+        let [summary, rest] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(inner);
+        inner = rest;
+        summary
+    });
+    // All PRs tints my PRs and those awaiting my review, with a legend line
+    // at the bottom of the pane.
+    if state.content_view == ContentView::AllOpenPrs && inner.height > 2 {
+        let [table, legend] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+        (summary_area, table, Some(legend))
+    } else {
+        (summary_area, inner, None)
+    }
+}
+
+/// How many PR rows fit under the table header when the content pane is
+/// `area`. The event loop records it for the reducer's scrolling.
+pub fn pr_table_rows(area: Rect, state: &AppState) -> usize {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let (_, table, _) = pr_table_areas(inner, state);
+    table.height.saturating_sub(1) as usize
+}
 
-    f.render_widget(table, area);
+/// `state.column_offset`, or 0 once the PR table in content pane `area` is
+/// wide enough to show every column, so a resize that makes everything fit
+/// again doesn't leave the table scrolled. The event loop applies it before
+/// each draw.
+pub fn fitted_column_offset(area: Rect, state: &AppState) -> usize {
+    let width = area.width.saturating_sub(2);
+    let unscrolled = column_window(&state.columns, &state.column_priority, width, 0);
+    if unscrolled.hidden_left || unscrolled.hidden_right {
+        state.column_offset
+    } else {
+        0
+    }
+}
+
+/// `<1d: 4 │ 1–3d: 9 │ 3–7d: 6 │ >7d: 12`, with the selected bucket (if any)
+/// highlighted.
+fn age_summary_line(state: &AppState) -> Line<'static> {
+    let counts = state.age_histogram();
+    let labels = time::age_bucket_labels(&state.age_buckets, state.theme().glyphs.range);
+    let mut spans = vec![Span::raw(" ")];
+    for (i, (label, count)) in labels.into_iter().zip(counts).enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(
+                format!(" {} ", state.theme().glyphs.separator),
+                theme::DIM,
+            ));
+        }
+        let style = if state.age_bucket_filter == Some(i) {
+            theme::HIGHLIGHT
+        } else {
+            theme::DIM
+        };
+        spans.push(Span::styled(format!("{}: {}", label, count), style));
+    }
+    Line::from(spans)
+}
+
+/// What an empty PR table says, depending on why it is empty: the fetch
+/// failed, nothing has arrived yet, filters hide everything, or there is
+/// genuinely nothing to show.
+pub fn empty_state_message(state: &AppState) -> String {
+    if state.view_fetch_failed() {
+        return "Fetch failed for this view — press r to retry".to_string();
+    }
+
+    let total = state.unfiltered_pr_count();
+    if total > 0 {
+        let mut hints = Vec::new();
+        if !state.search_query.is_empty() {
+            hints.push("/ to edit the search");
+        }
+        if state.merge_filter != MergeFilter::All {
+            hints.push("f to change the merge filter");
+        }
+        if state.review_filter() != ReviewFilter::All {
+            hints.push("F to change the review filter");
+        }
+        if !state.label_filter.is_empty() {
+            hints.push("Esc to clear the label filter");
+        }
+        if state.involves_me_only && state.content_view == ContentView::AllOpenPrs {
+            hints.push("I to show every PR");
+        }
+        if state.age_bucket_filter.is_some() {
+            hints.push("Alt-1..4 to change the age filter");
+        }
+        let what = if total == 1 {
+            "The only PR is".to_string()
+        } else {
+            format!("All {} PRs are", total)
+        };
+        return if hints.is_empty() {
+            format!("{} hidden by filters", what)
+        } else {
+            format!("{} hidden by filters (press {})", what, hints.join(", "))
+        };
+    }
+
+    if let (ContentView::RepoPrList { owner, name }, Some(label)) =
+        (&state.content_view, state.pr_state_filter.label())
+    {
+        let key = (format!("{}/{}", owner, name), state.pr_state_filter);
+        return if state.repo_finished_prs.contains_key(&key) {
+            format!("No {} pull requests", label)
+        } else {
+            format!("Loading {} pull requests...", label)
+        };
+    }
+
+    if state.loading {
+        return "No data yet — loading...".to_string();
+    }
+
+    match state.content_view {
+        ContentView::RecentlyMerged => "No recently merged pull requests",
+        ContentView::RecentlyClosed => "No recently closed pull requests",
+        ContentView::FailingChecks => "No pull requests with failing checks",
+        ContentView::Watched if state.watched.is_empty() => {
+            "Not watching any pull requests (press w on one to watch it)"
+        }
+        ContentView::Watched => "Loading watched pull requests...",
+        _ => "No open pull requests",
+    }
+    .to_string()
+}
+
+/// ` (fetched 43m ago)` for the data behind `view`, with a ⚠ if the latest
+/// fetch failed, in WARNING once it's older than twice its refresh interval.
+/// `None` until the first successful fetch.
+fn freshness_span(state: &AppState, view: &ContentView) -> Option<Span<'static>> {
+    let freshness = state.view_freshness(view)?;
+    let mut text = format!(" (fetched {}", relative_time(&freshness.last_success?));
+    if freshness.failing() {
+        text.push(' ');
+        text.push_str(state.theme().glyphs.warning);
+    }
+    let stale = state.view_is_stale(view, time::now());
+    if stale && state.reduce_motion {
+        text.push_str(", stale");
+    }
+    text.push(')');
+    let style = if stale { theme::WARNING } else { theme::DIM };
+    Some(Span::styled(text, style))
 }
 
 fn render_org_overview(
@@ -280,9 +936,13 @@ fn render_org_overview(
     org: &str,
     border_style: ratatui::style::Style,
 ) {
+    let mut title = vec![Span::raw(format!(" {}", org))];
+    title.extend(freshness_span(state, &state.content_view));
+    title.push(Span::raw(" "));
     let block = Block::default()
-        .title(format!(" {} ", org))
+        .title(Line::from(title))
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(border_style);
 
     let org_data = state.orgs.get(org);
@@ -295,6 +955,26 @@ fn render_org_overview(
         Line::from(""),
     ];
 
+    if state.unknown_owners.contains(org) {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "GitHub has no user or organization named {} — check github.owners",
+                org
+            ),
+            theme::ERROR,
+        )));
+        lines.push(Line::from(""));
+    } else if state
+        .failed_views
+        .contains(&ContentView::OrgOverview(org.to_string()))
+    {
+        lines.push(Line::from(Span::styled(
+            "Fetch failed for this org — press r to retry",
+            theme::ERROR,
+        )));
+        lines.push(Line::from(""));
+    }
+
     if let Some(data) = org_data {
         let active_repos = data.repos.iter().filter(|r| !r.is_archived).count();
         let total_prs: u32 = data
@@ -322,10 +1002,45 @@ fn render_org_overview(
                 theme::HEADER,
             )));
             for repo in repos_with_prs.iter().take(10) {
-                lines.push(Line::from(format!(
-                    "  {} — {} PRs",
-                    repo.name, repo.open_pr_count
-                )));
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} — {} PRs", repo.name, repo.open_pr_count)),
+                    Span::styled(repo_sparkline(state, &repo.full_name()), theme::PR_NUMBER),
+                ]));
+            }
+        }
+
+        let activity = org_activity(org, &state.all_open_prs);
+
+        if !activity.top_authors.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Top authors by open PRs:",
+                theme::HEADER,
+            )));
+            for (author, count) in &activity.top_authors {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<20}", author), theme::PR_AUTHOR),
+                    Span::raw(format!(" {}", count)),
+                ]));
+            }
+        }
+
+        if !activity.recent.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Recent PR activity:",
+                theme::HEADER,
+            )));
+            for pr in &activity.recent {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {}#{} ", pr.repo_name, pr.number),
+                        theme::PR_NUMBER,
+                    ),
+                    Span::raw(render_title(&pr.title, state.expand_shortcodes).into_owned()),
+                    Span::styled(format!(" — {}", pr.author), theme::PR_AUTHOR),
+                    Span::styled(format!(" ({})", relative_time(&pr.updated_at)), theme::DIM),
+                ]));
             }
         }
     } else {
@@ -337,54 +1052,109 @@ fn render_org_overview(
 }
 
 pub fn render_status_bar(f: &mut Frame, area: Rect, state: &AppState) {
-    let key_hints = if state.search_active {
+    let glyphs = state.theme().glyphs;
+    // A full title gets the whole left side instead of the key hints.
+    let key_hints = if state.status_message.is_some() || state.token_rejected {
+        ""
+    } else if state.search_active {
         "Esc: close search | Enter: filter"
+    } else if state.offline {
+        // Nothing to refresh from.
+        "j/k: nav | Enter: select | l: log | d: diff | f: filter | /: search | o: open | ?: help | q: quit"
     } else {
         "j/k: nav | Enter: select | l: log | d: diff | f: filter | /: search | r: refresh | o: open | ?: help | q: quit"
     };
 
-    let status = if state.loading {
-        "Loading...".to_string()
+    let loading = if state.reduce_motion {
+        "Loading"
+    } else {
+        "Loading..."
+    };
+    let status = if state.loading && state.stale {
+        format!("{} (showing cached data)", loading)
+    } else if state.loading {
+        loading.to_string()
+    } else if state.token_rejected {
+        "Token expired or revoked — press X to resolve a new one".to_string()
     } else if let Some(ref err) = state.error_message {
         format!("Error: {} (Esc to dismiss)", err)
+    } else if let Some(ref msg) = state.status_message {
+        render_title(msg, state.expand_shortcodes).into_owned()
+    } else if let Some(n) = state.pending_count {
+        n.to_string()
+    } else if state.pending_jump {
+        "'".to_string()
     } else {
         String::new()
     };
 
-    let rate_info = format!(
-        "API: {}/{}",
-        state.rate_limit.remaining, state.rate_limit.limit
-    );
-
-    let refresh_info = state
-        .last_refresh
-        .as_ref()
-        .map(|t| format!(" | {}", relative_time(t)))
-        .unwrap_or_default();
-
-    let right_text = format!("{}{}", rate_info, refresh_info);
+    // The rate readout is hidden entirely when no response has reported one
+    // (GitHub App installation tokens).
+    let right_text = if state.offline {
+        format!(
+            "OFFLINE — data from {}",
+            state
+                .offline_data_as_of
+                .as_ref()
+                .map(relative_time)
+                .unwrap_or_else(|| "cache".to_string())
+        )
+    } else {
+        [
+            state
+                .refresh_locked
+                .then(|| format!("{} locked", glyphs.locked)),
+            state
+                .auto_refresh_paused
+                .then(|| format!("{} auto-refresh paused", glyphs.paused)),
+            state
+                .rate_limit_backoff
+                .map(|secs| format!("rate limited, backing off {}s", secs)),
+            state
+                .refresh_retry_at
+                .map(|at| format!("refresh failing, retrying in {}", time::countdown(&at))),
+            (state.queued_tasks > 0).then(|| format!("queued: {}", state.queued_tasks)),
+            state
+                .rate_limit
+                .is_known()
+                .then(|| rate_limit_text(&state.rate_limit, glyphs.minus)),
+            state.last_refresh.as_ref().map(relative_time),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" | ")
+    };
 
-    // Calculate available space
+    // Calculate available space. The API/refresh readout on the right is kept
+    // whole and the status message is sized next, so the key hints give way
+    // first.
     let total_width = area.width as usize;
-    let left_len = key_hints.len();
-    let right_len = right_text.len();
+    let right_len = right_text.width();
+    let status_truncated = truncate_with_ellipsis(
+        &status,
+        total_width.saturating_sub(right_len + 3),
+        glyphs.ellipsis,
+    );
+    let status_len = status_truncated.width();
+    // A space either side of the status message when there is one.
+    let gaps = if status_len > 0 { 3 } else { 1 };
+    let key_hints = truncate_with_ellipsis(
+        key_hints,
+        total_width.saturating_sub(right_len + status_len + gaps),
+        glyphs.ellipsis,
+    );
+    let left_len = key_hints.width();
 
-    let center_start = left_len + 1;
     let center_width = total_width.saturating_sub(left_len + right_len + 2);
-    let status_truncated = if status.len() > center_width {
-        format!("{}...", &status[..center_width.saturating_sub(3)])
-    } else {
-        status
-    };
-
-    let padding = center_width.saturating_sub(status_truncated.len());
+    let padding = center_width.saturating_sub(status_len);
 
     let line = Line::from(vec![
         Span::styled(key_hints, theme::STATUS_BAR),
-        Span::styled(" ".repeat(center_start.min(1)), theme::STATUS_BAR),
+        Span::styled(" ", theme::STATUS_BAR),
         Span::styled(
             status_truncated,
-            if state.error_message.is_some() {
+            if state.error_message.is_some() || state.token_rejected {
                 theme::ERROR.bg(ratatui::style::Color::DarkGray)
             } else {
                 theme::STATUS_BAR
@@ -398,6 +1168,159 @@ pub fn render_status_bar(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(bar, area);
 }
 
+/// Cut `s` to at most `max` terminal columns, ending in `ellipsis` when
+/// anything was dropped (left off when even it doesn't fit). Wide characters
+/// (emoji, CJK) count as two columns.
+pub fn truncate_with_ellipsis(s: &str, max: usize, ellipsis: &str) -> String {
+    if s.width() <= max {
+        return s.to_string();
+    }
+    let ellipsis = if ellipsis.width() <= max {
+        ellipsis
+    } else {
+        ""
+    };
+    let budget = max - ellipsis.width();
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+/// Gitmoji shortcodes most often seen in PR titles.
+const SHORTCODES: &[(&str, &str)] = &[
+    ("art", "🎨"),
+    ("zap", "⚡"),
+    ("fire", "🔥"),
+    ("bug", "🐛"),
+    ("ambulance", "🚑"),
+    ("sparkles", "✨"),
+    ("memo", "📝"),
+    ("rocket", "🚀"),
+    ("lipstick", "💄"),
+    ("tada", "🎉"),
+    ("white_check_mark", "✅"),
+    ("lock", "🔒"),
+    ("bookmark", "🔖"),
+    ("rotating_light", "🚨"),
+    ("construction", "🚧"),
+    ("green_heart", "💚"),
+    ("arrow_down", "⬇️"),
+    ("arrow_up", "⬆️"),
+    ("pushpin", "📌"),
+    ("construction_worker", "👷"),
+    ("recycle", "♻️"),
+    ("heavy_plus_sign", "➕"),
+    ("heavy_minus_sign", "➖"),
+    ("wrench", "🔧"),
+    ("hammer", "🔨"),
+    ("globe_with_meridians", "🌐"),
+    ("pencil2", "✏️"),
+    ("rewind", "⏪"),
+    ("twisted_rightwards_arrows", "🔀"),
+    ("package", "📦"),
+    ("truck", "🚚"),
+    ("boom", "💥"),
+    ("bento", "🍱"),
+    ("wheelchair", "♿"),
+    ("bulb", "💡"),
+    ("card_file_box", "🗃️"),
+    ("loud_sound", "🔊"),
+    ("mute", "🔇"),
+    ("adhesive_bandage", "🩹"),
+    ("coffin", "⚰️"),
+    ("test_tube", "🧪"),
+    ("label", "🏷️"),
+    ("seedling", "🌱"),
+    ("children_crossing", "🚸"),
+    ("building_construction", "🏗️"),
+    ("see_no_evil", "🙈"),
+    ("alembic", "⚗️"),
+    ("mag", "🔍"),
+    ("goal_net", "🥅"),
+    ("dizzy", "💫"),
+    ("wastebasket", "🗑️"),
+    ("technologist", "🧑‍💻"),
+];
+
+/// PR title as displayed. With `expand_shortcodes`, known `:name:` shortcodes
+/// become their emoji; unknown ones are left alone.
+pub fn render_title(title: &str, expand_shortcodes: bool) -> Cow<'_, str> {
+    if !expand_shortcodes || !title.contains(':') {
+        return Cow::Borrowed(title);
+    }
+    let mut out = String::with_capacity(title.len());
+    let mut rest = title;
+    while let Some(start) = rest.find(':') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let emoji = after.find(':').and_then(|end| {
+            let code = &after[..end];
+            SHORTCODES
+                .iter()
+                .find(|(name, _)| *name == code)
+                .map(|(_, e)| (*e, end))
+        });
+        match emoji {
+            Some((e, end)) => {
+                out.push_str(e);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push(':');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// "API: 4,812/5,000 (−36 this refresh, resets 14:05)" with `minus` "−"; the
+/// parenthetical parts are omitted when unknown.
+fn rate_limit_text(tracker: &RateLimitTracker, minus: &str) -> String {
+    let rl = tracker.current();
+    let mut extras = Vec::new();
+    if let Some(cost) = tracker.cycle_cost() {
+        extras.push(format!("{}{} this refresh", minus, thousands(cost)));
+    }
+    if let Some(reset_at) = rl.reset_at {
+        extras.push(format!("resets {}", time::local(&reset_at).format("%H:%M")));
+    }
+    let extras = if extras.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", extras.join(", "))
+    };
+    format!(
+        "API: {}/{}{}",
+        thousands(rl.remaining),
+        thousands(rl.limit),
+        extras
+    )
+}
+
+/// Format with `,` thousands separators.
+fn thousands(n: u32) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
 pub fn render_search_overlay(f: &mut Frame, state: &AppState) {
     if !state.search_active {
         return;
@@ -417,38 +1340,112 @@ pub fn render_search_overlay(f: &mut Frame, state: &AppState) {
     f.render_widget(para, search_area);
 }
 
-pub fn render_error_modal(f: &mut Frame, area: Rect, state: &AppState) {
-    let Some(ref msg) = state.error_message else {
-        return;
-    };
-
-    let modal_width = (area.width / 2).max(40).min(area.width - 4);
-    let modal_height = 5u16;
+/// Where the error modal for `msg` goes on a screen of `area`, and how many
+/// lines the wrapped message takes. Sized to the message, up to most of the
+/// screen; anything longer scrolls.
+fn error_modal_area(area: Rect, msg: &str) -> (Rect, usize) {
+    let modal_width = (area.width / 2).max(40).min(area.width.saturating_sub(4));
+    let text_width = modal_width.saturating_sub(2) as usize;
+    let msg_rows = wrapped_line_count(msg, text_width);
+    let max_height = area.height.saturating_sub(4).max(5);
+    let modal_height = (msg_rows as u16).saturating_add(4).clamp(5, max_height);
     let x = (area.width.saturating_sub(modal_width)) / 2;
     let y = (area.height.saturating_sub(modal_height)) / 2;
-
     let modal_area = Rect {
         x,
         y,
         width: modal_width,
         height: modal_height,
     };
+    (modal_area, msg_rows)
+}
+
+fn error_scroll_limit(msg_rows: usize, text_rows: u16) -> u16 {
+    msg_rows.saturating_sub(text_rows as usize) as u16
+}
+
+/// How far the error modal can scroll on a screen of `area`; 0 without an
+/// error. The event loop records it so the reducer stops scrolling there.
+pub fn error_max_scroll(area: Rect, state: &AppState) -> u16 {
+    let Some(ref msg) = state.error_message else {
+        return 0;
+    };
+    let (modal_area, msg_rows) = error_modal_area(area, msg);
+    // Inside the border, less the gap and hint line under the text.
+    let text_rows = modal_area.height.saturating_sub(2).saturating_sub(2);
+    error_scroll_limit(msg_rows, text_rows)
+}
+
+pub fn render_error_modal(f: &mut Frame, area: Rect, state: &AppState) {
+    let Some(ref msg) = state.error_message else {
+        return;
+    };
+
+    let (modal_area, msg_rows) = error_modal_area(area, msg);
 
     f.render_widget(Clear, modal_area);
 
     let block = Block::default()
         .title(" Error ")
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(theme::ERROR);
 
-    let text = vec![
-        Line::from(Span::styled(msg.as_str(), theme::ERROR)),
-        Line::from(""),
-        Line::from(Span::styled("Press Esc to dismiss", theme::DIM)),
-    ];
+    let inner = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+    let [text_area, _, hint_area] = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
 
-    let para = Paragraph::new(text).block(block);
-    f.render_widget(para, modal_area);
+    let max_scroll = error_scroll_limit(msg_rows, text_area.height);
+    let scroll = state.error_scroll.min(max_scroll);
+    let para = Paragraph::new(Span::styled(msg.as_str(), theme::ERROR))
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    f.render_widget(para, text_area);
+
+    let hint = if max_scroll > 0 {
+        format!(
+            "Esc: dismiss · y: copy · j/k: scroll ({}/{})",
+            scroll, max_scroll
+        )
+    } else {
+        "Esc: dismiss · y: copy".to_string()
+    };
+    f.render_widget(Paragraph::new(Span::styled(hint, theme::DIM)), hint_area);
+}
+
+/// Rows `text` takes when word-wrapped to `width` columns, breaking words
+/// longer than a row. At least 1.
+pub fn wrapped_line_count(text: &str, width: usize) -> usize {
+    let width = width.max(1);
+    text.lines()
+        .map(|line| {
+            let mut rows = 1;
+            let mut col = 0;
+            for word in line.split(' ') {
+                let w = word.width();
+                let needed = if col == 0 { w } else { col + 1 + w };
+                if needed <= width {
+                    col = needed;
+                } else if w <= width {
+                    rows += 1;
+                    col = w;
+                } else {
+                    if col > 0 {
+                        rows += 1;
+                    }
+                    rows += (w - 1) / width;
+                    col = (w - 1) % width + 1;
+                }
+            }
+            rows
+        })
+        .sum::<usize>()
+        .max(1)
 }
 
 /// Human label + style for a PR's `mergeable` value, used in the detail pane.
@@ -490,6 +1487,18 @@ fn detail_body_lines(detail: &PrDetail, max_commits: usize) -> Vec<Line<'static>
         Span::styled(checks_text, checks_style),
     ]));
     lines.push(Line::from(""));
+
+    if !detail.closes_issues.is_empty() {
+        lines.push(Line::from(Span::styled("Closes:", theme::HEADER)));
+        for (number, title) in &detail.closes_issues {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  #{} ", number), theme::PR_NUMBER),
+                Span::raw(title.clone()),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
     lines.push(Line::from(Span::styled("Recent commits:", theme::HEADER)));
 
     if detail.commits.is_empty() {
@@ -540,24 +1549,64 @@ fn render_git_log_overlay(f: &mut Frame, state: &AppState) {
     };
 
     let modal_area = overlay_area(f, 75, 60);
-    let title = format!(" Git log — PR #{} — {} ", pr.number, pr.title);
+    let title = format!(
+        " Git log — PR #{} — {} ",
+        pr.number,
+        render_title(&pr.title, state.expand_shortcodes)
+    );
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(theme::BORDER_FOCUSED);
 
-    let body_capacity = modal_area.height.saturating_sub(4) as usize;
+    // The branch line and its spacer come first, from the PR itself.
+    let branch = Line::from(vec![
+        Span::styled("Branch ", theme::DIM),
+        Span::raw(pr.head_ref.clone().unwrap_or_else(|| "unknown".into())),
+        Span::styled(" into ", theme::DIM),
+        Span::raw(pr.base_ref.clone().unwrap_or_else(|| "unknown".into())),
+    ]);
+    let body_capacity = modal_area.height.saturating_sub(6) as usize;
     let mut lines: Vec<Line> = match state.pr_details.get(&pr.url) {
         Some(PrDetailEntry::Loaded(detail)) => {
-            detail_body_lines(detail, body_capacity.saturating_sub(3))
+            // The "Closes:" block takes a header, one line per issue and a spacer.
+            let closes_lines = match detail.closes_issues.len() {
+                0 => 0,
+                n => n + 2,
+            };
+            let mut lines =
+                detail_body_lines(detail, body_capacity.saturating_sub(3 + closes_lines));
+            // The description gets whatever room the commits leave, after a
+            // spacer and its header.
+            let room = body_capacity.saturating_sub(lines.len() + 2);
+            let description = markdown::to_text(&detail.body).lines;
+            if room > 0 && !description.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled("Description:", theme::HEADER)));
+                if description.len() > room {
+                    lines.extend(description.into_iter().take(room - 1));
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", state.theme().glyphs.ellipsis),
+                        theme::DIM,
+                    )));
+                } else {
+                    lines.extend(description);
+                }
+            }
+            lines
         }
         Some(PrDetailEntry::Failed(msg)) => {
             vec![Line::from(Span::styled(msg.clone(), theme::ERROR))]
         }
         Some(PrDetailEntry::Loading) | None => {
-            vec![Line::from(Span::styled("Loading commits…", theme::DIM))]
+            vec![Line::from(Span::styled(
+                format!("Loading commits{}", state.theme().glyphs.ellipsis),
+                theme::DIM,
+            ))]
         }
     };
+    lines.splice(0..0, [branch, Line::from("")]);
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "l/Esc: close · d: diff",
@@ -594,7 +1643,11 @@ fn render_diff_overlay(f: &mut Frame, state: &AppState) {
     };
 
     let modal_area = overlay_area(f, 90, 90);
-    let title = format!(" Diff — PR #{} — {} ", pr.number, pr.title);
+    let title = format!(
+        " Diff — PR #{} — {} ",
+        pr.number,
+        render_title(&pr.title, state.expand_shortcodes)
+    );
 
     let body_height = modal_area.height.saturating_sub(3) as usize;
 
@@ -614,7 +1667,10 @@ fn render_diff_overlay(f: &mut Frame, state: &AppState) {
             false,
         ),
         Some(DiffEntry::Loading) | None => (
-            vec![Line::from(Span::styled("Loading diff…", theme::DIM))],
+            vec![Line::from(Span::styled(
+                format!("Loading diff{}", state.theme().glyphs.ellipsis),
+                theme::DIM,
+            ))],
             false,
         ),
     };
@@ -639,6 +1695,7 @@ fn render_diff_overlay(f: &mut Frame, state: &AppState) {
         .title(title)
         .title_bottom(Line::from(Span::styled(hint, theme::DIM)))
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(theme::BORDER_FOCUSED);
 
     f.render_widget(Clear, modal_area);
@@ -646,16 +1703,85 @@ fn render_diff_overlay(f: &mut Frame, state: &AppState) {
     f.render_widget(para, modal_area);
 }
 
+/// Small popup with the highlighted PR's untruncated title, branch and URL, for
+/// when the table cells cut off what's needed.
+pub fn render_pr_info_popup(f: &mut Frame, state: &AppState) {
+    if !state.pr_info_open {
+        return;
+    }
+    let Some(pr) = state.selected_pr() else {
+        return;
+    };
+
+    let area = f.area();
+    let modal_width = 72u16.clamp(40, area.width.saturating_sub(4));
+    let modal_height = 12u16.min(area.height.saturating_sub(2));
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    let block = Block::default()
+        .title(format!(
+            " {}/{}#{} ",
+            pr.repo_owner, pr.repo_name, pr.number
+        ))
+        .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
+        .border_style(theme::BORDER_FOCUSED);
+
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<10}", label), theme::DIM),
+            Span::raw(value),
+        ])
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(pr.title.clone(), theme::HEADER)),
+        Line::from(""),
+        field("Author", pr.author.clone()),
+        field(
+            "Assigned",
+            if pr.assignees.is_empty() {
+                "nobody".into()
+            } else {
+                pr.assignees.join(", ")
+            },
+        ),
+        field(
+            "Branch",
+            pr.head_ref.clone().unwrap_or_else(|| "unknown".into()),
+        ),
+        field(
+            "Base",
+            pr.base_ref.clone().unwrap_or_else(|| "unknown".into()),
+        ),
+        field("URL", pr.url.clone()),
+        Line::from(""),
+        Line::from(Span::styled("Press v or Esc to close", theme::DIM)),
+    ];
+
+    f.render_widget(Clear, modal_area);
+    let para = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(para, modal_area);
+}
+
 /// Help overlay: keybindings plus the State/CI glyph legends (accessibility — glyphs
 /// are otherwise undocumented). Independent of the per-PR `Overlay` state.
 pub fn render_help_overlay(f: &mut Frame, state: &AppState) {
+    let glyphs = state.theme().glyphs;
     if !state.help_open {
         return;
     }
 
     let area = f.area();
     let modal_width = 66u16.clamp(40, area.width.saturating_sub(4));
-    let modal_height = 18u16.min(area.height.saturating_sub(2));
+    let modal_height = 46u16.min(area.height.saturating_sub(2));
     let x = (area.width.saturating_sub(modal_width)) / 2;
     let y = (area.height.saturating_sub(modal_height)) / 2;
     let modal_area = Rect {
@@ -668,6 +1794,7 @@ pub fn render_help_overlay(f: &mut Frame, state: &AppState) {
     let block = Block::default()
         .title(" Help ")
         .borders(Borders::ALL)
+        .border_set(state.theme().glyphs.border)
         .border_style(theme::BORDER_FOCUSED);
 
     let key = |k: &'static str, desc: &'static str| {
@@ -680,34 +1807,79 @@ pub fn render_help_overlay(f: &mut Frame, state: &AppState) {
     let lines = vec![
         Line::from(Span::styled("Keys", theme::HEADER)),
         key("j / k", "move up / down (scroll in diff)"),
+        key("Ctrl-j / k", "next / previous org or user overview"),
         key("Enter", "select / expand"),
+        key("e", "hide / show repos without open PRs in the tree"),
         key("l", "git-log overlay (content pane)"),
         key("d", "diff overlay (content pane)"),
         key("f", "cycle merge filter: all -> conflicting -> clean"),
+        key("F", "cycle review filter (per view; Esc clears)"),
+        key("I", "All PRs: only PRs involving me (again: all)"),
+        key("n / N", "next / previous PR with changes requested"),
+        key("Alt-1..4", "filter to an age bucket (again: clear)"),
+        key("s", "toggle sort: smallest diff first"),
+        key("i", "inbox order: newest -> least recently updated -> age"),
+        key("m", "repo view: open -> merged -> closed PRs"),
+        key("< / >", "scroll table columns (also Shift-Left/Right)"),
+        key("a", "filter to highlighted PR's author (again: clear)"),
+        key("w", "watch / unwatch the highlighted PR"),
+        key("t", "show the highlighted PR's full title"),
+        key("Ctrl-y", "copy PR as a Markdown link (Alt-y: whole list)"),
+        key("v", "full title, assignees, branch and URL of the PR"),
+        key("L", "lock: pause auto-refresh, freeze order"),
+        key("Ctrl-p", "pause / resume auto-refresh"),
+        key("g / G", "first / last row (5j, 12G, 3 Enter: counts)"),
         key("/", "search    r  refresh    o  open in browser"),
+        key("R", "force-refresh the current view only"),
+        key("u", "force-refresh just the inbox"),
+        key("O", "open menu: repo pulls, issues, actions, ..."),
+        key("U (inbox)", "open every inbox PR updated since last seen"),
+        key("b", "recently viewed: jump back to an org, repo or list"),
+        key("T", "filter by label (again: add another; Esc clears)"),
+        key("Ctrl-r", "reload the config file"),
+        key("X", "token rejected: resolve a new one and refresh"),
         key("Tab", "switch pane    h / Esc  back / close    q  quit"),
         Line::from(""),
         Line::from(Span::styled("State column", theme::HEADER)),
         Line::from(vec![
-            Span::styled("  ✓ ok", theme::MERGE_CLEAN),
+            Span::styled(format!("  {}", glyphs.mergeable), theme::MERGE_CLEAN),
             Span::raw(" mergeable   "),
-            Span::styled("✗ cf", theme::MERGE_CONFLICT),
+            Span::styled(glyphs.conflicting, theme::MERGE_CONFLICT),
             Span::raw(" conflicting   "),
             Span::styled("?", theme::DIM),
             Span::raw(" unknown (not yet computed)"),
         ]),
+        Line::from(vec![
+            Span::styled(format!("  {}", glyphs.behind), theme::WARNING),
+            Span::raw(" behind base (update branch)   "),
+            Span::styled(glyphs.merged, theme::MERGED),
+            Span::raw(" merged   "),
+            Span::styled(glyphs.closed, theme::DIM),
+            Span::raw(" closed"),
+        ]),
         Line::from(Span::styled("CI column", theme::HEADER)),
         Line::from(vec![
-            Span::styled("  ✓", theme::MERGE_CLEAN),
+            Span::styled(format!("  {}", glyphs.ci_passing), theme::MERGE_CLEAN),
             Span::raw(" passing   "),
-            Span::styled("✗", theme::MERGE_CONFLICT),
+            Span::styled(glyphs.ci_failing, theme::MERGE_CONFLICT),
             Span::raw(" failing   "),
-            Span::styled("…", theme::WARNING),
+            Span::styled(glyphs.ci_pending, theme::WARNING),
             Span::raw(" pending   "),
-            Span::styled("·", theme::DIM),
+            Span::styled(glyphs.ci_none, theme::DIM),
             Span::raw(" no checks"),
         ]),
         Line::from(""),
+        Line::from(Span::styled(
+            truncate_with_ellipsis(
+                &match &state.config_path {
+                    Some(path) => format!("Config: {}", path.display()),
+                    None => "Config: defaults (no config file found)".to_string(),
+                },
+                modal_width.saturating_sub(2) as usize,
+                glyphs.ellipsis,
+            ),
+            theme::DIM,
+        )),
         Line::from(Span::styled("Press ? or Esc to close", theme::DIM)),
     ];
 
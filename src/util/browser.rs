@@ -1,9 +1,15 @@
 use anyhow::Result;
 use tracing::debug;
 
+use crate::util::paths::shell_path;
+
 /// Open a URL in the user's default browser.
 pub fn open_url(url: &str) -> Result<()> {
     debug!(url = url, "Opening URL in browser");
-    open::that(url)?;
+    if cfg!(windows) {
+        open::that(shell_path(url))?;
+    } else {
+        open::that(url)?;
+    }
     Ok(())
 }
@@ -0,0 +1,17 @@
+use std::io::Write;
+
+use anyhow::Result;
+use base64::Engine;
+use tracing::debug;
+
+/// Put `text` on the system clipboard with an OSC 52 escape sequence. The
+/// terminal does the copying, so this also works over SSH, but only in
+/// terminals that support (and allow) OSC 52.
+pub fn copy(text: &str) -> Result<()> {
+    debug!(bytes = text.len(), "Copying to clipboard");
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()?;
+    Ok(())
+}
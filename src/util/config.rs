@@ -1,10 +1,29 @@
-use anyhow::{Context, Result};
-use directories::ProjectDirs;
+use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::github::auth::{AuthMethod, default_auth_order};
+use crate::github::graphql::request_headers;
+use crate::util::paths;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
+    /// The file this config was read from; `None` when running on defaults.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+    /// Problems found while loading that don't stop it. Logged by
+    /// [`Self::log_warnings`] once logging is set up, which needs the config.
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+    /// [`CacheConfig::dir`], else the platform default; resolved on load.
+    #[serde(skip)]
+    cache_dir: PathBuf,
+    /// Where state kept between runs lives: session, watchlist, history,
+    /// logs. Resolved on load.
+    #[serde(skip)]
+    data_dir: PathBuf,
     #[serde(default)]
     pub github: GithubConfig,
     #[serde(default)]
@@ -21,26 +40,174 @@ pub struct GithubConfig {
     pub orgs: Vec<String>,
     #[serde(default)]
     pub users: Vec<String>,
+    /// Orgs and users alike; each one's type is looked up on first use.
+    /// Entries also in `orgs` or `users` are fetched as listed there.
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Single repos (`owner/name`) to follow without listing their owner.
+    #[serde(default)]
+    pub repos: Vec<String>,
     #[serde(default)]
     pub include_repos: Vec<String>,
     #[serde(default)]
     pub exclude_repos: Vec<String>,
+    /// Repos (globs, like `exclude_repos`) whose PRs are kept out of the
+    /// inbox and All PRs. Unlike `exclude_repos`, they stay in the nav tree.
+    #[serde(default)]
+    pub mute_repos: Vec<String>,
+    /// Only include repos tagged with at least one of these topics.
+    #[serde(default)]
+    pub include_topics: Vec<String>,
+    /// Drop repos tagged with any of these topics.
+    #[serde(default)]
+    pub exclude_topics: Vec<String>,
+    /// Teams (`org/team-slug`) whose review requests also land in the inbox.
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// Token sources to try, in order: `env`, `gh`, `keyring`.
+    #[serde(default = "default_auth_order")]
+    pub auth_order: Vec<AuthMethod>,
     #[serde(default = "default_api_url")]
     pub api_url: String,
+    /// `User-Agent` sent with every API request.
+    #[serde(default = "default_user_agent")]
+    pub user_agent: String,
+    /// Extra headers sent with every API request, e.g. for Enterprise gateways
+    /// that route or authorize on custom headers. A value of `env:VAR_NAME`
+    /// is read from that environment variable.
+    #[serde(default)]
+    pub extra_headers: HashMap<String, String>,
+    /// PEM bundle of extra root certificates to trust, e.g. a corporate
+    /// proxy's CA.
+    #[serde(default)]
+    pub ca_bundle: Option<PathBuf>,
+    /// PEM client certificate for mTLS gateways. Holds the private key too
+    /// unless `client_key` is set.
+    #[serde(default)]
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key for `client_cert`.
+    #[serde(default)]
+    pub client_key: Option<PathBuf>,
+    /// Skip TLS certificate verification entirely. Insecure; a last resort
+    /// when `ca_bundle` can't be made to work.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+    /// Nav tint per org, from `[[github.org_color]]` entries.
+    #[serde(default)]
+    pub org_color: Vec<OrgColor>,
+    /// Fetch every org's repo list in one aliased GraphQL request instead of
+    /// one request per org.
+    #[serde(default)]
+    pub batch_queries: bool,
+    /// GitHub App credentials, used when `auth_order` includes `app`.
+    #[serde(default)]
+    pub app: Option<GithubAppConfig>,
+}
+
+/// `[github.app]`: authenticate as a GitHub App installation. Each field can
+/// instead come from `GHDASH_APP_ID`, `GHDASH_APP_INSTALLATION_ID` and
+/// `GHDASH_APP_PRIVATE_KEY_PATH`, which take precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GithubAppConfig {
+    pub app_id: Option<u64>,
+    pub installation_id: Option<u64>,
+    /// PEM private key downloaded from the App's settings page.
+    pub private_key_path: Option<PathBuf>,
+}
+
+/// One `[[github.org_color]]` entry. `color` is a name (`red`, `lightblue`),
+/// `#rrggbb` or a 0-255 palette index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgColor {
+    pub org: String,
+    pub color: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DashboardConfig {
     #[serde(default = "default_refresh_interval")]
     pub refresh_interval_secs: u64,
+    /// Per-source auto-refresh intervals; each falls back to
+    /// `refresh_interval_secs` when unset.
+    #[serde(default)]
+    pub inbox_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub all_prs_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub history_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub repos_interval_secs: Option<u64>,
     #[serde(default = "default_true")]
     pub show_draft_prs: bool,
+    /// How many days back the "Recently merged" view reaches.
+    #[serde(default = "default_merged_window_days")]
+    pub merged_window_days: u32,
+    /// How many days back the "Recently closed" view reaches.
+    #[serde(default = "default_closed_window_days")]
+    pub closed_window_days: u32,
+    /// How many API requests may be in flight at once; the rest wait their turn.
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Refocusing the terminal refreshes only if the last refresh is at least
+    /// this old, so window-manager focus churn can't burn the rate limit.
+    #[serde(default = "default_focus_refresh_debounce")]
+    pub focus_refresh_debounce_secs: u64,
+    /// Auto-refresh waits until there has been no input for this long, so
+    /// lists aren't replaced mid-scroll. 0 disables the wait.
+    #[serde(default = "default_idle_refresh_delay")]
+    pub idle_refresh_delay_secs: u64,
+    /// Most inbox PRs `U` opens at once without asking first.
+    #[serde(default = "default_open_unread_limit")]
+    pub open_unread_limit: usize,
+    /// Initial order of the inbox; cycled at runtime with `i`.
+    #[serde(default)]
+    pub inbox_sort: InboxSort,
+    /// Look for a newer ghdash release once a day at startup. Off by default;
+    /// the check only contacts github.com, with nothing identifying the user.
+    #[serde(default)]
+    pub check_updates: bool,
+}
+
+/// Order of the inbox (`dashboard.inbox_sort`), independent of other views.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InboxSort {
+    /// Most recently updated first.
+    #[default]
+    Newest,
+    /// Least recently updated first: the most stale to act on first.
+    Oldest,
+    /// Longest open first, by creation time.
+    Age,
+}
+
+impl InboxSort {
+    pub fn next(self) -> Self {
+        match self {
+            InboxSort::Newest => InboxSort::Oldest,
+            InboxSort::Oldest => InboxSort::Age,
+            InboxSort::Age => InboxSort::Newest,
+        }
+    }
+
+    /// Short label for the table title; `None` for the default order.
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            InboxSort::Newest => None,
+            InboxSort::Oldest => Some("least recently updated"),
+            InboxSort::Age => Some("oldest opened"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheConfig {
     #[serde(default = "default_cache_ttl")]
     pub ttl_secs: u64,
+    /// How long an owner whose repo list came back empty or refused is
+    /// skipped by refreshes.
+    #[serde(default = "default_negative_ttl")]
+    pub negative_ttl_secs: u64,
     #[serde(default)]
     pub dir: Option<PathBuf>,
 }
@@ -49,20 +216,183 @@ pub struct CacheConfig {
 pub struct UiConfig {
     #[serde(default = "default_nav_width")]
     pub nav_width_percent: u16,
+    /// Group repos under each org by their first topic in the nav tree.
+    #[serde(default)]
+    pub group_by_topic: bool,
+    /// Render `:rocket:`-style shortcodes in PR titles as emoji.
+    #[serde(default)]
+    pub expand_shortcodes: bool,
+    /// Enable `'<letter>` jumps to the next org/repo starting with that letter
+    /// in the nav pane.
+    #[serde(default)]
+    pub letter_jump: bool,
+    /// Which PR table columns to show, in order.
+    #[serde(default = "default_columns")]
+    pub columns: Vec<PrColumn>,
+    /// Which columns to keep when the table is too narrow for all of them,
+    /// most important first. Unlisted columns are dropped first; Title always
+    /// stays.
+    #[serde(default = "default_column_priority")]
+    pub column_priority: Vec<PrColumn>,
+    /// Upper bounds (changed lines, inclusive) for the XS, S, M and L size
+    /// badges; anything larger is XL.
+    #[serde(default = "default_size_thresholds")]
+    pub size_thresholds: [u32; 4],
+    /// Upper bounds, in days, of the first three buckets in the age summary
+    /// above the PR table.
+    #[serde(default = "default_age_buckets")]
+    pub age_buckets: [u32; 3],
+    /// Group the All PRs table by repository, with a header row per repo.
+    #[serde(default)]
+    pub group_all_prs_by_repo: bool,
+    /// Tiebreaker for PRs the current sort ranks equal, so the order is the
+    /// same on every refresh.
+    #[serde(default = "default_secondary_sort")]
+    pub secondary_sort: SecondarySort,
+    /// Show only the N repos with the most open PRs under each org in the nav.
+    #[serde(default)]
+    pub max_repos_per_org: Option<usize>,
+    /// Leave repos without open PRs out of the nav tree (`e` toggles). Org
+    /// overviews and repo counts still include them.
+    #[serde(default = "default_true")]
+    pub hide_empty_repos: bool,
+    /// Rows kept visible above and below the selected PR when the table
+    /// scrolls, like vim's `scrolloff`.
+    #[serde(default)]
+    pub scrolloff: usize,
+    /// For screen readers and slow links: no once-a-second countdown
+    /// redraws, plain "Loading" text, and text markers wherever color alone
+    /// carries meaning.
+    #[serde(default)]
+    pub reduce_motion: bool,
+    /// Force ASCII glyphs (`true`) or Unicode ones (`false`) instead of going
+    /// by `TERM` and the locale.
+    #[serde(default)]
+    pub ascii: Option<bool>,
+}
+
+/// Tiebreaker applied within the PR sort (`ui.secondary_sort`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecondarySort {
+    /// Keep the order GitHub returned.
+    None,
+    /// PR number, ascending.
+    Number,
+    /// `owner/name`, alphabetically.
+    Repo,
+    Title,
+    Author,
+}
+
+pub fn default_secondary_sort() -> SecondarySort {
+    SecondarySort::Number
+}
+
+/// A column of the PR table. Unknown names in `ui.columns` fail config load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrColumn {
+    Number,
+    /// Merge state (`mergeable`).
+    State,
+    /// CI check rollup.
+    Checks,
+    Title,
+    Author,
+    Repo,
+    /// Last update, or merge/close time in the history views.
+    Updated,
+    /// `+additions -deletions`.
+    Diffstat,
+    /// XS/S/M/L/XL badge from `size_thresholds`.
+    Size,
+    /// Comment + review count.
+    Comments,
+    /// Source branch.
+    Branch,
+    /// Target branch.
+    Base,
+    /// Assigned logins.
+    Assignees,
+}
+
+pub fn default_column_priority() -> Vec<PrColumn> {
+    vec![
+        PrColumn::Title,
+        PrColumn::Number,
+        PrColumn::State,
+        PrColumn::Checks,
+        PrColumn::Repo,
+        PrColumn::Size,
+        PrColumn::Diffstat,
+        PrColumn::Base,
+        PrColumn::Branch,
+        PrColumn::Author,
+        PrColumn::Assignees,
+        PrColumn::Updated,
+        PrColumn::Comments,
+    ]
+}
+
+pub fn default_age_buckets() -> [u32; 3] {
+    [1, 3, 7]
+}
+
+pub fn default_size_thresholds() -> [u32; 4] {
+    [10, 100, 500, 1000]
+}
+
+pub fn default_columns() -> Vec<PrColumn> {
+    vec![
+        PrColumn::Number,
+        PrColumn::State,
+        PrColumn::Checks,
+        PrColumn::Size,
+        PrColumn::Title,
+        PrColumn::Comments,
+        PrColumn::Author,
+        PrColumn::Repo,
+        PrColumn::Updated,
+    ]
 }
 
 fn default_api_url() -> String {
     "https://api.github.com/graphql".to_string()
 }
+fn default_user_agent() -> String {
+    "ghdash".to_string()
+}
 fn default_refresh_interval() -> u64 {
     300
 }
+fn default_merged_window_days() -> u32 {
+    7
+}
+fn default_closed_window_days() -> u32 {
+    7
+}
+fn default_max_concurrency() -> usize {
+    4
+}
+fn default_focus_refresh_debounce() -> u64 {
+    60
+}
+fn default_idle_refresh_delay() -> u64 {
+    10
+}
+fn default_open_unread_limit() -> usize {
+    10
+}
 fn default_true() -> bool {
     true
 }
 fn default_cache_ttl() -> u64 {
     600
 }
+fn default_negative_ttl() -> u64 {
+    crate::cache::store::DEFAULT_NEGATIVE_TTL_SECS
+}
 fn default_nav_width() -> u16 {
     30
 }
@@ -72,18 +402,243 @@ impl Default for GithubConfig {
         Self {
             orgs: Vec::new(),
             users: Vec::new(),
+            owners: Vec::new(),
+            repos: Vec::new(),
             include_repos: Vec::new(),
             exclude_repos: Vec::new(),
+            mute_repos: Vec::new(),
+            include_topics: Vec::new(),
+            exclude_topics: Vec::new(),
+            teams: Vec::new(),
+            auth_order: default_auth_order(),
             api_url: default_api_url(),
+            user_agent: default_user_agent(),
+            extra_headers: HashMap::new(),
+            ca_bundle: None,
+            client_cert: None,
+            client_key: None,
+            danger_accept_invalid_certs: false,
+            org_color: Vec::new(),
+            batch_queries: false,
+            app: None,
+        }
+    }
+}
+
+/// What kind of account an owner is, which decides how its repos are fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnerKind {
+    Org,
+    User,
+    /// Listed in `owners`, or under both `orgs` and `users`: the type is
+    /// asked of GitHub before the repos are fetched.
+    Unknown,
+}
+
+/// One configured owner, however many times it's listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Owner {
+    pub login: String,
+    pub kind: OwnerKind,
+}
+
+impl GithubConfig {
+    /// Every owner shown in the nav, once each: orgs first, then users, then
+    /// `owners`. A login in both `orgs` and `users` keeps its place among the
+    /// orgs.
+    pub fn owners(&self) -> Vec<Owner> {
+        let orgs = self.orgs.iter().map(|login| Owner {
+            login: login.clone(),
+            kind: if self.is_user(login) {
+                OwnerKind::Unknown
+            } else {
+                OwnerKind::Org
+            },
+        });
+        let users = self
+            .users
+            .iter()
+            .filter(|login| !self.is_org(login))
+            .map(|login| Owner {
+                login: login.clone(),
+                kind: OwnerKind::User,
+            });
+        let untyped = self
+            .owners
+            .iter()
+            .filter(|login| !self.is_org(login) && !self.is_user(login))
+            .map(|login| Owner {
+                login: login.clone(),
+                kind: OwnerKind::Unknown,
+            });
+        orgs.chain(users).chain(untyped).collect()
+    }
+
+    /// Owners for cross-owner searches, as `(orgs, users)`: owners known to
+    /// be orgs, and everyone else, searched with `user:`, which matches
+    /// organizations too.
+    pub fn search_scope(&self) -> (Vec<String>, Vec<String>) {
+        let (orgs, others): (Vec<Owner>, Vec<Owner>) = self
+            .owners()
+            .into_iter()
+            .partition(|o| o.kind == OwnerKind::Org);
+        (
+            orgs.into_iter().map(|o| o.login).collect(),
+            others.into_iter().map(|o| o.login).collect(),
+        )
+    }
+
+    /// Logins of [`Self::owners`], in the same order.
+    pub fn owner_logins(&self) -> Vec<String> {
+        self.owners().into_iter().map(|o| o.login).collect()
+    }
+
+    /// How `login` is fetched, if it's a configured owner.
+    pub fn owner_kind(&self, login: &str) -> Option<OwnerKind> {
+        self.owners()
+            .into_iter()
+            .find(|o| o.login.eq_ignore_ascii_case(login))
+            .map(|o| o.kind)
+    }
+
+    /// Entries of `repos` whose owner isn't configured anyway.
+    pub fn standalone_repos(&self) -> Vec<String> {
+        let owners = self.owner_logins();
+        self.repos
+            .iter()
+            .filter(|full_name| {
+                let owner = full_name.split_once('/').map_or("", |(owner, _)| owner);
+                !owners.iter().any(|o| o.eq_ignore_ascii_case(owner))
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn is_org(&self, login: &str) -> bool {
+        self.orgs.iter().any(|o| o.eq_ignore_ascii_case(login))
+    }
+
+    fn is_user(&self, login: &str) -> bool {
+        self.users.iter().any(|u| u.eq_ignore_ascii_case(login))
+    }
+
+    /// Check every org and user login and repo, then drop repeats within each
+    /// list (GitHub names are case-insensitive), keeping the first spelling. A
+    /// login listed as both an org and a user stays in both, and
+    /// [`Self::owners`] reports it once, as [`OwnerKind::Unknown`]. Returns
+    /// warnings for both.
+    fn validate_owners(&mut self) -> Result<Vec<String>> {
+        for org in &self.orgs {
+            if let Err(reason) = validate_login(org) {
+                bail!("Invalid login {:?} in github.orgs: {}", org, reason);
+            }
         }
+        for (list, logins) in [("users", &self.users), ("owners", &self.owners)] {
+            for user in logins {
+                // Enterprise Managed Users are `login_shortcode`.
+                let plain = user
+                    .split_once('_')
+                    .map_or(user.as_str(), |(login, _)| login);
+                if let Err(reason) = validate_login(plain) {
+                    bail!("Invalid login {:?} in github.{}: {}", user, list, reason);
+                }
+            }
+        }
+        for repo in &self.repos {
+            if let Err(reason) = validate_repo(repo) {
+                bail!("Invalid repo {:?} in github.repos: {}", repo, reason);
+            }
+        }
+        let mut warnings: Vec<String> = self
+            .users
+            .iter()
+            .filter(|u| self.is_org(u))
+            .map(|both| {
+                format!(
+                    "Owner {both} listed in both github.orgs and github.users; fetching it once, as an org if it is one"
+                )
+            })
+            .collect();
+        for (kind, noun, list) in [
+            ("orgs", "owner", &mut self.orgs),
+            ("users", "owner", &mut self.users),
+            ("owners", "owner", &mut self.owners),
+            ("repos", "repo", &mut self.repos),
+        ] {
+            let mut seen = HashSet::new();
+            list.retain(|entry| {
+                let first = seen.insert(entry.to_lowercase());
+                if !first {
+                    warnings.push(format!(
+                        "Ignoring duplicate {noun} {entry} in github.{kind}"
+                    ));
+                }
+                first
+            });
+        }
+        Ok(warnings)
+    }
+}
+
+/// Why `full_name` isn't an `owner/name` repo, if it isn't: the owner must
+/// be a valid login, the name letters, digits, `.`, `-` and `_`.
+pub fn validate_repo(full_name: &str) -> std::result::Result<(), &'static str> {
+    let Some((owner, name)) = full_name.split_once('/') else {
+        return Err("expected owner/name");
+    };
+    validate_login(owner)?;
+    if name.is_empty() || name == "." || name == ".." {
+        return Err("the repo name is missing");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    {
+        return Err("repo names are letters, digits, '.', '-' and '_'");
+    }
+    Ok(())
+}
+
+/// Why `login` can't be a GitHub user or organization name, if it can't:
+/// letters, digits and single hyphens, not at either end, at most 39
+/// characters.
+pub fn validate_login(login: &str) -> std::result::Result<(), &'static str> {
+    if login.is_empty() {
+        return Err("it is empty");
+    }
+    if login.chars().count() > 39 {
+        return Err("logins are at most 39 characters");
+    }
+    if !login.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err("only letters, digits and hyphens are allowed");
+    }
+    if login.starts_with('-') || login.ends_with('-') {
+        return Err("it can't start or end with a hyphen");
+    }
+    if login.contains("--") {
+        return Err("hyphens can't be doubled");
     }
+    Ok(())
 }
 
 impl Default for DashboardConfig {
     fn default() -> Self {
         Self {
             refresh_interval_secs: default_refresh_interval(),
+            inbox_interval_secs: None,
+            all_prs_interval_secs: None,
+            history_interval_secs: None,
+            repos_interval_secs: None,
             show_draft_prs: true,
+            merged_window_days: default_merged_window_days(),
+            closed_window_days: default_closed_window_days(),
+            max_concurrency: default_max_concurrency(),
+            focus_refresh_debounce_secs: default_focus_refresh_debounce(),
+            idle_refresh_delay_secs: default_idle_refresh_delay(),
+            open_unread_limit: default_open_unread_limit(),
+            inbox_sort: InboxSort::default(),
+            check_updates: false,
         }
     }
 }
@@ -92,6 +647,7 @@ impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             ttl_secs: default_cache_ttl(),
+            negative_ttl_secs: default_negative_ttl(),
             dir: None,
         }
     }
@@ -101,63 +657,351 @@ impl Default for UiConfig {
     fn default() -> Self {
         Self {
             nav_width_percent: default_nav_width(),
+            group_by_topic: false,
+            expand_shortcodes: false,
+            letter_jump: false,
+            columns: default_columns(),
+            column_priority: default_column_priority(),
+            size_thresholds: default_size_thresholds(),
+            age_buckets: default_age_buckets(),
+            group_all_prs_by_repo: false,
+            secondary_sort: default_secondary_sort(),
+            max_repos_per_org: None,
+            scrolloff: 0,
+            reduce_motion: false,
+            ascii: None,
+            hide_empty_repos: true,
         }
     }
 }
 
 impl AppConfig {
+    /// Load the config from `path`, else `$GHDASH_CONFIG`, else the first
+    /// existing file among [`paths::config_candidates`], else defaults. Then
+    /// apply `GHDASH_<SECTION>__<KEY>` overrides from the environment.
     pub fn load(path: Option<&Path>) -> Result<Self> {
+        let overrides: Vec<(String, String)> = std::env::vars()
+            .filter(|(name, _)| is_override_var(name))
+            .collect();
+        Self::load_with(
+            path,
+            &paths::system_env,
+            paths::platform_config_dir(),
+            &overrides,
+        )
+    }
+
+    /// [`AppConfig::load`] with the environment, platform config directory
+    /// and override variables supplied by the caller.
+    pub fn load_with(
+        path: Option<&Path>,
+        lookup: paths::EnvLookup,
+        platform: Option<PathBuf>,
+        overrides: &[(String, String)],
+    ) -> Result<Self> {
         if let Some(path) = path {
-            let content = std::fs::read_to_string(path)
-                .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-            let config: AppConfig =
-                toml::from_str(&content).with_context(|| "Failed to parse config file")?;
-            return Ok(config);
+            return Self::load_file(path, overrides);
+        }
+        if let Some(path) = paths::config_override(lookup) {
+            if !path.is_file() {
+                bail!(
+                    "{} points at {}, which is not a file",
+                    paths::CONFIG_ENV,
+                    path.display()
+                );
+            }
+            return Self::load_file(&path, overrides);
         }
 
         // Search candidate paths in order
-        let mut candidates = Vec::new();
-
-        // 1. ~/.config/ghdash/config.toml (standard XDG on all platforms)
-        if let Some(home) = std::env::var_os("HOME") {
-            candidates.push(PathBuf::from(home).join(".config/ghdash/config.toml"));
+        for config_path in &paths::config_candidates(lookup, platform) {
+            if config_path.exists() {
+                return Self::load_file(config_path, overrides);
+            }
         }
 
-        // 2. Platform-specific path from `directories` crate
-        //    (macOS: ~/Library/Application Support/ghdash/)
-        if let Some(proj_dirs) = ProjectDirs::from("", "", "ghdash") {
-            candidates.push(proj_dirs.config_dir().join("config.toml"));
-        }
+        // Fallback to default
+        Self::parse("", overrides)
+    }
 
-        for config_path in &candidates {
-            if config_path.exists() {
-                let content = std::fs::read_to_string(config_path).with_context(|| {
-                    format!("Failed to read config file: {}", config_path.display())
-                })?;
-                let config: AppConfig =
-                    toml::from_str(&content).with_context(|| "Failed to parse config file")?;
-                return Ok(config);
+    fn load_file(path: &Path, overrides: &[(String, String)]) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let mut config = Self::parse(&content, overrides)?;
+        config.source = Some(path.to_path_buf());
+        Ok(config)
+    }
+
+    /// Parse config text, expanding `${VAR}` in every string value first,
+    /// then apply the environment `overrides`.
+    fn parse(content: &str, overrides: &[(String, String)]) -> Result<Self> {
+        let mut value: toml::Value =
+            toml::from_str(content).with_context(|| "Failed to parse config file")?;
+        interpolate_value(&mut value, &|name| std::env::var(name).ok())?;
+        let mut config: Self = value
+            .clone()
+            .try_into()
+            .with_context(|| "Failed to parse config file")?;
+        if !overrides.is_empty() {
+            let schema = toml::Value::try_from(AppConfig::default())?;
+            for (name, raw) in overrides {
+                apply_env_override(&mut value, &schema, name, raw)?;
+                config = value
+                    .clone()
+                    .try_into()
+                    .with_context(|| format!("Invalid value {:?} in {}", raw, name))?;
+                check_override_known(&config, name)?;
+            }
+        }
+        config.warnings = config.github.validate_owners()?;
+        if config.github.auth_order.contains(&AuthMethod::Stdin) {
+            bail!(
+                "github.auth_order can't include \"stdin\"; pipe a token in with --token-stdin instead"
+            );
+        }
+        for (name, bounds) in [
+            ("ui.size_thresholds", &config.ui.size_thresholds[..]),
+            ("ui.age_buckets", &config.ui.age_buckets[..]),
+        ] {
+            if !bounds.windows(2).all(|w| w[0] < w[1]) {
+                bail!("{} must be in ascending order, got {:?}", name, bounds);
             }
         }
+        request_headers(&config.github)?;
+        config.resolve_dirs();
+        Ok(config)
+    }
 
-        // Fallback to default
-        Ok(AppConfig::default())
+    /// Pick the cache and data directories, noting any fallback among the
+    /// [`warnings`](Self::warnings) so it is logged once rather than on
+    /// every path lookup.
+    fn resolve_dirs(&mut self) {
+        let (cache_dir, cache_warning) = match &self.cache.dir {
+            Some(dir) => (dir.clone(), None),
+            None => paths::default_cache_dir(),
+        };
+        let (data_dir, data_warning) = paths::default_data_dir();
+        self.cache_dir = cache_dir;
+        self.data_dir = data_dir;
+        self.warnings.extend(cache_warning);
+        self.warnings.extend(data_warning);
+    }
+
+    /// Log the [`warnings`](Self::warnings) found while loading.
+    pub fn log_warnings(&self) {
+        for warning in &self.warnings {
+            warn!("{}", warning);
+        }
     }
 
     pub fn cache_dir(&self) -> PathBuf {
-        if let Some(ref dir) = self.cache.dir {
-            return dir.clone();
+        self.cache_dir.clone()
+    }
+
+    /// Where per-PR "last seen" state is persisted between runs.
+    pub fn session_path(&self) -> PathBuf {
+        self.data_dir.join("session.json")
+    }
+
+    /// Where the watched PRs are persisted.
+    pub fn watchlist_path(&self) -> PathBuf {
+        self.data_dir.join("watched.json")
+    }
+
+    /// Where open PR counts per repo are recorded over time.
+    pub fn pr_history_path(&self) -> PathBuf {
+        self.data_dir.join("pr_history.json")
+    }
+
+    /// When the update check last ran.
+    pub fn update_check_path(&self) -> PathBuf {
+        self.data_dir.join("update_check.json")
+    }
+
+    pub fn log_dir(&self) -> PathBuf {
+        self.data_dir.join("logs")
+    }
+}
+
+/// Prefix of environment variables that override config keys, as in
+/// `GHDASH_GITHUB__ORGS` or `GHDASH_UI__REDUCE_MOTION`.
+pub const ENV_OVERRIDE_PREFIX: &str = "GHDASH_";
+
+/// Whether `name` is a `GHDASH_<SECTION>__<KEY>` override.
+fn is_override_var(name: &str) -> bool {
+    name.strip_prefix(ENV_OVERRIDE_PREFIX)
+        .is_some_and(|rest| rest.contains("__"))
+}
+
+/// The config key override variable `name` sets, lowercased, one segment
+/// per table.
+fn override_path(name: &str) -> Vec<String> {
+    name.strip_prefix(ENV_OVERRIDE_PREFIX)
+        .unwrap_or(name)
+        .split("__")
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Fail if override variable `name` set a key `config` doesn't have, which
+/// deserializing would otherwise drop without a word. Serialized as JSON so
+/// unset optional keys are still there, as nulls.
+fn check_override_known(config: &AppConfig, name: &str) -> Result<()> {
+    let path = override_path(name);
+    let mut known = Some(serde_json::to_value(config)?);
+    for segment in &path {
+        known = known.and_then(|v| v.get(segment).cloned());
+    }
+    if known.is_none() {
+        bail!("{}: unknown config key {}", name, path.join("."));
+    }
+    Ok(())
+}
+
+/// Set the key named by override variable `name` in `value` from `raw`.
+/// `__` separates the path (`GHDASH_GITHUB__APP__APP_ID` is `github.app.app_id`).
+/// The type comes from the key's default in `schema`: lists take
+/// comma-separated items, booleans `true`/`false`, numbers digits. Keys
+/// without a default (optional ones) take whatever `raw` parses as.
+pub fn apply_env_override(
+    value: &mut toml::Value,
+    schema: &toml::Value,
+    name: &str,
+    raw: &str,
+) -> Result<()> {
+    let path = override_path(name);
+    if path.len() < 2 || path.iter().any(String::is_empty) {
+        bail!("{}: expected GHDASH_<SECTION>__<KEY>", name);
+    }
+    if schema.get(&path[0]).is_none() {
+        bail!("{}: unknown config section [{}]", name, path[0]);
+    }
+
+    let mut expected = Some(schema);
+    for segment in &path {
+        expected = expected.and_then(|v| v.get(segment));
+    }
+    let parsed = match expected {
+        Some(toml::Value::Array(items)) => toml::Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| match items.first() {
+                    Some(first) => typed_value(first, item, name),
+                    None => Ok(toml::Value::String(item.to_string())),
+                })
+                .collect::<Result<_>>()?,
+        ),
+        Some(toml::Value::Table(_)) => {
+            bail!(
+                "{}: {} can't be set from the environment",
+                name,
+                path.join(".")
+            )
+        }
+        Some(default) => typed_value(default, raw, name)?,
+        None => inferred_value(raw),
+    };
+
+    let (key, tables) = path.split_last().expect("at least two segments");
+    let mut table = value;
+    for segment in tables {
+        let toml::Value::Table(map) = table else {
+            bail!("{}: {} is not a table", name, segment);
+        };
+        table = map
+            .entry(segment.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    }
+    let toml::Value::Table(map) = table else {
+        bail!("{}: {} is not a table", name, tables.join("."));
+    };
+    map.insert(key.clone(), parsed);
+    Ok(())
+}
+
+/// `raw` converted to the type of `like`.
+fn typed_value(like: &toml::Value, raw: &str, name: &str) -> Result<toml::Value> {
+    let raw = raw.trim();
+    Ok(match like {
+        toml::Value::Boolean(_) => match raw.to_lowercase().as_str() {
+            "true" => toml::Value::Boolean(true),
+            "false" => toml::Value::Boolean(false),
+            _ => bail!("{}: expected true or false, got {:?}", name, raw),
+        },
+        toml::Value::Integer(_) => toml::Value::Integer(
+            raw.parse()
+                .with_context(|| format!("{}: expected a whole number, got {:?}", name, raw))?,
+        ),
+        toml::Value::Float(_) => toml::Value::Float(
+            raw.parse()
+                .with_context(|| format!("{}: expected a number, got {:?}", name, raw))?,
+        ),
+        _ => toml::Value::String(raw.to_string()),
+    })
+}
+
+/// Best guess for a key with no default to go by.
+fn inferred_value(raw: &str) -> toml::Value {
+    let raw = raw.trim();
+    if let Ok(b) = raw.parse() {
+        toml::Value::Boolean(b)
+    } else if let Ok(n) = raw.parse() {
+        toml::Value::Integer(n)
+    } else {
+        toml::Value::String(raw.to_string())
+    }
+}
+
+fn interpolate_value(
+    value: &mut toml::Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<()> {
+    match value {
+        toml::Value::String(s) => *s = interpolate_env(s, lookup)?,
+        toml::Value::Array(items) => {
+            for item in items {
+                interpolate_value(item, lookup)?;
+            }
         }
-        if let Some(proj_dirs) = ProjectDirs::from("", "", "ghdash") {
-            return proj_dirs.cache_dir().to_path_buf();
+        toml::Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                interpolate_value(item, lookup)?;
+            }
         }
-        PathBuf::from(".cache/ghdash")
+        _ => {}
     }
+    Ok(())
+}
 
-    pub fn log_dir(&self) -> PathBuf {
-        if let Some(proj_dirs) = ProjectDirs::from("", "", "ghdash") {
-            return proj_dirs.data_dir().join("logs");
+/// Replace each `${VAR}` in `text` with `lookup(VAR)`. Undefined variables and
+/// unterminated `${` are errors; a `$` not followed by `{` is left alone, and
+/// `$${` stands for a literal `${`.
+pub fn interpolate_env(text: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if let Some(before) = rest[..start].strip_suffix('$') {
+            out.push_str(before);
+            out.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
         }
-        PathBuf::from(".local/share/ghdash/logs")
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .with_context(|| format!("Unterminated ${{ in config value: {}", text))?;
+        let name = &after[..end];
+        let value = lookup(name).with_context(|| {
+            format!(
+                "Config references undefined environment variable ${{{}}}",
+                name
+            )
+        })?;
+        out.push_str(&value);
+        rest = &after[end + 1..];
     }
+    out.push_str(rest);
+    Ok(out)
 }
@@ -0,0 +1,43 @@
+use crate::github::models::PullRequest;
+
+/// Escape text for use inside a Markdown link label or table cell. Every
+/// ASCII punctuation character, the set CommonMark lets a backslash escape, is
+/// escaped: pipes would end the cell, brackets the label, and `*`, `_`, `` ` ``
+/// or `<` would start emphasis, code or HTML.
+pub fn markdown_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_punctuation() {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `[repo#123: Title](url)`, ready to paste into chat.
+pub fn pr_markdown_link(pr: &PullRequest) -> String {
+    format!(
+        "[{}#{}: {}]({})",
+        pr.repo_name,
+        pr.number,
+        markdown_escape(&pr.title),
+        pr.url
+    )
+}
+
+/// The given PRs as a Markdown table, one row per PR.
+pub fn prs_markdown_table(prs: &[PullRequest]) -> String {
+    let mut out = String::from("| PR | Title | Author |\n| --- | --- | --- |\n");
+    for pr in prs {
+        out.push_str(&format!(
+            "| [{}#{}]({}) | {} | {} |\n",
+            pr.repo_name,
+            pr.number,
+            pr.url,
+            markdown_escape(&pr.title),
+            markdown_escape(&pr.author)
+        ));
+    }
+    out
+}
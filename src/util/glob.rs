@@ -0,0 +1,37 @@
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    // Simple glob matching: * matches any sequence
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let mut pos = 0;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        match text[pos..].find(part) {
+            Some(idx) => {
+                if i == 0 && idx != 0 {
+                    return false;
+                }
+                pos += idx + part.len();
+            }
+            None => return false,
+        }
+    }
+
+    // If the pattern doesn't end with *, the text must end at pos
+    if !pattern.ends_with('*') {
+        return pos == text.len();
+    }
+
+    true
+}
+
+/// Whether any of `patterns` matches the repo, by `owner/name` or bare name.
+pub fn repo_matches(patterns: &[String], full_name: &str, name: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| glob_match(pattern, full_name) || glob_match(pattern, name))
+}
@@ -1,3 +1,12 @@
 pub mod browser;
+pub mod clipboard;
 pub mod config;
+pub mod export;
+pub mod glob;
+pub mod paths;
+pub mod pr_diff;
+pub mod pr_history;
+pub mod secret;
 pub mod time;
+pub mod update_check;
+pub mod watchlist;
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+
+/// Reads an environment variable; [`system_env`] in the app, a fixed map in
+/// tests.
+pub type EnvLookup<'a> = &'a dyn Fn(&str) -> Option<OsString>;
+
+/// The real process environment.
+pub fn system_env(name: &str) -> Option<OsString> {
+    std::env::var_os(name)
+}
+
+fn var(lookup: EnvLookup, name: &str) -> Option<PathBuf> {
+    lookup(name).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "ghdash")
+}
+
+/// The user's home directory: `$HOME`, or `%USERPROFILE%` on Windows where
+/// most shells don't set `HOME`.
+pub fn home_dir(lookup: EnvLookup) -> Option<PathBuf> {
+    var(lookup, "HOME").or_else(|| {
+        if cfg!(windows) {
+            var(lookup, "USERPROFILE")
+        } else {
+            None
+        }
+    })
+}
+
+/// Environment variable naming the exact config file to use.
+pub const CONFIG_ENV: &str = "GHDASH_CONFIG";
+
+/// The config file named by `$GHDASH_CONFIG`, if set.
+pub fn config_override(lookup: EnvLookup) -> Option<PathBuf> {
+    var(lookup, CONFIG_ENV)
+}
+
+/// Config files to try, in order: `$XDG_CONFIG_HOME/ghdash/config.toml`,
+/// `~/.config/ghdash/config.toml` on every platform,
+/// `%APPDATA%\ghdash\config.toml` on Windows, then the platform config
+/// directory (`platform`, from `directories`).
+pub fn config_candidates(lookup: EnvLookup, platform: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(xdg) = var(lookup, "XDG_CONFIG_HOME") {
+        candidates.push(xdg.join("ghdash").join("config.toml"));
+    }
+    if let Some(home) = home_dir(lookup) {
+        candidates.push(home.join(".config").join("ghdash").join("config.toml"));
+    }
+    if cfg!(windows)
+        && let Some(appdata) = var(lookup, "APPDATA")
+    {
+        candidates.push(appdata.join("ghdash").join("config.toml"));
+    }
+    if let Some(dir) = platform {
+        candidates.push(dir.join("config.toml"));
+    }
+    let mut seen = HashSet::new();
+    candidates.retain(|c| seen.insert(c.clone()));
+    candidates
+}
+
+/// The platform config directory from `directories`, e.g.
+/// `~/Library/Application Support/ghdash` on macOS.
+pub fn platform_config_dir() -> Option<PathBuf> {
+    project_dirs().map(|d| d.config_dir().to_path_buf())
+}
+
+/// The cache directory: the platform one if known, else
+/// `%LOCALAPPDATA%\ghdash\cache` on Windows, else `~/.cache/ghdash`. With
+/// the warning to log when it had to fall back.
+pub fn cache_dir(lookup: EnvLookup, platform: Option<PathBuf>) -> (PathBuf, Option<String>) {
+    match platform.or_else(|| windows_dir(lookup, "LOCALAPPDATA", "cache")) {
+        Some(dir) => (dir, None),
+        None => with_warning(fallback_dir(".cache/ghdash", home_dir(lookup).as_deref())),
+    }
+}
+
+/// [`cache_dir`] for this machine.
+pub fn default_cache_dir() -> (PathBuf, Option<String>) {
+    cache_dir(
+        &system_env,
+        project_dirs().map(|d| d.cache_dir().to_path_buf()),
+    )
+}
+
+/// Where state kept between runs lives (session, watchlist, history, logs):
+/// the platform data directory if known, else `%APPDATA%\ghdash\data` on
+/// Windows, else `~/.local/share/ghdash`. With the warning to log when it had
+/// to fall back.
+pub fn data_dir(lookup: EnvLookup, platform: Option<PathBuf>) -> (PathBuf, Option<String>) {
+    match platform.or_else(|| windows_dir(lookup, "APPDATA", "data")) {
+        Some(dir) => (dir, None),
+        None => with_warning(fallback_dir(
+            ".local/share/ghdash",
+            home_dir(lookup).as_deref(),
+        )),
+    }
+}
+
+/// [`data_dir`] for this machine.
+pub fn default_data_dir() -> (PathBuf, Option<String>) {
+    data_dir(
+        &system_env,
+        project_dirs().map(|d| d.data_dir().to_path_buf()),
+    )
+}
+
+fn windows_dir(lookup: EnvLookup, base_var: &str, leaf: &str) -> Option<PathBuf> {
+    if !cfg!(windows) {
+        return None;
+    }
+    var(lookup, base_var).map(|base| base.join("ghdash").join(leaf))
+}
+
+fn with_warning((dir, warning): (PathBuf, String)) -> (PathBuf, Option<String>) {
+    (dir, Some(warning))
+}
+
+/// Directory to use when the platform's standard directories can't be
+/// determined: `relative` under `home` when it's known, else `relative` to
+/// the working directory as a last resort. With the warning to log about it.
+pub fn fallback_dir(relative: &str, home: Option<&Path>) -> (PathBuf, String) {
+    match home.filter(|h| !h.as_os_str().is_empty()) {
+        Some(home) => {
+            let dir = home.join(relative);
+            let warning = format!(
+                "No platform directories found; using {} under the home directory",
+                dir.display()
+            );
+            (dir, warning)
+        }
+        None => (
+            PathBuf::from(relative),
+            format!(
+                "No platform directories or home directory found; using {} relative to the working directory",
+                relative
+            ),
+        ),
+    }
+}
+
+/// `target` in a form the Windows shell accepts: verbatim paths
+/// (`\\?\C:\…`, `\\?\UNC\server\share\…`), which `canonicalize` produces for
+/// long paths, are turned back into ordinary drive and UNC paths. URLs and
+/// other paths are returned unchanged.
+pub fn shell_path(target: &str) -> String {
+    if let Some(rest) = target.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = target.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        target.to_string()
+    }
+}
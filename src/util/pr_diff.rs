@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::github::models::{PullRequest, Repo};
+
+/// How one list differs from an earlier copy of itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListDiff {
+    /// Only in the new list.
+    pub added: usize,
+    /// Only in the old list.
+    pub removed: usize,
+    /// In both, but changed.
+    pub updated: usize,
+}
+
+/// Compare PR lists by URL. A PR in both counts as updated when its
+/// `updated_at` moved.
+pub fn diff_prs(old: &[PullRequest], new: &[PullRequest]) -> ListDiff {
+    diff_by(
+        old,
+        new,
+        |pr| pr.url.clone(),
+        |a, b| a.updated_at != b.updated_at,
+    )
+}
+
+/// Compare repo lists by `owner/name`. A repo in both counts as updated when
+/// its open PR count, description, topics or archived flag changed.
+pub fn diff_repos(old: &[Repo], new: &[Repo]) -> ListDiff {
+    diff_by(old, new, Repo::full_name, |a, b| {
+        a.open_pr_count != b.open_pr_count
+            || a.description != b.description
+            || a.topics != b.topics
+            || a.is_archived != b.is_archived
+    })
+}
+
+fn diff_by<T>(
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> String,
+    changed: impl Fn(&T, &T) -> bool,
+) -> ListDiff {
+    let before: HashMap<String, &T> = old.iter().map(|item| (key(item), item)).collect();
+    let mut diff = ListDiff::default();
+    let mut kept = 0;
+    for item in new {
+        match before.get(&key(item)) {
+            Some(previous) => {
+                kept += 1;
+                if changed(previous, item) {
+                    diff.updated += 1;
+                }
+            }
+            None => diff.added += 1,
+        }
+    }
+    diff.removed = before.len().saturating_sub(kept);
+    diff
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Samples kept per repo; the sparkline shows all of them.
+pub const MAX_SAMPLES: usize = 20;
+
+/// Fetches closer together than this update the latest sample instead of
+/// adding one, so a busy refresh interval doesn't crowd out the trend.
+pub const SAMPLE_SPACING_MINUTES: i64 = 60;
+
+/// Repos not sampled for this long (renamed, deleted, filtered out) are
+/// dropped.
+pub const MAX_AGE_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoSample {
+    pub at: DateTime<Utc>,
+    pub open_prs: u32,
+}
+
+/// Open PR counts per repo (`owner/name`) over time, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrCountHistory {
+    #[serde(default)]
+    pub repos: HashMap<String, Vec<RepoSample>>,
+}
+
+impl PrCountHistory {
+    /// Load the history file. A missing or unreadable file yields an empty
+    /// history, never an error.
+    pub fn load(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str(&content) {
+            Ok(history) => history,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to parse PR history file");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create PR history directory: {}", dir.display())
+            })?;
+        }
+        let content = serde_json::to_string(self).context("Failed to serialize PR history")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write PR history file: {}", path.display()))?;
+        debug!(path = %path.display(), repos = self.repos.len(), "PR history saved");
+        Ok(())
+    }
+
+    /// Record `repo`'s open PR count at `at`, keeping at most
+    /// [`MAX_SAMPLES`] samples. Returns whether the history changed.
+    pub fn append(&mut self, repo: &str, at: DateTime<Utc>, open_prs: u32) -> bool {
+        let samples = self.repos.entry(repo.to_string()).or_default();
+        let sample = RepoSample { at, open_prs };
+        let changed = match samples.last_mut() {
+            Some(last) if at - last.at < Duration::minutes(SAMPLE_SPACING_MINUTES) => {
+                std::mem::replace(&mut last.open_prs, open_prs) != open_prs
+            }
+            _ => {
+                samples.push(sample);
+                true
+            }
+        };
+        if samples.len() > MAX_SAMPLES {
+            samples.drain(..samples.len() - MAX_SAMPLES);
+        }
+        changed
+    }
+
+    /// Drop repos whose latest sample is older than [`MAX_AGE_DAYS`]. Returns
+    /// whether any were dropped.
+    pub fn prune(&mut self, now: DateTime<Utc>) -> bool {
+        let cutoff = now - Duration::days(MAX_AGE_DAYS);
+        let before = self.repos.len();
+        self.repos
+            .retain(|_, samples| samples.last().is_some_and(|s| s.at >= cutoff));
+        self.repos.len() != before
+    }
+
+    /// `repo`'s open PR counts, oldest first.
+    pub fn counts(&self, repo: &str) -> Vec<u32> {
+        self.repos
+            .get(repo)
+            .map(|samples| samples.iter().map(|s| s.open_prs).collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// A credential that must never be printed. `Debug` and `Display` both render
+/// as `***`; use [`SecretString::expose`] at the one place the raw value is needed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+/// Token prefixes and the minimum length of the alphanumeric tail that makes a
+/// match: classic `ghp_`/`gho_`/`ghu_`/`ghs_` tokens and fine-grained PATs.
+const TOKEN_SHAPES: &[(&str, usize)] = &[
+    ("ghp_", 36),
+    ("gho_", 36),
+    ("ghu_", 36),
+    ("ghs_", 36),
+    ("github_pat_", 22),
+];
+
+/// Replace anything shaped like a GitHub token with `***`.
+pub fn redact(text: &str) -> Cow<'_, str> {
+    if !TOKEN_SHAPES.iter().any(|(p, _)| text.contains(p)) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let token_len = TOKEN_SHAPES.iter().find_map(|(prefix, min_tail)| {
+            let tail = rest.strip_prefix(prefix)?;
+            let tail_len = tail
+                .bytes()
+                .take_while(|b| {
+                    b.is_ascii_alphanumeric() || (*prefix == "github_pat_" && *b == b'_')
+                })
+                .count();
+            (tail_len >= *min_tail).then_some(prefix.len() + tail_len)
+        });
+        match token_len {
+            Some(len) => {
+                out.push_str("***");
+                i += len;
+            }
+            None => {
+                let c = rest.chars().next().unwrap_or_default();
+                out.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Wraps a tracing writer so every formatted log line passes through [`redact`]
+/// before reaching it.
+pub struct RedactingMakeWriter<M>(M);
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M) -> Self {
+        Self(inner)
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+}
+
+pub struct RedactingWriter<W>(W);
+
+impl<W: io::Write> io::Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The fmt layer writes each event as one buffer, so a token is never
+        // split across calls.
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
@@ -1,8 +1,51 @@
-use chrono::{DateTime, Utc};
+#[cfg(feature = "test-support")]
+use std::cell::Cell;
+
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+
+#[cfg(feature = "test-support")]
+thread_local! {
+    static FIXED_NOW: Cell<Option<DateTime<Utc>>> = const { Cell::new(None) };
+    static FIXED_OFFSET: Cell<Option<FixedOffset>> = const { Cell::new(None) };
+}
+
+/// Current time. Returns `Utc::now()` unless a fixed instant has been pinned on
+/// this thread with `set_fixed_now` (`test-support` feature only), which tests
+/// use to get stable output.
+pub fn now() -> DateTime<Utc> {
+    #[cfg(feature = "test-support")]
+    if let Some(dt) = FIXED_NOW.with(|c| c.get()) {
+        return dt;
+    }
+    Utc::now()
+}
+
+/// Pin (or with `None`, unpin) the value returned by [`now`] on the current thread.
+#[cfg(feature = "test-support")]
+pub fn set_fixed_now(dt: Option<DateTime<Utc>>) {
+    FIXED_NOW.with(|c| c.set(dt));
+}
+
+/// `dt` on the local clock. Uses the system time zone unless an offset has been
+/// pinned on this thread with `set_fixed_offset` (`test-support` feature only).
+pub fn local(dt: &DateTime<Utc>) -> DateTime<FixedOffset> {
+    #[cfg(feature = "test-support")]
+    if let Some(offset) = FIXED_OFFSET.with(|c| c.get()) {
+        return dt.with_timezone(&offset);
+    }
+    dt.with_timezone(&Local.offset_from_utc_datetime(&dt.naive_utc()).fix())
+}
+
+/// Pin (or with `None`, unpin) the offset [`local`] converts to on the current
+/// thread.
+#[cfg(feature = "test-support")]
+pub fn set_fixed_offset(offset: Option<FixedOffset>) {
+    FIXED_OFFSET.with(|c| c.set(offset));
+}
 
 /// Format a timestamp as a human-readable relative time string.
 pub fn relative_time(dt: &DateTime<Utc>) -> String {
-    let now = Utc::now();
+    let now = now();
     let duration = now.signed_duration_since(dt);
 
     if duration.num_seconds() < 0 {
@@ -37,3 +80,53 @@ pub fn relative_time(dt: &DateTime<Utc>) -> String {
     let years = days / 365;
     format!("{}y ago", years)
 }
+
+/// Time left until `until`, e.g. `45s`, `8m` or `1h 5m`. Partial minutes
+/// round up, so it never reads `0m` while time remains.
+pub fn countdown(until: &DateTime<Utc>) -> String {
+    let secs = until.signed_duration_since(now()).num_seconds().max(0);
+    if secs < 60 {
+        return format!("{}s", secs);
+    }
+    let minutes = (secs + 59) / 60;
+    if minutes < 60 {
+        return format!("{}m", minutes);
+    }
+    format!("{}h {}m", minutes / 60, minutes % 60)
+}
+
+/// Which age bucket a timestamp falls in. `bounds_days` are the ascending
+/// upper bounds (exclusive, in days) of the first three buckets; anything
+/// older lands in the last one. Future timestamps count as brand new.
+pub fn age_bucket(dt: &DateTime<Utc>, now: &DateTime<Utc>, bounds_days: &[u32; 3]) -> usize {
+    let age = now.signed_duration_since(dt);
+    bounds_days
+        .iter()
+        .position(|days| age < chrono::Duration::days(i64::from(*days)))
+        .unwrap_or(bounds_days.len())
+}
+
+/// How many of `times` fall in each age bucket (see [`age_bucket`]).
+pub fn age_histogram<'a>(
+    times: impl IntoIterator<Item = &'a DateTime<Utc>>,
+    now: &DateTime<Utc>,
+    bounds_days: &[u32; 3],
+) -> [usize; 4] {
+    let mut counts = [0; 4];
+    for dt in times {
+        counts[age_bucket(dt, now, bounds_days)] += 1;
+    }
+    counts
+}
+
+/// Labels for the age buckets, e.g. `<1d`, `1–3d`, `3–7d`, `>7d` with `dash`
+/// "–".
+pub fn age_bucket_labels(bounds_days: &[u32; 3], dash: &str) -> [String; 4] {
+    let [a, b, c] = *bounds_days;
+    [
+        format!("<{}d", a),
+        format!("{}{}{}d", a, dash, b),
+        format!("{}{}{}d", b, dash, c),
+        format!(">{}d", c),
+    ]
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+use std::time::Duration as StdDuration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Latest published (non-draft, non-prerelease) ghdash release.
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/zombocoder/ghdash/releases/latest";
+
+/// Checks run at most this often, however many times ghdash starts.
+pub const CHECK_INTERVAL_HOURS: i64 = 24;
+
+/// When the last update check ran, so restarts within a day don't repeat it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateCheck {
+    #[serde(default)]
+    pub last_checked: Option<DateTime<Utc>>,
+}
+
+impl UpdateCheck {
+    /// Load the timestamp file. A missing or unreadable file counts as never
+    /// checked, never an error.
+    pub fn load(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str(&content) {
+            Ok(check) => check,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to parse update check file");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create update check directory: {}", dir.display())
+            })?;
+        }
+        let content = serde_json::to_string(self).context("Failed to serialize update check")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write update check file: {}", path.display()))?;
+        debug!(path = %path.display(), "Update check saved");
+        Ok(())
+    }
+
+    /// Whether a check is due at `now`. A timestamp in the future (clock
+    /// changes) doesn't hold checks off.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        match self.last_checked {
+            None => true,
+            Some(at) => at > now || now - at >= Duration::hours(CHECK_INTERVAL_HOURS),
+        }
+    }
+}
+
+/// `major.minor.patch` of a release tag or crate version, with an optional
+/// leading `v`. Pre-release and build suffixes are dropped.
+pub fn parse_version(s: &str) -> Option<(u64, u64, u64)> {
+    let s = s.trim();
+    let s = s.strip_prefix('v').unwrap_or(s);
+    let core = s.split(['-', '+']).next()?;
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// `latest` with its `v` stripped if it is newer than `current`; `None` when
+/// it isn't or either doesn't parse.
+pub fn newer_version(latest: &str, current: &str) -> Option<String> {
+    let (l, c) = (parse_version(latest)?, parse_version(current)?);
+    (l > c).then(|| {
+        let latest = latest.trim();
+        latest.strip_prefix('v').unwrap_or(latest).to_string()
+    })
+}
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+/// Tag of the latest ghdash release. Goes straight to github.com without the
+/// configured token or headers: it's a public endpoint and nothing about the
+/// user needs to go along.
+pub async fn fetch_latest_version() -> Result<String> {
+    let client = reqwest::Client::builder()
+        .user_agent(concat!("ghdash/", env!("CARGO_PKG_VERSION")))
+        .timeout(StdDuration::from_secs(10))
+        .build()
+        .context("Failed to create HTTP client")?;
+    let release: Release = client
+        .get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .context("Failed to reach GitHub releases")?
+        .error_for_status()
+        .context("GitHub releases request failed")?
+        .json()
+        .await
+        .context("Failed to parse latest release")?;
+    Ok(release.tag_name)
+}
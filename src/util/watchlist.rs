@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// A PR the user asked to keep an eye on, identified by repo and number so it
+/// can be looked up (or fetched) whatever list it currently appears in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WatchedPr {
+    pub owner: String,
+    pub name: String,
+    pub number: u32,
+}
+
+impl WatchedPr {
+    pub fn matches(&self, owner: &str, name: &str, number: u32) -> bool {
+        self.number == number
+            && self.owner.eq_ignore_ascii_case(owner)
+            && self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+/// Watched PRs, in the order they were added. Saved whenever it changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    #[serde(default)]
+    pub prs: Vec<WatchedPr>,
+}
+
+impl Watchlist {
+    /// Load the watchlist file. A missing or unreadable file yields an empty
+    /// list, never an error.
+    pub fn load(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        match serde_json::from_str(&content) {
+            Ok(watchlist) => watchlist,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to parse watchlist file");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| {
+                format!("Failed to create watchlist directory: {}", dir.display())
+            })?;
+        }
+        let content = serde_json::to_string(self).context("Failed to serialize watchlist")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write watchlist file: {}", path.display()))?;
+        debug!(path = %path.display(), prs = self.prs.len(), "Watchlist saved");
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{Duration, TimeZone, Utc};
+use futures::FutureExt;
+use ghdash::github::auth::{
+    APP_ID_VAR, AuthMethod, Environment, ExpiringToken, GithubApp, RefreshFn, TokenRefresher,
+    default_auth_order, read_token, resolve_token_with, viewer_cache_key,
+};
+use ghdash::util::config::{GithubAppConfig, GithubConfig};
+use ghdash::util::secret::SecretString;
+use ghdash::util::time;
+use rsa::RsaPrivateKey;
+use rsa::pkcs1::{EncodeRsaPrivateKey, LineEnding};
+
+#[derive(Default)]
+struct FakeEnv {
+    vars: HashMap<&'static str, &'static str>,
+    commands: HashMap<&'static str, &'static str>,
+}
+
+impl Environment for FakeEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        self.vars.get(key).map(|v| v.to_string())
+    }
+
+    fn command_output(&self, program: &str, _args: &[&str]) -> Option<String> {
+        self.commands.get(program).map(|v| v.to_string())
+    }
+}
+
+fn env_with_both() -> FakeEnv {
+    let mut env = FakeEnv::default();
+    env.vars.insert("GITHUB_TOKEN", "env-token");
+    env.commands.insert("gh", "gh-token\n");
+    env
+}
+
+#[test]
+fn test_default_order_prefers_gh() {
+    let (token, method) = resolve_token_with(&env_with_both(), &default_auth_order()).unwrap();
+    assert_eq!(token, "gh-token");
+    assert_eq!(method, AuthMethod::Gh);
+}
+
+#[test]
+fn test_env_first_order_prefers_github_token() {
+    let order = [AuthMethod::Env, AuthMethod::Gh];
+    let (token, method) = resolve_token_with(&env_with_both(), &order).unwrap();
+    assert_eq!(token, "env-token");
+    assert_eq!(method, AuthMethod::Env);
+}
+
+#[test]
+fn test_env_falls_back_to_gh_token_var() {
+    let mut env = FakeEnv::default();
+    env.vars.insert("GITHUB_TOKEN", "  ");
+    env.vars.insert("GH_TOKEN", "second");
+    let (token, _) = resolve_token_with(&env, &[AuthMethod::Env]).unwrap();
+    assert_eq!(token, "second");
+}
+
+#[cfg(windows)]
+#[test]
+fn test_gh_falls_back_to_gh_exe() {
+    let mut env = FakeEnv::default();
+    env.commands.insert("gh.exe", "exe-token\n");
+    let (token, method) = resolve_token_with(&env, &[AuthMethod::Gh]).unwrap();
+    assert_eq!(token, "exe-token");
+    assert_eq!(method, AuthMethod::Gh);
+}
+
+#[test]
+fn test_empty_gh_output_falls_through() {
+    let mut env = FakeEnv::default();
+    env.commands.insert("gh", "\n");
+    env.vars.insert("GH_TOKEN", "fallback");
+    let (token, method) = resolve_token_with(&env, &default_auth_order()).unwrap();
+    assert_eq!(token, "fallback");
+    assert_eq!(method, AuthMethod::Env);
+}
+
+/// A keyring holding `secret` under the ghdash service, on whichever
+/// backend this platform uses; records the lookups made.
+struct FakeKeyring {
+    secret: Option<&'static str>,
+    lookups: std::sync::Mutex<Vec<String>>,
+}
+
+impl Environment for FakeKeyring {
+    fn var(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<String> {
+        self.lookups
+            .lock()
+            .unwrap()
+            .push(format!("{} {}", program, args.join(" ")));
+        let expected = if cfg!(target_os = "macos") {
+            "security find-generic-password -s ghdash -w"
+        } else {
+            "secret-tool lookup service ghdash"
+        };
+        (format!("{} {}", program, args.join(" ")) == expected)
+            .then(|| self.secret.map(|s| format!("{s}\n")))
+            .flatten()
+    }
+}
+
+#[test]
+fn test_keyring_method() {
+    let keyring = FakeKeyring {
+        secret: Some("keyring-secret"),
+        lookups: Default::default(),
+    };
+    let (token, method) = resolve_token_with(&keyring, &[AuthMethod::Keyring]).unwrap();
+    assert_eq!(token, "keyring-secret");
+    assert_eq!(method, AuthMethod::Keyring);
+    assert_eq!(keyring.lookups.lock().unwrap().len(), 1);
+
+    // Nothing stored under the service is no token.
+    let keyring = FakeKeyring {
+        secret: None,
+        lookups: Default::default(),
+    };
+    assert!(resolve_token_with(&keyring, &[AuthMethod::Keyring]).is_err());
+}
+
+#[test]
+fn test_no_sources_is_an_error() {
+    // Every source answers, but with nothing usable.
+    let mut env = FakeEnv::default();
+    env.vars.insert("GITHUB_TOKEN", "");
+    env.vars.insert("GH_TOKEN", "  ");
+    for program in ["gh", "gh.exe", "security", "secret-tool"] {
+        env.commands.insert(program, "\n");
+    }
+    let err = resolve_token_with(&env, &default_auth_order()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Could not resolve GitHub token. Please either:\n\
+         - Run `gh auth login` to authenticate with the GitHub CLI\n\
+         - Set the GITHUB_TOKEN environment variable\n\
+         - Set the GH_TOKEN environment variable\n\
+         - Configure [github.app] and add \"app\" to github.auth_order\n\
+         - Pipe a token in with --token-stdin"
+    );
+}
+
+#[test]
+fn test_read_token_trims_input() {
+    assert_eq!(read_token("ghp_abc\n".as_bytes()).unwrap(), "ghp_abc");
+    assert!(read_token("  \n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_viewer_cache_key_digests_token_and_host() {
+    let api = "https://api.github.com/graphql";
+    let key = viewer_cache_key(api, "ghp_secret");
+    assert_eq!(key, viewer_cache_key(api, "ghp_secret"));
+    assert!(key.starts_with("viewer_"));
+    assert!(!key.contains("ghp_secret"));
+    assert_ne!(key, viewer_cache_key(api, "ghp_other"));
+    assert_ne!(
+        key,
+        viewer_cache_key("https://ghe.example.com/api/graphql", "ghp_secret")
+    );
+}
+
+/// A throwaway 2048-bit App key, generated once per run and written as
+/// PKCS#1 PEM into a temp dir that lives as long as the test binary.
+fn app_key_path() -> PathBuf {
+    static KEY_DIR: OnceLock<tempfile::TempDir> = OnceLock::new();
+    let dir = KEY_DIR.get_or_init(|| {
+        let key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let pem = key.to_pkcs1_pem(LineEnding::LF).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("github_app_key.pem"), pem.as_bytes()).unwrap();
+        dir
+    });
+    dir.path().join("github_app_key.pem")
+}
+
+fn app_config() -> GithubAppConfig {
+    GithubAppConfig {
+        app_id: Some(12345),
+        installation_id: Some(678),
+        private_key_path: Some(app_key_path()),
+    }
+}
+
+#[test]
+fn test_app_auth_unconfigured_is_none() {
+    let app = GithubApp::from_sources(None, &FakeEnv::default()).unwrap();
+    assert!(app.is_none());
+}
+
+#[test]
+fn test_app_auth_needs_every_field() {
+    let config = GithubAppConfig {
+        installation_id: None,
+        ..app_config()
+    };
+    let err = GithubApp::from_sources(Some(&config), &FakeEnv::default())
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("installation_id"));
+}
+
+#[test]
+fn test_app_auth_env_fills_and_overrides_config() {
+    let mut env = FakeEnv::default();
+    env.vars.insert(APP_ID_VAR, "999");
+    let config = GithubAppConfig {
+        app_id: None,
+        ..app_config()
+    };
+    let app = GithubApp::from_sources(Some(&config), &env)
+        .unwrap()
+        .unwrap();
+    let jwt = app.jwt(Utc::now()).unwrap();
+    let claims = jwt.split('.').nth(1).unwrap();
+    let claims: serde_json::Value =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).unwrap()).unwrap();
+    assert_eq!(claims["iss"], 999);
+
+    env.vars.insert(APP_ID_VAR, "not-a-number");
+    assert!(GithubApp::from_sources(Some(&config), &env).is_err());
+}
+
+#[test]
+fn test_app_auth_rejects_non_key_pem() {
+    let pem = "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n";
+    assert!(GithubApp::from_pem(1, 2, pem).is_err());
+    assert!(GithubApp::from_pem(1, 2, "not pem").is_err());
+}
+
+#[test]
+fn test_app_jwt_claims_and_signature() {
+    let app = GithubApp::from_sources(Some(&app_config()), &FakeEnv::default())
+        .unwrap()
+        .unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+    let jwt = app.jwt(now).unwrap();
+    let parts: Vec<&str> = jwt.split('.').collect();
+    assert_eq!(parts.len(), 3);
+
+    let decode = |part: &str| -> serde_json::Value {
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(part).unwrap()).unwrap()
+    };
+    assert_eq!(decode(parts[0])["alg"], "RS256");
+    let claims = decode(parts[1]);
+    assert_eq!(claims["iss"], 12345);
+    assert_eq!(claims["iat"], (now - Duration::seconds(60)).timestamp());
+    assert_eq!(claims["exp"], (now + Duration::minutes(9)).timestamp());
+    assert!(!URL_SAFE_NO_PAD.decode(parts[2]).unwrap().is_empty());
+}
+
+fn counting_refresher(
+    expires_at: chrono::DateTime<Utc>,
+    calls: &Arc<AtomicUsize>,
+) -> TokenRefresher {
+    let counter = calls.clone();
+    let refresh: RefreshFn = Arc::new(move || {
+        let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+        async move {
+            Ok(ExpiringToken {
+                token: SecretString::new(format!("minted-{n}")),
+                expires_at: time::now() + Duration::hours(1),
+            })
+        }
+        .boxed()
+    });
+    let first = ExpiringToken {
+        token: SecretString::new("first"),
+        expires_at,
+    };
+    TokenRefresher::new(first, refresh)
+}
+
+#[tokio::test]
+async fn test_token_refresher_keeps_a_fresh_token() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let refresher = counting_refresher(time::now() + Duration::hours(1), &calls);
+    assert_eq!(refresher.token().await.unwrap().expose(), "first");
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_token_refresher_renews_before_expiry() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let refresher = counting_refresher(time::now() + Duration::minutes(2), &calls);
+    assert_eq!(refresher.token().await.unwrap().expose(), "minted-1");
+    // The new token is good for an hour, so it's reused.
+    assert_eq!(refresher.token().await.unwrap().expose(), "minted-1");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_app_refresher_rebuilt_when_credentials_change() {
+    let mut config = GithubConfig {
+        app: Some(app_config()),
+        ..GithubConfig::default()
+    };
+    let app = GithubApp::from_sources(config.app.as_ref(), &FakeEnv::default())
+        .unwrap()
+        .unwrap();
+    let refresher = Arc::new(TokenRefresher::for_app(app, &config).unwrap());
+    let same = refresher.reconfigured(&config).unwrap();
+    assert!(Arc::ptr_eq(&refresher, &same));
+
+    config.app = Some(GithubAppConfig {
+        app_id: Some(54321),
+        ..app_config()
+    });
+    let rebuilt = refresher.reconfigured(&config).unwrap();
+    assert!(!Arc::ptr_eq(&refresher, &rebuilt));
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let fixed = Arc::new(counting_refresher(time::now(), &calls));
+    assert!(Arc::ptr_eq(&fixed, &fixed.reconfigured(&config).unwrap()));
+}
@@ -1,6 +1,16 @@
-use ghdash::cache::CacheStore;
+use chrono::{Duration, Utc};
+use ghdash::app::actions::SideEffect;
+use ghdash::app::event_loop::{cache_key, fetch_for_view, hydrate_from_cache};
+use ghdash::app::state::{AppState, ContentView};
+use ghdash::cache::{CacheStore, Negative};
+use ghdash::github::CancelToken;
+use ghdash::github::models::{PullRequest, Repo};
+use ghdash::util::config::AppConfig;
+use ghdash::util::time::set_fixed_now;
 use tempfile::TempDir;
 
+mod common;
+
 #[test]
 fn test_set_and_get() {
     let dir = TempDir::new().unwrap();
@@ -25,16 +35,69 @@ fn test_get_missing_key_returns_none() {
 #[test]
 fn test_expired_entry_returns_none() {
     let dir = TempDir::new().unwrap();
-    // TTL of 0 means everything is immediately expired
     let store = CacheStore::new(dir.path().to_path_buf(), 0);
 
+    let written_at = Utc::now();
+    set_fixed_now(Some(written_at));
     store.set("key", &42u32).unwrap();
 
-    // Even though we just wrote it, TTL=0 means age (0) > ttl (0) is false,
-    // but age=0 == ttl=0, so 0 > 0 is false — it should still be valid.
-    // Let's use a sleep to ensure expiration.
-    std::thread::sleep(std::time::Duration::from_millis(1100));
+    // age (0) > ttl (0) is false, so the entry is still valid at write time...
+    let result: Option<u32> = store.get("key");
+    assert_eq!(result, Some(42));
 
+    // ...and expires once the clock moves past the TTL.
+    set_fixed_now(Some(written_at + Duration::seconds(1)));
+    let result: Option<u32> = store.get("key");
+    assert_eq!(result, None);
+}
+
+#[test]
+fn test_get_ignoring_ttl_returns_expired_entry_with_timestamp() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 60);
+
+    let written_at = Utc::now();
+    set_fixed_now(Some(written_at));
+    store.set("key", &"stale".to_string()).unwrap();
+
+    set_fixed_now(Some(written_at + Duration::hours(3)));
+    assert_eq!(store.get::<String>("key"), None);
+    let (data, ts) = store.get_ignoring_ttl::<String>("key").unwrap();
+    assert_eq!(data, "stale");
+    assert_eq!(ts, written_at);
+    assert!(store.get_ignoring_ttl::<String>("missing").is_none());
+    set_fixed_now(None);
+}
+
+#[test]
+fn test_entry_expires_after_ttl() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+
+    let written_at = Utc::now();
+    set_fixed_now(Some(written_at));
+    store.set("key", &"value".to_string()).unwrap();
+
+    set_fixed_now(Some(written_at + Duration::seconds(600)));
+    let result: Option<String> = store.get("key");
+    assert_eq!(result, Some("value".to_string()));
+
+    set_fixed_now(Some(written_at + Duration::seconds(601)));
+    let result: Option<String> = store.get("key");
+    assert_eq!(result, None);
+}
+
+#[test]
+fn test_entry_from_the_future_returns_none() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+
+    let written_at = Utc::now();
+    set_fixed_now(Some(written_at));
+    store.set("key", &1u32).unwrap();
+
+    // Clock moved backwards (e.g. NTP adjustment): treat the entry as stale.
+    set_fixed_now(Some(written_at - Duration::seconds(5)));
     let result: Option<u32> = store.get("key");
     assert_eq!(result, None);
 }
@@ -76,31 +139,46 @@ fn test_invalidate_nonexistent_key_is_ok() {
 }
 
 #[test]
-fn test_invalidate_all() {
+fn test_expire_all_keeps_entries_until_replaced() {
     let dir = TempDir::new().unwrap();
     let store = CacheStore::new(dir.path().to_path_buf(), 600);
-
+    let start = Utc::now();
+    set_fixed_now(Some(start));
     store.set("a", &1u32).unwrap();
     store.set("b", &2u32).unwrap();
-    store.set("c", &3u32).unwrap();
 
-    store.invalidate_all().unwrap();
+    set_fixed_now(Some(start + Duration::seconds(5)));
+    // Clones share the expiry, like the stores handed to fetch tasks.
+    store.clone().expire_all();
+    assert_eq!(store.get::<u32>("a"), None);
+    assert_eq!(store.get::<u32>("b"), None);
+    // Still on disk for offline mode and the next start.
+    assert_eq!(store.get_ignoring_ttl::<u32>("b").map(|(v, _)| v), Some(2));
 
-    let a: Option<u32> = store.get("a");
-    let b: Option<u32> = store.get("b");
-    let c: Option<u32> = store.get("c");
-    assert_eq!(a, None);
-    assert_eq!(b, None);
-    assert_eq!(c, None);
+    store.set("a", &10u32).unwrap();
+    assert_eq!(store.get::<u32>("a"), Some(10));
+    assert_eq!(store.get::<u32>("b"), None);
+    set_fixed_now(None);
 }
 
 #[test]
-fn test_invalidate_all_on_empty_dir() {
+fn test_expire_single_key_keeps_the_entry() {
     let dir = TempDir::new().unwrap();
     let store = CacheStore::new(dir.path().to_path_buf(), 600);
+    let start = Utc::now();
+    set_fixed_now(Some(start));
+    store.set("a", &1u32).unwrap();
+    store.set("b", &2u32).unwrap();
 
-    // Should not error on empty directory
-    store.invalidate_all().unwrap();
+    set_fixed_now(Some(start + Duration::seconds(5)));
+    store.expire("a");
+    assert_eq!(store.get::<u32>("a"), None);
+    assert_eq!(store.get::<u32>("b"), Some(2));
+    assert_eq!(store.get_ignoring_ttl::<u32>("a").map(|(v, _)| v), Some(1));
+
+    store.set("a", &10u32).unwrap();
+    assert_eq!(store.get::<u32>("a"), Some(10));
+    set_fixed_now(None);
 }
 
 #[test]
@@ -137,3 +215,281 @@ fn test_creates_cache_dir_on_set() {
     store.set("key", &"val".to_string()).unwrap();
     assert!(nested.exists());
 }
+
+// --- View -> cache key mapping (force-refresh one view) ---
+
+fn view_key(view: ContentView, config: &AppConfig) -> Option<String> {
+    cache_key(&fetch_for_view(&view, config)?, config, "me")
+}
+
+#[test]
+fn test_view_cache_keys() {
+    let mut config = AppConfig::default();
+    config.github.users = vec!["solo".into()];
+
+    assert_eq!(
+        view_key(ContentView::Inbox, &config).unwrap(),
+        "inbox_v2_me"
+    );
+    assert_eq!(
+        view_key(ContentView::AllOpenPrs, &config).unwrap(),
+        "all_open_prs_v2"
+    );
+    assert_eq!(
+        view_key(ContentView::RecentlyMerged, &config).unwrap(),
+        "pr_history_v2_merged_7"
+    );
+    assert_eq!(
+        view_key(ContentView::RecentlyClosed, &config).unwrap(),
+        "pr_history_v2_closed_7"
+    );
+    // Repo lists are refreshed under their view's token instead.
+    assert_eq!(
+        view_key(
+            ContentView::RepoPrList {
+                owner: "acme".into(),
+                name: "api".into()
+            },
+            &config
+        ),
+        None
+    );
+    let prefetch = SideEffect::PrefetchRepoPrs {
+        owner: "acme".into(),
+        name: "api".into(),
+        cancel: CancelToken::new(),
+    };
+    assert_eq!(
+        cache_key(&prefetch, &config, "me").unwrap(),
+        "repo_prs_v2_acme/api"
+    );
+    assert_eq!(
+        view_key(ContentView::OrgOverview("acme".into()), &config).unwrap(),
+        "org_repos_v2_acme"
+    );
+    assert_eq!(
+        view_key(ContentView::OrgOverview("solo".into()), &config).unwrap(),
+        "user_repos_v2_solo"
+    );
+}
+
+#[test]
+fn test_inbox_key_includes_teams() {
+    let mut config = AppConfig::default();
+    config.github.teams = vec!["acme/platform".into(), "acme/infra".into()];
+    assert_eq!(
+        cache_key(&SideEffect::FetchInbox, &config, "me").unwrap(),
+        "inbox_v2_me_acme/platform+acme/infra"
+    );
+}
+
+#[test]
+fn test_uncached_effects_have_no_key() {
+    let config = AppConfig::default();
+    assert!(cache_key(&SideEffect::RefreshAll, &config, "me").is_none());
+    assert!(cache_key(&SideEffect::OpenUrl("https://x".into()), &config, "me").is_none());
+}
+
+fn make_repo(owner: &str, name: &str) -> Repo {
+    common::make_repo(owner, name, 1)
+}
+
+fn make_pr(number: u32) -> PullRequest {
+    common::make_pr("acme", "api", number)
+}
+
+fn hydrate_config() -> AppConfig {
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["acme".into(), "globex".into()];
+    config.github.users = vec!["solo".into()];
+    config
+}
+
+fn key(effect: SideEffect, config: &AppConfig) -> String {
+    cache_key(&effect, config, "me").unwrap()
+}
+
+#[test]
+fn test_hydrate_with_partial_cache() {
+    let dir = TempDir::new().unwrap();
+    // TTL 0: hydration ignores expiry, stale data beats an empty screen.
+    let store = CacheStore::new(dir.path().to_path_buf(), 0);
+    let config = hydrate_config();
+
+    store
+        .set(
+            &key(SideEffect::FetchOrgRepos("acme".into()), &config),
+            &vec![make_repo("acme", "api")],
+        )
+        .unwrap();
+    store
+        .set(
+            &key(SideEffect::FetchUserRepos("solo".into()), &config),
+            &vec![make_repo("solo", "dotfiles")],
+        )
+        .unwrap();
+    store
+        .set(&key(SideEffect::FetchInbox, &config), &vec![make_pr(7)])
+        .unwrap();
+
+    let mut state = AppState::new(
+        "me".into(),
+        vec!["acme".into(), "globex".into(), "solo".into()],
+    );
+    assert!(hydrate_from_cache(&mut state, &store, &config));
+
+    assert!(state.stale);
+    assert_eq!(state.orgs["acme"].repos.len(), 1);
+    assert_eq!(state.orgs["solo"].repos[0].name, "dotfiles");
+    assert!(state.orgs["globex"].repos.is_empty());
+    assert_eq!(state.inbox.len(), 1);
+    assert!(state.all_open_prs.is_empty());
+    assert!(state.recently_merged.is_empty());
+    // The hydrated repos appear in the nav tree.
+    assert!(state.nav_nodes.len() > 4 + 3);
+}
+
+#[test]
+fn test_hydrate_empty_cache_is_not_stale() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+    let config = hydrate_config();
+
+    let mut state = AppState::new("me".into(), vec!["acme".into()]);
+    assert!(!hydrate_from_cache(&mut state, &store, &config));
+    assert!(!state.stale);
+    assert!(state.inbox.is_empty());
+}
+
+#[test]
+fn test_hydrate_reads_keys_written_by_fetches() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+    let mut config = hydrate_config();
+    config.dashboard.merged_window_days = 14;
+    config.github.exclude_repos = vec!["*-archived".into()];
+
+    // Literal keys, as spawn_side_effect writes them.
+    store
+        .set(
+            "org_repos_v2_acme",
+            &vec![make_repo("acme", "api"), make_repo("acme", "old-archived")],
+        )
+        .unwrap();
+    store.set("inbox_v2_me", &vec![make_pr(1)]).unwrap();
+    store
+        .set("all_open_prs_v2", &vec![make_pr(1), make_pr(2)])
+        .unwrap();
+    store
+        .set("pr_history_v2_merged_14", &vec![make_pr(3)])
+        .unwrap();
+    store
+        .set("pr_history_v2_closed_7", &vec![make_pr(4)])
+        .unwrap();
+
+    let mut state = AppState::new("me".into(), vec!["acme".into()]);
+    assert!(hydrate_from_cache(&mut state, &store, &config));
+
+    // Repo filters apply to hydrated repos just as to fetched ones.
+    let names: Vec<_> = state.orgs["acme"]
+        .repos
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["api"]);
+    assert_eq!(state.inbox.len(), 1);
+    assert_eq!(state.all_open_prs.len(), 2);
+    assert_eq!(state.recently_merged[0].number, 3);
+    assert_eq!(state.recently_closed[0].number, 4);
+}
+
+#[test]
+fn test_hydrate_skips_repo_lists_cached_without_topics() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+    let mut config = hydrate_config();
+    config.github.include_topics = vec!["service".into()];
+
+    // Written before repos carried topics.
+    store
+        .set("org_repos_acme", &vec![make_repo("acme", "api")])
+        .unwrap();
+
+    let mut state = AppState::new("me".into(), vec!["acme".into()]);
+    assert!(!hydrate_from_cache(&mut state, &store, &config));
+}
+
+#[test]
+fn test_negative_entry_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+
+    store
+        .set_negative("org_repos_empty", Negative::Empty)
+        .unwrap();
+    store
+        .set_negative("org_repos_secret", Negative::NoAccess)
+        .unwrap();
+    assert_eq!(store.get_negative("org_repos_empty"), Some(Negative::Empty));
+    assert_eq!(
+        store.get_negative("org_repos_secret"),
+        Some(Negative::NoAccess)
+    );
+
+    // A marker isn't data, however it's read.
+    assert!(store.get::<Vec<Repo>>("org_repos_secret").is_none());
+    assert!(
+        store
+            .get_ignoring_ttl::<Vec<Repo>>("org_repos_secret")
+            .is_none()
+    );
+
+    // Real data replaces the marker, and plain entries have none.
+    store
+        .set("org_repos_secret", &vec![make_repo("secret", "api")])
+        .unwrap();
+    assert_eq!(store.get_negative("org_repos_secret"), None);
+    assert_eq!(store.get::<Vec<Repo>>("org_repos_secret").unwrap().len(), 1);
+}
+
+#[test]
+fn test_negative_entry_has_its_own_ttl() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600).with_negative_ttl(60);
+
+    let written_at = Utc::now();
+    set_fixed_now(Some(written_at));
+    store.set_negative("key", Negative::NoAccess).unwrap();
+
+    set_fixed_now(Some(written_at + Duration::seconds(60)));
+    assert_eq!(store.get_negative("key"), Some(Negative::NoAccess));
+    set_fixed_now(Some(written_at + Duration::seconds(61)));
+    assert_eq!(store.get_negative("key"), None);
+    set_fixed_now(None);
+}
+
+#[test]
+fn test_invalidation_keeps_negative_entries() {
+    let dir = TempDir::new().unwrap();
+    let store = CacheStore::new(dir.path().to_path_buf(), 600);
+
+    store
+        .set_negative("org_repos_secret", Negative::NoAccess)
+        .unwrap();
+    store
+        .set("org_repos_acme", &vec![make_repo("acme", "api")])
+        .unwrap();
+
+    store.invalidate("org_repos_secret").unwrap();
+    assert_eq!(
+        store.get_negative("org_repos_secret"),
+        Some(Negative::NoAccess)
+    );
+
+    store.expire_all();
+    assert!(store.get::<Vec<Repo>>("org_repos_acme").is_none());
+    assert_eq!(
+        store.get_negative("org_repos_secret"),
+        Some(Negative::NoAccess)
+    );
+}
@@ -0,0 +1,40 @@
+use ghdash::ui::chooser::Chooser;
+
+fn make_chooser() -> Chooser<u32> {
+    Chooser::new(
+        "Pick",
+        vec![("one".into(), 1), ("two".into(), 2), ("three".into(), 3)],
+    )
+}
+
+#[test]
+fn test_chooser_starts_at_first_option() {
+    let chooser = make_chooser();
+    assert_eq!(chooser.cursor(), 0);
+    assert_eq!(
+        chooser.labels().collect::<Vec<_>>(),
+        vec!["one", "two", "three"]
+    );
+    assert_eq!(chooser.into_selected(), Some(1));
+}
+
+#[test]
+fn test_chooser_cursor_clamps_at_both_ends() {
+    let mut chooser = make_chooser();
+    chooser.move_up();
+    assert_eq!(chooser.cursor(), 0);
+    for _ in 0..5 {
+        chooser.move_down();
+    }
+    assert_eq!(chooser.cursor(), 2);
+    chooser.move_up();
+    assert_eq!(chooser.into_selected(), Some(2));
+}
+
+#[test]
+fn test_empty_chooser_selects_nothing() {
+    let mut chooser: Chooser<u32> = Chooser::new("Nothing", vec![]);
+    chooser.move_down();
+    assert_eq!(chooser.cursor(), 0);
+    assert_eq!(chooser.into_selected(), None);
+}
@@ -0,0 +1,52 @@
+//! Repo and PR builders shared by the integration tests.
+
+// Each test crate compiles its own copy and uses only some of it.
+#![allow(dead_code)]
+
+use chrono::Utc;
+use ghdash::github::models::{PrState, PullRequest, Repo};
+
+pub fn make_repo(owner: &str, name: &str, open_prs: u32) -> Repo {
+    Repo {
+        name: name.into(),
+        owner: owner.into(),
+        url: format!("https://github.com/{}/{}", owner, name),
+        description: None,
+        open_pr_count: open_prs,
+        is_archived: false,
+        topics: vec![],
+    }
+}
+
+/// An open PR titled "PR `number`", created and updated now.
+pub fn make_pr(owner: &str, name: &str, number: u32) -> PullRequest {
+    let now = Utc::now();
+    PullRequest {
+        number,
+        title: format!("PR {number}"),
+        author: "author".into(),
+        repo_owner: owner.into(),
+        repo_name: name.into(),
+        url: format!("https://github.com/{}/{}/pull/{}", owner, name, number),
+        created_at: now,
+        updated_at: now,
+        is_draft: false,
+        state: PrState::Open,
+        additions: 1,
+        deletions: 1,
+        review_decision: None,
+        labels: vec![],
+        mergeable: None,
+        merge_state_status: None,
+        is_behind: false,
+        checks_status: None,
+        merged_at: None,
+        closed_at: None,
+        head_ref: None,
+        base_ref: None,
+        comment_count: 0,
+        review_count: 0,
+        my_review_state: None,
+        assignees: vec![],
+    }
+}
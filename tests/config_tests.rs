@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 
-use ghdash::util::config::AppConfig;
+use ghdash::github::GithubClient;
+use ghdash::github::auth::AuthMethod;
+use ghdash::github::graphql::build_extra_headers;
+use ghdash::util::config::{
+    AppConfig, GithubAppConfig, InboxSort, OrgColor, OwnerKind, PrColumn, SecondarySort,
+    default_column_priority, default_columns, interpolate_env, validate_login, validate_repo,
+};
 
 #[test]
 fn test_load_full_config() {
@@ -11,17 +20,42 @@ orgs = ["my-org", "other-org"]
 users = ["my-user"]
 include_repos = ["important-*"]
 exclude_repos = ["*-archived"]
+include_topics = ["service"]
+exclude_topics = ["deprecated"]
+teams = ["my-org/reviewers"]
+auth_order = ["env", "gh", "keyring"]
 api_url = "https://github.example.com/api/graphql"
+batch_queries = true
 
 [dashboard]
 refresh_interval_secs = 120
+inbox_interval_secs = 60
+repos_interval_secs = 1800
 show_draft_prs = false
+merged_window_days = 14
+closed_window_days = 30
+max_concurrency = 2
+focus_refresh_debounce_secs = 15
+inbox_sort = "oldest"
 
 [cache]
 ttl_secs = 300
+negative_ttl_secs = 120
 
 [ui]
 nav_width_percent = 40
+group_by_topic = true
+expand_shortcodes = true
+letter_jump = true
+reduce_motion = true
+ascii = true
+columns = ["number", "title", "diffstat", "comments", "branch", "base", "assignees", "updated"]
+column_priority = ["title", "updated"]
+size_thresholds = [5, 50, 200, 800]
+age_buckets = [2, 14, 30]
+secondary_sort = "author"
+max_repos_per_org = 15
+group_all_prs_by_repo = true
 "#;
     let mut f = NamedTempFile::new().unwrap();
     f.write_all(toml.as_bytes()).unwrap();
@@ -31,14 +65,58 @@ nav_width_percent = 40
     assert_eq!(config.github.users, vec!["my-user"]);
     assert_eq!(config.github.include_repos, vec!["important-*"]);
     assert_eq!(config.github.exclude_repos, vec!["*-archived"]);
+    assert_eq!(config.github.include_topics, vec!["service"]);
+    assert_eq!(config.github.exclude_topics, vec!["deprecated"]);
+    assert_eq!(config.github.teams, vec!["my-org/reviewers"]);
+    assert_eq!(
+        config.github.auth_order,
+        vec![AuthMethod::Env, AuthMethod::Gh, AuthMethod::Keyring]
+    );
     assert_eq!(
         config.github.api_url,
         "https://github.example.com/api/graphql"
     );
+    assert!(config.github.batch_queries);
     assert_eq!(config.dashboard.refresh_interval_secs, 120);
+    assert_eq!(config.dashboard.inbox_interval_secs, Some(60));
+    assert_eq!(config.dashboard.repos_interval_secs, Some(1800));
+    assert_eq!(config.dashboard.all_prs_interval_secs, None);
     assert!(!config.dashboard.show_draft_prs);
+    assert_eq!(config.dashboard.merged_window_days, 14);
+    assert_eq!(config.dashboard.closed_window_days, 30);
+    assert_eq!(config.dashboard.max_concurrency, 2);
+    assert_eq!(config.dashboard.focus_refresh_debounce_secs, 15);
     assert_eq!(config.cache.ttl_secs, 300);
+    assert_eq!(config.cache.negative_ttl_secs, 120);
     assert_eq!(config.ui.nav_width_percent, 40);
+    assert!(config.ui.group_by_topic);
+    assert!(config.ui.expand_shortcodes);
+    assert!(config.ui.letter_jump);
+    assert!(config.ui.reduce_motion);
+    assert_eq!(config.ui.ascii, Some(true));
+    assert_eq!(
+        config.ui.columns,
+        vec![
+            PrColumn::Number,
+            PrColumn::Title,
+            PrColumn::Diffstat,
+            PrColumn::Comments,
+            PrColumn::Branch,
+            PrColumn::Base,
+            PrColumn::Assignees,
+            PrColumn::Updated
+        ]
+    );
+    assert_eq!(
+        config.ui.column_priority,
+        vec![PrColumn::Title, PrColumn::Updated]
+    );
+    assert_eq!(config.ui.size_thresholds, [5, 50, 200, 800]);
+    assert_eq!(config.ui.age_buckets, [2, 14, 30]);
+    assert_eq!(config.ui.secondary_sort, SecondarySort::Author);
+    assert_eq!(config.dashboard.inbox_sort, InboxSort::Oldest);
+    assert_eq!(config.ui.max_repos_per_org, Some(15));
+    assert!(config.ui.group_all_prs_by_repo);
 }
 
 #[test]
@@ -54,10 +132,32 @@ orgs = ["my-org"]
     assert_eq!(config.github.orgs, vec!["my-org"]);
     assert!(config.github.users.is_empty());
     assert_eq!(config.github.api_url, "https://api.github.com/graphql");
+    assert!(!config.github.batch_queries);
     assert_eq!(config.dashboard.refresh_interval_secs, 300);
+    assert_eq!(config.dashboard.inbox_interval_secs, None);
+    assert_eq!(config.dashboard.merged_window_days, 7);
+    assert_eq!(
+        config.github.auth_order,
+        vec![AuthMethod::Gh, AuthMethod::Env]
+    );
+    assert_eq!(config.dashboard.closed_window_days, 7);
+    assert_eq!(config.dashboard.max_concurrency, 4);
+    assert_eq!(config.dashboard.focus_refresh_debounce_secs, 60);
+    assert_eq!(config.dashboard.idle_refresh_delay_secs, 10);
+    assert_eq!(config.dashboard.inbox_sort, InboxSort::Newest);
+    assert_eq!(config.dashboard.open_unread_limit, 10);
+    assert_eq!(config.ui.columns, default_columns());
+    assert_eq!(config.ui.column_priority, default_column_priority());
+    assert_eq!(config.ui.size_thresholds, [10, 100, 500, 1000]);
+    assert_eq!(config.ui.age_buckets, [1, 3, 7]);
+    assert_eq!(config.ui.secondary_sort, SecondarySort::Number);
+    assert_eq!(config.ui.max_repos_per_org, None);
+    assert!(!config.ui.group_all_prs_by_repo);
     assert!(config.dashboard.show_draft_prs);
     assert_eq!(config.cache.ttl_secs, 600);
+    assert_eq!(config.cache.negative_ttl_secs, 300);
     assert_eq!(config.ui.nav_width_percent, 30);
+    assert!(config.ui.hide_empty_repos);
 }
 
 #[test]
@@ -86,6 +186,357 @@ users = ["alice", "bob"]
     assert_eq!(config.github.users, vec!["alice", "bob"]);
 }
 
+#[test]
+fn test_load_dedupes_owners_keeping_first_seen_order() {
+    let toml = r#"
+[github]
+orgs = ["acme", "tools", "ACME"]
+users = ["alice", "bob", "Alice"]
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    assert_eq!(config.github.orgs, vec!["acme", "tools"]);
+    assert_eq!(config.github.users, vec!["alice", "bob"]);
+    assert_eq!(
+        config.github.owner_logins(),
+        vec!["acme", "tools", "alice", "bob"]
+    );
+    // Collected rather than logged: logging isn't set up until after load
+    assert_eq!(
+        config.warnings,
+        vec![
+            "Ignoring duplicate owner ACME in github.orgs",
+            "Ignoring duplicate owner Alice in github.users",
+        ]
+    );
+}
+
+#[test]
+fn test_validate_login_matrix() {
+    for ok in ["octocat", "my-org", "a", "Org-2024", &"x".repeat(39)] {
+        assert_eq!(validate_login(ok), Ok(()), "{ok:?}");
+    }
+    for bad in [
+        "",
+        "my org",
+        "-leading",
+        "trailing-",
+        "double--hyphen",
+        "under_score",
+        "dot.ted",
+        "ünïcode",
+        &"x".repeat(40),
+    ] {
+        assert!(validate_login(bad).is_err(), "{bad:?}");
+    }
+}
+
+fn load_owners(orgs: &str, users: &str) -> anyhow::Result<AppConfig> {
+    let mut f = NamedTempFile::new().unwrap();
+    write!(f, "[github]\norgs = {}\nusers = {}\n", orgs, users).unwrap();
+    AppConfig::load(Some(f.path()))
+}
+
+#[test]
+fn test_load_rejects_invalid_logins_naming_the_entry() {
+    let err = load_owners(r#"["good", "my org"]"#, "[]").unwrap_err();
+    let msg = format!("{:#}", err);
+    assert!(msg.contains("\"my org\""), "{msg}");
+    assert!(msg.contains("github.orgs"), "{msg}");
+
+    let err = load_owners("[]", r#"["-alice"]"#).unwrap_err();
+    assert!(format!("{:#}", err).contains("github.users"));
+
+    // Enterprise Managed User logins carry a `_shortcode` suffix.
+    assert!(load_owners("[]", r#"["alice_acme"]"#).is_ok());
+}
+
+#[test]
+fn test_owner_in_both_orgs_and_users_is_listed_once() {
+    let config = load_owners(r#"["acme", "widgets"]"#, r#"["Acme", "octocat"]"#).unwrap();
+    let kinds: Vec<_> = config
+        .github
+        .owners()
+        .into_iter()
+        .map(|o| (o.login, o.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("acme".to_string(), OwnerKind::Unknown),
+            ("widgets".to_string(), OwnerKind::Org),
+            ("octocat".to_string(), OwnerKind::User),
+        ]
+    );
+    assert_eq!(config.github.owner_kind("ACME"), Some(OwnerKind::Unknown));
+    assert_eq!(config.github.owner_kind("nobody"), None);
+}
+
+#[test]
+fn test_owners_list_alongside_orgs_and_users() {
+    let mut f = NamedTempFile::new().unwrap();
+    write!(
+        f,
+        "[github]\norgs = [\"acme\"]\nusers = [\"alice\"]\nowners = [\"widgets\", \"ALICE\", \"widgets\"]\n"
+    )
+    .unwrap();
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    let kinds: Vec<_> = config
+        .github
+        .owners()
+        .into_iter()
+        .map(|o| (o.login, o.kind))
+        .collect();
+    // Typed entries win; untyped ones are looked up.
+    assert_eq!(
+        kinds,
+        vec![
+            ("acme".to_string(), OwnerKind::Org),
+            ("alice".to_string(), OwnerKind::User),
+            ("widgets".to_string(), OwnerKind::Unknown),
+        ]
+    );
+    assert_eq!(
+        config.github.search_scope(),
+        (
+            vec!["acme".to_string()],
+            vec!["alice".to_string(), "widgets".to_string()]
+        )
+    );
+
+    // Configs from before `owners` load as they did.
+    let legacy = load_owners(r#"["acme"]"#, r#"["alice"]"#).unwrap();
+    assert!(legacy.github.owners.is_empty());
+    assert_eq!(legacy.github.owner_logins(), vec!["acme", "alice"]);
+    assert_eq!(
+        legacy.github.search_scope(),
+        (vec!["acme".to_string()], vec!["alice".to_string()])
+    );
+}
+
+#[test]
+fn test_owners_entries_are_validated() {
+    let mut f = NamedTempFile::new().unwrap();
+    write!(f, "[github]\nowners = [\"not a login\"]\n").unwrap();
+    let err = AppConfig::load(Some(f.path())).unwrap_err();
+    assert!(format!("{:#}", err).contains("github.owners"));
+}
+
+#[test]
+fn test_validate_repo_matrix() {
+    for ok in [
+        "acme/api",
+        "my-org/payment-service",
+        "a/b.c_d-e",
+        "o/.github",
+    ] {
+        assert_eq!(validate_repo(ok), Ok(()), "{ok:?}");
+    }
+    for bad in [
+        "acme",
+        "acme/",
+        "/api",
+        "-acme/api",
+        "acme/a b",
+        "acme/..",
+        "a/b/c",
+    ] {
+        assert!(validate_repo(bad).is_err(), "{bad:?}");
+    }
+}
+
+#[test]
+fn test_repos_outside_listed_owners_are_standalone() {
+    let mut f = NamedTempFile::new().unwrap();
+    write!(
+        f,
+        "[github]\norgs = [\"acme\"]\nrepos = [\"big-org/payment-service\", \"ACME/api\", \"big-org/payment-service\"]\n"
+    )
+    .unwrap();
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    // acme/api is already covered by the org.
+    assert_eq!(
+        config.github.standalone_repos(),
+        vec!["big-org/payment-service".to_string()]
+    );
+    assert_eq!(
+        config.warnings,
+        vec!["Ignoring duplicate repo big-org/payment-service in github.repos"]
+    );
+
+    let mut f = NamedTempFile::new().unwrap();
+    write!(f, "[github]\nrepos = [\"payment-service\"]\n").unwrap();
+    let err = AppConfig::load(Some(f.path())).unwrap_err();
+    assert!(format!("{:#}", err).contains("github.repos"));
+}
+
+fn lookup(vars: Vec<(&'static str, PathBuf)>) -> impl Fn(&str) -> Option<OsString> {
+    move |name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.clone().into_os_string())
+    }
+}
+
+#[test]
+fn test_load_with_ghdash_config_env() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("custom.toml");
+    std::fs::write(&file, "[github]\norgs = [\"from-env\"]\n").unwrap();
+
+    let config = AppConfig::load_with(
+        None,
+        &lookup(vec![("GHDASH_CONFIG", file.clone())]),
+        None,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(config.github.orgs, vec!["from-env"]);
+    assert_eq!(config.source, Some(file));
+}
+
+#[test]
+fn test_load_with_missing_ghdash_config_fails() {
+    let missing = PathBuf::from("/nonexistent/ghdash.toml");
+    let err = AppConfig::load_with(None, &lookup(vec![("GHDASH_CONFIG", missing)]), None, &[])
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_CONFIG"));
+}
+
+#[test]
+fn test_load_with_xdg_config_home_before_home() {
+    let xdg = tempfile::tempdir().unwrap();
+    let home = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(xdg.path().join("ghdash")).unwrap();
+    std::fs::create_dir_all(home.path().join(".config/ghdash")).unwrap();
+    let xdg_file = xdg.path().join("ghdash/config.toml");
+    std::fs::write(&xdg_file, "[github]\norgs = [\"xdg\"]\n").unwrap();
+    std::fs::write(
+        home.path().join(".config/ghdash/config.toml"),
+        "[github]\norgs = [\"home\"]\n",
+    )
+    .unwrap();
+
+    let env = lookup(vec![
+        ("XDG_CONFIG_HOME", xdg.path().to_path_buf()),
+        ("HOME", home.path().to_path_buf()),
+    ]);
+    let config = AppConfig::load_with(None, &env, None, &[]).unwrap();
+    assert_eq!(config.github.orgs, vec!["xdg"]);
+    assert_eq!(config.source, Some(xdg_file));
+}
+
+#[test]
+fn test_load_with_no_file_uses_defaults() {
+    let home = tempfile::tempdir().unwrap();
+    let config = AppConfig::load_with(
+        None,
+        &lookup(vec![("HOME", home.path().to_path_buf())]),
+        None,
+        &[],
+    )
+    .unwrap();
+    assert!(config.github.orgs.is_empty());
+    assert_eq!(config.source, None);
+}
+
+fn with_overrides(toml: &str, vars: &[(&str, &str)]) -> anyhow::Result<AppConfig> {
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    let vars: Vec<(String, String)> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    AppConfig::load_with(Some(f.path()), &|_| None, None, &vars)
+}
+
+#[test]
+fn test_env_override_lists_replace_file_values() {
+    let config = with_overrides(
+        "[github]\norgs = [\"from-file\"]\n",
+        &[
+            ("GHDASH_GITHUB__ORGS", "my-org, other-org"),
+            ("GHDASH_UI__SIZE_THRESHOLDS", "1,2,3,4"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(config.github.orgs, vec!["my-org", "other-org"]);
+    assert_eq!(config.ui.size_thresholds, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_env_override_scalars() {
+    let config = with_overrides(
+        "",
+        &[
+            ("GHDASH_DASHBOARD__REFRESH_INTERVAL_SECS", "90"),
+            ("GHDASH_UI__REDUCE_MOTION", "TRUE"),
+            (
+                "GHDASH_GITHUB__API_URL",
+                "https://ghe.example.com/api/graphql",
+            ),
+            ("GHDASH_DASHBOARD__INBOX_SORT", "oldest"),
+            // No default to go by: inferred from the value.
+            ("GHDASH_UI__MAX_REPOS_PER_ORG", "5"),
+            ("GHDASH_UI__ASCII", "true"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(config.dashboard.refresh_interval_secs, 90);
+    assert!(config.ui.reduce_motion);
+    assert_eq!(config.github.api_url, "https://ghe.example.com/api/graphql");
+    assert_eq!(config.dashboard.inbox_sort, InboxSort::Oldest);
+    assert_eq!(config.ui.max_repos_per_org, Some(5));
+    assert_eq!(config.ui.ascii, Some(true));
+}
+
+#[test]
+fn test_env_override_nested_table() {
+    let config = with_overrides(
+        "",
+        &[
+            ("GHDASH_GITHUB__APP__APP_ID", "1234"),
+            ("GHDASH_GITHUB__APP__PRIVATE_KEY_PATH", "/keys/app.pem"),
+        ],
+    )
+    .unwrap();
+    let app = config.github.app.unwrap();
+    assert_eq!(app.app_id, Some(1234));
+    assert_eq!(app.installation_id, None);
+    assert_eq!(app.private_key_path, Some(PathBuf::from("/keys/app.pem")));
+}
+
+#[test]
+fn test_env_override_bad_values_name_the_variable() {
+    let err = with_overrides("", &[("GHDASH_UI__REDUCE_MOTION", "sometimes")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_UI__REDUCE_MOTION"));
+
+    let err =
+        with_overrides("", &[("GHDASH_DASHBOARD__REFRESH_INTERVAL_SECS", "soon")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_DASHBOARD__REFRESH_INTERVAL_SECS"));
+
+    let err = with_overrides("", &[("GHDASH_UI__SIZE_THRESHOLDS", "1,2,x,4")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_UI__SIZE_THRESHOLDS"));
+
+    // Right type, wrong value: caught when the config is rebuilt.
+    let err = with_overrides("", &[("GHDASH_DASHBOARD__INBOX_SORT", "sideways")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_DASHBOARD__INBOX_SORT"));
+
+    let err = with_overrides("", &[("GHDASH_NOPE__KEY", "1")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown config section"));
+
+    let err = with_overrides("", &[("GHDASH_UI__TYPO", "1")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_UI__TYPO: unknown config key ui.typo"));
+
+    let err = with_overrides("", &[("GHDASH_GITHUB__APP__APP_IDD", "1")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_GITHUB__APP__APP_IDD"));
+
+    let err = with_overrides("", &[("GHDASH_GITHUB__EXTRA_HEADERS", "x")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("can't be set from the environment"));
+}
+
 #[test]
 fn test_load_nonexistent_file_fails() {
     let result = AppConfig::load(Some(std::path::Path::new("/nonexistent/path/config.toml")));
@@ -115,3 +566,364 @@ fn test_default_config() {
     assert!(config.cache.dir.is_none());
     assert_eq!(config.ui.nav_width_percent, 30);
 }
+
+#[test]
+fn test_load_user_agent_and_extra_headers() {
+    let toml = r#"
+[github]
+user_agent = "ghdash/acme-gateway"
+
+[github.extra_headers]
+X-Acme-Route = "github"
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    assert_eq!(config.github.user_agent, "ghdash/acme-gateway");
+    assert_eq!(
+        config
+            .github
+            .extra_headers
+            .get("X-Acme-Route")
+            .map(String::as_str),
+        Some("github")
+    );
+    assert!(GithubClient::new("token", &config.github).is_ok());
+}
+
+#[test]
+fn test_default_user_agent_and_no_extra_headers() {
+    let config = AppConfig::default();
+    assert_eq!(config.github.user_agent, "ghdash");
+    assert!(config.github.extra_headers.is_empty());
+}
+
+#[test]
+fn test_client_rejects_invalid_header_name() {
+    let mut config = AppConfig::default();
+    config
+        .github
+        .extra_headers
+        .insert("Bad Header".into(), "x".into());
+    assert!(GithubClient::new("token", &config.github).is_err());
+}
+
+#[test]
+fn test_client_rejects_invalid_header_value() {
+    let mut config = AppConfig::default();
+    config
+        .github
+        .extra_headers
+        .insert("X-Route".into(), "line\nbreak".into());
+    assert!(GithubClient::new("token", &config.github).is_err());
+}
+
+#[test]
+fn test_client_rejects_authorization_header() {
+    let mut config = AppConfig::default();
+    config
+        .github
+        .extra_headers
+        .insert("authorization".into(), "token abc".into());
+    assert!(GithubClient::new("token", &config.github).is_err());
+}
+
+#[test]
+fn test_client_rejects_invalid_user_agent() {
+    let mut config = AppConfig::default();
+    config.github.user_agent = "ghdash\n".into();
+    assert!(GithubClient::new("token", &config.github).is_err());
+}
+
+#[test]
+fn test_invalid_header_name_fails_config_load() {
+    let toml = r#"
+[github.extra_headers]
+"Bad Header" = "x"
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    let err = AppConfig::load(Some(f.path())).unwrap_err();
+    assert!(format!("{:#}", err).contains("Invalid header name"));
+}
+
+#[test]
+fn test_extra_headers_read_env_values() {
+    let extra = HashMap::from([
+        ("X-Corp-Auth".to_string(), "env:CORP_AUTH".to_string()),
+        ("X-Route".to_string(), "github".to_string()),
+    ]);
+    let lookup = |name: &str| (name == "CORP_AUTH").then(|| "s3cret".to_string());
+    let headers = build_extra_headers(&extra, &lookup).unwrap();
+    assert_eq!(headers["x-corp-auth"], "s3cret");
+    assert!(headers["x-corp-auth"].is_sensitive());
+    assert_eq!(headers["x-route"], "github");
+}
+
+#[test]
+fn test_extra_header_from_undefined_env_fails() {
+    let extra = HashMap::from([("X-Corp-Auth".to_string(), "env:CORP_AUTH".to_string())]);
+    let err = build_extra_headers(&extra, &|_| None).unwrap_err();
+    assert!(format!("{:#}", err).contains("CORP_AUTH"));
+}
+
+fn test_data(file: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(file)
+}
+
+#[test]
+fn test_load_tls_options() {
+    let toml = r#"
+[github]
+ca_bundle = "/etc/ssl/corp-ca.pem"
+client_cert = "/etc/ghdash/client.pem"
+client_key = "/etc/ghdash/client.key"
+danger_accept_invalid_certs = true
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    assert_eq!(
+        config.github.ca_bundle,
+        Some(PathBuf::from("/etc/ssl/corp-ca.pem"))
+    );
+    assert_eq!(
+        config.github.client_cert,
+        Some(PathBuf::from("/etc/ghdash/client.pem"))
+    );
+    assert_eq!(
+        config.github.client_key,
+        Some(PathBuf::from("/etc/ghdash/client.key"))
+    );
+    assert!(config.github.danger_accept_invalid_certs);
+
+    let defaults = AppConfig::default().github;
+    assert!(defaults.ca_bundle.is_none() && defaults.client_cert.is_none());
+    assert!(!defaults.danger_accept_invalid_certs);
+}
+
+#[test]
+fn test_client_with_ca_bundle_and_client_cert() {
+    let mut config = AppConfig::default();
+    config.github.ca_bundle = Some(test_data("client_cert.pem"));
+    config.github.client_cert = Some(test_data("client_cert.pem"));
+    config.github.client_key = Some(test_data("client_key.pem"));
+    assert!(GithubClient::new("token", &config.github).is_ok());
+}
+
+#[test]
+fn test_missing_ca_bundle_names_the_path() {
+    let mut config = AppConfig::default();
+    config.github.ca_bundle = Some(PathBuf::from("/nonexistent/ca.pem"));
+    let err = GithubClient::new("token", &config.github).unwrap_err();
+    let msg = format!("{:#}", err);
+    assert!(msg.contains("github.ca_bundle") && msg.contains("/nonexistent/ca.pem"));
+}
+
+#[test]
+fn test_ca_bundle_without_certificates_fails() {
+    let mut config = AppConfig::default();
+    config.github.ca_bundle = Some(test_data("client_key.pem"));
+    let err = GithubClient::new("token", &config.github).unwrap_err();
+    assert!(format!("{:#}", err).contains("no PEM certificates"));
+}
+
+#[test]
+fn test_client_cert_without_key_fails() {
+    let mut config = AppConfig::default();
+    config.github.client_cert = Some(test_data("client_cert.pem"));
+    let err = GithubClient::new("token", &config.github).unwrap_err();
+    assert!(format!("{:#}", err).contains("client_cert.pem"));
+
+    let mut config = AppConfig::default();
+    config.github.client_key = Some(test_data("client_key.pem"));
+    assert!(GithubClient::new("token", &config.github).is_err());
+}
+
+#[test]
+fn test_unknown_auth_method_fails() {
+    let toml = r#"
+[github]
+auth_order = ["env", "vault"]
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    assert!(AppConfig::load(Some(f.path())).is_err());
+}
+
+#[test]
+fn test_stdin_in_auth_order_points_to_the_flag() {
+    let toml = r#"
+[github]
+auth_order = ["stdin", "env"]
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    let err = AppConfig::load(Some(f.path())).unwrap_err();
+    assert!(format!("{err:#}").contains("--token-stdin"));
+}
+
+#[test]
+fn test_unordered_bounds_fail() {
+    for (toml, name) in [
+        (
+            "[ui]\nsize_thresholds = [10, 500, 100, 1000]\n",
+            "ui.size_thresholds",
+        ),
+        ("[ui]\nage_buckets = [3, 3, 7]\n", "ui.age_buckets"),
+    ] {
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(toml.as_bytes()).unwrap();
+        let err = AppConfig::load(Some(f.path())).unwrap_err();
+        assert!(format!("{err:#}").contains(name));
+    }
+}
+
+#[test]
+fn test_unknown_column_fails() {
+    let toml = r#"
+[ui]
+columns = ["number", "reviewers"]
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    let err = AppConfig::load(Some(f.path())).unwrap_err();
+    assert!(format!("{:#}", err).contains("reviewers"));
+}
+
+#[test]
+fn test_load_org_colors() {
+    let toml = r##"
+[github]
+orgs = ["my-org", "other-org"]
+
+[[github.org_color]]
+org = "my-org"
+color = "lightblue"
+
+[[github.org_color]]
+org = "other-org"
+color = "#ff8800"
+"##;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    assert_eq!(
+        config.github.org_color,
+        vec![
+            OrgColor {
+                org: "my-org".into(),
+                color: "lightblue".into()
+            },
+            OrgColor {
+                org: "other-org".into(),
+                color: "#ff8800".into()
+            },
+        ]
+    );
+    assert!(AppConfig::default().github.org_color.is_empty());
+}
+
+fn fake_env(name: &str) -> Option<String> {
+    match name {
+        "HOME" => Some("/home/me".into()),
+        "GHE_HOST" => Some("github.example.com".into()),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_interpolate_env() {
+    assert_eq!(
+        interpolate_env("${HOME}/.ghdash-cache", &fake_env).unwrap(),
+        "/home/me/.ghdash-cache"
+    );
+    assert_eq!(
+        interpolate_env("https://${GHE_HOST}/api/${GHE_HOST}", &fake_env).unwrap(),
+        "https://github.example.com/api/github.example.com"
+    );
+    // A bare `$` is not a reference.
+    assert_eq!(interpolate_env("cost$5", &fake_env).unwrap(), "cost$5");
+    // `$${` escapes a literal `${`.
+    assert_eq!(
+        interpolate_env("$${NOPE} and ${HOME}", &fake_env).unwrap(),
+        "${NOPE} and /home/me"
+    );
+    assert_eq!(interpolate_env("a$${b", &fake_env).unwrap(), "a${b");
+}
+
+#[test]
+fn test_interpolate_env_errors() {
+    let err = interpolate_env("${NOPE}/cache", &fake_env).unwrap_err();
+    assert!(err.to_string().contains("NOPE"));
+    assert!(interpolate_env("${HOME", &fake_env).is_err());
+}
+
+#[test]
+fn test_load_interpolates_string_values() {
+    let home = std::env::var("HOME").unwrap();
+    let toml = r#"
+# ${NOT_EXPANDED_IN_COMMENTS}
+[github]
+include_repos = ["${HOME}"]
+
+[cache]
+dir = "${HOME}/.ghdash-cache"
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    assert_eq!(
+        config.cache.dir,
+        Some(std::path::PathBuf::from(format!("{}/.ghdash-cache", home)))
+    );
+    assert_eq!(config.github.include_repos, vec![home]);
+}
+
+#[test]
+fn test_load_fails_on_undefined_variable() {
+    let toml = r#"
+[github]
+api_url = "https://${GHDASH_TEST_SURELY_UNDEFINED}/api/graphql"
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+    let err = AppConfig::load(Some(f.path())).unwrap_err();
+    assert!(format!("{:#}", err).contains("GHDASH_TEST_SURELY_UNDEFINED"));
+}
+
+#[test]
+fn test_github_app_section() {
+    let toml = r#"
+[github]
+orgs = ["my-org"]
+auth_order = ["app", "gh"]
+
+[github.app]
+app_id = 12345
+installation_id = 678
+private_key_path = "/etc/ghdash/app.pem"
+"#;
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(toml.as_bytes()).unwrap();
+
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    assert_eq!(
+        config.github.auth_order,
+        vec![AuthMethod::App, AuthMethod::Gh]
+    );
+    assert_eq!(
+        config.github.app,
+        Some(GithubAppConfig {
+            app_id: Some(12345),
+            installation_id: Some(678),
+            private_key_path: Some("/etc/ghdash/app.pem".into()),
+        })
+    );
+    assert_eq!(AppConfig::default().github.app, None);
+}
@@ -0,0 +1,598 @@
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use tempfile::NamedTempFile;
+use tokio::sync::Notify;
+
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use ghdash::app::actions::{Action, DataPayload, SideEffect};
+use ghdash::app::event_loop::{Dispatcher, map_event_to_action};
+use ghdash::app::state::{AppState, ContentView, FocusedPane, NavNode};
+use ghdash::cache::CacheStore;
+use ghdash::github::fixtures::FixtureSource;
+use ghdash::github::graphql::{GithubClient, GraphqlError, Page, paginate};
+use ghdash::github::source::Fetch;
+use ghdash::github::{
+    CancelToken, DataSource, PrDetail, PrHistoryKind, PrState, PrStateFilter, PullRequest, Repo,
+};
+use ghdash::util::config::{AppConfig, OwnerKind};
+
+mod common;
+
+use common::make_repo;
+
+fn make_pr(owner: &str, name: &str, number: u32, state: PrState) -> PullRequest {
+    let mut pr = common::make_pr(owner, name, number);
+    let finished = (state != PrState::Open).then_some(pr.created_at);
+    pr.state = state;
+    pr.merged_at = finished.filter(|_| state == PrState::Merged);
+    pr.closed_at = finished;
+    pr
+}
+
+fn fake_fixtures() -> FixtureSource {
+    let prs = vec![
+        make_pr("acme", "api", 1, PrState::Open),
+        make_pr("acme", "web", 2, PrState::Open),
+        make_pr("octocat", "dotfiles", 3, PrState::Open),
+        make_pr("acme", "api", 4, PrState::Merged),
+        make_pr("acme", "web", 5, PrState::Closed),
+    ];
+    FixtureSource::new(
+        "octocat",
+        vec![
+            make_repo("acme", "api", 1),
+            make_repo("acme", "web", 1),
+            make_repo("octocat", "dotfiles", 1),
+        ],
+        vec![prs[1].clone()],
+        prs,
+    )
+}
+
+fn fake_source() -> Arc<dyn DataSource> {
+    Arc::new(fake_fixtures())
+}
+
+/// A dispatcher over `state` fetching from `source`, with `cache`.
+fn dispatcher(
+    state: AppState,
+    config: &AppConfig,
+    source: Arc<dyn DataSource>,
+    cache: Option<CacheStore>,
+) -> Dispatcher {
+    let login = state.viewer_login.clone();
+    Dispatcher::new(state, config.clone(), source, login, cache)
+}
+
+/// Run `effect` the way the event loop does: spawn it and handle every action
+/// it reports, with whatever effects those start, until all of them have
+/// finished. Returns the owner of every repo list that was loaded.
+async fn drive(d: &mut Dispatcher, effect: SideEffect) -> Vec<String> {
+    d.spawn(effect);
+    let mut repo_loads = Vec::new();
+    while let Some(action) = d.next_until_idle().await {
+        if let Action::DataLoaded(DataPayload::OrgRepos { org, .. }) = &action {
+            repo_loads.push(org.clone());
+        }
+        d.handle(action);
+    }
+    repo_loads
+}
+
+#[tokio::test]
+async fn test_refresh_all_loads_every_section_from_the_source() {
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["acme".into()];
+    config.github.users = vec!["octocat".into()];
+    let state = AppState::new("octocat".into(), vec!["acme".into(), "octocat".into()]);
+    let mut d = dispatcher(state, &config, fake_source(), None);
+
+    drive(&mut d, SideEffect::RefreshAll).await;
+
+    let state = &d.state;
+    assert_eq!(state.orgs["acme"].repos.len(), 2);
+    assert_eq!(state.orgs["octocat"].repos.len(), 1);
+    assert_eq!(state.all_open_prs.len(), 3);
+    assert_eq!(state.inbox.len(), 1);
+    assert_eq!(state.inbox[0].number, 2);
+    assert_eq!(state.recently_merged.len(), 1);
+    assert_eq!(state.recently_closed.len(), 1);
+    assert!(!state.loading);
+}
+
+#[tokio::test]
+async fn test_batched_org_fetch_uses_the_source() {
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["acme".into(), "octocat".into()];
+    config.github.batch_queries = true;
+    let state = AppState::new("octocat".into(), config.github.orgs.clone());
+    let mut d = dispatcher(state, &config, fake_source(), None);
+
+    drive(
+        &mut d,
+        SideEffect::FetchOrgReposBatched(config.github.orgs.clone()),
+    )
+    .await;
+
+    let state = &d.state;
+    assert_eq!(state.orgs["acme"].repos.len(), 2);
+    assert_eq!(state.orgs["octocat"].repos.len(), 1);
+}
+
+#[tokio::test]
+async fn test_repeated_owner_is_fetched_once() {
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(b"[github]\norgs = [\"acme\", \"Acme\"]\nusers = [\"octocat\", \"ACME\"]\n")
+        .unwrap();
+    let config = AppConfig::load(Some(f.path())).unwrap();
+    let state = AppState::new("octocat".into(), config.github.owner_logins());
+    let mut d = dispatcher(state, &config, fake_source(), None);
+
+    let loads = drive(&mut d, SideEffect::RefreshAll).await;
+
+    let state = &d.state;
+    let acme_nodes = state
+        .nav_nodes
+        .iter()
+        .filter(|n| matches!(n, NavNode::Org(name) if name.eq_ignore_ascii_case("acme")))
+        .count();
+    assert_eq!(acme_nodes, 1);
+    assert_eq!(loads.iter().filter(|o| o.as_str() == "acme").count(), 1);
+    assert_eq!(state.all_open_prs.len(), 3);
+}
+
+/// Page requests made for `paged` repos, and the gate every page after the
+/// first waits on.
+static PAGE_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+static PAGE_GATE: Notify = Notify::const_new();
+
+/// The fixture data, with `octocat` a user account, `acme` an organization,
+/// and no other owners. `flaky` is an org whose repos can't be reached, and
+/// `paged` repos list five pages of open PRs. Counts owner type lookups.
+struct Accounts {
+    fixtures: FixtureSource,
+    lookups: AtomicUsize,
+}
+
+impl DataSource for Accounts {
+    fn is_fixture(&self) -> bool {
+        true
+    }
+
+    fn fetch_viewer(&self) -> BoxFuture<'_, anyhow::Result<String>> {
+        self.fixtures.fetch_viewer()
+    }
+
+    fn fetch_owner_kind<'a>(&'a self, login: &'a str) -> Fetch<'a, Option<OwnerKind>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        let kind = match login {
+            "acme" => Some(OwnerKind::Org),
+            "octocat" => Some(OwnerKind::User),
+            _ => None,
+        };
+        futures::future::ready(Ok((kind, None))).boxed()
+    }
+
+    fn fetch_org_repos<'a>(&'a self, org: &'a str) -> Fetch<'a, Vec<Repo>> {
+        if org == "flaky" {
+            return futures::future::ready(Err(anyhow::anyhow!("connection reset by peer")))
+                .boxed();
+        }
+        if org != "acme" {
+            let err = GraphqlError {
+                kind: Some("NOT_FOUND".into()),
+                message: format!("Could not resolve to an Organization with the login of '{org}'."),
+            };
+            return futures::future::ready(Err(err.into())).boxed();
+        }
+        self.fixtures.fetch_org_repos(org)
+    }
+
+    fn fetch_orgs_batched<'a>(&'a self, orgs: &'a [String]) -> Fetch<'a, Vec<Vec<Repo>>> {
+        self.fixtures.fetch_orgs_batched(orgs)
+    }
+
+    fn fetch_user_repos<'a>(&'a self, user: &'a str) -> Fetch<'a, Vec<Repo>> {
+        self.fixtures.fetch_user_repos(user)
+    }
+
+    fn fetch_inbox<'a>(
+        &'a self,
+        login: &'a str,
+        teams: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        self.fixtures.fetch_inbox(login, teams)
+    }
+
+    fn fetch_all_open_prs<'a>(
+        &'a self,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        self.fixtures.fetch_all_open_prs(orgs, users, repos)
+    }
+
+    fn fetch_involved_prs<'a>(
+        &'a self,
+        login: &'a str,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        self.fixtures.fetch_involved_prs(login, orgs, users, repos)
+    }
+
+    fn fetch_pr_history<'a>(
+        &'a self,
+        kind: PrHistoryKind,
+        orgs: &'a [String],
+        users: &'a [String],
+        repos: &'a [String],
+        window_days: u32,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        self.fixtures
+            .fetch_pr_history(kind, orgs, users, repos, window_days)
+    }
+
+    fn fetch_repo_prs_in_state<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        state: PrStateFilter,
+        cancel: &'a CancelToken,
+    ) -> Fetch<'a, Vec<PullRequest>> {
+        if owner == "flaky" {
+            return futures::future::ready(Err(anyhow::anyhow!("connection reset by peer")))
+                .boxed();
+        }
+        if owner == "paged" {
+            return async move {
+                paginate(cancel, |cursor| async move {
+                    let page: u32 = cursor.map_or(0, |c| c.parse().unwrap());
+                    PAGE_REQUESTS.fetch_add(1, Ordering::SeqCst);
+                    if page > 0 {
+                        PAGE_GATE.notified().await;
+                    }
+                    Ok(Page {
+                        items: vec![make_pr(owner, name, page + 1, PrState::Open)],
+                        next_cursor: (page < 4).then(|| (page + 1).to_string()),
+                        rate_limit: None,
+                    })
+                })
+                .await
+            }
+            .boxed();
+        }
+        self.fixtures
+            .fetch_repo_prs_in_state(owner, name, state, cancel)
+    }
+
+    fn fetch_pr<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PullRequest> {
+        self.fixtures.fetch_pr(owner, name, number)
+    }
+
+    fn fetch_pr_detail<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> Fetch<'a, PrDetail> {
+        self.fixtures.fetch_pr_detail(owner, name, number)
+    }
+
+    fn fetch_pr_diff<'a>(
+        &'a self,
+        owner: &'a str,
+        name: &'a str,
+        number: u32,
+    ) -> BoxFuture<'a, anyhow::Result<String>> {
+        self.fixtures.fetch_pr_diff(owner, name, number)
+    }
+}
+
+#[tokio::test]
+async fn test_failed_refresh_keeps_old_cache_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = CacheStore::new(dir.path().to_path_buf(), 600);
+    let (old_repos, _) = fake_fixtures().fetch_org_repos("acme").await.unwrap();
+    cache.set("org_repos_v2_flaky", &old_repos).unwrap();
+    cache.set("org_repos_v2_acme", &Vec::<Repo>::new()).unwrap();
+    let source = Arc::new(Accounts {
+        fixtures: fake_fixtures(),
+        lookups: AtomicUsize::new(0),
+    });
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["acme".into(), "flaky".into()];
+    let state = AppState::new("octocat".into(), config.github.owner_logins());
+    let mut d = dispatcher(state, &config, source, Some(cache.clone()));
+
+    drive(&mut d, SideEffect::RefreshAll).await;
+
+    // acme was fetched past its cached entry and replaced it ...
+    assert_eq!(d.state.orgs["acme"].repos.len(), 2);
+    assert_eq!(
+        cache.get::<Vec<Repo>>("org_repos_v2_acme").unwrap().len(),
+        2
+    );
+    // ... while flaky's old list is still there for the next start.
+    assert!(cache.get::<Vec<Repo>>("org_repos_v2_flaky").is_none());
+    let (kept, _) = cache
+        .get_ignoring_ttl::<Vec<Repo>>("org_repos_v2_flaky")
+        .unwrap();
+    assert_eq!(kept.len(), old_repos.len());
+}
+
+#[tokio::test]
+async fn test_failed_finished_pr_fetch_is_recorded_against_the_repo() {
+    let source = Arc::new(Accounts {
+        fixtures: fake_fixtures(),
+        lookups: AtomicUsize::new(0),
+    });
+    let config = AppConfig::default();
+    let state = AppState::new("octocat".into(), vec!["flaky".into()]);
+    let mut d = dispatcher(state, &config, source, None);
+
+    drive(
+        &mut d,
+        SideEffect::FetchRepoFinishedPrs {
+            owner: "flaky".into(),
+            name: "api".into(),
+            state: PrStateFilter::Merged,
+            cancel: CancelToken::new(),
+        },
+    )
+    .await;
+
+    assert!(
+        d.state
+            .failed_finished_prs
+            .contains(&("flaky/api".to_string(), PrStateFilter::Merged))
+    );
+    assert!(
+        d.state
+            .error_message
+            .as_deref()
+            .unwrap()
+            .contains("flaky/api")
+    );
+}
+
+#[tokio::test]
+async fn test_verify_viewer_replaces_a_stale_cached_login() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = CacheStore::new(dir.path().to_path_buf(), 600);
+    cache.set("viewer_abc", &"someone-else").unwrap();
+    let config = AppConfig::default();
+    let state = AppState::new("someone-else".into(), vec![]);
+    let mut d = dispatcher(state, &config, fake_source(), Some(cache.clone()));
+
+    let verify = |expected: &str| SideEffect::VerifyViewer {
+        expected: expected.into(),
+        cache_key: "viewer_abc".into(),
+    };
+    drive(&mut d, verify("someone-else")).await;
+
+    // The fixtures sign in as octocat.
+    assert_eq!(d.state.viewer_login, "octocat");
+    assert!(
+        d.state
+            .error_message
+            .as_deref()
+            .unwrap()
+            .contains("octocat")
+    );
+    // Reloading expired the cache; the logins were still replaced.
+    let cached = |key: &str| {
+        cache
+            .get_ignoring_ttl::<String>(key)
+            .map(|(login, _)| login)
+    };
+    assert_eq!(cached("viewer_abc").as_deref(), Some("octocat"));
+    assert_eq!(cached("viewer").as_deref(), Some("octocat"));
+
+    // A matching login changes nothing.
+    d.state.error_message = None;
+    drive(&mut d, verify("octocat")).await;
+    assert!(d.state.error_message.is_none());
+}
+
+#[tokio::test]
+async fn test_unreachable_github_when_verifying_falls_back_to_the_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = CacheStore::new(dir.path().to_path_buf(), 600);
+    let (inbox, _) = fake_fixtures().fetch_inbox("octocat", &[]).await.unwrap();
+    cache.set("inbox_v2_octocat", &inbox).unwrap();
+    let mut config = AppConfig::default();
+    // Nothing listens on port 1.
+    config.github.api_url = "https://127.0.0.1:1/graphql".into();
+    let client = GithubClient::new("token", &config.github).unwrap();
+    let state = AppState::new("octocat".into(), vec![]);
+    let mut d = dispatcher(state, &config, Arc::new(client), Some(cache));
+
+    d.spawn(SideEffect::VerifyViewer {
+        expected: "octocat".into(),
+        cache_key: "viewer_abc".into(),
+    });
+    let action = d.next_until_idle().await.unwrap();
+    assert!(matches!(action, Action::WentOffline));
+
+    // As the event loop does.
+    d.go_offline(Arc::new(GithubClient::offline(&config.github)));
+    d.handle(action);
+    while let Some(action) = d.next_until_idle().await {
+        d.handle(action);
+    }
+    assert!(d.state.offline);
+    assert_eq!(d.state.inbox.len(), 1);
+    assert!(d.state.offline_data_as_of.is_some());
+}
+
+#[tokio::test]
+async fn test_owner_types_are_resolved_once_and_cached() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = CacheStore::new(dir.path().to_path_buf(), 600);
+    let source = Arc::new(Accounts {
+        fixtures: fake_fixtures(),
+        lookups: AtomicUsize::new(0),
+    });
+    let mut config = AppConfig::default();
+    config.github.owners = vec!["acme".into(), "octocat".into()];
+    let state = AppState::new("octocat".into(), config.github.owner_logins());
+    let mut d = dispatcher(state, &config, source.clone(), Some(cache.clone()));
+
+    for owner in config.github.owners() {
+        let effect = SideEffect::fetch_repos(&owner);
+        assert!(matches!(effect, SideEffect::FetchOwnerRepos(_)));
+        drive(&mut d, effect).await;
+    }
+    assert_eq!(d.state.orgs["acme"].repos.len(), 2);
+    assert_eq!(d.state.orgs["octocat"].repos.len(), 1);
+    assert_eq!(source.lookups.load(Ordering::SeqCst), 2);
+
+    // A refetch past the repo cache still knows octocat is a user.
+    cache.invalidate("owner_repos_v2_octocat").unwrap();
+    let loads = drive(&mut d, SideEffect::FetchOwnerRepos("octocat".into())).await;
+    assert_eq!(loads, vec!["octocat".to_string()]);
+    assert_eq!(source.lookups.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_unknown_owner_is_flagged_in_the_nav_not_a_modal() {
+    let source = Arc::new(Accounts {
+        fixtures: FixtureSource::new("octocat", vec![], vec![], vec![]),
+        lookups: AtomicUsize::new(0),
+    });
+    let mut config = AppConfig::default();
+    config.github.owners = vec!["nobody".into()];
+    let mut state = AppState::new("octocat".into(), config.github.owner_logins());
+    state.loading_orgs.insert("nobody".into());
+
+    let mut d = dispatcher(state, &config, source, None);
+
+    let loads = drive(&mut d, SideEffect::FetchOwnerRepos("nobody".into())).await;
+
+    let state = &d.state;
+    assert!(loads.is_empty());
+    assert!(state.unknown_owners.contains("nobody"));
+    assert!(state.error_message.is_none());
+    assert!(state.loading_orgs.is_empty());
+}
+
+#[tokio::test]
+async fn test_leaving_a_repo_view_mid_fetch_keeps_the_pages_so_far() {
+    let source = Arc::new(Accounts {
+        fixtures: FixtureSource::new("octocat", vec![], vec![], vec![]),
+        lookups: AtomicUsize::new(0),
+    });
+    let config = AppConfig::default();
+    let mut state = AppState::new("octocat".into(), vec![]);
+    state.content_view = ContentView::RepoPrList {
+        owner: "paged".into(),
+        name: "api".into(),
+    };
+    let mut d = dispatcher(state, &config, source, None);
+
+    d.dispatch(Action::RefreshCurrentView);
+    while PAGE_REQUESTS.load(Ordering::SeqCst) < 2 {
+        tokio::task::yield_now().await;
+    }
+
+    // Leave for the inbox while the second page is in flight.
+    d.state.focused_pane = FocusedPane::Navigation;
+    d.state.nav_cursor = d
+        .state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::MyInbox))
+        .unwrap();
+    d.dispatch(Action::Select);
+    assert_eq!(d.state.content_view, ContentView::Inbox);
+    PAGE_GATE.notify_one();
+    while let Some(action) = d.next_until_idle().await {
+        d.handle(action);
+    }
+
+    assert_eq!(PAGE_REQUESTS.load(Ordering::SeqCst), 2);
+    let numbers: Vec<u32> = d.state.repo_prs["paged/api"]
+        .iter()
+        .map(|pr| pr.number)
+        .collect();
+    assert_eq!(numbers, vec![1, 2]);
+}
+
+#[test]
+fn test_ctrl_j_k_cycle_owners_from_the_dashboard() {
+    let state = AppState::new("octocat".into(), vec!["acme".into()]);
+    let key = |c: char, modifiers: KeyModifiers| {
+        map_event_to_action(
+            &Event::Key(KeyEvent::new(KeyCode::Char(c), modifiers)),
+            &state,
+        )
+    };
+    assert!(matches!(
+        key('j', KeyModifiers::CONTROL),
+        Some(Action::NextOwner)
+    ));
+    assert!(matches!(
+        key('k', KeyModifiers::CONTROL),
+        Some(Action::PrevOwner)
+    ));
+    assert!(matches!(
+        key('j', KeyModifiers::NONE),
+        Some(Action::MoveDown)
+    ));
+    assert!(matches!(key('k', KeyModifiers::NONE), Some(Action::MoveUp)));
+}
+
+#[test]
+fn test_pending_jump_takes_only_plain_letters() {
+    let mut state = AppState::new("octocat".into(), vec!["acme".into()]);
+    state.pending_jump = true;
+    let key = |c: char, modifiers: KeyModifiers| {
+        map_event_to_action(
+            &Event::Key(KeyEvent::new(KeyCode::Char(c), modifiers)),
+            &state,
+        )
+    };
+    assert!(matches!(
+        key('c', KeyModifiers::NONE),
+        Some(Action::JumpToLetter('c'))
+    ));
+    assert!(matches!(
+        key('C', KeyModifiers::SHIFT),
+        Some(Action::JumpToLetter('C'))
+    ));
+    assert!(matches!(
+        key('c', KeyModifiers::CONTROL),
+        Some(Action::Quit)
+    ));
+    assert!(matches!(
+        key('j', KeyModifiers::ALT),
+        Some(Action::ClearPending)
+    ));
+}
+
+#[test]
+fn test_open_menu_is_reachable_from_the_inbox() {
+    let mut state = AppState::new("octocat".into(), vec!["acme".into()]);
+    state.content_view = ContentView::Inbox;
+    state.focused_pane = FocusedPane::Content;
+    let key = |c: char| {
+        map_event_to_action(
+            &Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT)),
+            &state,
+        )
+    };
+    assert!(matches!(key('O'), Some(Action::OpenMenu)));
+    assert!(matches!(key('U'), Some(Action::OpenUnreadInbox)));
+}
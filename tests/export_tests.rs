@@ -0,0 +1,60 @@
+use ghdash::github::models::PullRequest;
+use ghdash::util::export::{markdown_escape, pr_markdown_link, prs_markdown_table};
+
+mod common;
+
+fn make_pr(repo_name: &str, number: u32, title: &str) -> PullRequest {
+    PullRequest {
+        title: title.into(),
+        author: "octocat".into(),
+        ..common::make_pr("org-a", repo_name, number)
+    }
+}
+
+#[test]
+fn test_markdown_escape_pipes_brackets_and_backslashes() {
+    assert_eq!(markdown_escape("plain title"), "plain title");
+    assert_eq!(markdown_escape("a | b"), "a \\| b");
+    assert_eq!(markdown_escape("[WIP] fix"), "\\[WIP\\] fix");
+    assert_eq!(markdown_escape("C:\\path"), "C\\:\\\\path");
+}
+
+#[test]
+fn test_markdown_escape_commonmark_punctuation() {
+    assert_eq!(
+        markdown_escape("*bold* _em_ `code` <b> ~x~"),
+        "\\*bold\\* \\_em\\_ \\`code\\` \\<b\\> \\~x\\~"
+    );
+    assert_eq!(markdown_escape("# 1. a!"), "\\# 1\\. a\\!");
+    // Non-ASCII punctuation has no Markdown meaning
+    assert_eq!(markdown_escape("naïve — ok"), "naïve — ok");
+}
+
+#[test]
+fn test_pr_markdown_link() {
+    let pr = make_pr("api", 123, "[WIP] Fix login | redirect");
+    assert_eq!(
+        pr_markdown_link(&pr),
+        "[api#123: \\[WIP\\] Fix login \\| redirect](https://github.com/org-a/api/pull/123)"
+    );
+}
+
+#[test]
+fn test_prs_markdown_table() {
+    let prs = vec![make_pr("api", 1, "First"), make_pr("web", 2, "a|b")];
+    assert_eq!(
+        prs_markdown_table(&prs),
+        "| PR | Title | Author |\n\
+         | --- | --- | --- |\n\
+         | [api#1](https://github.com/org-a/api/pull/1) | First | octocat |\n\
+         | [web#2](https://github.com/org-a/web/pull/2) | a\\|b | octocat |\n"
+    );
+}
+
+#[test]
+fn test_prs_markdown_table_empty_is_just_the_header() {
+    assert_eq!(
+        prs_markdown_table(&[]),
+        "| PR | Title | Author |\n| --- | --- | --- |\n"
+    );
+}
@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use ghdash::github::fixtures::FixtureSource;
+use ghdash::github::{CancelToken, DataSource, PrHistoryKind, PrState, PrStateFilter};
+
+fn sample() -> FixtureSource {
+    FixtureSource::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/sample")).unwrap()
+}
+
+fn owners(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_sample_fixtures_load() {
+    let fixtures = sample();
+    assert_eq!(fixtures.fetch_viewer().await.unwrap(), "octocat");
+    assert_eq!(fixtures.owners(), owners(&["acme", "octocat"]));
+}
+
+#[test]
+fn test_fixture_source_is_flagged_as_fixture() {
+    assert!(sample().is_fixture());
+}
+
+#[test]
+fn test_missing_required_file_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("repos.json"), "[]").unwrap();
+    let err = FixtureSource::load(dir.path()).unwrap_err();
+    assert!(format!("{err:#}").contains("inbox.json"));
+}
+
+#[tokio::test]
+async fn test_viewer_defaults_without_viewer_file() {
+    let dir = tempfile::tempdir().unwrap();
+    for file in ["repos.json", "inbox.json", "prs.json"] {
+        std::fs::write(dir.path().join(file), "[]").unwrap();
+    }
+    let fixtures = FixtureSource::load(dir.path()).unwrap();
+    assert_eq!(fixtures.fetch_viewer().await.unwrap(), "octocat");
+    assert!(fixtures.owners().is_empty());
+}
+
+#[tokio::test]
+async fn test_repos_are_filtered_by_owner() {
+    let fixtures = sample();
+    let (repos, _) = fixtures.fetch_org_repos("acme").await.unwrap();
+    assert!(!repos.is_empty());
+    assert!(repos.iter().all(|r| r.owner == "acme"));
+
+    let (batched, _) = fixtures
+        .fetch_orgs_batched(&owners(&["octocat", "nobody"]))
+        .await
+        .unwrap();
+    assert_eq!(batched.len(), 2);
+    assert!(batched[0].iter().all(|r| r.owner == "octocat"));
+    assert!(batched[1].is_empty());
+}
+
+#[tokio::test]
+async fn test_all_open_prs_only_lists_open_prs_of_the_owners() {
+    let fixtures = sample();
+    let (prs, _) = fixtures
+        .fetch_all_open_prs(&owners(&["acme"]), &[], &[])
+        .await
+        .unwrap();
+    assert!(!prs.is_empty());
+    assert!(
+        prs.iter()
+            .all(|pr| pr.state == PrState::Open && pr.repo_owner == "acme")
+    );
+}
+
+#[tokio::test]
+async fn test_history_is_newest_first() {
+    let fixtures = sample();
+    let (prs, _) = fixtures
+        .fetch_pr_history(PrHistoryKind::Merged, &owners(&["acme"]), &[], &[], 7)
+        .await
+        .unwrap();
+    assert!(prs.len() > 1);
+    assert!(prs.iter().all(|pr| pr.state == PrState::Merged));
+    assert!(prs.windows(2).all(|w| w[0].merged_at >= w[1].merged_at));
+}
+
+#[tokio::test]
+async fn test_repo_prs_are_filtered_by_repo_and_state() {
+    let fixtures = sample();
+    let cancel = CancelToken::new();
+    let (open, _) = fixtures
+        .fetch_repo_prs("acme", "api", &cancel)
+        .await
+        .unwrap();
+    assert!(
+        open.iter()
+            .all(|pr| pr.repo_name == "api" && pr.state == PrState::Open)
+    );
+    let (closed, _) = fixtures
+        .fetch_repo_prs_in_state("acme", "infra", PrStateFilter::Closed, &cancel)
+        .await
+        .unwrap();
+    assert_eq!(closed.len(), 1);
+}
+
+#[tokio::test]
+async fn test_single_pr_detail_and_diff() {
+    let fixtures = sample();
+    let (pr, _) = fixtures.fetch_pr("acme", "api", 101).await.unwrap();
+    assert_eq!(pr.number, 101);
+    assert!(fixtures.fetch_pr("acme", "api", 9999).await.is_err());
+
+    let (detail, _) = fixtures.fetch_pr_detail("acme", "api", 101).await.unwrap();
+    assert!(!detail.commits.is_empty());
+    assert!(fixtures.fetch_pr_detail("acme", "api", 104).await.is_err());
+
+    assert!(fixtures.fetch_pr_diff("acme", "api", 101).await.is_err());
+}
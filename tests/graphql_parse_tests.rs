@@ -1,4 +1,7 @@
-use ghdash::github::models::{CiStatus, PullRequest, Repo};
+use ghdash::github::GithubClient;
+use ghdash::github::graphql::parse_search_pr;
+use ghdash::github::models::{CiStatus, PrHistoryKind, PrState, PullRequest, Repo};
+use ghdash::util::config::OwnerKind;
 
 #[test]
 fn test_repo_full_name() {
@@ -9,6 +12,7 @@ fn test_repo_full_name() {
         description: Some("A repo".into()),
         open_pr_count: 5,
         is_archived: false,
+        topics: vec![],
     };
     assert_eq!(repo.full_name(), "my-org/my-repo");
 }
@@ -25,12 +29,22 @@ fn test_pr_repo_full_name() {
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         is_draft: false,
+        state: PrState::Open,
         additions: 0,
         deletions: 0,
         review_decision: None,
         mergeable: None,
         merge_state_status: None,
+        is_behind: false,
         checks_status: None,
+        merged_at: None,
+        closed_at: None,
+        head_ref: None,
+        base_ref: None,
+        comment_count: 0,
+        review_count: 0,
+        my_review_state: None,
+        assignees: vec![],
         labels: vec![],
     };
     assert_eq!(pr.repo_full_name(), "org/repo");
@@ -45,6 +59,7 @@ fn test_repo_serialization_roundtrip() {
         description: None,
         open_pr_count: 3,
         is_archived: false,
+        topics: vec![],
     };
 
     let json = serde_json::to_string(&repo).unwrap();
@@ -69,12 +84,22 @@ fn test_pr_serialization_roundtrip() {
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         is_draft: true,
+        state: PrState::Open,
         additions: 100,
         deletions: 50,
         review_decision: Some("APPROVED".into()),
         mergeable: Some("MERGEABLE".into()),
         merge_state_status: Some("CLEAN".into()),
+        is_behind: false,
         checks_status: Some("SUCCESS".into()),
+        merged_at: None,
+        closed_at: None,
+        head_ref: None,
+        base_ref: None,
+        comment_count: 0,
+        review_count: 0,
+        my_review_state: None,
+        assignees: vec![],
         labels: vec!["bug".into(), "urgent".into()],
     };
 
@@ -100,6 +125,34 @@ fn test_rate_limit_default() {
     assert!(rl.reset_at.is_none());
 }
 
+#[test]
+fn test_extract_rate_limit_present() {
+    let data = serde_json::json!({
+        "data": {
+            "rateLimit": { "remaining": 4812, "limit": 5000, "resetAt": "2024-06-01T14:05:00Z" }
+        }
+    });
+    let rl = GithubClient::extract_rate_limit(&data).unwrap();
+    assert_eq!(rl.remaining, 4812);
+    assert_eq!(rl.limit, 5000);
+    assert_eq!(
+        rl.reset_at.unwrap().to_rfc3339(),
+        "2024-06-01T14:05:00+00:00"
+    );
+}
+
+#[test]
+fn test_extract_rate_limit_null_for_app_tokens() {
+    let data = serde_json::json!({ "data": { "viewer": { "login": "bot" }, "rateLimit": null } });
+    assert!(GithubClient::extract_rate_limit(&data).is_none());
+}
+
+#[test]
+fn test_extract_rate_limit_missing_block() {
+    let data = serde_json::json!({ "data": { "viewer": { "login": "bot" } } });
+    assert!(GithubClient::extract_rate_limit(&data).is_none());
+}
+
 #[test]
 fn test_repo_with_description() {
     let repo = Repo {
@@ -109,6 +162,7 @@ fn test_repo_with_description() {
         description: Some("A cool project".into()),
         open_pr_count: 0,
         is_archived: true,
+        topics: vec![],
     };
 
     assert_eq!(repo.description, Some("A cool project".into()));
@@ -127,12 +181,22 @@ fn test_pr_with_no_review_decision() {
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         is_draft: true,
+        state: PrState::Open,
         additions: 0,
         deletions: 0,
         review_decision: None,
         mergeable: None,
         merge_state_status: None,
+        is_behind: false,
         checks_status: None,
+        merged_at: None,
+        closed_at: None,
+        head_ref: None,
+        base_ref: None,
+        comment_count: 0,
+        review_count: 0,
+        my_review_state: None,
+        assignees: vec![],
         labels: vec![],
     };
 
@@ -165,7 +229,96 @@ fn test_pr_deserializes_without_merge_fields() {
     assert_eq!(pr.number, 7);
     assert!(pr.mergeable.is_none());
     assert!(pr.merge_state_status.is_none());
+    assert!(!pr.is_behind);
+    assert_eq!(pr.state, PrState::Open);
     assert!(pr.checks_status.is_none());
+    assert!(pr.merged_at.is_none());
+    assert!(pr.closed_at.is_none());
+    assert!(pr.head_ref.is_none());
+    assert!(pr.base_ref.is_none());
+    assert_eq!(pr.comment_count, 0);
+    assert_eq!(pr.review_count, 0);
+    assert!(pr.my_review_state.is_none());
+    assert!(pr.assignees.is_empty());
+}
+
+#[test]
+fn test_legacy_cached_pr_state_follows_merge_and_close_times() {
+    let legacy = |merged_at: Option<&str>, closed_at: Option<&str>| -> PullRequest {
+        serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Legacy cached PR",
+            "author": "bob",
+            "repo_owner": "org",
+            "repo_name": "repo",
+            "url": "https://github.com/org/repo/pull/7",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+            "is_draft": false,
+            "additions": 3,
+            "deletions": 1,
+            "review_decision": null,
+            "labels": [],
+            "merged_at": merged_at,
+            "closed_at": closed_at,
+        }))
+        .unwrap()
+    };
+    let at = Some("2026-01-03T00:00:00Z");
+    assert_eq!(legacy(at, at).state, PrState::Merged);
+    assert_eq!(legacy(None, at).state, PrState::Closed);
+    assert_eq!(legacy(None, None).state, PrState::Open);
+
+    // A stored state is kept as is.
+    let mut pr = legacy(None, at);
+    pr.state = PrState::Open;
+    let json = serde_json::to_string(&pr).unwrap();
+    let back: PullRequest = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.state, PrState::Open);
+}
+
+#[test]
+fn test_parse_pr_assignees() {
+    let node = serde_json::json!({
+        "number": 12,
+        "title": "Split the billing job",
+        "author": { "login": "alice" },
+        "repository": { "name": "api", "owner": { "login": "acme" } },
+        "assignees": { "nodes": [{ "login": "bob" }, { "login": "carol" }] },
+    });
+    let pr = parse_search_pr(&node);
+    assert_eq!(pr.author, "alice");
+    assert_eq!(pr.assignees, vec!["bob", "carol"]);
+
+    let unassigned = parse_search_pr(&serde_json::json!({ "number": 13 }));
+    assert!(unassigned.assignees.is_empty());
+}
+
+#[test]
+fn test_reviewed_by_me_ignores_pending_reviews() {
+    let pr_with = |state: Option<&str>| -> PullRequest {
+        serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Reviewed",
+            "author": "bob",
+            "repo_owner": "org",
+            "repo_name": "repo",
+            "url": "https://github.com/org/repo/pull/7",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-02T00:00:00Z",
+            "is_draft": false,
+            "additions": 3,
+            "deletions": 1,
+            "review_decision": null,
+            "labels": [],
+            "my_review_state": state,
+        }))
+        .unwrap()
+    };
+    assert!(pr_with(Some("COMMENTED")).reviewed_by_me());
+    assert!(pr_with(Some("APPROVED")).reviewed_by_me());
+    assert!(!pr_with(Some("PENDING")).reviewed_by_me());
+    assert!(!pr_with(None).reviewed_by_me());
 }
 
 #[test]
@@ -180,12 +333,22 @@ fn test_pr_conflicting_merge_state_roundtrip() {
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         is_draft: false,
+        state: PrState::Open,
         additions: 1,
         deletions: 1,
         review_decision: None,
         mergeable: Some("CONFLICTING".into()),
         merge_state_status: Some("DIRTY".into()),
+        is_behind: false,
         checks_status: Some("FAILURE".into()),
+        merged_at: None,
+        closed_at: None,
+        head_ref: None,
+        base_ref: None,
+        comment_count: 0,
+        review_count: 0,
+        my_review_state: None,
+        assignees: vec![],
         labels: vec![],
     };
 
@@ -209,12 +372,22 @@ fn pr_with_checks(state: Option<&str>) -> PullRequest {
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         is_draft: false,
+        state: PrState::Open,
         additions: 0,
         deletions: 0,
         review_decision: None,
         mergeable: None,
         merge_state_status: None,
+        is_behind: false,
         checks_status: state.map(|s| s.to_string()),
+        merged_at: None,
+        closed_at: None,
+        head_ref: None,
+        base_ref: None,
+        comment_count: 0,
+        review_count: 0,
+        my_review_state: None,
+        assignees: vec![],
         labels: vec![],
     }
 }
@@ -254,3 +427,301 @@ fn test_ci_status_none() {
     // Unknown/other states fall back to None rather than misreporting.
     assert_eq!(pr_with_checks(Some("WEIRD")).ci_status(), CiStatus::None);
 }
+
+#[test]
+fn test_parse_topics() {
+    let node = serde_json::json!({
+        "repositoryTopics": {
+            "nodes": [
+                { "topic": { "name": "service" } },
+                { "topic": { "name": "rust" } }
+            ]
+        }
+    });
+    assert_eq!(
+        GithubClient::parse_topics(&node),
+        vec!["service".to_string(), "rust".to_string()]
+    );
+    assert!(GithubClient::parse_topics(&serde_json::json!({})).is_empty());
+}
+
+fn repo_node(owner: &str, name: &str, open: u64) -> serde_json::Value {
+    serde_json::json!({
+        "name": name,
+        "owner": { "login": owner },
+        "url": format!("https://github.com/{}/{}", owner, name),
+        "description": null,
+        "isArchived": false,
+        "pullRequests": { "totalCount": open },
+        "repositoryTopics": { "nodes": [] }
+    })
+}
+
+#[test]
+fn test_parse_org_batch() {
+    let data = serde_json::json!({
+        "data": {
+            "org0": { "repositories": {
+                "pageInfo": { "hasNextPage": true, "endCursor": "abc" },
+                "nodes": [repo_node("org-a", "api", 2), repo_node("org-a", "web", 0)]
+            }},
+            "org1": { "repositories": {
+                "pageInfo": { "hasNextPage": false, "endCursor": "zzz" },
+                "nodes": [repo_node("org-b", "tools", 5)]
+            }},
+            "rateLimit": { "remaining": 4990, "limit": 5000, "resetAt": null }
+        }
+    });
+    let pages = GithubClient::parse_org_batch(&data, 2).unwrap();
+    assert_eq!(pages.len(), 2);
+    let names: Vec<&str> = pages[0].0.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["api", "web"]);
+    assert_eq!(pages[0].1.as_deref(), Some("abc"));
+    assert_eq!(pages[1].0[0].full_name(), "org-b/tools");
+    assert_eq!(pages[1].0[0].open_pr_count, 5);
+    assert_eq!(pages[1].1, None);
+
+    // A missing alias is an error, not an empty org.
+    assert!(GithubClient::parse_org_batch(&data, 3).is_err());
+}
+
+#[test]
+fn test_org_repos_batch_query_aliases_each_org() {
+    let query = ghdash::github::queries::org_repos_batch_query(2);
+    assert!(query.starts_with(
+        "query($org0: String!, $cursor0: String, $org1: String!, $cursor1: String) {"
+    ));
+    assert!(query.contains("org0: organization(login: $org0)"));
+    assert!(query.contains("org1: organization(login: $org1)"));
+    assert!(query.contains("after: $cursor1"));
+    assert_eq!(query.matches("rateLimit").count(), 1);
+    assert_eq!(query.matches('{').count(), query.matches('}').count());
+}
+
+#[test]
+fn test_repo_deserializes_without_topics() {
+    let legacy = r#"{
+        "name": "api",
+        "owner": "org",
+        "url": "https://github.com/org/api",
+        "description": null,
+        "open_pr_count": 2,
+        "is_archived": false
+    }"#;
+    let repo: Repo = serde_json::from_str(legacy).expect("legacy cache must deserialize");
+    assert!(repo.topics.is_empty());
+}
+
+#[test]
+fn test_history_search_query() {
+    let since = chrono::NaiveDate::from_ymd_opt(2024, 5, 25).unwrap();
+    let orgs = vec!["org-a".to_string()];
+    let users = vec!["me".to_string()];
+    assert_eq!(
+        GithubClient::history_search_query(PrHistoryKind::Merged, &orgs, &users, &[], since),
+        "is:pr is:merged merged:>=2024-05-25 org:org-a user:me"
+    );
+    assert_eq!(
+        GithubClient::history_search_query(PrHistoryKind::Closed, &orgs, &[], &[], since),
+        "is:pr is:closed is:unmerged closed:>=2024-05-25 org:org-a"
+    );
+    let repos = vec!["big-org/payment-service".to_string()];
+    assert_eq!(
+        GithubClient::history_search_query(PrHistoryKind::Merged, &[], &[], &repos, since),
+        "is:pr is:merged merged:>=2024-05-25 repo:big-org/payment-service"
+    );
+}
+
+#[test]
+fn test_parse_owner_kind() {
+    let owner =
+        |typename: &str| serde_json::json!({"data": {"repositoryOwner": {"__typename": typename}}});
+    assert_eq!(
+        GithubClient::parse_owner_kind(&owner("Organization")),
+        Some(OwnerKind::Org)
+    );
+    assert_eq!(
+        GithubClient::parse_owner_kind(&owner("User")),
+        Some(OwnerKind::User)
+    );
+    // No such login: GitHub answers with a null owner.
+    let missing = serde_json::json!({"data": {"repositoryOwner": null}});
+    assert_eq!(GithubClient::parse_owner_kind(&missing), None);
+}
+
+#[test]
+fn test_all_open_prs_query_adds_standalone_repos() {
+    let orgs = vec!["org-a".to_string()];
+    let repos = vec!["big-org/payment-service".to_string()];
+    assert_eq!(
+        GithubClient::all_open_prs_query(&orgs, &[], &repos),
+        "is:open is:pr archived:false org:org-a repo:big-org/payment-service"
+    );
+    assert_eq!(
+        GithubClient::all_open_prs_query(&[], &[], &repos),
+        "is:open is:pr archived:false repo:big-org/payment-service"
+    );
+}
+
+#[test]
+fn test_involves_search_query() {
+    let orgs = vec!["org-a".to_string()];
+    let users = vec!["me".to_string()];
+    assert_eq!(
+        GithubClient::involves_search_query("alice", &orgs, &users, &[]),
+        "is:open is:pr archived:false involves:alice org:org-a user:me"
+    );
+    let repos = vec!["big-org/payment-service".to_string()];
+    assert_eq!(
+        GithubClient::involves_search_query("alice", &orgs, &[], &repos),
+        "is:open is:pr archived:false involves:alice org:org-a repo:big-org/payment-service"
+    );
+}
+
+#[test]
+fn test_team_review_query() {
+    assert_eq!(
+        GithubClient::team_review_query("acme/platform"),
+        "is:open is:pr team-review-requested:acme/platform archived:false"
+    );
+}
+
+#[test]
+fn test_history_kind_finished_at() {
+    let mut pr = pr_with_checks(None);
+    let merged: chrono::DateTime<chrono::Utc> = "2024-05-30T10:00:00Z".parse().unwrap();
+    let closed: chrono::DateTime<chrono::Utc> = "2024-05-30T11:00:00Z".parse().unwrap();
+    pr.merged_at = Some(merged);
+    pr.closed_at = Some(closed);
+    assert_eq!(PrHistoryKind::Merged.finished_at(&pr), Some(merged));
+    assert_eq!(PrHistoryKind::Closed.finished_at(&pr), Some(closed));
+}
+
+#[tokio::test]
+async fn test_offline_client_never_hits_network() {
+    let client = GithubClient::offline(&ghdash::util::config::GithubConfig::default());
+    assert!(client.is_offline());
+    let err = client.fetch_viewer().await.unwrap_err();
+    assert!(err.to_string().contains("offline"));
+    assert!(!ghdash::github::graphql::is_network_error(&err));
+}
+
+#[test]
+fn test_secondary_rate_limit_detection() {
+    use ghdash::github::graphql::secondary_rate_limit_wait;
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    let body = r#"{"message":"You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#;
+    assert_eq!(
+        secondary_rate_limit_wait(StatusCode::FORBIDDEN, None, body),
+        Some(Duration::from_secs(60))
+    );
+    assert_eq!(
+        secondary_rate_limit_wait(StatusCode::FORBIDDEN, Some("17"), body),
+        Some(Duration::from_secs(17))
+    );
+    // `Retry-After` alone is enough, on 429 as well as 403.
+    assert_eq!(
+        secondary_rate_limit_wait(StatusCode::TOO_MANY_REQUESTS, Some("5"), ""),
+        Some(Duration::from_secs(5))
+    );
+    // An ordinary permission error is not a rate limit.
+    assert_eq!(
+        secondary_rate_limit_wait(StatusCode::FORBIDDEN, None, "Resource not accessible"),
+        None
+    );
+    assert_eq!(
+        secondary_rate_limit_wait(StatusCode::BAD_GATEWAY, Some("5"), body),
+        None
+    );
+}
+
+#[test]
+fn test_fresh_client_is_not_backing_off() {
+    let client = GithubClient::offline(&ghdash::util::config::GithubConfig::default());
+    assert!(client.backoff_remaining().is_none());
+}
+
+#[test]
+fn test_failure_kind_tells_rejected_token_from_other_statuses() {
+    use ghdash::github::graphql::{ApiStatusError, FailureKind, failure_kind};
+    use reqwest::StatusCode;
+
+    let rejected = anyhow::Error::new(ApiStatusError {
+        status: StatusCode::UNAUTHORIZED,
+        body: r#"{"message":"Bad credentials"}"#.into(),
+    })
+    .context("Failed to fetch viewer");
+    assert_eq!(failure_kind(&rejected), FailureKind::Unauthorized);
+
+    let server = anyhow::Error::new(ApiStatusError {
+        status: StatusCode::INTERNAL_SERVER_ERROR,
+        body: String::new(),
+    });
+    assert_eq!(failure_kind(&server), FailureKind::Other);
+    assert_eq!(
+        failure_kind(&anyhow::anyhow!("Missing viewer login")),
+        FailureKind::Other
+    );
+}
+
+#[test]
+fn test_no_access_errors() {
+    use ghdash::github::graphql::{ApiStatusError, GraphqlError, is_no_access};
+    use reqwest::StatusCode;
+
+    let graphql = |kind: &str| {
+        anyhow::Error::new(GraphqlError {
+            kind: Some(kind.into()),
+            message: "Could not resolve to an Organization with the login of 'x'.".into(),
+        })
+        .context("Failed to fetch org repos")
+    };
+    assert!(is_no_access(&graphql("NOT_FOUND")));
+    assert!(is_no_access(&graphql("FORBIDDEN")));
+    assert!(!is_no_access(&graphql("INTERNAL")));
+
+    let forbidden = anyhow::Error::new(ApiStatusError {
+        status: StatusCode::FORBIDDEN,
+        body: r#"{"message":"Resource not accessible by integration"}"#.into(),
+    });
+    assert!(is_no_access(&forbidden));
+    let rate_limited = anyhow::Error::new(ApiStatusError {
+        status: StatusCode::FORBIDDEN,
+        body: r#"{"message":"API rate limit exceeded"}"#.into(),
+    });
+    assert!(!is_no_access(&rate_limited));
+    assert!(!is_no_access(&anyhow::anyhow!("connection reset")));
+}
+
+#[tokio::test]
+async fn test_failure_kind_unreachable_host_is_network() {
+    use ghdash::github::graphql::{FailureKind, failure_kind};
+
+    // Nothing listens on port 1, so the connection is refused locally.
+    let config = ghdash::util::config::GithubConfig {
+        api_url: "https://127.0.0.1:1/graphql".into(),
+        ..Default::default()
+    };
+    let client = GithubClient::new("token", &config).unwrap();
+    let err = client.fetch_viewer().await.unwrap_err();
+    assert_eq!(failure_kind(&err), FailureKind::Network);
+}
+
+#[test]
+fn test_pr_state_from_graphql() {
+    assert_eq!(PrState::from_graphql("OPEN"), PrState::Open);
+    assert_eq!(PrState::from_graphql("MERGED"), PrState::Merged);
+    assert_eq!(PrState::from_graphql("CLOSED"), PrState::Closed);
+    assert_eq!(PrState::from_graphql("SOMETHING_NEW"), PrState::Open);
+}
+
+#[test]
+fn test_pr_state_roundtrip() {
+    let mut pr = pr_with_checks(None);
+    pr.state = PrState::Merged;
+    let json = serde_json::to_string(&pr).unwrap();
+    let deserialized: PullRequest = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.state, PrState::Merged);
+}
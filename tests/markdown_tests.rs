@@ -0,0 +1,71 @@
+use ghdash::ui::markdown::to_text;
+use ratatui::style::Modifier;
+
+fn plain(markdown: &str) -> Vec<String> {
+    to_text(markdown)
+        .lines
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect()
+}
+
+#[test]
+fn test_headings_lose_their_hashes() {
+    assert_eq!(plain("# Title\n### Sub ###"), vec!["Title", "Sub"]);
+    // Not a heading without the space.
+    assert_eq!(plain("#123 is related"), vec!["#123 is related"]);
+}
+
+#[test]
+fn test_bullets_keep_nesting() {
+    assert_eq!(
+        plain("- one\n  * two\n+ three"),
+        vec!["• one", "  • two", "• three"]
+    );
+}
+
+#[test]
+fn test_links_show_their_url() {
+    assert_eq!(
+        plain("See [the docs](https://example.com) and [https://x.io](https://x.io)."),
+        vec!["See the docs (https://example.com) and https://x.io."]
+    );
+    // A bracket that isn't a link stays as written.
+    assert_eq!(plain("- [ ] todo"), vec!["• [ ] todo"]);
+    assert_eq!(
+        plain("[x] see [docs](https://d.io)"),
+        vec!["[x] see docs (https://d.io)"]
+    );
+}
+
+#[test]
+fn test_inline_code_and_bold() {
+    let text = to_text("Run `cargo test` **first**");
+    let spans = &text.lines[0].spans;
+    assert_eq!(spans[1].content, "cargo test");
+    assert_eq!(spans[3].content, "first");
+    assert!(spans[3].style.add_modifier.contains(Modifier::BOLD));
+    // Unclosed markers are literal.
+    assert_eq!(plain("a `b and **c"), vec!["a `b and **c"]);
+}
+
+#[test]
+fn test_code_blocks_are_verbatim() {
+    assert_eq!(
+        plain("```rust\nlet x = `y`;\n# not a heading\n```\nafter"),
+        vec!["  let x = `y`;", "  # not a heading", "after"]
+    );
+}
+
+#[test]
+fn test_comments_dropped_and_blank_lines_collapsed() {
+    assert_eq!(
+        plain("\n\n<!-- template\nhint -->\nOne\n\n\n\nTwo\r\n\r\n"),
+        vec!["One", "", "Two"]
+    );
+}
+
+#[test]
+fn test_quotes() {
+    assert_eq!(plain("> quoted"), vec!["│ quoted"]);
+}
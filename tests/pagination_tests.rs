@@ -0,0 +1,81 @@
+use std::cell::Cell;
+
+use anyhow::bail;
+use ghdash::github::CancelToken;
+use ghdash::github::graphql::{Page, paginate};
+
+/// Serves `pages` pages of two items each, counting the requests.
+fn mock_page(
+    requests: &Cell<usize>,
+    pages: usize,
+    cursor: Option<String>,
+) -> anyhow::Result<Page<usize>> {
+    let index = cursor.map_or(0, |c| c.parse::<usize>().unwrap());
+    requests.set(requests.get() + 1);
+    Ok(Page {
+        items: vec![index * 2, index * 2 + 1],
+        next_cursor: (index + 1 < pages).then(|| (index + 1).to_string()),
+        rate_limit: None,
+    })
+}
+
+#[tokio::test]
+async fn test_paginate_fetches_every_page() {
+    let requests = Cell::new(0);
+    let (items, _) = paginate(&CancelToken::new(), |cursor| {
+        let page = mock_page(&requests, 5, cursor);
+        async move { page }
+    })
+    .await
+    .unwrap();
+    assert_eq!(requests.get(), 5);
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_cancelled_pagination_stops_after_current_page() {
+    let requests = Cell::new(0);
+    let cancel = CancelToken::new();
+    let (items, _) = paginate(&cancel, |cursor| {
+        let page = mock_page(&requests, 50, cursor);
+        // The user navigates away while the second page is in flight.
+        if requests.get() == 2 {
+            cancel.cancel();
+        }
+        async move { page }
+    })
+    .await
+    .unwrap();
+    assert_eq!(requests.get(), 2);
+    assert_eq!(items, vec![0, 1, 2, 3]);
+}
+
+#[tokio::test]
+async fn test_cancelled_token_still_returns_first_page() {
+    let requests = Cell::new(0);
+    let cancel = CancelToken::new();
+    cancel.cancel();
+    let (items, _) = paginate(&cancel, |cursor| {
+        let page = mock_page(&requests, 3, cursor);
+        async move { page }
+    })
+    .await
+    .unwrap();
+    assert_eq!(requests.get(), 1);
+    assert_eq!(items, vec![0, 1]);
+}
+
+#[tokio::test]
+async fn test_paginate_propagates_page_errors() {
+    let result = paginate::<usize, _, _>(&CancelToken::new(), |_| async { bail!("timeout") }).await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_cancel_token_clones_share_the_flag() {
+    let cancel = CancelToken::new();
+    let task = cancel.clone();
+    assert!(!task.is_cancelled());
+    cancel.cancel();
+    assert!(task.is_cancelled());
+}
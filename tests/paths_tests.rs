@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use ghdash::util::paths::{
+    cache_dir, config_candidates, data_dir, fallback_dir, home_dir, shell_path,
+};
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+    let vars: HashMap<String, OsString> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), OsString::from(v)))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+#[test]
+fn test_config_candidates_home_then_platform() {
+    let lookup = env(&[("HOME", "/home/alice")]);
+    let platform = PathBuf::from("/platform/ghdash");
+    assert_eq!(
+        config_candidates(&lookup, Some(platform)),
+        vec![
+            PathBuf::from("/home/alice/.config/ghdash/config.toml"),
+            PathBuf::from("/platform/ghdash/config.toml"),
+        ]
+    );
+}
+
+#[test]
+fn test_config_candidates_xdg_config_home_first() {
+    let lookup = env(&[("HOME", "/home/alice"), ("XDG_CONFIG_HOME", "/xdg")]);
+    assert_eq!(
+        config_candidates(&lookup, None),
+        vec![
+            PathBuf::from("/xdg/ghdash/config.toml"),
+            PathBuf::from("/home/alice/.config/ghdash/config.toml"),
+        ]
+    );
+    // The usual XDG_CONFIG_HOME is only listed once.
+    let lookup = env(&[
+        ("HOME", "/home/alice"),
+        ("XDG_CONFIG_HOME", "/home/alice/.config"),
+    ]);
+    assert_eq!(config_candidates(&lookup, None).len(), 1);
+}
+
+#[test]
+fn test_config_candidates_without_home_or_platform() {
+    assert!(config_candidates(&env(&[("HOME", "")]), None).is_empty());
+}
+
+#[test]
+fn test_platform_dirs_win() {
+    let lookup = env(&[("HOME", "/home/alice")]);
+    assert_eq!(
+        cache_dir(&lookup, Some(PathBuf::from("/var/cache/ghdash"))),
+        (PathBuf::from("/var/cache/ghdash"), None)
+    );
+    assert_eq!(
+        data_dir(&lookup, Some(PathBuf::from("/var/lib/ghdash"))),
+        (PathBuf::from("/var/lib/ghdash"), None)
+    );
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_dirs_fall_back_to_home() {
+    let lookup = env(&[("HOME", "/home/alice"), ("APPDATA", "/ignored")]);
+    assert_eq!(
+        cache_dir(&lookup, None).0,
+        PathBuf::from("/home/alice/.cache/ghdash")
+    );
+    let (dir, warning) = data_dir(&lookup, None);
+    assert_eq!(dir, PathBuf::from("/home/alice/.local/share/ghdash"));
+    assert_eq!(
+        warning.as_deref(),
+        Some(
+            "No platform directories found; using /home/alice/.local/share/ghdash under the home directory"
+        )
+    );
+}
+
+#[test]
+fn test_fallback_dir_prefers_home() {
+    assert_eq!(
+        fallback_dir(".cache/ghdash", Some(Path::new("/home/alice"))).0,
+        PathBuf::from("/home/alice/.cache/ghdash")
+    );
+}
+
+#[test]
+fn test_fallback_dir_without_home_is_relative() {
+    assert_eq!(
+        fallback_dir(".local/share/ghdash", None).0,
+        PathBuf::from(".local/share/ghdash")
+    );
+    assert_eq!(
+        fallback_dir(".cache/ghdash", Some(Path::new(""))).0,
+        PathBuf::from(".cache/ghdash")
+    );
+}
+
+#[test]
+fn test_shell_path_unwraps_verbatim_paths() {
+    assert_eq!(
+        shell_path(r"\\?\C:\Users\alice\report.html"),
+        r"C:\Users\alice\report.html"
+    );
+    assert_eq!(
+        shell_path(r"\\?\UNC\server\share\report.html"),
+        r"\\server\share\report.html"
+    );
+    assert_eq!(
+        shell_path("https://github.com/acme/api/pull/1"),
+        "https://github.com/acme/api/pull/1"
+    );
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows_home_from_userprofile() {
+    let lookup = env(&[("USERPROFILE", r"C:\Users\alice")]);
+    assert_eq!(home_dir(&lookup), Some(PathBuf::from(r"C:\Users\alice")));
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_userprofile_ignored_off_windows() {
+    assert_eq!(home_dir(&env(&[("USERPROFILE", "/users/alice")])), None);
+}
+
+#[cfg(windows)]
+#[test]
+fn test_windows_dirs_use_appdata() {
+    let lookup = env(&[
+        ("USERPROFILE", r"C:\Users\alice"),
+        ("APPDATA", r"C:\Users\alice\AppData\Roaming"),
+        ("LOCALAPPDATA", r"C:\Users\alice\AppData\Local"),
+    ]);
+    assert_eq!(
+        config_candidates(&lookup, None),
+        vec![
+            PathBuf::from(r"C:\Users\alice\.config\ghdash\config.toml"),
+            PathBuf::from(r"C:\Users\alice\AppData\Roaming\ghdash\config.toml"),
+        ]
+    );
+    assert_eq!(
+        cache_dir(&lookup, None),
+        PathBuf::from(r"C:\Users\alice\AppData\Local\ghdash\cache")
+    );
+    assert_eq!(
+        data_dir(&lookup, None),
+        PathBuf::from(r"C:\Users\alice\AppData\Roaming\ghdash\data")
+    );
+}
@@ -0,0 +1,61 @@
+use chrono::Duration;
+
+use ghdash::github::models::{PullRequest, Repo};
+use ghdash::util::pr_diff::{ListDiff, diff_prs, diff_repos};
+
+mod common;
+
+fn make_repo(name: &str, open_prs: u32) -> Repo {
+    common::make_repo("acme", name, open_prs)
+}
+
+fn make_pr(number: u32) -> PullRequest {
+    common::make_pr("acme", "api", number)
+}
+
+#[test]
+fn test_identical_lists_have_no_changes() {
+    let prs = vec![make_pr(1), make_pr(2)];
+    assert_eq!(diff_prs(&prs, &prs), ListDiff::default());
+}
+
+#[test]
+fn test_pr_diff_counts_added_removed_and_updated() {
+    let old = vec![make_pr(1), make_pr(2), make_pr(3)];
+    let mut bumped = old[1].clone();
+    bumped.updated_at += Duration::minutes(5);
+    let new = vec![old[0].clone(), bumped, make_pr(4), make_pr(5)];
+    assert_eq!(
+        diff_prs(&old, &new),
+        ListDiff {
+            added: 2,
+            removed: 1,
+            updated: 1,
+        }
+    );
+}
+
+#[test]
+fn test_pr_diff_from_empty_counts_everything_added() {
+    let new = vec![make_pr(1), make_pr(2)];
+    assert_eq!(diff_prs(&[], &new).added, 2);
+    assert_eq!(diff_prs(&new, &[]).removed, 2);
+}
+
+#[test]
+fn test_repo_diff_counts_changed_pr_counts() {
+    let old = vec![make_repo("api", 1), make_repo("web", 2)];
+    let new = vec![
+        make_repo("api", 3),
+        make_repo("web", 2),
+        make_repo("cli", 0),
+    ];
+    assert_eq!(
+        diff_repos(&old, &new),
+        ListDiff {
+            added: 1,
+            removed: 0,
+            updated: 1,
+        }
+    );
+}
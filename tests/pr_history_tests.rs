@@ -0,0 +1,78 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ghdash::util::pr_history::{MAX_SAMPLES, PrCountHistory};
+use tempfile::TempDir;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap()
+}
+
+#[test]
+fn test_append_adds_spaced_samples() {
+    let mut history = PrCountHistory::default();
+    history.append("o/r", start(), 3);
+    history.append("o/r", start() + Duration::hours(1), 5);
+    history.append("o/r", start() + Duration::hours(3), 4);
+    assert_eq!(history.counts("o/r"), vec![3, 5, 4]);
+    assert!(history.counts("o/other").is_empty());
+}
+
+#[test]
+fn test_append_within_spacing_updates_latest() {
+    let mut history = PrCountHistory::default();
+    history.append("o/r", start(), 3);
+    history.append("o/r", start() + Duration::minutes(5), 4);
+    history.append("o/r", start() + Duration::minutes(50), 6);
+    assert_eq!(history.counts("o/r"), vec![6]);
+    assert_eq!(history.repos["o/r"][0].at, start());
+}
+
+#[test]
+fn test_append_reports_whether_anything_changed() {
+    let mut history = PrCountHistory::default();
+    assert!(history.append("o/r", start(), 3));
+    assert!(!history.append("o/r", start() + Duration::minutes(5), 3));
+    assert!(history.append("o/r", start() + Duration::minutes(10), 4));
+    assert!(history.append("o/r", start() + Duration::hours(2), 4));
+}
+
+#[test]
+fn test_append_keeps_newest_samples() {
+    let mut history = PrCountHistory::default();
+    for i in 0..30 {
+        history.append("o/r", start() + Duration::hours(i), i as u32);
+    }
+    let counts = history.counts("o/r");
+    assert_eq!(counts.len(), MAX_SAMPLES);
+    assert_eq!(counts.first(), Some(&10));
+    assert_eq!(counts.last(), Some(&29));
+}
+
+#[test]
+fn test_prune_drops_repos_not_seen_lately() {
+    let mut history = PrCountHistory::default();
+    history.append("o/gone", start(), 1);
+    history.append("o/live", start(), 1);
+    history.append("o/live", start() + Duration::days(29), 2);
+    history.prune(start() + Duration::days(31));
+    assert!(history.counts("o/gone").is_empty());
+    assert_eq!(history.counts("o/live"), vec![1, 2]);
+}
+
+#[test]
+fn test_save_and_load_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("nested").join("pr_history.json");
+    let mut history = PrCountHistory::default();
+    history.append("o/r", start(), 7);
+    history.save(&path).unwrap();
+    assert_eq!(PrCountHistory::load(&path), history);
+}
+
+#[test]
+fn test_load_missing_or_corrupted_file_is_empty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("pr_history.json");
+    assert!(PrCountHistory::load(&path).repos.is_empty());
+    std::fs::write(&path, "not json").unwrap();
+    assert!(PrCountHistory::load(&path).repos.is_empty());
+}
@@ -0,0 +1,70 @@
+use ghdash::app::event_loop::filter_repos;
+use ghdash::github::models::Repo;
+
+fn make_repo(name: &str, topics: &[&str]) -> Repo {
+    Repo {
+        name: name.into(),
+        owner: "org".into(),
+        url: format!("https://github.com/org/{}", name),
+        description: None,
+        open_pr_count: 0,
+        is_archived: false,
+        topics: topics.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+fn repos() -> Vec<Repo> {
+    vec![
+        make_repo("api", &["service", "rust"]),
+        make_repo("sdk", &["library"]),
+        make_repo("legacy-web", &["service", "deprecated"]),
+        make_repo("scratch", &[]),
+    ]
+}
+
+fn names(repos: &[Repo]) -> Vec<&str> {
+    repos.iter().map(|r| r.name.as_str()).collect()
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_no_filters_keeps_everything() {
+    let filtered = filter_repos(repos(), &[], &[], &[], &[]);
+    assert_eq!(filtered.len(), 4);
+}
+
+#[test]
+fn test_name_globs() {
+    let filtered = filter_repos(repos(), &[], &strings(&["legacy-*"]), &[], &[]);
+    assert_eq!(names(&filtered), vec!["api", "sdk", "scratch"]);
+
+    let filtered = filter_repos(repos(), &strings(&["org/s*"]), &[], &[], &[]);
+    assert_eq!(names(&filtered), vec!["sdk", "scratch"]);
+}
+
+#[test]
+fn test_include_topics() {
+    let filtered = filter_repos(repos(), &[], &[], &strings(&["service"]), &[]);
+    assert_eq!(names(&filtered), vec!["api", "legacy-web"]);
+}
+
+#[test]
+fn test_exclude_topics() {
+    let filtered = filter_repos(repos(), &[], &[], &[], &strings(&["deprecated"]));
+    assert_eq!(names(&filtered), vec!["api", "sdk", "scratch"]);
+}
+
+#[test]
+fn test_topics_match_case_insensitively() {
+    let filtered = filter_repos(
+        repos(),
+        &[],
+        &[],
+        &strings(&["Service"]),
+        &strings(&["DEPRECATED"]),
+    );
+    assert_eq!(names(&filtered), vec!["api"]);
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ghdash::app::actions::{Action, DataPayload, SideEffect};
+use ghdash::app::scheduler::RefreshScheduler;
+use ghdash::app::state::ContentView;
+use ghdash::util::config::AppConfig;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+}
+
+fn config() -> AppConfig {
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["org-a".into()];
+    config.github.users = vec!["alice".into()];
+    config.dashboard.refresh_interval_secs = 300;
+    config.dashboard.inbox_interval_secs = Some(60);
+    config.dashboard.repos_interval_secs = Some(1800);
+    config
+}
+
+fn refreshed(effects: &[SideEffect]) -> Vec<ContentView> {
+    effects
+        .iter()
+        .map(|effect| match effect {
+            SideEffect::RefreshView(view) => view.clone(),
+            other => panic!("unexpected effect {:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_nothing_due_before_first_interval() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    assert!(scheduler.due(start()).is_empty());
+    assert!(scheduler.due(start() + Duration::seconds(59)).is_empty());
+    assert_eq!(scheduler.next_due(), Some(start() + Duration::seconds(60)));
+}
+
+#[test]
+fn test_intervals_per_source() {
+    let intervals = RefreshScheduler::new(&config(), start()).intervals();
+    assert_eq!(intervals.len(), 6);
+    assert_eq!(intervals[&ContentView::Inbox], 60);
+    assert_eq!(intervals[&ContentView::AllOpenPrs], 300);
+    assert_eq!(intervals[&ContentView::RecentlyMerged], 300);
+    assert_eq!(intervals[&ContentView::OrgOverview("alice".into())], 1800);
+}
+
+#[test]
+fn test_only_due_sources_fire() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    let effects = scheduler.due(start() + Duration::seconds(60));
+    assert_eq!(refreshed(&effects), vec![ContentView::Inbox]);
+    // Rescheduled from when it fired.
+    assert_eq!(scheduler.next_due(), Some(start() + Duration::seconds(120)));
+
+    let effects = scheduler.due(start() + Duration::seconds(300));
+    assert_eq!(
+        refreshed(&effects),
+        vec![
+            ContentView::Inbox,
+            ContentView::AllOpenPrs,
+            ContentView::RecentlyMerged,
+            ContentView::RecentlyClosed,
+        ]
+    );
+
+    // Everything else was just refreshed, so only the repo lists come due.
+    scheduler.due(start() + Duration::seconds(1790));
+    let effects = scheduler.due(start() + Duration::seconds(1800));
+    let views = refreshed(&effects);
+    assert!(views.contains(&ContentView::OrgOverview("org-a".into())));
+    assert!(views.contains(&ContentView::OrgOverview("alice".into())));
+    assert_eq!(views.len(), 2);
+}
+
+#[test]
+fn test_everything_due_is_one_full_refresh() {
+    let mut config = config();
+    config.dashboard.inbox_interval_secs = None;
+    config.dashboard.repos_interval_secs = None;
+    let mut scheduler = RefreshScheduler::new(&config, start());
+    assert!(matches!(
+        scheduler.due(start() + Duration::seconds(300)).as_slice(),
+        [SideEffect::RefreshAll]
+    ));
+    assert_eq!(scheduler.next_due(), Some(start() + Duration::seconds(600)));
+}
+
+#[test]
+fn test_manual_refresh_resets_its_timer() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    scheduler.record(
+        &SideEffect::RefreshView(ContentView::Inbox),
+        start() + Duration::seconds(50),
+    );
+    assert!(scheduler.due(start() + Duration::seconds(60)).is_empty());
+    assert_eq!(
+        refreshed(&scheduler.due(start() + Duration::seconds(110))),
+        vec![ContentView::Inbox]
+    );
+
+    // Failing checks is derived from the all-PRs list.
+    scheduler.record(
+        &SideEffect::RefreshView(ContentView::FailingChecks),
+        start() + Duration::seconds(200),
+    );
+    assert!(
+        !refreshed(&scheduler.due(start() + Duration::seconds(300)))
+            .contains(&ContentView::AllOpenPrs)
+    );
+}
+
+#[test]
+fn test_full_manual_refresh_resets_every_timer() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    scheduler.record(&SideEffect::RefreshAll, start() + Duration::seconds(1000));
+    assert_eq!(
+        scheduler.next_due(),
+        Some(start() + Duration::seconds(1060))
+    );
+    let effects = scheduler.due(start() + Duration::seconds(1800));
+    assert!(
+        !refreshed(&effects).contains(&ContentView::OrgOverview("org-a".into())),
+        "repo lists were reset at 1000s, so they're next due at 2800s"
+    );
+}
+
+#[test]
+fn test_unrelated_effects_leave_timers_alone() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    scheduler.record(&SideEffect::FetchInbox, start() + Duration::seconds(50));
+    assert_eq!(scheduler.next_due(), Some(start() + Duration::seconds(60)));
+}
+
+fn failed(view: ContentView) -> Action {
+    Action::FetchFailed {
+        view,
+        msg: "connection refused".into(),
+    }
+}
+
+#[test]
+fn test_failures_back_off_doubling_up_to_thirty_minutes() {
+    let mut config = config();
+    config.dashboard.inbox_interval_secs = None;
+    config.dashboard.repos_interval_secs = None;
+    let mut scheduler = RefreshScheduler::new(&config, start());
+    let mut now = start() + Duration::seconds(300);
+    let mut waits = Vec::new();
+    for _ in 0..5 {
+        assert!(!scheduler.due(now).is_empty());
+        // Every source failing in one round counts once.
+        scheduler.observe(&failed(ContentView::Inbox), now);
+        scheduler.observe(&failed(ContentView::AllOpenPrs), now);
+        let retry = scheduler.retry_at().unwrap();
+        waits.push((retry - now).num_minutes());
+        assert!(scheduler.due(retry - Duration::seconds(1)).is_empty());
+        assert_eq!(scheduler.next_due(), Some(retry));
+        now = retry;
+    }
+    assert_eq!(waits, vec![10, 20, 30, 30, 30]);
+}
+
+#[test]
+fn test_success_ends_backoff() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    scheduler.observe(&failed(ContentView::Inbox), start());
+    assert!(scheduler.retry_at().is_some());
+    scheduler.observe(
+        &Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+        start() + Duration::seconds(5),
+    );
+    assert_eq!(scheduler.retry_at(), None);
+    assert_eq!(scheduler.next_due(), Some(start() + Duration::seconds(60)));
+}
+
+#[test]
+fn test_manual_refresh_starts_a_new_failure_round() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    scheduler.observe(&failed(ContentView::Inbox), start());
+    let first = scheduler.retry_at().unwrap();
+    scheduler.record(&SideEffect::RefreshAll, start() + Duration::seconds(10));
+    scheduler.observe(&failed(ContentView::Inbox), start() + Duration::seconds(10));
+    // 60s base: 2 minutes after the first failure, 4 after the second.
+    assert_eq!(first, start() + Duration::seconds(120));
+    assert_eq!(scheduler.retry_at(), Some(start() + Duration::seconds(250)));
+}
+
+#[test]
+fn test_failures_outside_the_schedule_are_ignored() {
+    let mut scheduler = RefreshScheduler::new(&config(), start());
+    scheduler.observe(&failed(ContentView::Watched), start());
+    assert_eq!(scheduler.retry_at(), None);
+}
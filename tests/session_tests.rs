@@ -0,0 +1,57 @@
+use chrono::{TimeZone, Utc};
+use ghdash::app::session::Session;
+use ghdash::app::state::ContentView;
+use tempfile::TempDir;
+
+#[test]
+fn test_save_and_load_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("nested").join("session.json");
+
+    let mut session = Session::default();
+    let t = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+    session
+        .seen
+        .insert("https://github.com/o/r/pull/1".into(), t);
+    session.save(&path).unwrap();
+
+    let loaded = Session::load(&path);
+    assert_eq!(loaded.seen.get("https://github.com/o/r/pull/1"), Some(&t));
+}
+
+#[test]
+fn test_recent_views_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("session.json");
+    let session = Session {
+        recent_views: vec![
+            ContentView::RepoPrList {
+                owner: "o".into(),
+                name: "r".into(),
+            },
+            ContentView::Inbox,
+        ],
+        ..Session::default()
+    };
+    session.save(&path).unwrap();
+    assert_eq!(Session::load(&path).recent_views, session.recent_views);
+
+    // Sessions written before the list existed still load.
+    std::fs::write(&path, r#"{"seen":{}}"#).unwrap();
+    assert!(Session::load(&path).recent_views.is_empty());
+}
+
+#[test]
+fn test_load_missing_file_is_empty() {
+    let dir = TempDir::new().unwrap();
+    let loaded = Session::load(&dir.path().join("session.json"));
+    assert!(loaded.seen.is_empty());
+}
+
+#[test]
+fn test_load_corrupted_file_is_empty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("session.json");
+    std::fs::write(&path, "not json").unwrap();
+    assert!(Session::load(&path).seen.is_empty());
+}
@@ -0,0 +1,21 @@
+use ghdash::ui::glyphs::{ASCII, UNICODE};
+use ghdash::ui::sparkline::sparkline;
+
+#[test]
+fn test_sparkline_scales_between_min_and_max() {
+    assert_eq!(sparkline(&[0, 7], &UNICODE.bars), "▁█");
+    assert_eq!(sparkline(&[10, 12, 14, 17], &UNICODE.bars), "▁▃▅█");
+    assert_eq!(sparkline(&[40, 41, 40], &UNICODE.bars), "▁█▁");
+}
+
+#[test]
+fn test_sparkline_flat_and_empty() {
+    assert_eq!(sparkline(&[5, 5, 5], &UNICODE.bars), "▁▁▁");
+    assert_eq!(sparkline(&[3], &UNICODE.bars), "▁");
+    assert_eq!(sparkline(&[], &UNICODE.bars), "");
+}
+
+#[test]
+fn test_sparkline_ascii_bars() {
+    assert_eq!(sparkline(&[10, 12, 14, 17], &ASCII.bars), "_:=#");
+}
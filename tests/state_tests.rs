@@ -1,44 +1,31 @@
 use ghdash::app::actions::{Action, DataPayload, SideEffect};
-use ghdash::app::state::{AppState, ContentView, FocusedPane, NavNode, Overlay};
+use ghdash::app::state::{
+    AppState, ContentView, FocusedPane, MergeFilter, NavNode, Overlay, PrRelation, PrSort,
+    RateLimitTracker, RefreshSummary, ReviewFilter, SearchQuery,
+};
+use ghdash::app::state::{
+    has_labels, is_stale, label_counts, refresh_deferred_until, scroll_offset,
+};
 use ghdash::app::update::update;
-use ghdash::github::models::{PullRequest, RateLimit, Repo};
+use ghdash::github::CancelToken;
+use ghdash::github::models::{PrHistoryKind, PrState, PrStateFilter, PullRequest, RateLimit};
+use ghdash::util::config::{AppConfig, InboxSort, SecondarySort};
+use ghdash::util::pr_diff::ListDiff;
+
+mod common;
+
+use common::make_repo;
 
 fn make_state() -> AppState {
     AppState::new("testuser".into(), vec!["org-a".into(), "org-b".into()])
 }
 
-fn make_repo(owner: &str, name: &str, open_prs: u32) -> Repo {
-    Repo {
-        name: name.into(),
-        owner: owner.into(),
-        url: format!("https://github.com/{}/{}", owner, name),
-        description: None,
-        open_pr_count: open_prs,
-        is_archived: false,
-    }
-}
-
 fn make_pr(repo_owner: &str, repo_name: &str, number: u32, title: &str) -> PullRequest {
     PullRequest {
-        number,
         title: title.into(),
-        author: "author".into(),
-        repo_owner: repo_owner.into(),
-        repo_name: repo_name.into(),
-        url: format!(
-            "https://github.com/{}/{}/pull/{}",
-            repo_owner, repo_name, number
-        ),
-        created_at: chrono::Utc::now(),
-        updated_at: chrono::Utc::now(),
-        is_draft: false,
         additions: 10,
         deletions: 5,
-        review_decision: None,
-        mergeable: None,
-        merge_state_status: None,
-        checks_status: None,
-        labels: vec![],
+        ..common::make_pr(repo_owner, repo_name, number)
     }
 }
 
@@ -47,10 +34,14 @@ fn make_pr(repo_owner: &str, repo_name: &str, number: u32, title: &str) -> PullR
 #[test]
 fn test_initial_state_has_nav_nodes() {
     let state = make_state();
-    // Should have: MyInbox, AllPrs, Org(org-a), Org(org-b)
-    assert_eq!(state.nav_nodes.len(), 4);
+    // Should have: MyInbox, AllPrs, RecentlyMerged, RecentlyClosed, FailingChecks,
+    // Org(org-a), Org(org-b)
+    assert_eq!(state.nav_nodes.len(), 7);
     assert!(matches!(&state.nav_nodes[0], NavNode::MyInbox));
     assert!(matches!(&state.nav_nodes[1], NavNode::AllPrs));
+    assert!(matches!(&state.nav_nodes[2], NavNode::RecentlyMerged));
+    assert!(matches!(&state.nav_nodes[3], NavNode::RecentlyClosed));
+    assert!(matches!(&state.nav_nodes[4], NavNode::FailingChecks));
 }
 
 #[test]
@@ -102,571 +93,3745 @@ fn test_move_down_at_end_stays() {
     assert_eq!(state.nav_cursor, max);
 }
 
-// --- Pane switching ---
+// --- Counts ---
 
 #[test]
-fn test_switch_pane() {
+fn test_count_digits_compose() {
     let mut state = make_state();
-    assert_eq!(state.focused_pane, FocusedPane::Navigation);
-    update(&mut state, Action::SwitchPane);
-    assert_eq!(state.focused_pane, FocusedPane::Content);
-    update(&mut state, Action::SwitchPane);
-    assert_eq!(state.focused_pane, FocusedPane::Navigation);
+    update(&mut state, Action::CountDigit(1));
+    update(&mut state, Action::CountDigit(2));
+    assert_eq!(state.pending_count, Some(12));
+    // Background results don't disturb a count being typed.
+    update(&mut state, Action::Tick);
+    assert_eq!(state.pending_count, Some(12));
 }
 
-// --- Select ---
-
 #[test]
-fn test_select_inbox() {
+fn test_count_prefixed_motions() {
     let mut state = make_state();
-    // Cursor at 0 = MyInbox
-    update(&mut state, Action::Select);
-    assert_eq!(state.content_view, ContentView::Inbox);
+    state.inbox = (1..=20).map(|n| make_pr("org-a", "api", n, "PR")).collect();
+    state.focused_pane = FocusedPane::Content;
+
+    update(&mut state, Action::CountDigit(5));
+    update(&mut state, Action::MoveDown);
+    assert_eq!(state.content_cursor, 5);
+    assert_eq!(state.pending_count, None);
+
+    update(&mut state, Action::CountDigit(2));
+    update(&mut state, Action::MoveUp);
+    assert_eq!(state.content_cursor, 3);
+
+    // Overshooting clamps to the list.
+    update(&mut state, Action::CountDigit(9));
+    update(&mut state, Action::CountDigit(9));
+    update(&mut state, Action::MoveDown);
+    assert_eq!(state.content_cursor, 19);
+
+    update(&mut state, Action::JumpTop);
+    assert_eq!(state.content_cursor, 0);
+    update(&mut state, Action::JumpBottom);
+    assert_eq!(state.content_cursor, 19);
+
+    // 12G jumps to row 12 (1-based).
+    update(&mut state, Action::CountDigit(1));
+    update(&mut state, Action::CountDigit(2));
+    update(&mut state, Action::JumpBottom);
+    assert_eq!(state.content_cursor, 11);
 }
 
 #[test]
-fn test_select_all_prs() {
+fn test_count_then_enter_selects_that_row() {
     let mut state = make_state();
-    state.nav_cursor = 1; // AllPrs
-    update(&mut state, Action::Select);
-    assert_eq!(state.content_view, ContentView::AllOpenPrs);
+    state.inbox = (1..=5).map(|n| make_pr("org-a", "api", n, "PR")).collect();
+    state.focused_pane = FocusedPane::Content;
+
+    update(&mut state, Action::CountDigit(3));
+    let effects = update(&mut state, Action::Select);
+    assert_eq!(state.content_cursor, 2);
+    assert!(matches!(
+        effects.as_slice(),
+        [SideEffect::OpenUrl(url)] if url.ends_with("/pull/3")
+    ));
 }
 
 #[test]
-fn test_select_org_toggles_expand() {
+fn test_count_cleared_by_escape_and_other_keys() {
     let mut state = make_state();
-    state.nav_cursor = 2; // First org
+    update(&mut state, Action::CountDigit(4));
+    update(&mut state, Action::ClearPending);
+    assert_eq!(state.pending_count, None);
 
-    let org_name = match &state.nav_nodes[2] {
-        NavNode::Org(name) => name.clone(),
-        _ => panic!("Expected Org node"),
-    };
+    update(&mut state, Action::CountDigit(4));
+    update(&mut state, Action::ToggleHelp);
+    assert_eq!(state.pending_count, None);
+    // The discarded count doesn't leak into the next motion.
+    update(&mut state, Action::ToggleHelp);
+    update(&mut state, Action::MoveDown);
+    assert_eq!(state.nav_cursor, 1);
+}
 
-    // Initially expanded, selecting should collapse
-    assert!(state.nav_expanded.contains(&org_name));
-    update(&mut state, Action::Select);
-    assert!(!state.nav_expanded.contains(&org_name));
+// --- Letter jumping ---
 
-    // Select again to expand
-    // After collapse, nav tree changed; find the org again
-    let org_idx = state
-        .nav_nodes
-        .iter()
-        .position(|n| matches!(n, NavNode::Org(name) if *name == org_name))
-        .unwrap();
-    state.nav_cursor = org_idx;
-    update(&mut state, Action::Select);
-    assert!(state.nav_expanded.contains(&org_name));
+fn make_jump_state() -> AppState {
+    let mut state = make_state();
+    state.orgs.get_mut("org-a").unwrap().repos = vec![
+        make_repo("org-a", "mobile", 3),
+        make_repo("org-a", "api", 2),
+        make_repo("org-a", "Metrics", 1),
+    ];
+    state.rebuild_nav_tree();
+    state
 }
 
-// --- DataLoaded ---
+fn nav_name(state: &AppState) -> String {
+    match &state.nav_nodes[state.nav_cursor] {
+        NavNode::Org(name) | NavNode::Repo { name, .. } => name.clone(),
+        other => panic!("cursor on {:?}", other),
+    }
+}
 
 #[test]
-fn test_data_loaded_org_repos() {
-    let mut state = make_state();
-    let repos = vec![
-        make_repo("org-a", "repo1", 3),
-        make_repo("org-a", "repo2", 0),
-    ];
+fn test_letter_jump_cycles_through_matches() {
+    let mut state = make_jump_state();
+
+    update(&mut state, Action::StartLetterJump);
+    assert!(state.pending_jump);
+    update(&mut state, Action::JumpToLetter('m'));
+    assert!(!state.pending_jump);
+    assert_eq!(nav_name(&state), "mobile");
+
+    // Case-insensitive, and repeated presses move on to the next match.
+    update(&mut state, Action::StartLetterJump);
+    update(&mut state, Action::JumpToLetter('m'));
+    assert_eq!(nav_name(&state), "Metrics");
+
+    // Wraps around past the end of the list.
+    update(&mut state, Action::StartLetterJump);
+    update(&mut state, Action::JumpToLetter('m'));
+    assert_eq!(nav_name(&state), "mobile");
+
+    update(&mut state, Action::StartLetterJump);
+    update(&mut state, Action::JumpToLetter('O'));
+    assert_eq!(nav_name(&state), "org-b");
+    update(&mut state, Action::StartLetterJump);
+    update(&mut state, Action::JumpToLetter('o'));
+    assert_eq!(nav_name(&state), "org-a");
+}
 
-    update(
-        &mut state,
-        Action::DataLoaded(DataPayload::OrgRepos {
-            org: "org-a".into(),
-            repos,
-            rate_limit: RateLimit {
-                remaining: 4999,
-                limit: 5000,
-                reset_at: None,
-            },
-        }),
-    );
+#[test]
+fn test_letter_jump_without_match_keeps_cursor() {
+    let mut state = make_jump_state();
+    state.nav_cursor = 2;
 
-    let org_data = state.orgs.get("org-a").unwrap();
-    assert_eq!(org_data.repos.len(), 2);
-    assert_eq!(state.rate_limit.remaining, 4999);
+    update(&mut state, Action::StartLetterJump);
+    update(&mut state, Action::JumpToLetter('z'));
+    assert_eq!(state.nav_cursor, 2);
+    assert!(state.status_message.as_deref().unwrap().contains("'z'"));
+    assert!(!state.pending_jump);
+}
 
-    // Nav tree should now include repos under org-a
-    let repo_nodes: Vec<_> = state
-        .nav_nodes
-        .iter()
-        .filter(|n| matches!(n, NavNode::Repo { owner, .. } if owner == "org-a"))
-        .collect();
-    assert_eq!(repo_nodes.len(), 2);
+#[test]
+fn test_letter_jump_prefix_cleared_by_other_keys() {
+    let mut state = make_jump_state();
+    update(&mut state, Action::StartLetterJump);
+    update(&mut state, Action::ClearPending);
+    assert!(!state.pending_jump);
+    assert_eq!(state.nav_cursor, 0);
 }
 
+// --- Owner cycling ---
+
 #[test]
-fn test_data_loaded_inbox() {
+fn test_next_owner_cycles_overviews_and_moves_nav_cursor() {
     let mut state = make_state();
-    let prs = vec![make_pr("org-a", "repo1", 1, "Fix bug")];
+    update(&mut state, Action::NextOwner);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-a".into()));
+    assert!(matches!(&state.nav_nodes[state.nav_cursor], NavNode::Org(o) if o == "org-a"));
 
-    update(
-        &mut state,
-        Action::DataLoaded(DataPayload::InboxPrs {
-            prs: prs.clone(),
-            rate_limit: RateLimit::default(),
-        }),
-    );
+    update(&mut state, Action::NextOwner);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-b".into()));
+    assert!(matches!(&state.nav_nodes[state.nav_cursor], NavNode::Org(o) if o == "org-b"));
 
-    assert_eq!(state.inbox.len(), 1);
-    assert_eq!(state.inbox[0].title, "Fix bug");
+    // Wraps around.
+    update(&mut state, Action::NextOwner);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-a".into()));
 }
 
 #[test]
-fn test_data_loaded_all_open_prs() {
+fn test_prev_owner_from_a_repo_view() {
     let mut state = make_state();
-    let prs = vec![
-        make_pr("org-a", "repo1", 1, "PR 1"),
-        make_pr("org-a", "repo1", 2, "PR 2"),
-    ];
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-b".into(),
+        name: "api".into(),
+    };
+    update(&mut state, Action::PrevOwner);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-a".into()));
 
-    update(
-        &mut state,
-        Action::DataLoaded(DataPayload::AllOpenPrs {
-            prs,
-            rate_limit: RateLimit::default(),
-        }),
-    );
+    update(&mut state, Action::PrevOwner);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-b".into()));
 
-    assert_eq!(state.all_open_prs.len(), 2);
+    // From a virtual view, PrevOwner starts at the last owner.
+    state.content_view = ContentView::Inbox;
+    update(&mut state, Action::PrevOwner);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-b".into()));
 }
 
-// --- Loading state ---
+// --- Refresh lock ---
 
 #[test]
-fn test_loading_completes_when_no_orgs_loading() {
+fn test_refresh_lock_freezes_order() {
     let mut state = make_state();
-    assert!(state.loading);
+    state.inbox = vec![
+        make_pr("org-a", "api", 1, "One"),
+        make_pr("org-a", "api", 2, "Two"),
+        make_pr("org-a", "api", 3, "Three"),
+    ];
 
-    // Simulate data loaded (loading_orgs is empty by default)
+    update(&mut state, Action::ToggleRefreshLock);
+    assert!(state.refresh_locked);
+
+    // A refresh lands with a different order and a new PR, most recently
+    // updated first as fetched.
+    let mut prs = vec![
+        make_pr("org-a", "api", 4, "Four"),
+        make_pr("org-a", "api", 3, "Three"),
+        make_pr("org-a", "api", 1, "One"),
+        make_pr("org-a", "api", 2, "Two"),
+    ];
+    let now = chrono::Utc::now();
+    for (i, pr) in prs.iter_mut().enumerate() {
+        pr.updated_at = now - chrono::Duration::minutes(i as i64);
+    }
     update(
         &mut state,
         Action::DataLoaded(DataPayload::InboxPrs {
-            prs: vec![],
-            rate_limit: RateLimit::default(),
+            prs,
+            rate_limit: None,
         }),
     );
-
-    assert!(!state.loading);
-    assert!(state.last_refresh.is_some());
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3, 4]);
+
+    // Unlocking shows the fetched order again.
+    update(&mut state, Action::ToggleRefreshLock);
+    assert!(!state.refresh_locked);
+    assert!(state.locked_order.is_none());
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![4, 3, 1, 2]);
 }
 
-// --- Error handling ---
+// --- Fetch failures ---
 
 #[test]
-fn test_load_error_sets_message() {
+fn test_successful_fetch_clears_failure() {
     let mut state = make_state();
-    update(&mut state, Action::LoadError("Network error".into()));
-    assert_eq!(state.error_message, Some("Network error".into()));
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: ContentView::OrgOverview("org-a".into()),
+            msg: "Failed to fetch repos for org-a: boom".into(),
+        },
+    );
+    assert!(
+        state
+            .failed_views
+            .contains(&ContentView::OrgOverview("org-a".into()))
+    );
     assert!(!state.loading);
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "api", 1)],
+            rate_limit: None,
+        }),
+    );
+    assert!(state.failed_views.is_empty());
 }
 
 #[test]
-fn test_dismiss_error() {
+fn test_repo_fetch_records_open_pr_history() {
     let mut state = make_state();
-    state.error_message = Some("err".into());
-    update(&mut state, Action::DismissError);
-    assert!(state.error_message.is_none());
-}
+    // Collapsed, so the repos aren't prefetched too.
+    state.nav_expanded.clear();
+    let effects = update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "api", 4)],
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(state.pr_history.counts("org-a/api"), vec![4]);
+    assert!(matches!(effects.as_slice(), [SideEffect::SavePrHistory(h)] if *h == state.pr_history));
 
-// --- Refresh ---
+    // The same count again moments later changes nothing worth saving.
+    let effects = update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "api", 4)],
+            rate_limit: None,
+        }),
+    );
+    assert!(effects.is_empty());
 
-#[test]
-fn test_refresh_returns_side_effect() {
+    // Cached data shown offline isn't a new sample.
     let mut state = make_state();
-    let effects = update(&mut state, Action::Refresh);
-    assert!(state.loading);
-    assert!(state.error_message.is_none());
-    assert_eq!(effects.len(), 1);
-    assert!(matches!(effects[0], SideEffect::RefreshAll));
+    state.nav_expanded.clear();
+    state.offline = true;
+    let effects = update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "api", 4)],
+            rate_limit: None,
+        }),
+    );
+    assert!(state.pr_history.repos.is_empty());
+    assert!(effects.is_empty());
 }
 
-// --- Search ---
+// --- Filter counts ---
 
 #[test]
-fn test_toggle_search() {
+fn test_view_counts_before_and_after_filters() {
     let mut state = make_state();
-    assert!(!state.search_active);
-    update(&mut state, Action::ToggleSearch);
-    assert!(state.search_active);
-    update(&mut state, Action::ToggleSearch);
-    assert!(!state.search_active);
-}
+    let mut conflicting = make_pr("org-a", "api", 3, "Rework auth");
+    conflicting.mergeable = Some("CONFLICTING".into());
+    state.inbox = vec![
+        make_pr("org-a", "api", 1, "Fix login"),
+        make_pr("org-a", "web", 2, "Fix layout"),
+        conflicting,
+    ];
+    assert!(!state.filters_active());
+    assert_eq!(state.view_counts(), (3, 3));
 
-#[test]
-fn test_search_input() {
-    let mut state = make_state();
-    update(&mut state, Action::ToggleSearch);
-    update(&mut state, Action::SearchInput('h'));
-    update(&mut state, Action::SearchInput('i'));
-    assert_eq!(state.search_query, "hi");
+    state.search_query = "fix".into();
+    assert!(state.filters_active());
+    assert_eq!(state.view_counts(), (2, 3));
+
+    state.search_query.clear();
+    state.merge_filter = MergeFilter::Conflicting;
+    assert!(state.filters_active());
+    assert_eq!(state.view_counts(), (1, 3));
+
+    // Other lists are counted under the same filters, for nav badges.
+    let others = vec![make_pr("org-b", "cli", 9, "Docs")];
+    assert_eq!(state.filtered_counts(&others), (0, 1));
+    assert!(state.shared_filters_active());
 }
 
 #[test]
-fn test_search_backspace() {
+fn test_view_only_filters_leave_nav_badges_alone() {
     let mut state = make_state();
-    update(&mut state, Action::ToggleSearch);
-    update(&mut state, Action::SearchInput('a'));
-    update(&mut state, Action::SearchInput('b'));
-    update(&mut state, Action::SearchBackspace);
-    assert_eq!(state.search_query, "a");
+    let mut labelled = make_pr("org-a", "api", 1, "Fix login");
+    labelled.labels = vec!["bug".into()];
+    state.inbox = vec![labelled, make_pr("org-a", "web", 2, "Fix layout")];
+    state.label_filter = vec!["bug".into()];
+
+    // The label narrows the view, but nav badges don't apply it ...
+    assert!(state.filters_active());
+    assert_eq!(state.view_counts(), (1, 2));
+    assert!(!state.shared_filters_active());
+    // ... so their counts aren't shown as filtered.
+    assert_eq!(state.filtered_counts(&state.inbox.clone()), (2, 2));
 }
 
+// --- Repo cap ---
+
 #[test]
-fn test_search_filters_prs() {
+fn test_max_repos_per_org_caps_nav_and_links_to_overview() {
     let mut state = make_state();
-    state.content_view = ContentView::Inbox;
-    state.inbox = vec![
-        make_pr("org", "repo", 1, "Fix login bug"),
-        make_pr("org", "repo", 2, "Add dashboard feature"),
-        make_pr("org", "repo", 3, "Login page redesign"),
+    state.hide_empty_repos = false;
+    state.orgs.get_mut("org-a").unwrap().repos = vec![
+        make_repo("org-a", "quiet", 0),
+        make_repo("org-a", "busy", 9),
+        make_repo("org-a", "medium", 4),
+        make_repo("org-a", "small", 1),
     ];
-
-    state.search_active = true;
-    state.search_query = "login".into();
-
-    let filtered = state.current_pr_list();
-    assert_eq!(filtered.len(), 2);
+    state.rebuild_nav_tree();
     assert!(
-        filtered
+        !state
+            .nav_nodes
             .iter()
-            .all(|pr| pr.title.to_lowercase().contains("login"))
+            .any(|n| matches!(n, NavNode::MoreRepos(..)))
     );
-}
 
-// --- Quit ---
+    state.max_repos_per_org = Some(2);
+    state.rebuild_nav_tree();
+    let names: Vec<String> = state
+        .nav_nodes
+        .iter()
+        .filter_map(|n| match n {
+            NavNode::Repo { name, .. } => Some(name.clone()),
+            NavNode::MoreRepos(org, hidden) => Some(format!("{}+{}", org, hidden)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, vec!["busy", "medium", "org-a+2"]);
 
-#[test]
-fn test_quit() {
-    let mut state = make_state();
-    update(&mut state, Action::Quit);
-    assert!(state.should_quit);
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::MoreRepos(..)))
+        .unwrap();
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::OrgOverview("org-a".into()));
 }
 
-// --- Back ---
+// --- Pane switching ---
 
 #[test]
-fn test_back_closes_search() {
+fn test_switch_pane() {
     let mut state = make_state();
-    state.search_active = true;
-    state.search_query = "test".into();
-    update(&mut state, Action::Back);
-    assert!(!state.search_active);
-    assert!(state.search_query.is_empty());
+    assert_eq!(state.focused_pane, FocusedPane::Navigation);
+    update(&mut state, Action::SwitchPane);
+    assert_eq!(state.focused_pane, FocusedPane::Content);
+    update(&mut state, Action::SwitchPane);
+    assert_eq!(state.focused_pane, FocusedPane::Navigation);
 }
 
+// --- Select ---
+
 #[test]
-fn test_back_dismisses_error() {
+fn test_select_inbox() {
     let mut state = make_state();
-    state.error_message = Some("err".into());
-    update(&mut state, Action::Back);
-    assert!(state.error_message.is_none());
+    // Cursor at 0 = MyInbox
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::Inbox);
 }
 
 #[test]
-fn test_back_switches_to_nav_pane() {
+fn test_select_all_prs() {
     let mut state = make_state();
-    state.focused_pane = FocusedPane::Content;
-    update(&mut state, Action::Back);
-    assert_eq!(state.focused_pane, FocusedPane::Navigation);
+    state.nav_cursor = 1; // AllPrs
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::AllOpenPrs);
 }
 
-// --- Open in browser ---
-
 #[test]
-fn test_open_in_browser_from_content_with_pr() {
+fn test_select_org_toggles_expand() {
     let mut state = make_state();
-    state.focused_pane = FocusedPane::Content;
-    state.content_view = ContentView::Inbox;
-    state.inbox = vec![make_pr("org", "repo", 42, "My PR")];
-    state.content_cursor = 0;
+    state.nav_cursor = 5; // First org
 
-    let effects = update(&mut state, Action::OpenInBrowser);
-    assert_eq!(effects.len(), 1);
-    match &effects[0] {
-        SideEffect::OpenUrl(url) => {
-            assert!(url.contains("42"));
-        }
-        _ => panic!("Expected OpenUrl side effect"),
-    }
-}
+    let org_name = match &state.nav_nodes[5] {
+        NavNode::Org(name) => name.clone(),
+        _ => panic!("Expected Org node"),
+    };
 
-#[test]
-fn test_open_in_browser_from_nav_on_org() {
-    let mut state = make_state();
-    state.focused_pane = FocusedPane::Navigation;
-    // Find an Org node
+    // Initially expanded, selecting should collapse
+    assert!(state.nav_expanded.contains(&org_name));
+    update(&mut state, Action::Select);
+    assert!(!state.nav_expanded.contains(&org_name));
+
+    // Select again to expand
+    // After collapse, nav tree changed; find the org again
     let org_idx = state
         .nav_nodes
         .iter()
-        .position(|n| matches!(n, NavNode::Org(_)))
+        .position(|n| matches!(n, NavNode::Org(name) if *name == org_name))
         .unwrap();
     state.nav_cursor = org_idx;
-
-    let effects = update(&mut state, Action::OpenInBrowser);
-    assert_eq!(effects.len(), 1);
-    assert!(
-        matches!(&effects[0], SideEffect::OpenUrl(url) if url.starts_with("https://github.com/"))
-    );
+    update(&mut state, Action::Select);
+    assert!(state.nav_expanded.contains(&org_name));
 }
 
-// --- Nav tree rebuild with repos ---
-
 #[test]
-fn test_nav_tree_sorts_repos_by_pr_count() {
+fn test_select_recently_merged_switches_view() {
     let mut state = make_state();
+    state.nav_cursor = 2;
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::RecentlyMerged);
+}
 
-    let repos = vec![
-        make_repo("org-a", "low-prs", 1),
-        make_repo("org-a", "high-prs", 10),
-        make_repo("org-a", "mid-prs", 5),
-    ];
-
+#[test]
+fn test_recently_merged_loaded_feeds_pr_list() {
+    let mut state = make_state();
+    let mut pr = make_pr("org-a", "api", 1, "Ship it");
+    pr.merged_at = Some(chrono::Utc::now());
     update(
         &mut state,
-        Action::DataLoaded(DataPayload::OrgRepos {
-            org: "org-a".into(),
-            repos,
-            rate_limit: RateLimit::default(),
+        Action::DataLoaded(DataPayload::PrHistory {
+            kind: PrHistoryKind::Merged,
+            prs: vec![pr],
+            rate_limit: None,
         }),
     );
-
-    let repo_names: Vec<String> = state
-        .nav_nodes
-        .iter()
-        .filter_map(|n| match n {
-            NavNode::Repo { owner, name, .. } if owner == "org-a" => Some(name.clone()),
-            _ => None,
-        })
-        .collect();
-
-    assert_eq!(repo_names, vec!["high-prs", "mid-prs", "low-prs"]);
+    state.content_view = ContentView::RecentlyMerged;
+    assert_eq!(state.current_pr_list().len(), 1);
+    assert!(state.inbox.is_empty());
+    assert!(state.recently_closed.is_empty());
 }
 
 #[test]
-fn test_archived_repos_excluded_from_nav() {
+fn test_recently_closed_loaded_feeds_pr_list() {
     let mut state = make_state();
-
-    let mut archived = make_repo("org-a", "old-repo", 0);
-    archived.is_archived = true;
-    let repos = vec![make_repo("org-a", "active-repo", 2), archived];
-
+    let mut pr = make_pr("org-a", "api", 2, "Abandoned idea");
+    pr.closed_at = Some(chrono::Utc::now());
     update(
         &mut state,
-        Action::DataLoaded(DataPayload::OrgRepos {
-            org: "org-a".into(),
-            repos,
-            rate_limit: RateLimit::default(),
+        Action::DataLoaded(DataPayload::PrHistory {
+            kind: PrHistoryKind::Closed,
+            prs: vec![pr],
+            rate_limit: None,
         }),
     );
+    assert!(state.recently_merged.is_empty());
 
-    let repo_names: Vec<String> = state
+    state.nav_cursor = 3;
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::RecentlyClosed);
+    assert_eq!(state.current_pr_list().len(), 1);
+}
+
+#[test]
+fn test_group_by_topic_nav_tree() {
+    let mut state = make_state();
+    state.hide_empty_repos = false;
+    state.group_by_topic = true;
+    let mut api = make_repo("org-a", "api", 3);
+    api.topics = vec!["service".into()];
+    let mut sdk = make_repo("org-a", "sdk", 1);
+    sdk.topics = vec!["library".into(), "service".into()];
+    let scratch = make_repo("org-a", "scratch", 0);
+    state.orgs.get_mut("org-a").unwrap().repos = vec![api, sdk, scratch];
+    state.rebuild_nav_tree();
+
+    let labels: Vec<String> = state
         .nav_nodes
         .iter()
-        .filter_map(|n| match n {
-            NavNode::Repo { name, .. } => Some(name.clone()),
-            _ => None,
+        .skip_while(|n| !matches!(n, NavNode::Org(name) if name == "org-a"))
+        .skip(1)
+        .take_while(|n| !matches!(n, NavNode::Org(_)))
+        .map(|n| match n {
+            NavNode::Topic(t) => format!("#{}", t),
+            NavNode::Repo { name, .. } => name.clone(),
+            other => panic!("unexpected node {:?}", other),
         })
         .collect();
-
-    assert_eq!(repo_names, vec!["active-repo"]);
+    assert_eq!(
+        labels,
+        vec!["#library", "sdk", "#service", "api", "#untagged", "scratch"]
+    );
 }
 
-// --- PR overlays: git log & diff (task zkk5) ---
-
 #[test]
-fn test_toggle_git_log_flips_overlay() {
+fn test_hide_empty_repos_in_nav() {
     let mut state = make_state();
-    assert_eq!(state.overlay, Overlay::None);
-    update(&mut state, Action::ToggleGitLog);
-    assert_eq!(state.overlay, Overlay::GitLog);
-    update(&mut state, Action::ToggleGitLog);
-    assert_eq!(state.overlay, Overlay::None);
+    state.nav_expanded.insert("org-a".into());
+    state.orgs.get_mut("org-a").unwrap().repos = vec![
+        make_repo("org-a", "api", 2),
+        make_repo("org-a", "idle", 0),
+        make_repo("org-a", "sdk", 1),
+    ];
+    let repo_names = |state: &AppState| -> Vec<String> {
+        state
+            .nav_nodes
+            .iter()
+            .filter_map(|n| match n {
+                NavNode::Repo { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+
+    // Hidden by default.
+    state.rebuild_nav_tree();
+    assert!(state.hide_empty_repos);
+    assert_eq!(repo_names(&state), vec!["api", "sdk"]);
+    // The org still counts every repo.
+    assert_eq!(state.orgs["org-a"].repos.len(), 3);
+
+    update(&mut state, Action::ToggleEmptyRepos);
+    assert!(!state.hide_empty_repos);
+    assert_eq!(repo_names(&state), vec!["api", "sdk", "idle"]);
+
+    update(&mut state, Action::ToggleEmptyRepos);
+    assert_eq!(repo_names(&state), vec!["api", "sdk"]);
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("Hiding repos without open PRs")
+    );
 }
 
 #[test]
-fn test_toggle_diff_flips_overlay() {
+fn test_select_topic_heading_is_noop() {
     let mut state = make_state();
-    update(&mut state, Action::ToggleDiff);
-    assert_eq!(state.overlay, Overlay::Diff);
-    update(&mut state, Action::ToggleDiff);
-    assert_eq!(state.overlay, Overlay::None);
+    state.group_by_topic = true;
+    state.orgs.get_mut("org-a").unwrap().repos = vec![make_repo("org-a", "api", 1)];
+    state.rebuild_nav_tree();
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Topic(_)))
+        .unwrap();
+    let effects = update(&mut state, Action::Select);
+    assert!(effects.is_empty());
+    assert_eq!(state.content_view, ContentView::Inbox);
 }
 
 #[test]
-fn test_toggle_switches_between_overlays() {
+fn test_standalone_repos_listed_after_orgs() {
     let mut state = make_state();
-    update(&mut state, Action::ToggleGitLog);
-    assert_eq!(state.overlay, Overlay::GitLog);
-    // Pressing the diff key while the log is open switches to the diff.
-    update(&mut state, Action::ToggleDiff);
-    assert_eq!(state.overlay, Overlay::Diff);
+    state.standalone_repos = vec!["big-org/payment-service".to_string()];
+    state.all_open_prs = vec![
+        make_pr("big-org", "payment-service", 1, "Fix refunds"),
+        make_pr("big-org", "payment-service", 2, "Bump deps"),
+        make_pr("org-a", "api", 3, "Other"),
+    ];
+    state.rebuild_nav_tree();
+
+    let section = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::ReposSection))
+        .unwrap();
+    assert!(
+        state.nav_nodes[..section]
+            .iter()
+            .any(|n| matches!(n, NavNode::Org(name) if name == "org-b"))
+    );
+    assert!(matches!(
+        &state.nav_nodes[section + 1],
+        NavNode::Repo { owner, name, open_prs: 2 } if owner == "big-org" && name == "payment-service"
+    ));
+
+    // The heading is a label; the repo opens its PR list.
+    state.nav_cursor = section;
+    assert!(update(&mut state, Action::Select).is_empty());
+    assert_eq!(state.content_view, ContentView::Inbox);
+    state.nav_cursor = section + 1;
+    update(&mut state, Action::Select);
+    assert_eq!(
+        state.content_view,
+        ContentView::RepoPrList {
+            owner: "big-org".into(),
+            name: "payment-service".into(),
+        }
+    );
 }
 
 #[test]
-fn test_back_closes_overlay_before_switching_pane() {
+fn test_refresh_current_view_targets_only_that_view() {
     let mut state = make_state();
-    state.focused_pane = FocusedPane::Content;
-    update(&mut state, Action::ToggleGitLog);
-    assert_eq!(state.overlay, Overlay::GitLog);
-
-    // Back should close the overlay first, leaving focus on Content.
-    update(&mut state, Action::Back);
-    assert_eq!(state.overlay, Overlay::None);
-    assert_eq!(state.focused_pane, FocusedPane::Content);
+    state.content_view = ContentView::RecentlyClosed;
+    state.error_message = Some("boom".into());
+    let effects = update(&mut state, Action::RefreshCurrentView);
+    assert_eq!(effects.len(), 1);
+    assert!(matches!(
+        &effects[0],
+        SideEffect::RefreshView(ContentView::RecentlyClosed)
+    ));
+    assert!(state.error_message.is_none());
 }
 
 #[test]
-fn test_pr_detail_loaded_upgrades_list_merge_state() {
-    use ghdash::app::state::PrDetailEntry;
-    use ghdash::github::models::PrDetail;
-
+fn test_refresh_inbox_fetches_only_the_inbox() {
     let mut state = make_state();
-    // A PR whose list value is UNKNOWN (typical of the search API).
-    let mut pr = make_pr("org-a", "repo1", 7, "Needs fresh state");
-    pr.mergeable = Some("UNKNOWN".into());
-    let url = pr.url.clone();
+    state.loading = false;
+    state.content_view = ContentView::AllOpenPrs;
+    let effects = update(&mut state, Action::RefreshInbox);
+    assert_eq!(effects.len(), 1);
+    assert!(matches!(
+        &effects[0],
+        SideEffect::RefreshView(ContentView::Inbox)
+    ));
+    assert!(state.inbox_loading);
+    assert!(!state.loading);
+    assert_eq!(state.content_view, ContentView::AllOpenPrs);
+
     update(
         &mut state,
-        Action::DataLoaded(DataPayload::AllOpenPrs {
-            prs: vec![pr],
-            rate_limit: RateLimit::default(),
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![make_pr("org-a", "api", 1, "Review me")],
+            rate_limit: None,
         }),
     );
+    assert!(!state.inbox_loading);
+    assert_eq!(state.inbox.len(), 1);
 
-    let detail = PrDetail {
-        mergeable: Some("CONFLICTING".into()),
-        merge_state_status: Some("DIRTY".into()),
-        checks_status: Some("FAILURE".into()),
-        commits: vec![],
-    };
+    update(&mut state, Action::RefreshInbox);
     update(
         &mut state,
-        Action::DataLoaded(DataPayload::PrDetailLoaded {
-            key: url.clone(),
-            detail,
-            rate_limit: RateLimit::default(),
-        }),
-    );
-
-    // Detail is cached and the list column reflects the fresh value.
-    assert!(matches!(
-        state.pr_details.get(&url),
-        Some(PrDetailEntry::Loaded(_))
-    ));
-    assert_eq!(
-        state.all_open_prs[0].mergeable.as_deref(),
-        Some("CONFLICTING")
-    );
-    assert_eq!(
-        state.all_open_prs[0].merge_state_status.as_deref(),
-        Some("DIRTY")
+        Action::FetchFailed {
+            view: ContentView::Inbox,
+            msg: "timed out".into(),
+        },
     );
+    assert!(!state.inbox_loading);
 }
 
 #[test]
-fn test_refresh_clears_pr_details() {
-    use ghdash::app::state::PrDetailEntry;
-
+fn test_offline_refresh_is_disabled() {
     let mut state = make_state();
-    state
-        .pr_details
-        .insert("some-url".into(), PrDetailEntry::Loading);
-    assert!(!state.pr_details.is_empty());
-
-    update(&mut state, Action::Refresh);
-    assert!(state.pr_details.is_empty());
+    state.offline = true;
+    state.loading = false;
+    assert!(update(&mut state, Action::Refresh).is_empty());
+    assert!(update(&mut state, Action::RefreshCurrentView).is_empty());
+    assert!(update(&mut state, Action::RefreshInbox).is_empty());
+    assert!(!state.inbox_loading);
+    assert!(!state.loading);
 }
 
 #[test]
-fn test_pr_detail_failed_records_error() {
-    use ghdash::app::state::PrDetailEntry;
-
+fn test_cached_data_age_keeps_oldest() {
     let mut state = make_state();
-    update(
-        &mut state,
-        Action::DataLoaded(DataPayload::PrDetailFailed {
-            key: "url-x".into(),
-            msg: "boom".into(),
-        }),
-    );
-    assert!(matches!(
-        state.pr_details.get("url-x"),
-        Some(PrDetailEntry::Failed(_))
-    ));
+    let newer = chrono::Utc::now();
+    let older = newer - chrono::Duration::hours(3);
+    update(&mut state, Action::CachedDataAge(newer));
+    update(&mut state, Action::CachedDataAge(older));
+    update(&mut state, Action::CachedDataAge(newer));
+    assert_eq!(state.offline_data_as_of, Some(older));
 }
 
-// --- Merge-state filter + help (task pp0u) ---
+#[test]
+fn test_viewer_changed_reloads_as_the_new_login() {
+    let mut state = make_state();
+    state.loading = false;
+    let effects = update(&mut state, Action::ViewerChanged("octocat".into()));
+    assert_eq!(state.viewer_login, "octocat");
+    assert!(state.loading);
+    assert!(effects.iter().any(|e| matches!(e, SideEffect::RefreshAll)));
+    let msg = state.error_message.as_deref().unwrap();
+    assert!(msg.contains("octocat") && msg.contains("testuser"), "{msg}");
+}
 
 #[test]
-fn test_cycle_merge_filter_order() {
-    use ghdash::app::state::MergeFilter;
+fn test_update_available_toast_until_next_key() {
     let mut state = make_state();
-    assert_eq!(state.merge_filter, MergeFilter::All);
-    update(&mut state, Action::CycleMergeFilter);
-    assert_eq!(state.merge_filter, MergeFilter::Conflicting);
-    update(&mut state, Action::CycleMergeFilter);
-    assert_eq!(state.merge_filter, MergeFilter::Clean);
-    update(&mut state, Action::CycleMergeFilter);
-    assert_eq!(state.merge_filter, MergeFilter::All);
+    update(&mut state, Action::UpdateAvailable("0.4.0".into()));
+    assert_eq!(state.status_message.as_deref(), Some("v0.4.0 available"));
+    update(&mut state, Action::Tick);
+    assert_eq!(state.status_message.as_deref(), Some("v0.4.0 available"));
+    update(&mut state, Action::MoveDown);
+    assert_eq!(state.status_message, None);
 }
 
 #[test]
-fn test_toggle_help_flips_flag() {
+fn test_config_reload_adds_and_removes_owners() {
     let mut state = make_state();
-    assert!(!state.help_open);
-    update(&mut state, Action::ToggleHelp);
+    state
+        .orgs
+        .get_mut("org-a")
+        .unwrap()
+        .repos
+        .push(make_repo("org-a", "api", 1));
+
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["org-a".into(), "org-c".into()];
+    config.github.users = vec!["someone".into()];
+    config.ui.letter_jump = true;
+    let effects = update(&mut state, Action::ConfigReloaded(Box::new(config)));
+
+    let mut owners: Vec<_> = state.orgs.keys().cloned().collect();
+    owners.sort();
+    assert_eq!(owners, vec!["org-a", "org-c", "someone"]);
+    // Existing owners keep what was already fetched.
+    assert_eq!(state.orgs["org-a"].repos.len(), 1);
+    assert!(state.letter_jump);
+    assert!(
+        !state
+            .nav_nodes
+            .iter()
+            .any(|n| matches!(n, NavNode::Org(name) if name == "org-b"))
+    );
+
+    assert!(matches!(&effects[0], SideEffect::FetchOrgRepos(o) if o == "org-c"));
+    assert!(matches!(&effects[1], SideEffect::FetchUserRepos(u) if u == "someone"));
+    assert!(
+        effects
+            .iter()
+            .any(|e| matches!(e, SideEffect::RefreshView(ContentView::AllOpenPrs)))
+    );
+}
+
+#[test]
+fn test_config_reload_with_same_owners_fetches_nothing() {
+    let mut state = make_state();
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["org-b".into(), "org-a".into()];
+    let effects = update(&mut state, Action::ConfigReloaded(Box::new(config)));
+    assert!(effects.is_empty());
+    assert_eq!(state.status_message.as_deref(), Some("Config reloaded"));
+}
+
+#[test]
+fn test_muted_repos_are_kept_out_of_inbox_and_all_prs() {
+    let mut state = make_state();
+    state.mute_repos = vec!["org-a/noisy-*".into(), "bots".into()];
+    let prs = vec![
+        make_pr("org-a", "api", 1, "Keep"),
+        make_pr("org-a", "noisy-deps", 2, "Muted by full name"),
+        make_pr("org-b", "bots", 3, "Muted by name"),
+    ];
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: prs.clone(),
+            rate_limit: None,
+        }),
+    );
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs,
+            rate_limit: None,
+        }),
+    );
+    let numbers = |prs: &[PullRequest]| prs.iter().map(|p| p.number).collect::<Vec<_>>();
+    state.content_view = ContentView::Inbox;
+    assert_eq!(numbers(&state.current_pr_list()), vec![1]);
+    state.content_view = ContentView::AllOpenPrs;
+    assert_eq!(numbers(&state.current_pr_list()), vec![1]);
+    // The lists keep them for the views derived from them.
+    assert_eq!(state.all_open_prs.len(), 3);
+}
+
+#[test]
+fn test_muted_repo_view_still_lists_its_prs() {
+    let mut state = make_state();
+    state.mute_repos = vec!["noisy".into()];
+    state.all_open_prs = vec![
+        make_pr("org-a", "api", 1, "Keep"),
+        make_pr("org-a", "noisy", 2, "Muted"),
+        make_pr("org-a", "noisy", 3, "Muted too"),
+    ];
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "noisy".into(),
+    };
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![2, 3]);
+}
+
+#[test]
+fn test_config_reload_changing_mutes_needs_no_refetch() {
+    let mut state = make_state();
+    state.inbox = vec![
+        make_pr("org-a", "api", 1, "Keep"),
+        make_pr("org-a", "noisy", 2, "Muted"),
+    ];
+    state.content_view = ContentView::Inbox;
+    let mut config = AppConfig::default();
+    config.github.orgs = vec!["org-a".into(), "org-b".into()];
+    config.github.mute_repos = vec!["noisy".into()];
+    let effects = update(&mut state, Action::ConfigReloaded(Box::new(config.clone())));
+    assert!(effects.is_empty());
+    assert_eq!(state.current_pr_list().len(), 1);
+
+    // Unmuting brings the PR straight back.
+    config.github.mute_repos.clear();
+    let effects = update(&mut state, Action::ConfigReloaded(Box::new(config)));
+    assert!(effects.is_empty());
+    assert_eq!(state.current_pr_list().len(), 2);
+}
+
+#[test]
+fn test_grouped_all_prs_keeps_each_repo_contiguous() {
+    let mut state = make_state();
+    state.group_all_prs_by_repo = true;
+    state.all_open_prs = vec![
+        make_pr("org-a", "web", 1, "Newest"),
+        make_pr("org-a", "api", 2, "Second"),
+        make_pr("org-a", "web", 3, "Third"),
+        make_pr("org-a", "api", 4, "Oldest"),
+    ];
+    state.content_view = ContentView::AllOpenPrs;
+    state.focused_pane = FocusedPane::Content;
+
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 3, 2, 4]);
+
+    // The cursor walks PRs only; header rows aren't part of the list.
+    update(&mut state, Action::MoveDown);
+    update(&mut state, Action::MoveDown);
+    assert_eq!(state.selected_pr().unwrap().number, 2);
+    update(&mut state, Action::JumpBottom);
+    assert_eq!(state.selected_pr().unwrap().number, 4);
+
+    // Other views keep their flat order.
+    state.content_view = ContentView::Inbox;
+    state.inbox = state.all_open_prs.clone();
+    assert!(!state.groups_by_repo());
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn test_scroll_columns_clamps_to_last_column() {
+    let mut state = make_state();
+    update(&mut state, Action::ScrollColumnsLeft);
+    assert_eq!(state.column_offset, 0);
+    // Nine default columns, eight besides Title.
+    for _ in 0..10 {
+        update(&mut state, Action::ScrollColumnsRight);
+    }
+    assert_eq!(state.column_offset, 7);
+    update(&mut state, Action::ScrollColumnsLeft);
+    assert_eq!(state.column_offset, 6);
+}
+
+#[test]
+fn test_cycle_sort_orders_smallest_diff_first() {
+    let mut state = make_state();
+    let mut big = make_pr("org-a", "api", 1, "Big");
+    big.additions = 400;
+    let mut small = make_pr("org-a", "api", 2, "Small");
+    small.additions = 1;
+    small.deletions = 1;
+    let mut medium = make_pr("org-a", "api", 3, "Medium");
+    medium.deletions = 50;
+    state.inbox = vec![big, small, medium];
+    state.content_cursor = 2;
+
+    update(&mut state, Action::CycleSort);
+    assert_eq!(state.sort, PrSort::SmallestFirst);
+    assert_eq!(state.content_cursor, 0);
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![2, 3, 1]);
+
+    update(&mut state, Action::CycleSort);
+    assert_eq!(state.sort, PrSort::Default);
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+}
+
+fn inbox_numbers(state: &AppState) -> Vec<u32> {
+    state.current_pr_list().iter().map(|p| p.number).collect()
+}
+
+/// PR 1 is the oldest opened but most recently updated; PR 3 the reverse.
+fn aged_inbox() -> Vec<PullRequest> {
+    let now = chrono::Utc::now();
+    let days = chrono::Duration::days;
+    [(1, 30, 0), (2, 20, 5), (3, 10, 9)]
+        .into_iter()
+        .map(|(number, opened, updated)| {
+            let mut pr = make_pr("org-a", "api", number, "PR");
+            pr.created_at = now - days(opened);
+            pr.updated_at = now - days(updated);
+            pr
+        })
+        .collect()
+}
+
+#[test]
+fn test_inbox_sort_applies_to_loaded_inbox() {
+    let mut state = make_state();
+    state.inbox_sort = InboxSort::Oldest;
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: aged_inbox(),
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(inbox_numbers(&state), vec![3, 2, 1]);
+}
+
+#[test]
+fn test_cycle_inbox_sort() {
+    let mut state = make_state();
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: aged_inbox(),
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(inbox_numbers(&state), vec![1, 2, 3]);
+    state.content_cursor = 2;
+
+    update(&mut state, Action::CycleInboxSort);
+    assert_eq!(state.inbox_sort, InboxSort::Oldest);
+    assert_eq!(state.content_cursor, 0);
+    assert_eq!(inbox_numbers(&state), vec![3, 2, 1]);
+
+    update(&mut state, Action::CycleInboxSort);
+    assert_eq!(state.inbox_sort, InboxSort::Age);
+    assert_eq!(inbox_numbers(&state), vec![1, 2, 3]);
+
+    update(&mut state, Action::CycleInboxSort);
+    assert_eq!(state.inbox_sort, InboxSort::Newest);
+}
+
+#[test]
+fn test_first_data_clears_stale_flag() {
+    let mut state = make_state();
+    state.stale = true;
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+    assert!(!state.stale);
+    assert!(!state.loading);
+}
+
+#[test]
+fn test_refresh_stays_stale_until_every_owner_loads() {
+    let mut state = make_state();
+    state.stale = true;
+    update(&mut state, Action::Refresh);
+    let org_repos = |org: &str| {
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: org.into(),
+            repos: vec![],
+            rate_limit: None,
+        })
+    };
+
+    update(&mut state, org_repos("org-a"));
+    assert!(state.stale);
+    assert!(state.loading);
+
+    update(&mut state, org_repos("org-b"));
+    assert!(!state.stale);
+    assert!(!state.loading);
+}
+
+#[test]
+fn test_show_full_title_is_transient() {
+    let mut state = make_state();
+    let long = "A very long title that the table column would cut off well before the end";
+    state.inbox = vec![
+        make_pr("org-a", "api", 1, long),
+        make_pr("org-a", "api", 2, "Short"),
+    ];
+    state.focused_pane = FocusedPane::Content;
+
+    update(&mut state, Action::ShowFullTitle);
+    assert_eq!(state.status_message.as_deref(), Some(long));
+
+    // Background data doesn't dismiss it, the next key does.
+    update(&mut state, Action::Tick);
+    assert!(state.status_message.is_some());
+    update(&mut state, Action::MoveDown);
+    assert!(state.status_message.is_none());
+
+    // Pressed twice, it toggles off.
+    update(&mut state, Action::ShowFullTitle);
+    assert_eq!(state.status_message.as_deref(), Some("Short"));
+    update(&mut state, Action::ShowFullTitle);
+    assert!(state.status_message.is_none());
+}
+
+#[test]
+fn test_pr_info_popup_toggles_and_closes_on_back() {
+    let mut state = make_state();
+    state.focused_pane = FocusedPane::Content;
+
+    // Nothing highlighted: nothing to show.
+    update(&mut state, Action::TogglePrInfo);
+    assert!(!state.pr_info_open);
+
+    state.inbox = vec![make_pr("org-a", "api", 1, "Fix")];
+    update(&mut state, Action::TogglePrInfo);
+    assert!(state.pr_info_open);
+    update(&mut state, Action::TogglePrInfo);
+    assert!(!state.pr_info_open);
+
+    update(&mut state, Action::TogglePrInfo);
+    update(&mut state, Action::Back);
+    assert!(!state.pr_info_open);
+    // Esc closed the popup only, not the content pane focus.
+    assert_eq!(state.focused_pane, FocusedPane::Content);
+}
+
+#[test]
+fn test_failing_checks_view_merges_lists_oldest_first() {
+    let mut state = make_state();
+    let now = chrono::Utc::now();
+    let mut old = make_pr("org-a", "api", 1, "Old breakage");
+    old.checks_status = Some("FAILURE".into());
+    old.created_at = now - chrono::Duration::days(5);
+    let mut green = make_pr("org-a", "api", 2, "Green");
+    green.checks_status = Some("SUCCESS".into());
+    let mut errored = make_pr("org-b", "web", 3, "Errored");
+    errored.checks_status = Some("ERROR".into());
+    errored.created_at = now - chrono::Duration::days(1);
+    let mut repo_only = make_pr("org-a", "cli", 4, "Only in repo list");
+    repo_only.checks_status = Some("FAILURE".into());
+    repo_only.created_at = now - chrono::Duration::days(3);
+
+    state.all_open_prs = vec![errored, green, old.clone()];
+    // `old` is also in a per-repo list; it must appear once.
+    state.repo_prs.insert("org-a/api".into(), vec![old]);
+    state.repo_prs.insert("org-a/cli".into(), vec![repo_only]);
+
+    state.nav_cursor = 4;
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::FailingChecks);
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 4, 3]);
+}
+
+// --- Repo PR prefetch ---
+
+fn load_org_a_repos(state: &mut AppState) {
+    update(
+        state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![
+                make_repo("org-a", "busy", 9),
+                make_repo("org-a", "quiet", 0),
+                make_repo("org-a", "some", 2),
+            ],
+            rate_limit: None,
+        }),
+    );
+}
+
+fn org_a_index(state: &AppState) -> usize {
+    state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Org(name) if name == "org-a"))
+        .unwrap()
+}
+
+#[test]
+fn test_expanding_org_prefetches_busiest_repos() {
+    let mut state = make_state();
+    load_org_a_repos(&mut state);
+    state.nav_cursor = org_a_index(&state);
+
+    // Orgs start expanded: the first select collapses without fetching.
+    let effects = update(&mut state, Action::Select);
+    assert!(effects.is_empty());
+
+    state.nav_cursor = org_a_index(&state);
+    let effects = update(&mut state, Action::Select);
+    let prefetched: Vec<_> = effects
+        .iter()
+        .map(|e| match e {
+            SideEffect::PrefetchRepoPrs { owner, name, .. } => format!("{}/{}", owner, name),
+            other => panic!("unexpected effect {:?}", other),
+        })
+        .collect();
+    // Repos without open PRs are skipped; busiest first.
+    assert_eq!(prefetched, vec!["org-a/busy", "org-a/some"]);
+}
+
+#[test]
+fn test_expanded_org_prefetches_when_its_repos_load() {
+    let prefetched = |effects: &[SideEffect]| -> Vec<String> {
+        effects
+            .iter()
+            .filter_map(|e| match e {
+                SideEffect::PrefetchRepoPrs { owner, name, .. } => {
+                    Some(format!("{}/{}", owner, name))
+                }
+                _ => None,
+            })
+            .collect()
+    };
+    let org_repos = || {
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "busy", 9)],
+            rate_limit: None,
+        })
+    };
+
+    // Expanded from the start (or a restored session).
+    let mut state = make_state();
+    assert_eq!(
+        prefetched(&update(&mut state, org_repos())),
+        vec!["org-a/busy"]
+    );
+
+    // Refresh drops the prefetched lists; the reload fetches them again.
+    state.repo_prs.insert("org-a/busy".into(), vec![]);
+    update(&mut state, Action::Refresh);
+    assert_eq!(
+        prefetched(&update(&mut state, org_repos())),
+        vec!["org-a/busy"]
+    );
+
+    // Collapsed orgs wait until they're expanded.
+    state.nav_expanded.remove("org-a");
+    assert!(prefetched(&update(&mut state, org_repos())).is_empty());
+}
+
+#[test]
+fn test_expanding_org_skips_already_prefetched_repos() {
+    let mut state = make_state();
+    load_org_a_repos(&mut state);
+    state.repo_prs.insert("org-a/busy".into(), vec![]);
+
+    let repos = state.repos_to_prefetch("org-a", 5);
+    assert_eq!(repos, vec![("org-a".to_string(), "some".to_string())]);
+}
+
+#[test]
+fn test_repo_view_prefers_prefetched_prs() {
+    let mut state = make_state();
+    state.all_open_prs = vec![make_pr("org-a", "busy", 1, "From search")];
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::RepoPrs {
+            owner: "org-a".into(),
+            name: "busy".into(),
+            prs: vec![
+                make_pr("org-a", "busy", 1, "From search"),
+                make_pr("org-a", "busy", 2, "Beyond the search page"),
+            ],
+            rate_limit: None,
+            partial: false,
+            cancel: CancelToken::new(),
+        }),
+    );
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "busy".into(),
+    };
+
+    assert_eq!(state.current_pr_list().len(), 2);
+}
+
+fn repo_fetch_token(effects: &[SideEffect]) -> CancelToken {
+    match effects {
+        [SideEffect::RefreshRepoPrs { cancel, .. }]
+        | [SideEffect::FetchRepoFinishedPrs { cancel, .. }] => cancel.clone(),
+        other => panic!("unexpected effects {:?}", other),
+    }
+}
+
+#[test]
+fn test_leaving_repo_view_cancels_its_fetch() {
+    let mut state = make_state();
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "busy".into(),
+    };
+    let cancel = repo_fetch_token(&update(&mut state, Action::RefreshCurrentView));
+    assert!(!cancel.is_cancelled());
+
+    // Moving around within the view keeps it going.
+    update(&mut state, Action::SwitchPane);
+    update(&mut state, Action::MoveDown);
+    assert!(!cancel.is_cancelled());
+
+    state.focused_pane = FocusedPane::Navigation;
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::MyInbox))
+        .unwrap();
+    update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::Inbox);
+    assert!(cancel.is_cancelled());
+    assert!(state.view_fetches.is_empty());
+}
+
+#[test]
+fn test_newer_fetch_for_same_view_cancels_the_old_one() {
+    let mut state = make_state();
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "busy".into(),
+    };
+    let merged = repo_fetch_token(&update(&mut state, Action::CyclePrStateFilter));
+    let closed = repo_fetch_token(&update(&mut state, Action::CyclePrStateFilter));
+    assert_eq!(state.pr_state_filter, PrStateFilter::Closed);
+    assert!(merged.is_cancelled());
+    assert!(!closed.is_cancelled());
+}
+
+#[test]
+fn test_finished_repo_fetch_is_forgotten() {
+    let mut state = make_state();
+    let view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "busy".into(),
+    };
+    state.content_view = view.clone();
+    let stale = repo_fetch_token(&update(&mut state, Action::RefreshCurrentView));
+    let cancel = repo_fetch_token(&update(&mut state, Action::RefreshCurrentView));
+    let loaded = |cancel: &CancelToken| {
+        Action::DataLoaded(DataPayload::RepoPrs {
+            owner: "org-a".into(),
+            name: "busy".into(),
+            prs: vec![],
+            rate_limit: None,
+            partial: false,
+            cancel: cancel.clone(),
+        })
+    };
+
+    // The replaced fetch finishing leaves the newer one's token alone.
+    update(&mut state, loaded(&stale));
+    assert!(state.view_fetches[&view].is_same(&cancel));
+    update(&mut state, loaded(&cancel));
+    assert!(state.view_fetches.is_empty());
+}
+
+#[test]
+fn test_partial_repo_prs_never_replace_a_whole_list() {
+    let mut state = make_state();
+    let view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "busy".into(),
+    };
+    let loaded = |numbers: &[u32], partial: bool| {
+        Action::DataLoaded(DataPayload::RepoPrs {
+            owner: "org-a".into(),
+            name: "busy".into(),
+            prs: numbers
+                .iter()
+                .map(|&n| make_pr("org-a", "busy", n, "PR"))
+                .collect(),
+            rate_limit: None,
+            partial,
+            cancel: CancelToken::new(),
+        })
+    };
+    let numbers = |state: &AppState| -> Vec<u32> {
+        state.repo_prs["org-a/busy"]
+            .iter()
+            .map(|pr| pr.number)
+            .collect()
+    };
+
+    // With nothing better, the pages so far are shown but not counted as a
+    // successful fetch.
+    update(&mut state, loaded(&[1], true));
+    assert_eq!(numbers(&state), vec![1]);
+    assert!(!state.freshness.contains_key(&view));
+
+    update(&mut state, loaded(&[1, 2, 3], false));
+    assert!(state.freshness[&view].last_success.is_some());
+    update(&mut state, loaded(&[4], true));
+    assert_eq!(numbers(&state), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_repo_view_cycles_through_merged_and_closed_prs() {
+    let mut state = make_state();
+    state.repo_prs.insert(
+        "org-a/busy".into(),
+        vec![make_pr("org-a", "busy", 1, "Open")],
+    );
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "busy".into(),
+    };
+
+    let effects = update(&mut state, Action::CyclePrStateFilter);
+    assert_eq!(state.pr_state_filter, PrStateFilter::Merged);
+    assert!(matches!(
+        effects.as_slice(),
+        [SideEffect::FetchRepoFinishedPrs {
+            state: PrStateFilter::Merged,
+            ..
+        }]
+    ));
+    // Nothing is shown until the merged list arrives.
+    assert!(state.current_pr_list().is_empty());
+
+    let mut merged = make_pr("org-a", "busy", 2, "Merged");
+    merged.merged_at = Some(merged.updated_at);
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::RepoFinishedPrs {
+            owner: "org-a".into(),
+            name: "busy".into(),
+            state: PrStateFilter::Merged,
+            prs: vec![merged],
+            rate_limit: None,
+            cancel: CancelToken::new(),
+        }),
+    );
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![2]);
+
+    update(&mut state, Action::CyclePrStateFilter);
+    assert_eq!(state.pr_state_filter, PrStateFilter::Closed);
+    update(&mut state, Action::CyclePrStateFilter);
+    assert_eq!(state.pr_state_filter, PrStateFilter::Open);
+    assert_eq!(state.current_pr_list().len(), 1);
+
+    // Coming back to merged reuses the list already fetched.
+    let effects = update(&mut state, Action::CyclePrStateFilter);
+    assert!(effects.is_empty());
+    assert_eq!(state.current_pr_list().len(), 1);
+}
+
+#[test]
+fn test_pr_state_filter_only_applies_to_repo_views() {
+    let mut state = make_state();
+    state.content_view = ContentView::Inbox;
+    let effects = update(&mut state, Action::CyclePrStateFilter);
+    assert!(effects.is_empty());
+    assert_eq!(state.pr_state_filter, PrStateFilter::Open);
+}
+
+#[test]
+fn test_refresh_drops_prefetched_repo_prs() {
+    let mut state = make_state();
+    state.repo_prs.insert("org-a/busy".into(), vec![]);
+    update(&mut state, Action::Refresh);
+    assert!(state.repo_prs.is_empty());
+}
+
+// --- DataLoaded ---
+
+#[test]
+fn test_data_loaded_org_repos() {
+    let mut state = make_state();
+    state.hide_empty_repos = false;
+    let repos = vec![
+        make_repo("org-a", "repo1", 3),
+        make_repo("org-a", "repo2", 0),
+    ];
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos,
+            rate_limit: Some(RateLimit {
+                remaining: 4999,
+                limit: 5000,
+                reset_at: None,
+            }),
+        }),
+    );
+
+    let org_data = state.orgs.get("org-a").unwrap();
+    assert_eq!(org_data.repos.len(), 2);
+    assert_eq!(state.rate_limit.current().remaining, 4999);
+
+    // Nav tree should now include repos under org-a
+    let repo_nodes: Vec<_> = state
+        .nav_nodes
+        .iter()
+        .filter(|n| matches!(n, NavNode::Repo { owner, .. } if owner == "org-a"))
+        .collect();
+    assert_eq!(repo_nodes.len(), 2);
+}
+
+#[test]
+fn test_data_loaded_inbox() {
+    let mut state = make_state();
+    let prs = vec![make_pr("org-a", "repo1", 1, "Fix bug")];
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: prs.clone(),
+            rate_limit: None,
+        }),
+    );
+
+    assert_eq!(state.inbox.len(), 1);
+    assert_eq!(state.inbox[0].title, "Fix bug");
+}
+
+#[test]
+fn test_data_loaded_all_open_prs() {
+    let mut state = make_state();
+    let prs = vec![
+        make_pr("org-a", "repo1", 1, "PR 1"),
+        make_pr("org-a", "repo1", 2, "PR 2"),
+    ];
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs,
+            rate_limit: None,
+        }),
+    );
+
+    assert_eq!(state.all_open_prs.len(), 2);
+}
+
+// --- Loading state ---
+
+#[test]
+fn test_loading_completes_when_no_orgs_loading() {
+    let mut state = make_state();
+    assert!(state.loading);
+
+    // Simulate data loaded (loading_orgs is empty by default)
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+
+    assert!(!state.loading);
+    assert!(state.last_refresh.is_some());
+}
+
+#[test]
+fn test_last_refresh_uses_clock() {
+    let mut state = make_state();
+    let pinned = chrono::Utc::now() - chrono::Duration::hours(1);
+    ghdash::util::time::set_fixed_now(Some(pinned));
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+
+    assert_eq!(state.last_refresh, Some(pinned));
+}
+
+// --- Rate limit tracking ---
+
+fn rl(remaining: u32, reset_hour: Option<u32>) -> RateLimit {
+    use chrono::TimeZone;
+    RateLimit {
+        remaining,
+        limit: 5000,
+        reset_at: reset_hour.map(|h| chrono::Utc.with_ymd_and_hms(2024, 6, 1, h, 0, 0).unwrap()),
+    }
+}
+
+#[test]
+fn test_rate_limit_keeps_lowest_remaining_out_of_order() {
+    let mut tracker = RateLimitTracker::default();
+    tracker.observe(rl(4990, Some(14)));
+    tracker.observe(rl(4980, Some(14)));
+    // An older response landing late must not bump the count back up.
+    tracker.observe(rl(4985, Some(14)));
+    assert_eq!(tracker.current().remaining, 4980);
+}
+
+#[test]
+fn test_rate_limit_ignores_empty_snapshots() {
+    let mut tracker = RateLimitTracker::default();
+    tracker.observe(rl(4990, Some(14)));
+    tracker.observe(RateLimit::default());
+    assert_eq!(tracker.current().remaining, 4990);
+    assert_eq!(tracker.current().limit, 5000);
+}
+
+#[test]
+fn test_rate_limit_cycle_cost() {
+    let mut tracker = RateLimitTracker::default();
+    tracker.observe(rl(4900, Some(14)));
+    tracker.begin_cycle();
+    assert_eq!(tracker.cycle_cost(), Some(0));
+    tracker.observe(rl(4880, Some(14)));
+    tracker.observe(rl(4864, Some(14)));
+    assert_eq!(tracker.cycle_cost(), Some(36));
+}
+
+#[test]
+fn test_rate_limit_first_cycle_cost_starts_at_first_snapshot() {
+    let mut tracker = RateLimitTracker::default();
+    assert_eq!(tracker.cycle_cost(), None);
+    tracker.observe(rl(4990, Some(14)));
+    tracker.observe(rl(4985, Some(14)));
+    assert_eq!(tracker.cycle_cost(), Some(5));
+}
+
+#[test]
+fn test_rate_limit_reset_rollover() {
+    let mut tracker = RateLimitTracker::default();
+    tracker.observe(rl(100, Some(14)));
+    tracker.begin_cycle();
+    tracker.observe(rl(90, Some(14)));
+    // The window resets: a higher remaining with a later reset_at wins.
+    tracker.observe(rl(4995, Some(15)));
+    assert_eq!(tracker.current().remaining, 4995);
+    assert_eq!(tracker.cycle_cost(), Some(5));
+    // A straggler from the old window is ignored.
+    tracker.observe(rl(80, Some(14)));
+    assert_eq!(tracker.current().remaining, 4995);
+}
+
+#[test]
+fn test_rate_limit_throttled_until() {
+    use chrono::TimeZone;
+    let before_reset = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 13, 52, 0).unwrap();
+    let mut tracker = RateLimitTracker::default();
+    assert_eq!(tracker.throttled_until(before_reset), None);
+    tracker.observe(rl(250, Some(14)));
+    assert_eq!(tracker.throttled_until(before_reset), None);
+    tracker.observe(rl(12, Some(14)));
+    assert_eq!(
+        tracker.throttled_until(before_reset),
+        Some(chrono::Utc.with_ymd_and_hms(2024, 6, 1, 14, 0, 0).unwrap())
+    );
+    // Once the window has reset the throttle lifts, even before new numbers.
+    let after_reset = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 14, 0, 1).unwrap();
+    assert_eq!(tracker.throttled_until(after_reset), None);
+}
+
+#[test]
+fn test_throttled_rate_limit_holds_auto_refresh() {
+    use chrono::TimeZone;
+    ghdash::util::time::set_fixed_now(Some(
+        chrono::Utc.with_ymd_and_hms(2024, 6, 1, 13, 52, 0).unwrap(),
+    ));
+    let mut state = make_state();
+    state.loading = false;
+    assert!(state.auto_refresh_allowed());
+    state.rate_limit.observe(rl(12, Some(14)));
+    assert!(!state.auto_refresh_allowed());
+    // A manual refresh still goes through.
+    let effects = update(&mut state, Action::Refresh);
+    assert!(!effects.is_empty());
+    ghdash::util::time::set_fixed_now(None);
+}
+
+#[test]
+fn test_missing_rate_limit_keeps_previous_value() {
+    let mut state = make_state();
+    assert!(!state.rate_limit.is_known());
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: Some(rl(4900, Some(14))),
+        }),
+    );
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+    assert!(state.rate_limit.is_known());
+    assert_eq!(state.rate_limit.current().remaining, 4900);
+}
+
+#[test]
+fn test_refresh_begins_rate_limit_cycle() {
+    let mut state = make_state();
+    state.rate_limit.observe(rl(4900, Some(14)));
+    state.loading = false;
+    update(&mut state, Action::Refresh);
+    state.rate_limit.observe(rl(4890, Some(14)));
+    assert_eq!(state.rate_limit.cycle_cost(), Some(10));
+}
+
+// --- Unread since last session ---
+
+#[test]
+fn test_unread_when_updated_since_last_seen() {
+    let mut state = make_state();
+    let pr = make_pr("org-a", "repo1", 1, "Changed");
+    state
+        .last_seen
+        .insert(pr.url.clone(), pr.updated_at - chrono::Duration::hours(1));
+    assert!(state.is_unread(&pr));
+
+    state.last_seen.insert(pr.url.clone(), pr.updated_at);
+    assert!(!state.is_unread(&pr));
+}
+
+#[test]
+fn test_new_prs_unread_only_after_first_session() {
+    let mut state = make_state();
+    let pr = make_pr("org-a", "repo1", 1, "New");
+    // First run: no baseline, nothing is marked.
+    assert!(!state.is_unread(&pr));
+
+    state.last_seen.insert(
+        "https://github.com/org-a/repo1/pull/99".into(),
+        chrono::Utc::now(),
+    );
+    assert!(state.is_unread(&pr));
+}
+
+#[test]
+fn test_opening_pr_clears_unread_mark() {
+    let mut state = make_state();
+    let pr = make_pr("org-a", "repo1", 1, "Changed");
+    state
+        .last_seen
+        .insert(pr.url.clone(), pr.updated_at - chrono::Duration::hours(1));
+    state.inbox = vec![pr.clone()];
+    state.focused_pane = FocusedPane::Content;
+
+    let effects = update(&mut state, Action::OpenInBrowser);
+    assert!(matches!(effects.as_slice(), [SideEffect::OpenUrl(url)] if *url == pr.url));
+    assert!(!state.is_unread(&pr));
+}
+
+/// An inbox of `count` PRs, every one updated since it was last seen, plus
+/// one already seen.
+fn unread_inbox_state(count: u32) -> AppState {
+    let mut state = make_state();
+    for number in 1..=count + 1 {
+        let pr = make_pr("org-a", "repo1", number, "PR");
+        let seen = if number == count + 1 {
+            pr.updated_at
+        } else {
+            pr.updated_at - chrono::Duration::hours(1)
+        };
+        state.last_seen.insert(pr.url.clone(), seen);
+        state.inbox.push(pr);
+    }
+    state
+}
+
+fn opened_urls(effects: &[SideEffect]) -> Vec<String> {
+    effects
+        .iter()
+        .filter_map(|e| match e {
+            SideEffect::OpenUrl(url) => Some(url.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_unread_inbox_skips_seen_prs() {
+    let state = unread_inbox_state(3);
+    let numbers: Vec<u32> = state.unread_inbox().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_open_unread_inbox_opens_each_and_marks_seen() {
+    let mut state = unread_inbox_state(3);
+    let effects = update(&mut state, Action::OpenUnreadInbox);
+    assert_eq!(opened_urls(&effects).len(), 3);
+    assert!(state.unread_inbox().is_empty());
+    assert_eq!(state.status_message.as_deref(), Some("Opened 3 PRs"));
+
+    assert!(update(&mut state, Action::OpenUnreadInbox).is_empty());
+    assert_eq!(state.status_message.as_deref(), Some("No unread inbox PRs"));
+}
+
+#[test]
+fn test_open_unread_inbox_asks_above_the_limit() {
+    let mut state = unread_inbox_state(5);
+    state.open_unread_limit = 2;
+    assert!(update(&mut state, Action::OpenUnreadInbox).is_empty());
+    assert!(state.chooser.is_some());
+
+    // The first choice opens only up to the limit.
+    let effects = update(&mut state, Action::ChooserConfirm);
+    let urls = opened_urls(&effects);
+    assert_eq!(urls.len(), 2);
+    assert!(urls[0].ends_with("/pull/1"));
+    assert_eq!(state.unread_inbox().len(), 3);
+
+    // The second opens the rest.
+    update(&mut state, Action::OpenUnreadInbox);
+    update(&mut state, Action::ChooserDown);
+    let effects = update(&mut state, Action::ChooserConfirm);
+    assert_eq!(opened_urls(&effects).len(), 3);
+    assert!(state.unread_inbox().is_empty());
+}
+
+#[test]
+fn test_seen_snapshot_covers_only_displayed_prs() {
+    let mut state = make_state();
+    state.table_rows = 1;
+    state.content_view = ContentView::AllOpenPrs;
+    state.focused_pane = FocusedPane::Content;
+    state.all_open_prs = vec![
+        make_pr("org-a", "repo1", 1, "Shown"),
+        make_pr("org-b", "repo2", 2, "Below the fold"),
+    ];
+    update(&mut state, Action::Tick);
+    let snapshot = state.seen_snapshot();
+    assert_eq!(snapshot.len(), 1);
+    assert!(snapshot.contains_key(&state.all_open_prs[0].url));
+    // Still unread while on screen; seen once the view is left.
+    assert!(state.last_seen.is_empty());
+
+    update(&mut state, Action::MoveDown);
+    update(&mut state, Action::NextOwner);
+    assert_eq!(state.last_seen.len(), 2);
+    assert!(state.displayed.is_empty());
+}
+
+// --- Error handling ---
+
+#[test]
+fn test_load_error_sets_message() {
+    let mut state = make_state();
+    update(&mut state, Action::LoadError("Network error".into()));
+    assert_eq!(state.error_message, Some("Network error".into()));
+    assert!(!state.loading);
+}
+
+#[test]
+fn test_dismiss_error() {
+    let mut state = make_state();
+    state.error_message = Some("err".into());
+    update(&mut state, Action::DismissError);
+    assert!(state.error_message.is_none());
+}
+
+// --- Refresh ---
+
+#[test]
+fn test_refresh_returns_side_effect() {
+    let mut state = make_state();
+    let effects = update(&mut state, Action::Refresh);
+    assert!(state.loading);
+    assert!(state.error_message.is_none());
+    assert_eq!(effects.len(), 1);
+    assert!(matches!(effects[0], SideEffect::RefreshAll));
+}
+
+// --- Search ---
+
+#[test]
+fn test_toggle_search() {
+    let mut state = make_state();
+    assert!(!state.search_active);
+    update(&mut state, Action::ToggleSearch);
+    assert!(state.search_active);
+    update(&mut state, Action::ToggleSearch);
+    assert!(!state.search_active);
+}
+
+#[test]
+fn test_search_input() {
+    let mut state = make_state();
+    update(&mut state, Action::ToggleSearch);
+    update(&mut state, Action::SearchInput('h'));
+    update(&mut state, Action::SearchInput('i'));
+    assert_eq!(state.search_query, "hi");
+}
+
+#[test]
+fn test_search_backspace() {
+    let mut state = make_state();
+    update(&mut state, Action::ToggleSearch);
+    update(&mut state, Action::SearchInput('a'));
+    update(&mut state, Action::SearchInput('b'));
+    update(&mut state, Action::SearchBackspace);
+    assert_eq!(state.search_query, "a");
+}
+
+#[test]
+fn test_search_filters_prs() {
+    let mut state = make_state();
+    state.content_view = ContentView::Inbox;
+    state.inbox = vec![
+        make_pr("org", "repo", 1, "Fix login bug"),
+        make_pr("org", "repo", 2, "Add dashboard feature"),
+        make_pr("org", "repo", 3, "Login page redesign"),
+    ];
+
+    state.search_active = true;
+    state.search_query = "login".into();
+
+    let filtered = state.current_pr_list();
+    assert_eq!(filtered.len(), 2);
+    assert!(
+        filtered
+            .iter()
+            .all(|pr| pr.title.to_lowercase().contains("login"))
+    );
+}
+
+// --- Quit ---
+
+#[test]
+fn test_quit() {
+    let mut state = make_state();
+    update(&mut state, Action::Quit);
+    assert!(state.should_quit);
+}
+
+// --- Back ---
+
+#[test]
+fn test_back_closes_search() {
+    let mut state = make_state();
+    state.search_active = true;
+    state.search_query = "test".into();
+    update(&mut state, Action::Back);
+    assert!(!state.search_active);
+    assert!(state.search_query.is_empty());
+}
+
+#[test]
+fn test_back_dismisses_error() {
+    let mut state = make_state();
+    state.error_message = Some("err".into());
+    update(&mut state, Action::Back);
+    assert!(state.error_message.is_none());
+}
+
+#[test]
+fn test_back_switches_to_nav_pane() {
+    let mut state = make_state();
+    state.focused_pane = FocusedPane::Content;
+    update(&mut state, Action::Back);
+    assert_eq!(state.focused_pane, FocusedPane::Navigation);
+}
+
+// --- Open in browser ---
+
+#[test]
+fn test_open_in_browser_from_content_with_pr() {
+    let mut state = make_state();
+    state.focused_pane = FocusedPane::Content;
+    state.content_view = ContentView::Inbox;
+    state.inbox = vec![make_pr("org", "repo", 42, "My PR")];
+    state.content_cursor = 0;
+
+    let effects = update(&mut state, Action::OpenInBrowser);
+    assert_eq!(effects.len(), 1);
+    match &effects[0] {
+        SideEffect::OpenUrl(url) => {
+            assert!(url.contains("42"));
+        }
+        _ => panic!("Expected OpenUrl side effect"),
+    }
+}
+
+#[test]
+fn test_open_in_browser_from_nav_on_org() {
+    let mut state = make_state();
+    state.focused_pane = FocusedPane::Navigation;
+    // Find an Org node
+    let org_idx = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Org(_)))
+        .unwrap();
+    state.nav_cursor = org_idx;
+
+    let effects = update(&mut state, Action::OpenInBrowser);
+    assert_eq!(effects.len(), 1);
+    assert!(
+        matches!(&effects[0], SideEffect::OpenUrl(url) if url.starts_with("https://github.com/"))
+    );
+}
+
+// --- Nav tree rebuild with repos ---
+
+#[test]
+fn test_nav_tree_sorts_repos_by_pr_count() {
+    let mut state = make_state();
+
+    let repos = vec![
+        make_repo("org-a", "low-prs", 1),
+        make_repo("org-a", "high-prs", 10),
+        make_repo("org-a", "mid-prs", 5),
+    ];
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos,
+            rate_limit: None,
+        }),
+    );
+
+    let repo_names: Vec<String> = state
+        .nav_nodes
+        .iter()
+        .filter_map(|n| match n {
+            NavNode::Repo { owner, name, .. } if owner == "org-a" => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(repo_names, vec!["high-prs", "mid-prs", "low-prs"]);
+}
+
+#[test]
+fn test_archived_repos_excluded_from_nav() {
+    let mut state = make_state();
+
+    let mut archived = make_repo("org-a", "old-repo", 0);
+    archived.is_archived = true;
+    let repos = vec![make_repo("org-a", "active-repo", 2), archived];
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos,
+            rate_limit: None,
+        }),
+    );
+
+    let repo_names: Vec<String> = state
+        .nav_nodes
+        .iter()
+        .filter_map(|n| match n {
+            NavNode::Repo { name, .. } => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(repo_names, vec!["active-repo"]);
+}
+
+// --- PR overlays: git log & diff (task zkk5) ---
+
+#[test]
+fn test_toggle_git_log_flips_overlay() {
+    let mut state = make_state();
+    assert_eq!(state.overlay, Overlay::None);
+    update(&mut state, Action::ToggleGitLog);
+    assert_eq!(state.overlay, Overlay::GitLog);
+    update(&mut state, Action::ToggleGitLog);
+    assert_eq!(state.overlay, Overlay::None);
+}
+
+#[test]
+fn test_toggle_diff_flips_overlay() {
+    let mut state = make_state();
+    update(&mut state, Action::ToggleDiff);
+    assert_eq!(state.overlay, Overlay::Diff);
+    update(&mut state, Action::ToggleDiff);
+    assert_eq!(state.overlay, Overlay::None);
+}
+
+#[test]
+fn test_toggle_switches_between_overlays() {
+    let mut state = make_state();
+    update(&mut state, Action::ToggleGitLog);
+    assert_eq!(state.overlay, Overlay::GitLog);
+    // Pressing the diff key while the log is open switches to the diff.
+    update(&mut state, Action::ToggleDiff);
+    assert_eq!(state.overlay, Overlay::Diff);
+}
+
+#[test]
+fn test_back_closes_overlay_before_switching_pane() {
+    let mut state = make_state();
+    state.focused_pane = FocusedPane::Content;
+    update(&mut state, Action::ToggleGitLog);
+    assert_eq!(state.overlay, Overlay::GitLog);
+
+    // Back should close the overlay first, leaving focus on Content.
+    update(&mut state, Action::Back);
+    assert_eq!(state.overlay, Overlay::None);
+    assert_eq!(state.focused_pane, FocusedPane::Content);
+}
+
+#[test]
+fn test_pr_detail_loaded_upgrades_list_merge_state() {
+    use ghdash::app::state::PrDetailEntry;
+    use ghdash::github::models::PrDetail;
+
+    let mut state = make_state();
+    // A PR whose list value is UNKNOWN (typical of the search API).
+    let mut pr = make_pr("org-a", "repo1", 7, "Needs fresh state");
+    pr.mergeable = Some("UNKNOWN".into());
+    let url = pr.url.clone();
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs: vec![pr],
+            rate_limit: None,
+        }),
+    );
+
+    let detail = PrDetail {
+        mergeable: Some("CONFLICTING".into()),
+        merge_state_status: Some("DIRTY".into()),
+        checks_status: Some("FAILURE".into()),
+        commits: vec![],
+        closes_issues: vec![],
+        body: String::new(),
+    };
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::PrDetailLoaded {
+            key: url.clone(),
+            detail,
+            rate_limit: None,
+        }),
+    );
+
+    // Detail is cached and the list column reflects the fresh value.
+    assert!(matches!(
+        state.pr_details.get(&url),
+        Some(PrDetailEntry::Loaded(_))
+    ));
+    assert_eq!(
+        state.all_open_prs[0].mergeable.as_deref(),
+        Some("CONFLICTING")
+    );
+    assert_eq!(
+        state.all_open_prs[0].merge_state_status.as_deref(),
+        Some("DIRTY")
+    );
+}
+
+#[test]
+fn test_fresh_merge_state_tracks_behind_base() {
+    let mut state = make_state();
+    let mut pr = make_pr("org-a", "repo1", 7, "Stale branch");
+    pr.is_behind = true;
+    let url = pr.url.clone();
+    state.all_open_prs = vec![pr.clone()];
+    state.inbox = vec![pr];
+
+    state.apply_fresh_merge_state(&url, Some("MERGEABLE".into()), Some("CLEAN".into()));
+    assert!(!state.all_open_prs[0].is_behind);
+    assert!(!state.inbox[0].is_behind);
+
+    state.apply_fresh_merge_state(&url, Some("MERGEABLE".into()), Some("BEHIND".into()));
+    assert!(state.all_open_prs[0].is_behind);
+    assert!(state.inbox[0].is_behind);
+}
+
+#[test]
+fn test_refresh_clears_pr_details() {
+    use ghdash::app::state::PrDetailEntry;
+
+    let mut state = make_state();
+    state
+        .pr_details
+        .insert("some-url".into(), PrDetailEntry::Loading);
+    assert!(!state.pr_details.is_empty());
+
+    update(&mut state, Action::Refresh);
+    assert!(state.pr_details.is_empty());
+}
+
+#[test]
+fn test_pr_detail_failed_records_error() {
+    use ghdash::app::state::PrDetailEntry;
+
+    let mut state = make_state();
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::PrDetailFailed {
+            key: "url-x".into(),
+            msg: "boom".into(),
+        }),
+    );
+    assert!(matches!(
+        state.pr_details.get("url-x"),
+        Some(PrDetailEntry::Failed(_))
+    ));
+}
+
+// --- Merge-state filter + help (task pp0u) ---
+
+#[test]
+fn test_cycle_merge_filter_order() {
+    let mut state = make_state();
+    assert_eq!(state.merge_filter, MergeFilter::All);
+    update(&mut state, Action::CycleMergeFilter);
+    assert_eq!(state.merge_filter, MergeFilter::Conflicting);
+    update(&mut state, Action::CycleMergeFilter);
+    assert_eq!(state.merge_filter, MergeFilter::Clean);
+    update(&mut state, Action::CycleMergeFilter);
+    assert_eq!(state.merge_filter, MergeFilter::All);
+}
+
+#[test]
+fn test_toggle_help_flips_flag() {
+    let mut state = make_state();
+    assert!(!state.help_open);
+    update(&mut state, Action::ToggleHelp);
     assert!(state.help_open);
     update(&mut state, Action::ToggleHelp);
     assert!(!state.help_open);
 }
 
 #[test]
-fn test_back_closes_help_before_overlay() {
+fn test_back_closes_help_before_overlay() {
+    let mut state = make_state();
+    state.overlay = Overlay::Diff;
+    update(&mut state, Action::ToggleHelp);
+    assert!(state.help_open);
+
+    // Back closes help first, leaving the PR overlay untouched.
+    update(&mut state, Action::Back);
+    assert!(!state.help_open);
+    assert_eq!(state.overlay, Overlay::Diff);
+}
+
+#[test]
+fn test_merge_filter_selects_and_composes_with_search() {
+    let mut state = make_state();
+    let mut clean = make_pr("org-a", "repo1", 1, "clean one");
+    clean.mergeable = Some("MERGEABLE".into());
+    let mut conflict = make_pr("org-a", "repo1", 2, "conflict two");
+    conflict.mergeable = Some("CONFLICTING".into());
+    let mut unknown = make_pr("org-a", "repo1", 3, "unknown three");
+    unknown.mergeable = Some("UNKNOWN".into());
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs: vec![clean, conflict, unknown],
+            rate_limit: None,
+        }),
+    );
+    state.content_view = ContentView::AllOpenPrs;
+
+    assert_eq!(state.current_pr_list().len(), 3);
+
+    state.merge_filter = MergeFilter::Clean;
+    let clean_list = state.current_pr_list();
+    assert_eq!(clean_list.len(), 1);
+    assert_eq!(clean_list[0].number, 1);
+
+    state.merge_filter = MergeFilter::Conflicting;
+    assert_eq!(state.current_pr_list().len(), 1);
+    assert_eq!(state.current_pr_list()[0].number, 2);
+
+    // Composes with search: Clean + a query excluding the clean PR -> empty.
+    state.merge_filter = MergeFilter::Clean;
+    state.search_query = "conflict".into();
+    assert_eq!(state.current_pr_list().len(), 0);
+}
+
+// --- Review filter ---
+
+#[test]
+fn test_cycle_review_filter_order() {
+    let mut state = make_state();
+    state.content_view = ContentView::AllOpenPrs;
+    assert_eq!(state.review_filter(), ReviewFilter::All);
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(state.review_filter(), ReviewFilter::NeedsReview);
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(state.review_filter(), ReviewFilter::ChangesRequested);
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(state.review_filter(), ReviewFilter::Approved);
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(state.review_filter(), ReviewFilter::All);
+}
+
+#[test]
+fn test_review_filter_is_kept_per_view() {
+    let mut state = make_state();
+    state.content_view = ContentView::AllOpenPrs;
+    update(&mut state, Action::CycleReviewFilter);
+
+    state.content_view = ContentView::Inbox;
+    assert_eq!(state.review_filter(), ReviewFilter::All);
+    update(&mut state, Action::CycleReviewFilter);
+    update(&mut state, Action::CycleReviewFilter);
+
+    state.content_view = ContentView::AllOpenPrs;
+    assert_eq!(state.review_filter(), ReviewFilter::NeedsReview);
+    state.content_view = ContentView::Inbox;
+    assert_eq!(state.review_filter(), ReviewFilter::ChangesRequested);
+}
+
+#[test]
+fn test_review_filter_selects_and_composes_with_other_filters() {
+    let mut state = make_state();
+    let mut approved = make_pr("org-a", "repo1", 1, "approved clean");
+    approved.review_decision = Some("APPROVED".into());
+    approved.mergeable = Some("MERGEABLE".into());
+    let mut changes = make_pr("org-a", "repo1", 2, "changes wanted");
+    changes.review_decision = Some("CHANGES_REQUESTED".into());
+    let mut required = make_pr("org-a", "repo1", 3, "review required");
+    required.review_decision = Some("REVIEW_REQUIRED".into());
+    let undecided = make_pr("org-a", "repo1", 4, "no decision");
+    let mut approved_conflict = make_pr("org-a", "repo1", 5, "approved conflict");
+    approved_conflict.review_decision = Some("APPROVED".into());
+    approved_conflict.mergeable = Some("CONFLICTING".into());
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs: vec![approved, changes, required, undecided, approved_conflict],
+            rate_limit: None,
+        }),
+    );
+    state.content_view = ContentView::AllOpenPrs;
+    let numbers = |state: &AppState| -> Vec<u32> {
+        state.current_pr_list().iter().map(|p| p.number).collect()
+    };
+
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(numbers(&state), vec![3, 4]);
+    assert!(state.filters_active());
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(numbers(&state), vec![2]);
+    update(&mut state, Action::CycleReviewFilter);
+    assert_eq!(numbers(&state), vec![1, 5]);
+
+    state.merge_filter = MergeFilter::Clean;
+    assert_eq!(numbers(&state), vec![1]);
+    state.search_query = "conflict".into();
+    assert!(numbers(&state).is_empty());
+
+    // Nav counts for other views ignore this view's review filter.
+    assert_eq!(state.filtered_counts(&state.all_open_prs.clone()), (0, 5));
+    state.search_query.clear();
+    assert_eq!(state.filtered_counts(&state.all_open_prs.clone()), (1, 5));
+}
+
+#[test]
+fn test_back_clears_review_filter_before_leaving_content() {
+    let mut state = make_state();
+    state.content_view = ContentView::AllOpenPrs;
+    state.focused_pane = FocusedPane::Content;
+    update(&mut state, Action::CycleReviewFilter);
+
+    update(&mut state, Action::Back);
+    assert_eq!(state.review_filter(), ReviewFilter::All);
+    assert_eq!(state.focused_pane, FocusedPane::Content);
+
+    update(&mut state, Action::Back);
+    assert_eq!(state.focused_pane, FocusedPane::Navigation);
+}
+
+// --- Org activity ---
+
+#[test]
+fn test_org_activity_recent_and_top_authors() {
+    let now = chrono::Utc::now();
+    let mut prs = Vec::new();
+    for i in 0..12u32 {
+        let mut pr = make_pr("org-a", "repo1", i, "PR");
+        pr.author = if i % 3 == 0 { "alice" } else { "bob" }.into();
+        pr.updated_at = now - chrono::Duration::hours(i64::from(i));
+        prs.push(pr);
+    }
+    let mut other = make_pr("org-b", "repo2", 99, "Other org");
+    other.author = "carol".into();
+    prs.push(other);
+
+    let activity = ghdash::app::state::org_activity("org-a", &prs);
+
+    assert_eq!(activity.recent.len(), 10);
+    assert_eq!(activity.recent[0].number, 0);
+    assert_eq!(activity.recent[9].number, 9);
+    assert_eq!(
+        activity.top_authors,
+        vec![("bob".to_string(), 8), ("alice".to_string(), 4)]
+    );
+}
+
+#[test]
+fn test_org_activity_caps_authors_and_breaks_ties_by_name() {
+    let prs: Vec<_> = ["f", "e", "d", "c", "b", "a"]
+        .iter()
+        .enumerate()
+        .map(|(i, author)| {
+            let mut pr = make_pr("org-a", "repo1", i as u32, "PR");
+            pr.author = (*author).into();
+            pr
+        })
+        .collect();
+
+    let activity = ghdash::app::state::org_activity("org-a", &prs);
+    let authors: Vec<_> = activity
+        .top_authors
+        .iter()
+        .map(|(a, _)| a.as_str())
+        .collect();
+    assert_eq!(authors, vec!["a", "b", "c", "d", "e"]);
+}
+
+#[test]
+fn test_org_activity_empty_for_unknown_org() {
+    let prs = vec![make_pr("org-a", "repo1", 1, "PR")];
+    let activity = ghdash::app::state::org_activity("nope", &prs);
+    assert!(activity.recent.is_empty());
+    assert!(activity.top_authors.is_empty());
+}
+
+// --- Filter by author ---
+
+fn make_authored_pr(number: u32, author: &str) -> PullRequest {
+    let mut pr = make_pr("org-a", "repo1", number, "PR");
+    pr.author = author.into();
+    pr
+}
+
+#[test]
+fn test_filter_by_author_narrows_and_keeps_selection() {
+    let mut state = make_state();
+    state.inbox = vec![
+        make_authored_pr(1, "alice"),
+        make_authored_pr(2, "bob"),
+        make_authored_pr(3, "alice"),
+    ];
+    state.focused_pane = FocusedPane::Content;
+    state.content_cursor = 2;
+
+    update(&mut state, Action::FilterByAuthor);
+    assert_eq!(state.search_query, "author:alice");
+    let numbers: Vec<_> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 3]);
+    assert_eq!(state.selected_pr().unwrap().number, 3);
+}
+
+#[test]
+fn test_filter_by_author_second_press_clears() {
+    let mut state = make_state();
+    state.inbox = vec![make_authored_pr(1, "alice"), make_authored_pr(2, "bob")];
+    state.focused_pane = FocusedPane::Content;
+    state.content_cursor = 1;
+
+    update(&mut state, Action::FilterByAuthor);
+    update(&mut state, Action::FilterByAuthor);
+    assert!(state.search_query.is_empty());
+    assert_eq!(state.current_pr_list().len(), 2);
+    assert_eq!(state.selected_pr().unwrap().number, 2);
+}
+
+#[test]
+fn test_filter_by_author_keeps_the_rest_of_the_search() {
+    let mut state = make_state();
+    let mut fix = make_authored_pr(1, "alice");
+    fix.title = "Fix crash".into();
+    state.inbox = vec![fix, make_authored_pr(2, "alice")];
+    state.focused_pane = FocusedPane::Content;
+    state.search_query = "fix".into();
+
+    update(&mut state, Action::FilterByAuthor);
+    assert_eq!(state.search_query, "fix author:alice");
+    assert_eq!(state.current_pr_list().len(), 1);
+
+    update(&mut state, Action::FilterByAuthor);
+    assert_eq!(state.search_query, "fix");
+}
+
+#[test]
+fn test_author_qualifier_is_exact_and_combines_with_text() {
+    let mut state = make_state();
+    let mut fix = make_authored_pr(1, "alice");
+    fix.title = "Fix crash".into();
+    state.inbox = vec![
+        fix,
+        make_authored_pr(2, "alice"),
+        make_authored_pr(3, "alicesmith"),
+    ];
+
+    state.search_query = "author:Alice".into();
+    assert_eq!(state.current_pr_list().len(), 2);
+
+    state.search_query = "author:alice fix".into();
+    let numbers: Vec<_> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1]);
+}
+
+// --- All PRs relation tint ---
+
+#[test]
+fn test_pr_relation_prefers_mine_over_review_requested() {
+    let mut state = make_state();
+    let mut mine = make_pr("org-a", "api", 1, "Mine");
+    mine.author = "TestUser".into();
+    let requested = make_pr("org-a", "api", 2, "Needs my review");
+    let other = make_pr("org-a", "web", 2, "Same number, other repo");
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![mine.clone(), requested.clone()],
+            rate_limit: None,
+        }),
+    );
+
+    assert_eq!(state.pr_relation(&mine), PrRelation::Mine);
+    assert_eq!(state.pr_relation(&requested), PrRelation::ReviewRequested);
+    assert_eq!(state.pr_relation(&other), PrRelation::Other);
+}
+
+#[test]
+fn test_inbox_keys_follow_inbox_reload() {
+    let mut state = make_state();
+    let pr = make_pr("org-a", "api", 1, "Needs my review");
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![pr.clone()],
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(state.pr_relation(&pr), PrRelation::ReviewRequested);
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(state.pr_relation(&pr), PrRelation::Other);
+}
+
+// --- All PRs "involving me" filter ---
+
+#[test]
+fn test_involves_me_filter_falls_back_to_author_and_inbox() {
+    let mut state = make_state();
+    let mut mine = make_pr("org-a", "api", 1, "Mine");
+    mine.author = "testuser".into();
+    let requested = make_pr("org-a", "api", 2, "Needs my review");
+    let other = make_pr("org-a", "web", 3, "Someone else's");
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![requested.clone()],
+            rate_limit: None,
+        }),
+    );
+    state.all_open_prs = vec![mine, requested, other];
+    state.content_view = ContentView::AllOpenPrs;
+
+    let effects = update(&mut state, Action::ToggleInvolvesMe);
+    assert!(matches!(effects.as_slice(), [SideEffect::FetchInvolvedPrs]));
+    let numbers: Vec<_> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 2]);
+    assert!(state.filters_active());
+    assert_eq!(state.view_counts(), (2, 3));
+
+    // Once the search answers, it decides (e.g. PRs only commented on).
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InvolvedPrs {
+            prs: vec![make_pr("org-a", "web", 3, "Commented on")],
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(state.current_pr_list().len(), 3);
+
+    // Toggling again shows everything without searching again.
+    assert!(update(&mut state, Action::ToggleInvolvesMe).is_empty());
+    assert!(!state.filters_active());
+    assert!(update(&mut state, Action::ToggleInvolvesMe).is_empty());
+}
+
+#[test]
+fn test_involves_me_only_applies_to_all_prs() {
+    let mut state = make_state();
+    state.inbox = vec![make_pr("org-a", "api", 1, "Assigned")];
+    assert!(update(&mut state, Action::ToggleInvolvesMe).is_empty());
+    assert!(!state.involves_me_only);
+    assert!(state.status_message.is_some());
+
+    state.involves_me_only = true;
+    assert_eq!(state.current_pr_list().len(), 1);
+    assert!(!state.filters_active());
+}
+
+#[test]
+fn test_refresh_re_runs_involves_search_when_filtering() {
+    let mut state = make_state();
+    state.content_view = ContentView::AllOpenPrs;
+    state.involves_me_only = true;
+    state.involved_urls = Some(Default::default());
+
+    let effects = update(&mut state, Action::Refresh);
+    assert!(matches!(
+        effects.as_slice(),
+        [SideEffect::RefreshAll, SideEffect::FetchInvolvedPrs]
+    ));
+    assert!(state.involved_urls.is_none());
+
+    update(&mut state, Action::Back);
+    assert!(!state.involves_me_only);
+}
+
+// --- n / N: next match ---
+
+#[test]
+fn test_next_match_cycles_changes_requested_prs() {
+    let mut state = make_state();
+    let mut prs: Vec<PullRequest> = (1..=5)
+        .map(|n| make_pr("org-a", "api", n, &format!("PR {}", n)))
+        .collect();
+    prs[1].review_decision = Some("CHANGES_REQUESTED".into());
+    prs[3].review_decision = Some("CHANGES_REQUESTED".into());
+    prs[4].review_decision = Some("APPROVED".into());
+    state.inbox = prs;
+    state.focused_pane = FocusedPane::Content;
+
+    update(&mut state, Action::NextMatch);
+    assert_eq!(state.content_cursor, 1);
+    update(&mut state, Action::NextMatch);
+    assert_eq!(state.content_cursor, 3);
+    // Wraps around.
+    update(&mut state, Action::NextMatch);
+    assert_eq!(state.content_cursor, 1);
+    update(&mut state, Action::PrevMatch);
+    assert_eq!(state.content_cursor, 3);
+
+    // With a review filter, its category is what's matched.
+    state
+        .review_filters
+        .insert(ContentView::Inbox, ReviewFilter::Approved);
+    state.content_cursor = 0;
+    update(&mut state, Action::PrevMatch);
+    assert_eq!(state.content_cursor, 0);
+    assert_eq!(state.selected_pr().unwrap().number, 5);
+}
+
+#[test]
+fn test_next_match_steps_through_the_filtered_list() {
+    let mut state = make_state();
+    let mut prs: Vec<PullRequest> = (1..=6)
+        .map(|n| make_pr("org-a", "api", n, &format!("PR {}", n)))
+        .collect();
+    for i in [1, 3, 4] {
+        prs[i].review_decision = Some("APPROVED".into());
+    }
+    prs[2].review_decision = Some("CHANGES_REQUESTED".into());
+    state.inbox = prs;
+    state.focused_pane = FocusedPane::Content;
+    state
+        .review_filters
+        .insert(ContentView::Inbox, ReviewFilter::Approved);
+
+    // The cursor indexes the filtered rows (#2, #4, #5), not the inbox.
+    let mut visited = Vec::new();
+    for _ in 0..3 {
+        update(&mut state, Action::NextMatch);
+        visited.push(state.selected_pr().unwrap().number);
+    }
+    assert_eq!(visited, vec![4, 5, 2]);
+    update(&mut state, Action::PrevMatch);
+    assert_eq!(state.selected_pr().unwrap().number, 5);
+}
+
+#[test]
+fn test_next_match_without_matches_says_so() {
+    let mut state = make_state();
+    state.inbox = vec![make_pr("org-a", "api", 1, "Fine")];
+    update(&mut state, Action::NextMatch);
+    assert_eq!(state.content_cursor, 0);
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("No matching PRs (changes requested)")
+    );
+}
+
+// --- Focus refresh debounce ---
+
+#[test]
+fn test_focus_gained_refreshes_only_after_debounce() {
+    let mut state = make_state();
+    state.loading = false;
+    state.focus_refresh_debounce_secs = 60;
+
+    // Nothing has finished loading yet: an initial refresh is still running.
+    assert!(update(&mut state, Action::FocusGained).is_empty());
+
+    state.last_refresh = Some(chrono::Utc::now() - chrono::Duration::seconds(10));
+    assert!(update(&mut state, Action::FocusGained).is_empty());
+    assert!(!state.loading);
+
+    state.last_refresh = Some(chrono::Utc::now() - chrono::Duration::seconds(61));
+    let effects = update(&mut state, Action::FocusGained);
+    assert!(matches!(effects.as_slice(), [SideEffect::RefreshAll]));
+    assert!(state.loading);
+
+    // A refresh in flight absorbs further focus churn.
+    assert!(update(&mut state, Action::FocusGained).is_empty());
+}
+
+#[test]
+fn test_focus_gained_respects_refresh_lock() {
+    let mut state = make_state();
+    state.loading = false;
+    state.last_refresh = Some(chrono::Utc::now() - chrono::Duration::hours(1));
+    state.refresh_locked = true;
+    assert!(update(&mut state, Action::FocusGained).is_empty());
+}
+
+#[test]
+fn test_refresh_deferred_until_idle_times() {
+    let now = chrono::Utc::now();
+    let ago = |secs| Some(now - chrono::Duration::seconds(secs));
+    // No input yet.
+    assert_eq!(refresh_deferred_until(None, now, 10), None);
+    // Just typed: wait out the full delay.
+    assert_eq!(
+        refresh_deferred_until(Some(now), now, 10),
+        Some(now + chrono::Duration::seconds(10))
+    );
+    assert_eq!(
+        refresh_deferred_until(ago(3), now, 10),
+        Some(now + chrono::Duration::seconds(7))
+    );
+    // Idle for exactly the delay, or longer: go ahead.
+    assert_eq!(refresh_deferred_until(ago(10), now, 10), None);
+    assert_eq!(refresh_deferred_until(ago(600), now, 10), None);
+    // A zero delay never defers.
+    assert_eq!(refresh_deferred_until(Some(now), now, 0), None);
+}
+
+#[test]
+fn test_key_press_defers_auto_refresh() {
+    let mut state = make_state();
+    state.loading = false;
+    assert!(state.auto_refresh_allowed());
+    update(&mut state, Action::MoveDown);
+    assert!(state.last_input.is_some());
+    assert!(!state.auto_refresh_allowed());
+    state.idle_refresh_delay_secs = 0;
+    assert!(state.auto_refresh_allowed());
+    // Background results aren't input.
+    state.last_input = None;
+    update(&mut state, Action::Tick);
+    assert!(state.last_input.is_none());
+}
+
+#[test]
+fn test_timer_and_focus_refreshes_are_not_input() {
+    let mut state = make_state();
+    state.loading = false;
+    assert!(matches!(
+        update(&mut state, Action::AutoRefresh).as_slice(),
+        [SideEffect::RefreshAll]
+    ));
+    assert!(state.loading);
+    assert!(state.last_input.is_none());
+
+    state.loading = false;
+    state.last_refresh = Some(chrono::Utc::now() - chrono::Duration::hours(1));
+    assert!(matches!(
+        update(&mut state, Action::FocusGained).as_slice(),
+        [SideEffect::RefreshAll]
+    ));
+    assert!(state.last_input.is_none());
+
+    // A refresh the user asks for is.
+    update(&mut state, Action::Refresh);
+    assert!(state.last_input.is_some());
+}
+
+#[test]
+fn test_toggle_auto_refresh_pause() {
+    let mut state = make_state();
+    state.loading = false;
+    update(&mut state, Action::ToggleAutoRefreshPause);
+    state.last_input = None;
+    assert!(state.auto_refresh_paused);
+    assert!(!state.auto_refresh_allowed());
+    // Manual refresh still goes through.
+    assert!(matches!(
+        update(&mut state, Action::Refresh).as_slice(),
+        [SideEffect::RefreshAll]
+    ));
+    state.loading = false;
+    update(&mut state, Action::ToggleAutoRefreshPause);
+    state.last_input = None;
+    assert!(state.auto_refresh_allowed());
+}
+
+#[test]
+fn test_only_first_failure_of_a_streak_shows_the_modal() {
+    let mut state = make_state();
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: ContentView::Inbox,
+            msg: "Failed to fetch inbox: timeout".into(),
+        },
+    );
+    assert!(state.error_message.is_some());
+    update(&mut state, Action::DismissError);
+
+    state.refresh_retry_at = Some(chrono::Utc::now() + chrono::Duration::minutes(4));
+    state.loading = true;
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: ContentView::Inbox,
+            msg: "Failed to fetch inbox: timeout".into(),
+        },
+    );
+    assert_eq!(state.error_message, None);
+    assert!(!state.loading);
+    assert!(state.failed_views.contains(&ContentView::Inbox));
+}
+
+#[test]
+fn test_rejected_token_prompts_once_and_stops_auto_refresh() {
+    let mut state = make_state();
+    update(&mut state, Action::TokenRejected);
+    assert!(state.token_rejected);
+    assert!(!state.loading);
+    assert!(!state.auto_refresh_allowed());
+    // Each failed fetch is folded into the prompt rather than a modal.
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: ContentView::Inbox,
+            msg: "Failed to fetch inbox: GitHub API returned 401 Unauthorized".into(),
+        },
+    );
+    assert_eq!(state.error_message, None);
+
+    let effects = update(&mut state, Action::TokenRenewed);
+    assert!(!state.token_rejected);
+    assert!(matches!(effects.as_slice(), [SideEffect::RefreshAll]));
+    assert!(state.loading);
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("Token resolved, refreshing")
+    );
+}
+
+#[test]
+fn test_is_stale_after_twice_the_interval() {
+    let now = chrono::Utc::now();
+    let ago = |mins| now - chrono::Duration::minutes(mins);
+    assert!(!is_stale(now, now, 300));
+    assert!(!is_stale(ago(9), now, 300));
+    // Exactly two intervals is still on time; one refresh may be in flight.
+    assert!(!is_stale(ago(10), now, 300));
+    assert!(is_stale(ago(11), now, 300));
+    assert!(is_stale(ago(43), now, 600));
+}
+
+#[test]
+fn test_fetch_outcomes_recorded_per_section() {
+    let mut state = make_state();
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+    let inbox = state.view_freshness(&ContentView::Inbox).unwrap();
+    assert!(inbox.last_success.is_some());
+    assert!(!inbox.failing());
+    // Other sections keep their own times.
+    assert_eq!(state.view_freshness(&ContentView::AllOpenPrs), None);
+
+    let org = ContentView::OrgOverview("org-a".into());
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "api", 1)],
+            rate_limit: None,
+        }),
+    );
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: org.clone(),
+            msg: "Failed to fetch repos for org-a: timeout".into(),
+        },
+    );
+    let freshness = state.view_freshness(&org).unwrap();
+    assert!(freshness.failing());
+    assert!(freshness.last_attempt > freshness.last_success);
+    assert!(!state.view_freshness(&ContentView::Inbox).unwrap().failing());
+}
+
+#[test]
+fn test_view_staleness_uses_the_section_interval() {
+    let mut state = make_state();
+    let now = chrono::Utc::now();
+    state.refresh_intervals.insert(ContentView::AllOpenPrs, 300);
+    state.refresh_intervals.insert(ContentView::Inbox, 60);
+    for section in [ContentView::AllOpenPrs, ContentView::Inbox] {
+        state.freshness.insert(
+            section,
+            ghdash::app::state::Freshness {
+                last_success: Some(now - chrono::Duration::minutes(5)),
+                last_attempt: Some(now - chrono::Duration::minutes(5)),
+            },
+        );
+    }
+    assert!(state.view_is_stale(&ContentView::Inbox, now));
+    assert!(!state.view_is_stale(&ContentView::AllOpenPrs, now));
+    // Views filtered from the all PRs list share its freshness.
+    assert!(!state.view_is_stale(&ContentView::FailingChecks, now));
+    assert_eq!(
+        state.view_freshness(&ContentView::RepoPrList {
+            owner: "org-a".into(),
+            name: "api".into(),
+        }),
+        state.view_freshness(&ContentView::AllOpenPrs)
+    );
+    // Never fetched, or no interval to compare against: not stale.
+    assert!(!state.view_is_stale(&ContentView::RecentlyMerged, now));
+    state.refresh_intervals.clear();
+    assert!(!state.view_is_stale(&ContentView::Inbox, now));
+}
+
+#[test]
+fn test_repo_list_tracks_its_own_fetch_time() {
+    let mut state = make_state();
+    let repo = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "api".into(),
+    };
+    let old = chrono::Utc::now() - chrono::Duration::hours(1);
+    state.freshness.insert(
+        ContentView::AllOpenPrs,
+        ghdash::app::state::Freshness {
+            last_success: Some(old),
+            last_attempt: Some(old),
+        },
+    );
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::RepoPrs {
+            owner: "org-a".into(),
+            name: "api".into(),
+            prs: vec![],
+            rate_limit: None,
+            partial: false,
+            cancel: CancelToken::new(),
+        }),
+    );
+    let own = state.view_freshness(&repo).unwrap();
+    assert!(own.last_success.unwrap() > old);
+    // Other repos still show the all PRs list's fetch time.
+    assert_eq!(
+        state.view_freshness(&ContentView::RepoPrList {
+            owner: "org-a".into(),
+            name: "web".into(),
+        }),
+        state.view_freshness(&ContentView::AllOpenPrs)
+    );
+    // A failed fetch of one repo's list marks only that list.
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: repo.clone(),
+            msg: "boom".into(),
+        },
+    );
+    assert!(state.view_freshness(&repo).unwrap().failing());
+    assert!(
+        !state
+            .view_freshness(&ContentView::AllOpenPrs)
+            .unwrap()
+            .failing()
+    );
+}
+
+#[test]
+fn test_offline_results_leave_freshness_alone() {
+    let mut state = make_state();
+    state.offline = true;
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(state.view_freshness(&ContentView::Inbox), None);
+}
+
+// --- Age bucket filter ---
+
+#[test]
+fn test_age_bucket_filter_narrows_list_but_not_histogram() {
+    let mut state = make_state();
+    let now = chrono::Utc::now();
+    let prs: Vec<PullRequest> = [0, 2, 5, 30]
+        .iter()
+        .enumerate()
+        .map(|(i, days)| {
+            let mut pr = make_pr("org-a", "repo1", i as u32 + 1, "PR");
+            pr.updated_at = now - chrono::Duration::days(*days) - chrono::Duration::minutes(1);
+            pr
+        })
+        .collect();
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs,
+            rate_limit: None,
+        }),
+    );
+    state.content_view = ContentView::AllOpenPrs;
+    state.content_cursor = 2;
+
+    update(&mut state, Action::SelectAgeBucket(3));
+    assert_eq!(state.age_bucket_filter, Some(3));
+    assert_eq!(state.content_cursor, 0);
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![4]);
+    assert!(state.filters_active());
+    assert_eq!(state.age_histogram(), [1, 1, 1, 1]);
+
+    // Selecting the active bucket again clears it; out-of-range is ignored.
+    update(&mut state, Action::SelectAgeBucket(3));
+    assert_eq!(state.age_bucket_filter, None);
+    update(&mut state, Action::SelectAgeBucket(7));
+    assert_eq!(state.age_bucket_filter, None);
+    assert_eq!(state.current_pr_list().len(), 4);
+}
+
+// --- Org colors ---
+
+#[test]
+fn test_org_color_lookup_ignores_case() {
+    let mut state = make_state();
+    state.apply_org_colors(&[ghdash::util::config::OrgColor {
+        org: "Org-A".into(),
+        color: "cyan".into(),
+    }]);
+    assert_eq!(state.org_color("org-a"), Some(ratatui::style::Color::Cyan));
+    assert_eq!(state.org_color("ORG-A"), Some(ratatui::style::Color::Cyan));
+    assert_eq!(state.org_color("org-b"), None);
+}
+
+// --- Copy as Markdown ---
+
+#[test]
+fn test_copy_pr_link_and_list() {
+    let mut state = make_state();
+    state.content_view = ContentView::AllOpenPrs;
+    assert!(update(&mut state, Action::CopyPrLink).is_empty());
+    assert!(update(&mut state, Action::CopyPrList).is_empty());
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs: vec![
+                make_pr("org-a", "repo1", 1, "First"),
+                make_pr("org-a", "repo1", 2, "Second"),
+            ],
+            rate_limit: None,
+        }),
+    );
+    state.content_cursor = 1;
+
+    let effects = update(&mut state, Action::CopyPrLink);
+    assert!(matches!(
+        effects.as_slice(),
+        [SideEffect::CopyToClipboard(text)]
+            if text == "[repo1#2: Second](https://github.com/org-a/repo1/pull/2)"
+    ));
+    assert_eq!(state.status_message.as_deref(), Some("Copied repo1#2"));
+
+    let effects = update(&mut state, Action::CopyPrList);
+    assert!(matches!(
+        effects.as_slice(),
+        [SideEffect::CopyToClipboard(text)] if text.lines().count() == 4
+    ));
+}
+
+// --- Open menu ---
+
+fn open_menu_labels(state: &AppState) -> Vec<String> {
+    state
+        .chooser
+        .as_ref()
+        .map(|c| c.labels().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_open_menu_for_repo_opens_the_picked_page() {
+    let mut state = make_state();
+    state.orgs.get_mut("org-a").unwrap().repos = vec![make_repo("org-a", "api", 1)];
+    state.rebuild_nav_tree();
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Repo { .. }))
+        .unwrap();
+
+    update(&mut state, Action::OpenMenu);
+    assert_eq!(
+        open_menu_labels(&state),
+        vec![
+            "Code",
+            "Pull requests",
+            "Issues",
+            "Actions",
+            "Releases",
+            "Settings"
+        ]
+    );
+    assert_eq!(state.chooser.as_ref().unwrap().title, "Open org-a/api");
+
+    update(&mut state, Action::ChooserDown);
+    update(&mut state, Action::ChooserDown);
+    update(&mut state, Action::ChooserDown);
+    update(&mut state, Action::ChooserUp);
+    let effects = update(&mut state, Action::ChooserConfirm);
+    assert!(matches!(
+        effects.as_slice(),
+        [SideEffect::OpenUrl(url)] if url == "https://github.com/org-a/api/issues"
+    ));
+    assert!(state.chooser.is_none());
+}
+
+fn select_nav(state: &mut AppState, pick: impl Fn(&NavNode) -> bool) {
+    state.focused_pane = FocusedPane::Navigation;
+    state.nav_cursor = state.nav_nodes.iter().position(pick).unwrap();
+    update(state, Action::Select);
+}
+
+fn recent_menu_labels(state: &AppState) -> Vec<String> {
+    state
+        .chooser
+        .as_ref()
+        .unwrap()
+        .labels()
+        .map(|l| l.to_string())
+        .collect()
+}
+
+#[test]
+fn test_recent_views_most_recent_first_without_repeats() {
+    let mut state = make_state();
+    state.orgs.get_mut("org-a").unwrap().repos = vec![make_repo("org-a", "api", 1)];
+    state.rebuild_nav_tree();
+
+    select_nav(&mut state, |n| matches!(n, NavNode::Repo { .. }));
+    select_nav(&mut state, |n| matches!(n, NavNode::AllPrs));
+    select_nav(&mut state, |n| matches!(n, NavNode::MyInbox));
+    select_nav(&mut state, |n| matches!(n, NavNode::Repo { .. }));
+    assert_eq!(
+        state.recent_views,
+        vec![
+            ContentView::RepoPrList {
+                owner: "org-a".into(),
+                name: "api".into()
+            },
+            ContentView::Inbox,
+            ContentView::AllOpenPrs,
+        ]
+    );
+
+    // The menu leaves out the view already on screen.
+    update(&mut state, Action::OpenRecentViews);
+    assert_eq!(recent_menu_labels(&state), vec!["Inbox", "All PRs"]);
+    update(&mut state, Action::ChooserDown);
+    update(&mut state, Action::ChooserConfirm);
+    assert_eq!(state.content_view, ContentView::AllOpenPrs);
+    assert_eq!(state.recent_views[0], ContentView::AllOpenPrs);
+    assert!(state.chooser.is_none());
+}
+
+#[test]
+fn test_recent_views_capped() {
     let mut state = make_state();
-    state.overlay = Overlay::Diff;
-    update(&mut state, Action::ToggleHelp);
-    assert!(state.help_open);
+    state.orgs.get_mut("org-a").unwrap().repos = (0..15)
+        .map(|i| make_repo("org-a", &format!("r{:02}", i), 1))
+        .collect();
+    state.rebuild_nav_tree();
+    for i in 0..15 {
+        let name = format!("r{:02}", i);
+        select_nav(
+            &mut state,
+            |n| matches!(n, NavNode::Repo { name: n, .. } if *n == name),
+        );
+    }
+    assert_eq!(
+        state.recent_views.len(),
+        ghdash::app::state::MAX_RECENT_VIEWS
+    );
+    assert_eq!(
+        state.recent_views[0],
+        ContentView::RepoPrList {
+            owner: "org-a".into(),
+            name: "r14".into()
+        }
+    );
+}
 
-    // Back closes help first, leaving the PR overlay untouched.
-    update(&mut state, Action::Back);
-    assert!(!state.help_open);
-    assert_eq!(state.overlay, Overlay::Diff);
+#[test]
+fn test_recent_views_menu_empty_says_so() {
+    let mut state = make_state();
+    update(&mut state, Action::OpenRecentViews);
+    assert!(state.chooser.is_none());
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("No recently viewed views")
+    );
 }
 
 #[test]
-fn test_merge_filter_selects_and_composes_with_search() {
-    use ghdash::app::state::MergeFilter;
+fn test_restored_recent_views_drop_unknown_owners() {
     let mut state = make_state();
-    let mut clean = make_pr("org-a", "repo1", 1, "clean one");
-    clean.mergeable = Some("MERGEABLE".into());
-    let mut conflict = make_pr("org-a", "repo1", 2, "conflict two");
-    conflict.mergeable = Some("CONFLICTING".into());
-    let mut unknown = make_pr("org-a", "repo1", 3, "unknown three");
-    unknown.mergeable = Some("UNKNOWN".into());
+    state.restore_recent_views(vec![
+        ContentView::OrgOverview("gone".into()),
+        ContentView::RepoPrList {
+            owner: "org-b".into(),
+            name: "api".into(),
+        },
+        ContentView::Watched,
+    ]);
+    assert_eq!(
+        state.recent_views,
+        vec![
+            ContentView::RepoPrList {
+                owner: "org-b".into(),
+                name: "api".into()
+            },
+            ContentView::Watched,
+        ]
+    );
+}
+
+#[test]
+fn test_restored_recent_views_keep_standalone_repos() {
+    let mut state = make_state();
+    state.standalone_repos = vec!["big-org/payments".into()];
+    let payments = ContentView::RepoPrList {
+        owner: "big-org".into(),
+        name: "payments".into(),
+    };
+    state.restore_recent_views(vec![
+        payments.clone(),
+        ContentView::RepoPrList {
+            owner: "big-org".into(),
+            name: "other".into(),
+        },
+        ContentView::OrgOverview("big-org".into()),
+    ]);
+    assert_eq!(
+        state.recent_views,
+        vec![payments, ContentView::OrgOverview("big-org".into())]
+    );
+}
+
+#[test]
+fn test_open_menu_for_owner_and_selected_pr() {
+    let mut state = make_state();
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Org(o) if o == "org-b"))
+        .unwrap();
+    update(&mut state, Action::OpenMenu);
+    assert_eq!(open_menu_labels(&state), vec!["Overview", "Repositories"]);
+    update(&mut state, Action::CloseChooser);
+    assert!(state.chooser.is_none());
 
+    // In the content pane the menu is for the highlighted PR's repo.
     update(
         &mut state,
         Action::DataLoaded(DataPayload::AllOpenPrs {
-            prs: vec![clean, conflict, unknown],
-            rate_limit: RateLimit::default(),
+            prs: vec![make_pr("org-a", "web", 7, "PR")],
+            rate_limit: None,
         }),
     );
     state.content_view = ContentView::AllOpenPrs;
+    state.focused_pane = FocusedPane::Content;
+    update(&mut state, Action::OpenMenu);
+    assert_eq!(state.chooser.as_ref().unwrap().title, "Open org-a/web");
+
+    // Nothing to open from a virtual node.
+    state.chooser = None;
+    state.focused_pane = FocusedPane::Navigation;
+    state.nav_cursor = 0;
+    update(&mut state, Action::OpenMenu);
+    assert!(state.chooser.is_none());
+}
+
+// --- Secondary sort ---
+
+#[test]
+fn test_secondary_sort_orders_ties_only() {
+    let mut state = make_state();
+    let t0 = chrono::Utc::now();
+    let t1 = t0 - chrono::Duration::hours(1);
+    let at = |number: u32, repo: &str, updated| {
+        let mut pr = make_pr("org-a", repo, number, "PR");
+        pr.updated_at = updated;
+        pr
+    };
+    // Fetched order: two ties at t0, one PR at t1, then another tie at t0
+    // that must not be pulled up past the t1 PR.
+    state.inbox = vec![
+        at(9, "web", t0),
+        at(3, "api", t0),
+        at(5, "api", t1),
+        at(2, "web", t0),
+        at(1, "api", t0),
+    ];
+    let numbers = |state: &AppState| -> Vec<u32> {
+        state.current_pr_list().iter().map(|p| p.number).collect()
+    };
+
+    assert_eq!(state.secondary_sort, SecondarySort::Number);
+    assert_eq!(numbers(&state), vec![3, 9, 5, 1, 2]);
+
+    state.secondary_sort = SecondarySort::Repo;
+    assert_eq!(numbers(&state), vec![3, 9, 5, 1, 2]);
+
+    state.secondary_sort = SecondarySort::None;
+    assert_eq!(numbers(&state), vec![9, 3, 5, 2, 1]);
+}
 
+#[test]
+fn test_secondary_sort_breaks_smallest_first_ties() {
+    let mut state = make_state();
+    let mut a = make_pr("org-a", "api", 8, "b title");
+    a.author = "zed".into();
+    let mut b = make_pr("org-a", "api", 4, "A title");
+    b.author = "amy".into();
+    state.inbox = vec![a, b];
+    state.sort = PrSort::SmallestFirst;
+
+    let numbers = |state: &AppState| -> Vec<u32> {
+        state.current_pr_list().iter().map(|p| p.number).collect()
+    };
+    assert_eq!(numbers(&state), vec![4, 8]);
+    state.secondary_sort = SecondarySort::Title;
+    assert_eq!(numbers(&state), vec![4, 8]);
+    state.secondary_sort = SecondarySort::None;
+    assert_eq!(numbers(&state), vec![8, 4]);
+}
+
+// --- Structured search ---
+
+#[test]
+fn test_search_query_parses_structured_filters() {
+    assert_eq!(
+        SearchQuery::parse("Fix author:Alice base:Release-1.2 login"),
+        SearchQuery {
+            author: Some("alice".into()),
+            base: Some("release-1.2".into()),
+            text: "fix login".into(),
+        }
+    );
+    // A bare prefix is just text.
+    assert_eq!(
+        SearchQuery::parse("base: main"),
+        SearchQuery {
+            author: None,
+            base: None,
+            text: "base: main".into(),
+        }
+    );
+    assert_eq!(SearchQuery::parse(""), SearchQuery::default());
+}
+
+#[test]
+fn test_base_filter_matches_target_branch() {
+    let mut state = make_state();
+    let mut release = make_pr("org-a", "repo1", 1, "Backport fix");
+    release.base_ref = Some("release-1.2".into());
+    let mut main = make_pr("org-a", "repo1", 2, "Fix");
+    main.base_ref = Some("main".into());
+    // Cached before base refs were fetched.
+    let unknown = make_pr("org-a", "repo1", 3, "Old fix");
+    state.inbox = vec![release, main, unknown];
+
+    state.search_query = "base:release-1.2".into();
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1]);
+
+    state.search_query = "base:MAIN fix".into();
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![2]);
+}
+
+// --- Label filter ---
+
+fn labeled(number: u32, labels: &[&str]) -> PullRequest {
+    let mut pr = make_pr("org-a", "repo1", number, "PR");
+    pr.labels = labels.iter().map(|l| l.to_string()).collect();
+    pr
+}
+
+#[test]
+fn test_label_counts_most_common_first() {
+    let prs = vec![
+        labeled(1, &["needs-review", "backend"]),
+        labeled(2, &["blocked", "backend"]),
+        labeled(3, &["needs-review", "backend"]),
+        labeled(4, &[]),
+    ];
+    assert_eq!(
+        label_counts(&prs),
+        vec![
+            ("backend".to_string(), 3),
+            ("needs-review".to_string(), 2),
+            ("blocked".to_string(), 1),
+        ]
+    );
+    assert!(label_counts(&[]).is_empty());
+}
+
+#[test]
+fn test_has_labels_requires_all() {
+    let pr = labeled(1, &["Needs-Review", "backend"]);
+    assert!(has_labels(&pr, &[]));
+    assert!(has_labels(&pr, &["needs-review".into()]));
+    assert!(has_labels(&pr, &["backend".into(), "needs-review".into()]));
+    assert!(!has_labels(&pr, &["backend".into(), "blocked".into()]));
+}
+
+#[test]
+fn test_label_menu_stacks_filters_and_esc_clears() {
+    let mut state = make_state();
+    state.inbox = vec![
+        labeled(1, &["needs-review", "backend"]),
+        labeled(2, &["blocked", "backend"]),
+        labeled(3, &["needs-review"]),
+    ];
+    state.focused_pane = FocusedPane::Content;
+
+    update(&mut state, Action::OpenLabelMenu);
+    let labels: Vec<String> = state
+        .chooser
+        .as_ref()
+        .unwrap()
+        .labels()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(
+        labels,
+        vec!["backend (2)", "needs-review (2)", "blocked (1)"]
+    );
+    update(&mut state, Action::ChooserConfirm);
+    assert_eq!(state.label_filter, vec!["backend".to_string()]);
+    assert!(state.filters_active());
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1, 2]);
+
+    // The next menu only offers labels left in the narrowed list.
+    update(&mut state, Action::OpenLabelMenu);
+    update(&mut state, Action::ChooserDown);
+    update(&mut state, Action::ChooserConfirm);
+    assert_eq!(
+        state.label_filter,
+        vec!["backend".to_string(), "needs-review".to_string()]
+    );
+    let numbers: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    assert_eq!(numbers, vec![1]);
+
+    update(&mut state, Action::Back);
+    assert!(state.label_filter.is_empty());
+    assert_eq!(state.focused_pane, FocusedPane::Content);
     assert_eq!(state.current_pr_list().len(), 3);
+}
 
-    state.merge_filter = MergeFilter::Clean;
-    let clean_list = state.current_pr_list();
-    assert_eq!(clean_list.len(), 1);
-    assert_eq!(clean_list[0].number, 1);
+#[test]
+fn test_label_menu_without_labels_says_so() {
+    let mut state = make_state();
+    state.inbox = vec![labeled(1, &[])];
+    update(&mut state, Action::OpenLabelMenu);
+    assert!(state.chooser.is_none());
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("No labels to filter by")
+    );
+}
 
-    state.merge_filter = MergeFilter::Conflicting;
-    assert_eq!(state.current_pr_list().len(), 1);
-    assert_eq!(state.current_pr_list()[0].number, 2);
+// --- Watched PRs ---
 
-    // Composes with search: Clean + a query excluding the clean PR -> empty.
-    state.merge_filter = MergeFilter::Clean;
-    state.search_query = "conflict".into();
-    assert_eq!(state.current_pr_list().len(), 0);
+fn watched_fetches(effects: &[SideEffect]) -> Vec<u32> {
+    effects
+        .iter()
+        .filter_map(|e| match e {
+            SideEffect::FetchWatchedPr(w) => Some(w.number),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_toggle_watch_adds_nav_entry_and_saves() {
+    let mut state = make_state();
+    state.inbox = vec![make_pr("org-a", "repo1", 1, "Keep an eye on this")];
+    state.focused_pane = FocusedPane::Content;
+
+    let effects = update(&mut state, Action::ToggleWatch);
+    assert!(matches!(&effects[..], [SideEffect::SaveWatchlist(prs)] if prs.len() == 1));
+    assert_eq!(state.status_message.as_deref(), Some("Watching repo1#1"));
+    assert!(
+        state
+            .nav_nodes
+            .iter()
+            .any(|n| matches!(n, NavNode::Watched))
+    );
+
+    let effects = update(&mut state, Action::ToggleWatch);
+    assert!(matches!(&effects[..], [SideEffect::SaveWatchlist(prs)] if prs.is_empty()));
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("Stopped watching repo1#1")
+    );
+    assert!(
+        !state
+            .nav_nodes
+            .iter()
+            .any(|n| matches!(n, NavNode::Watched))
+    );
+}
+
+#[test]
+fn test_watched_view_finds_loaded_prs_and_fetches_the_rest_once() {
+    let mut state = make_state();
+    state.loading = false;
+    let open = make_pr("org-a", "repo1", 1, "Still open");
+    let mut merged = make_pr("org-a", "repo1", 2, "Shipped");
+    merged.state = PrState::Merged;
+    merged.merged_at = Some(chrono::Utc::now());
+    let elsewhere = make_pr("other", "lib", 3, "Outside my orgs");
+    state.all_open_prs = vec![open.clone()];
+    state.recently_merged = vec![merged.clone()];
+    for pr in [&open, &merged, &elsewhere] {
+        state.toggle_watch(pr);
+    }
+
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Watched))
+        .unwrap();
+    let effects = update(&mut state, Action::Select);
+    assert_eq!(state.content_view, ContentView::Watched);
+    assert_eq!(watched_fetches(&effects), vec![3]);
+    let mut listed: Vec<u32> = state.current_pr_list().iter().map(|p| p.number).collect();
+    listed.sort();
+    assert_eq!(listed, vec![1, 2]);
+
+    // Already requested: re-entering the view doesn't fetch again.
+    assert!(watched_fetches(&update(&mut state, Action::Select)).is_empty());
+
+    let watched = state.watched[2].clone();
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::WatchedPr {
+            watched,
+            pr: Box::new(elsewhere),
+            rate_limit: None,
+        }),
+    );
+    assert_eq!(state.current_pr_list().len(), 3);
+
+    // Refreshing the view fetches every watched PR afresh.
+    let mut refetched = watched_fetches(&update(&mut state, Action::RefreshCurrentView));
+    refetched.sort();
+    assert_eq!(refetched, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_watched_prs_are_not_fetched_offline() {
+    let mut state = make_state();
+    state.offline = true;
+    state.toggle_watch(&make_pr("other", "lib", 3, "Outside my orgs"));
+    state.nav_cursor = state
+        .nav_nodes
+        .iter()
+        .position(|n| matches!(n, NavNode::Watched))
+        .unwrap();
+    assert!(watched_fetches(&update(&mut state, Action::Select)).is_empty());
+}
+
+#[test]
+fn test_refresh_drops_individually_fetched_watched_prs() {
+    let mut state = make_state();
+    let pr = make_pr("other", "lib", 3, "Outside my orgs");
+    state.toggle_watch(&pr);
+    let watched = state.watched[0].clone();
+    state.watched_requested.insert(watched.clone());
+    state.watched_prs.insert(watched, pr);
+
+    update(&mut state, Action::Refresh);
+    assert!(state.watched_prs.is_empty());
+    assert!(state.watched_requested.is_empty());
+}
+
+// --- Scrolling ---
+
+#[test]
+fn test_scroll_offset_keeps_margin_around_cursor() {
+    // 10 rows visible out of 50, 2 rows of context.
+    assert_eq!(scroll_offset(0, 7, 50, 10, 2), 0);
+    assert_eq!(scroll_offset(0, 8, 50, 10, 2), 1);
+    assert_eq!(scroll_offset(20, 21, 50, 10, 2), 19);
+    // The last page stays full; the first never scrolls past the top.
+    assert_eq!(scroll_offset(0, 49, 50, 10, 2), 40);
+    assert_eq!(scroll_offset(5, 0, 50, 10, 2), 0);
+    // A margin too big for the viewport centres the cursor instead.
+    assert_eq!(scroll_offset(0, 20, 50, 5, 10), 18);
+    // Unknown viewport height.
+    assert_eq!(scroll_offset(7, 20, 50, 0, 2), 0);
+}
+
+#[test]
+fn test_moving_the_cursor_scrolls_the_table() {
+    let mut state = make_state();
+    state.inbox = (1..=20)
+        .map(|n| make_pr("org-a", "repo1", n, "Change"))
+        .collect();
+    state.focused_pane = FocusedPane::Content;
+    state.table_rows = 5;
+    state.scrolloff = 1;
+
+    update(&mut state, Action::CountDigit(4));
+    update(&mut state, Action::MoveDown);
+    assert_eq!(state.content_cursor, 4);
+    assert_eq!(state.content_offset, 1);
+
+    update(&mut state, Action::JumpBottom);
+    assert_eq!(state.content_offset, 15);
+    update(&mut state, Action::JumpTop);
+    assert_eq!(state.content_offset, 0);
+}
+
+// --- Refresh summary ---
+
+/// Report every section of a full refresh: `org-a` gains a PR, `org-b`
+/// fails, and the inbox comes back as `inbox`.
+fn finish_refresh(state: &mut AppState, inbox: Vec<PullRequest>) {
+    update(
+        state,
+        Action::DataLoaded(DataPayload::OrgRepos {
+            org: "org-a".into(),
+            repos: vec![make_repo("org-a", "api", 2)],
+            rate_limit: None,
+        }),
+    );
+    update(
+        state,
+        Action::FetchFailed {
+            view: ContentView::OrgOverview("org-b".into()),
+            msg: "boom".into(),
+        },
+    );
+    update(
+        state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: inbox,
+            rate_limit: None,
+        }),
+    );
+    for kind in [PrHistoryKind::Merged, PrHistoryKind::Closed] {
+        update(
+            state,
+            Action::DataLoaded(DataPayload::PrHistory {
+                kind,
+                prs: vec![],
+                rate_limit: None,
+            }),
+        );
+    }
+    update(
+        state,
+        Action::DataLoaded(DataPayload::AllOpenPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+}
+
+fn loaded_state() -> AppState {
+    let mut state = make_state();
+    for org in ["org-a", "org-b"] {
+        update(
+            &mut state,
+            Action::DataLoaded(DataPayload::OrgRepos {
+                org: org.into(),
+                repos: vec![make_repo(org, "api", 1)],
+                rate_limit: None,
+            }),
+        );
+    }
+    state
+}
+
+#[test]
+fn test_refresh_summary_after_every_section_reports() {
+    let mut state = loaded_state();
+    update(&mut state, Action::Refresh);
+    let inbox = vec![
+        make_pr("org-a", "api", 1, "a"),
+        make_pr("org-a", "api", 2, "b"),
+    ];
+    finish_refresh(&mut state, inbox);
+    assert_eq!(
+        state.status_message.as_deref(),
+        Some("Refreshed: +2 PRs in inbox, 1 repo updated, 1 org failed")
+    );
+    assert!(state.refresh_tracker.is_none());
+}
+
+#[test]
+fn test_refresh_summary_waits_for_the_last_section() {
+    let mut state = loaded_state();
+    update(&mut state, Action::Refresh);
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::InboxPrs {
+            prs: vec![],
+            rate_limit: None,
+        }),
+    );
+    assert!(state.status_message.is_none());
+    assert!(state.refresh_tracker.is_some());
+}
+
+#[test]
+fn test_no_summary_without_a_full_refresh() {
+    let mut state = loaded_state();
+    finish_refresh(&mut state, vec![]);
+    assert!(
+        !state
+            .status_message
+            .as_deref()
+            .is_some_and(|m| m.starts_with("Refreshed"))
+    );
+}
+
+#[test]
+fn test_refresh_summary_message() {
+    assert_eq!(RefreshSummary::default().message(), "Refreshed: no changes");
+    let summary = RefreshSummary {
+        inbox: ListDiff {
+            added: 1,
+            removed: 2,
+            updated: 0,
+        },
+        all_open_prs: ListDiff {
+            added: 0,
+            removed: 0,
+            updated: 3,
+        },
+        repos_changed: 0,
+        failed_owners: 0,
+        failed_lists: 1,
+    };
+    assert_eq!(
+        summary.message(),
+        "Refreshed: +1 PR in inbox, -2 PRs from inbox, 3 PRs updated, 1 list failed"
+    );
+}
+
+#[test]
+fn test_error_modal_scrolls_and_copies() {
+    let mut state = make_state();
+    update(
+        &mut state,
+        Action::LoadError("GraphQL errors: a very long list".into()),
+    );
+    // As recorded by the last frame.
+    state.error_max_scroll = 2;
+    for _ in 0..5 {
+        update(&mut state, Action::MoveDown);
+    }
+    assert_eq!(state.error_scroll, 2);
+    update(&mut state, Action::MoveUp);
+    assert_eq!(state.error_scroll, 1);
+    assert_eq!(state.content_cursor, 0);
+
+    let effects = update(&mut state, Action::CopyError);
+    assert!(matches!(
+        &effects[..],
+        [SideEffect::CopyToClipboard(text)] if text == "GraphQL errors: a very long list"
+    ));
+
+    // A new error starts at the top.
+    update(&mut state, Action::LoadError("another".into()));
+    assert_eq!(state.error_scroll, 0);
 }
@@ -0,0 +1,29 @@
+use ghdash::app::event_loop::TaskQueue;
+
+#[tokio::test]
+async fn test_waiting_tasks_are_counted() {
+    let queue = TaskQueue::new(1);
+    let held = queue.acquire().await.unwrap();
+    assert_eq!(queue.queued(), 0);
+
+    let waiter = {
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            let _permit = queue.acquire().await;
+        })
+    };
+    while queue.queued() == 0 {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(queue.queued(), 1);
+
+    drop(held);
+    waiter.await.unwrap();
+    assert_eq!(queue.queued(), 0);
+}
+
+#[tokio::test]
+async fn test_zero_concurrency_still_makes_progress() {
+    let queue = TaskQueue::new(0);
+    assert!(queue.acquire().await.is_ok());
+}
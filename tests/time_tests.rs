@@ -1,5 +1,8 @@
-use chrono::{Duration, Utc};
-use ghdash::util::time::relative_time;
+use chrono::{Duration, FixedOffset, TimeZone, Utc};
+use ghdash::util::time::{
+    age_bucket, age_bucket_labels, age_histogram, countdown, local, now, relative_time,
+    set_fixed_now, set_fixed_offset,
+};
 
 #[test]
 fn test_just_now() {
@@ -120,3 +123,76 @@ fn test_boundary_365_days() {
     let t = Utc::now() - Duration::days(365);
     assert_eq!(relative_time(&t), "1y ago");
 }
+
+#[test]
+fn test_fixed_now_is_used_by_relative_time() {
+    let pinned = Utc::now() - Duration::days(3);
+    set_fixed_now(Some(pinned));
+    assert_eq!(now(), pinned);
+    assert_eq!(relative_time(&(pinned - Duration::hours(2))), "2h ago");
+
+    set_fixed_now(None);
+    assert_ne!(now(), pinned);
+}
+
+#[test]
+fn test_age_bucket_boundaries() {
+    let now = Utc::now();
+    let bounds = [1, 3, 7];
+    let bucket = |age: Duration| age_bucket(&(now - age), &now, &bounds);
+    assert_eq!(bucket(Duration::zero()), 0);
+    assert_eq!(bucket(Duration::hours(23)), 0);
+    // Each bound belongs to the bucket above it.
+    assert_eq!(bucket(Duration::days(1)), 1);
+    assert_eq!(bucket(Duration::days(3) - Duration::seconds(1)), 1);
+    assert_eq!(bucket(Duration::days(3)), 2);
+    assert_eq!(bucket(Duration::days(7) - Duration::seconds(1)), 2);
+    assert_eq!(bucket(Duration::days(7)), 3);
+    assert_eq!(bucket(Duration::days(400)), 3);
+    // Clock skew: a timestamp from the future is brand new.
+    assert_eq!(bucket(-Duration::hours(1)), 0);
+}
+
+#[test]
+fn test_age_histogram_counts_each_bucket() {
+    let now = Utc::now();
+    let times: Vec<_> = [0, 2, 2, 5, 30, 90]
+        .iter()
+        .map(|days| now - Duration::days(*days))
+        .collect();
+    assert_eq!(age_histogram(&times, &now, &[1, 3, 7]), [1, 2, 1, 2]);
+    assert_eq!(age_histogram(&times, &now, &[3, 10, 60]), [3, 1, 1, 1]);
+}
+
+#[test]
+fn test_age_bucket_labels() {
+    assert_eq!(
+        age_bucket_labels(&[1, 3, 7], "–"),
+        ["<1d", "1–3d", "3–7d", ">7d"]
+    );
+    assert_eq!(
+        age_bucket_labels(&[1, 3, 7], "-"),
+        ["<1d", "1-3d", "3-7d", ">7d"]
+    );
+}
+
+#[test]
+fn test_countdown() {
+    let base = Utc::now();
+    set_fixed_now(Some(base));
+    assert_eq!(countdown(&(base + Duration::seconds(45))), "45s");
+    assert_eq!(countdown(&(base + Duration::seconds(7 * 60 + 10))), "8m");
+    assert_eq!(countdown(&(base + Duration::minutes(65))), "1h 5m");
+    assert_eq!(countdown(&(base - Duration::minutes(1))), "0s");
+    set_fixed_now(None);
+}
+
+#[test]
+fn test_local_uses_the_pinned_offset() {
+    let at = Utc.with_ymd_and_hms(2024, 6, 1, 12, 8, 0).unwrap();
+    set_fixed_offset(Some(FixedOffset::east_opt(2 * 3600).unwrap()));
+    assert_eq!(local(&at).format("%H:%M").to_string(), "14:08");
+    set_fixed_offset(Some(FixedOffset::west_opt(5 * 3600 + 30 * 60).unwrap()));
+    assert_eq!(local(&at).format("%H:%M").to_string(), "06:38");
+    set_fixed_offset(None);
+}
@@ -0,0 +1,495 @@
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use ghdash::app::state::{
+    AppState, ContentView, FocusedPane, Freshness, Overlay, PrDetailEntry, PrSort,
+};
+use ghdash::app::view;
+use ghdash::github::models::{CommitInfo, PrDetail, PrState, PullRequest, RateLimit};
+use ghdash::util::config::PrColumn;
+use ghdash::util::time::{set_fixed_now, set_fixed_offset};
+use insta::assert_snapshot;
+use ratatui::{Terminal, backend::TestBackend};
+
+mod common;
+
+use common::make_repo;
+
+fn fixed_now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+}
+
+fn make_pr(repo_name: &str, number: u32, title: &str, age: Duration) -> PullRequest {
+    PullRequest {
+        title: title.into(),
+        author: "octocat".into(),
+        created_at: fixed_now() - age,
+        updated_at: fixed_now() - age,
+        additions: 10,
+        deletions: 5,
+        ..common::make_pr("org-a", repo_name, number)
+    }
+}
+
+fn make_prs() -> Vec<PullRequest> {
+    let mut approved = make_pr("api", 42, "Fix login redirect", Duration::minutes(5));
+    approved.review_decision = Some("APPROVED".into());
+    approved.mergeable = Some("MERGEABLE".into());
+    approved.checks_status = Some("SUCCESS".into());
+
+    let mut conflicting = make_pr("web", 7, "Refactor settings page", Duration::hours(3));
+    conflicting.mergeable = Some("CONFLICTING".into());
+    conflicting.checks_status = Some("FAILURE".into());
+
+    let mut draft = make_pr("api", 43, "WIP: new rate limiter", Duration::days(2));
+    draft.additions = 640;
+    draft.is_draft = true;
+    draft.checks_status = Some("PENDING".into());
+
+    vec![approved, conflicting, draft]
+}
+
+/// Empty, fully loaded state with a single org.
+fn make_state() -> AppState {
+    let mut state = AppState::new("testuser".into(), vec!["org-a".into()]);
+    state.loading = false;
+    state
+}
+
+fn make_populated_state() -> AppState {
+    let mut state = make_state();
+    state.orgs.get_mut("org-a").unwrap().repos =
+        vec![make_repo("org-a", "api", 2), make_repo("org-a", "web", 1)];
+    state.rebuild_nav_tree();
+    state.inbox = make_prs();
+    state.all_open_prs = make_prs();
+    state.rate_limit.observe(RateLimit {
+        remaining: 4812,
+        limit: 5000,
+        reset_at: None,
+    });
+    state.last_refresh = Some(fixed_now() - Duration::minutes(2));
+    state
+}
+
+fn render(state: &AppState, width: u16, height: u16) -> Terminal<TestBackend> {
+    set_fixed_now(Some(fixed_now()));
+    set_fixed_offset(Some(FixedOffset::east_opt(0).unwrap()));
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| view::render(f, state)).unwrap();
+    terminal
+}
+
+#[test]
+fn test_snapshot_empty() {
+    let state = make_state();
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_loading() {
+    let state = AppState::new("testuser".into(), vec!["org-a".into()]);
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_populated_inbox() {
+    let mut state = make_populated_state();
+    state.focused_pane = FocusedPane::Content;
+    state.content_cursor = 1;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_inbox_already_reviewed() {
+    let mut state = make_populated_state();
+    state.inbox[1].my_review_state = Some("COMMENTED".into());
+    state.inbox[2].my_review_state = Some("PENDING".into());
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_ascii_glyphs() {
+    let mut state = make_populated_state();
+    state.ascii = Some(true);
+    state.nav_expanded.insert("org-a".into());
+    state.rebuild_nav_tree();
+    for (hours_ago, api) in [(3, 0), (2, 2), (1, 1)] {
+        state
+            .pr_history
+            .append("org-a/api", fixed_now() - Duration::hours(hours_ago), api);
+    }
+    state.inbox[1].my_review_state = Some("COMMENTED".into());
+    state.refresh_locked = true;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_search_active() {
+    let mut state = make_populated_state();
+    state.content_view = ContentView::AllOpenPrs;
+    state.search_active = true;
+    state.search_query = "fix".into();
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_recently_merged() {
+    let mut state = make_populated_state();
+    let mut merged = make_pr("api", 40, "Bump tokio", Duration::days(3));
+    merged.state = PrState::Merged;
+    merged.merged_at = Some(fixed_now() - Duration::hours(6));
+    state.recently_merged = vec![merged];
+    state.content_view = ContentView::RecentlyMerged;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_recently_closed() {
+    let mut state = make_populated_state();
+    let mut closed = make_pr("web", 9, "Try a new router", Duration::days(20));
+    closed.state = PrState::Closed;
+    closed.closed_at = Some(fixed_now() - Duration::days(2));
+    state.recently_closed = vec![closed];
+    state.content_view = ContentView::RecentlyClosed;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_failing_checks() {
+    let mut state = make_populated_state();
+    state.all_open_prs[0].checks_status = Some("FAILURE".into());
+    state.content_view = ContentView::FailingChecks;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_expanded_shortcodes() {
+    let mut state = make_populated_state();
+    state.expand_shortcodes = true;
+    state.inbox[0].title = ":bug: Fix login redirect".into();
+    state.inbox[1].title = ":sparkles: Refactor settings page".into();
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_custom_columns() {
+    let mut state = make_populated_state();
+    state.columns = vec![
+        PrColumn::Repo,
+        PrColumn::Number,
+        PrColumn::Title,
+        PrColumn::Diffstat,
+    ];
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_size_column() {
+    let mut state = make_populated_state();
+    state.columns = vec![
+        PrColumn::Repo,
+        PrColumn::Number,
+        PrColumn::Title,
+        PrColumn::Diffstat,
+        PrColumn::Size,
+    ];
+    state.sort = PrSort::SmallestFirst;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_offline_status_bar() {
+    let mut state = make_populated_state();
+    state.offline = true;
+    state.offline_data_as_of = Some(fixed_now() - Duration::hours(3));
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_refresh_locked() {
+    let mut state = make_populated_state();
+    state.toggle_refresh_lock();
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_auto_refresh_paused() {
+    let mut state = make_populated_state();
+    state.auto_refresh_paused = true;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_refresh_failing_retry_countdown() {
+    let mut state = make_populated_state();
+    state.refresh_retry_at = Some(fixed_now() + Duration::minutes(4));
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_token_rejected_prompt() {
+    let mut state = make_populated_state();
+    state.token_rejected = true;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_secondary_rate_limit_backoff() {
+    let mut state = make_populated_state();
+    state.rate_limit_backoff = Some(42);
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_rate_limit_throttled_countdown() {
+    let mut state = make_populated_state();
+    state.rate_limit.observe(RateLimit {
+        remaining: 12,
+        limit: 5000,
+        reset_at: Some(fixed_now() + Duration::minutes(8)),
+    });
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_full_title_in_status_bar() {
+    let mut state = make_populated_state();
+    state.status_message =
+        Some("Fix login redirect when the session cookie expires mid-flow".into());
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_error_modal() {
+    let mut state = make_populated_state();
+    state.error_message = Some("Failed to fetch inbox: GitHub API returned 502".into());
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_long_error_wraps_and_scrolls() {
+    let mut state = make_populated_state();
+    let errors: Vec<String> = (1..=12)
+        .map(|i| format!("Could not resolve to a Repository with the name 'org-a/repo-{i}'."))
+        .collect();
+    state.error_message = Some(format!(
+        "Failed to fetch all open PRs: GraphQL errors: {}",
+        errors.join(" ")
+    ));
+    state.error_scroll = 2;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_narrow_terminal() {
+    // The PR table's fixed column widths can't all fit at this width, and the
+    // layout solver's tie-breaking is not stable when over-constrained, so the
+    // narrow snapshot covers the nav tree, org overview and status bar instead.
+    let mut state = make_populated_state();
+    state.content_view = ContentView::OrgOverview("org-a".into());
+    assert_snapshot!(render(&state, 50, 16).backend());
+}
+
+#[test]
+fn test_snapshot_org_overview_with_pr_history() {
+    let mut state = make_populated_state();
+    state.nav_expanded.insert("org-a".into());
+    state.rebuild_nav_tree();
+    for (hours_ago, api, web) in [(5, 0, 1), (4, 1, 1), (3, 1, 2), (2, 2, 1), (1, 3, 1)] {
+        let at = fixed_now() - Duration::hours(hours_ago);
+        state.pr_history.append("org-a/api", at, api);
+        state.pr_history.append("org-a/web", at, web);
+    }
+    state.content_view = ContentView::OrgOverview("org-a".into());
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_org_overview_stale_and_failing() {
+    let mut state = make_populated_state();
+    let view = ContentView::OrgOverview("org-a".into());
+    state.refresh_intervals.insert(view.clone(), 600);
+    state.freshness.insert(
+        view.clone(),
+        Freshness {
+            last_success: Some(fixed_now() - Duration::minutes(43)),
+            last_attempt: Some(fixed_now() - Duration::minutes(3)),
+        },
+    );
+    state.content_view = view;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_inbox_fetch_time() {
+    let mut state = make_populated_state();
+    state.refresh_intervals.insert(ContentView::Inbox, 300);
+    state.freshness.insert(
+        ContentView::Inbox,
+        Freshness {
+            last_success: Some(fixed_now() - Duration::minutes(2)),
+            last_attempt: Some(fixed_now() - Duration::minutes(2)),
+        },
+    );
+    assert_snapshot!(render(&state, 140, 12).backend());
+}
+
+#[test]
+fn test_snapshot_scrolled_columns() {
+    let mut state = make_populated_state();
+    state.column_offset = 2;
+    assert_snapshot!(render(&state, 80, 12).backend());
+}
+
+#[test]
+fn test_snapshot_long_list_scrolls_with_scrolloff() {
+    let mut state = make_populated_state();
+    state.inbox = (1..=30)
+        .map(|n| {
+            make_pr(
+                "api",
+                n,
+                &format!("Change {}", n),
+                Duration::hours(n as i64),
+            )
+        })
+        .collect();
+    state.focused_pane = FocusedPane::Content;
+    state.scrolloff = 2;
+    state.content_cursor = 20;
+    // A stale offset from a previous frame; rendering keeps the cursor two
+    // rows clear of the bottom edge.
+    state.content_offset = 0;
+    assert_snapshot!(render(&state, 100, 14).backend());
+}
+
+#[test]
+fn test_snapshot_all_prs_grouped_by_repo() {
+    let mut state = make_populated_state();
+    state.group_all_prs_by_repo = true;
+    state.content_view = ContentView::AllOpenPrs;
+    state.focused_pane = FocusedPane::Content;
+    state.content_cursor = 1;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_reduce_motion() {
+    let mut state = make_populated_state();
+    state.reduce_motion = true;
+    state.loading = true;
+    state.loading_orgs.insert("org-a".into());
+    // Mine, review requested (in the inbox), and neither.
+    state.viewer_login = "octocat".into();
+    state.all_open_prs[1].author = "someone".into();
+    state.all_open_prs[2].author = "someone".into();
+    state.inbox.truncate(2);
+    state.rebuild_inbox_keys();
+    state.content_view = ContentView::AllOpenPrs;
+    state.refresh_intervals.insert(ContentView::AllOpenPrs, 300);
+    state.freshness.insert(
+        ContentView::AllOpenPrs,
+        Freshness {
+            last_success: Some(fixed_now() - Duration::minutes(43)),
+            last_attempt: Some(fixed_now() - Duration::minutes(43)),
+        },
+    );
+    assert_snapshot!(render(&state, 180, 12).backend());
+}
+
+#[test]
+fn test_snapshot_git_log_with_closing_issues() {
+    let mut state = make_populated_state();
+    state.focused_pane = FocusedPane::Content;
+    state.overlay = Overlay::GitLog;
+    state.inbox[0].head_ref = Some("fix/login-redirect-sso".into());
+    state.inbox[0].base_ref = Some("main".into());
+    let url = state.selected_pr().unwrap().url;
+    state.pr_details.insert(
+        url,
+        PrDetailEntry::Loaded(PrDetail {
+            mergeable: Some("MERGEABLE".into()),
+            merge_state_status: Some("CLEAN".into()),
+            checks_status: Some("SUCCESS".into()),
+            commits: vec![CommitInfo {
+                oid: "0123456789abcdef".into(),
+                headline: "Handle missing redirect param".into(),
+                committed_date: fixed_now() - Duration::hours(1),
+                author: "Octo Cat".into(),
+            }],
+            closes_issues: vec![
+                (12, "Login loops forever".into()),
+                (15, "Redirect drops query".into()),
+            ],
+            body: String::new(),
+        }),
+    );
+    assert_snapshot!(render(&state, 140, 24).backend());
+}
+
+#[test]
+fn test_snapshot_git_log_with_description() {
+    let mut state = make_populated_state();
+    state.focused_pane = FocusedPane::Content;
+    state.overlay = Overlay::GitLog;
+    let url = state.selected_pr().unwrap().url;
+    state.pr_details.insert(
+        url,
+        PrDetailEntry::Loaded(PrDetail {
+            mergeable: Some("MERGEABLE".into()),
+            merge_state_status: Some("CLEAN".into()),
+            checks_status: Some("SUCCESS".into()),
+            commits: vec![CommitInfo {
+                oid: "0123456789abcdef".into(),
+                headline: "Handle missing redirect param".into(),
+                committed_date: fixed_now() - Duration::hours(1),
+                author: "Octo Cat".into(),
+            }],
+            closes_issues: vec![],
+            body: "## Summary\n\nKeeps the `next` param across the \
+                   [login flow](https://example.com/docs).\n\n\
+                   <!-- Describe how you tested -->\n\
+                   - **Fix**: redirect loop\n- Adds tests\n"
+                .into(),
+        }),
+    );
+    assert_snapshot!(render(&state, 140, 30).backend());
+}
+
+#[test]
+fn test_snapshot_pr_info_popup() {
+    let mut state = make_populated_state();
+    state.focused_pane = FocusedPane::Content;
+    state.inbox[0].title =
+        "Fix login redirect when the session cookie expires mid-flow and the SSO provider drops the return URL".into();
+    state.inbox[0].head_ref = Some("fix/login-redirect-sso".into());
+    state.inbox[0].base_ref = Some("main".into());
+    state.pr_info_open = true;
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_open_menu() {
+    let mut state = make_populated_state();
+    state.focused_pane = FocusedPane::Content;
+    state.chooser = state.open_menu();
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
+
+#[test]
+fn test_snapshot_watched_with_finished_prs() {
+    let mut state = make_populated_state();
+    let mut merged = make_pr("api", 40, "Bump tokio", Duration::hours(6));
+    merged.state = PrState::Merged;
+    merged.merged_at = Some(fixed_now() - Duration::hours(6));
+    let mut closed = make_pr("web", 9, "Try a new router", Duration::days(2));
+    closed.state = PrState::Closed;
+    closed.closed_at = Some(fixed_now() - Duration::days(2));
+    state.recently_merged = vec![merged.clone()];
+    state.recently_closed = vec![closed.clone()];
+    for pr in [&state.inbox[0].clone(), &merged, &closed] {
+        state.toggle_watch(pr);
+    }
+    state.content_view = ContentView::Watched;
+    // Only the open one counts in the nav.
+    assert_snapshot!(render(&state, 140, 20).backend());
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ghdash::util::update_check::{UpdateCheck, newer_version, parse_version};
+use tempfile::TempDir;
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap()
+}
+
+#[test]
+fn test_parse_version() {
+    assert_eq!(parse_version("0.3.1"), Some((0, 3, 1)));
+    assert_eq!(parse_version("v1.10.0"), Some((1, 10, 0)));
+    assert_eq!(parse_version("v0.4.0-rc.1"), Some((0, 4, 0)));
+    assert_eq!(parse_version("1.2.3+build.5"), Some((1, 2, 3)));
+    for bad in ["", "v", "1.2", "1.2.3.4", "one.two.three", "nightly"] {
+        assert_eq!(parse_version(bad), None, "{bad:?}");
+    }
+}
+
+#[test]
+fn test_newer_version_compares_numerically() {
+    assert_eq!(newer_version("v0.4.0", "0.3.1"), Some("0.4.0".to_string()));
+    assert_eq!(
+        newer_version("v0.10.0", "0.9.9"),
+        Some("0.10.0".to_string())
+    );
+    assert_eq!(newer_version("1.0.0", "0.99.99"), Some("1.0.0".to_string()));
+    assert_eq!(newer_version("v0.3.1", "0.3.1"), None);
+    assert_eq!(newer_version("v0.3.0", "0.3.1"), None);
+    assert_eq!(newer_version("latest", "0.3.1"), None);
+}
+
+#[test]
+fn test_check_is_due_once_a_day() {
+    assert!(UpdateCheck::default().is_due(start()));
+
+    let check = UpdateCheck {
+        last_checked: Some(start()),
+    };
+    assert!(!check.is_due(start()));
+    assert!(!check.is_due(start() + Duration::hours(23)));
+    assert!(check.is_due(start() + Duration::hours(24)));
+    // A clock set back doesn't hold the check off.
+    assert!(check.is_due(start() - Duration::hours(1)));
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("nested").join("update_check.json");
+    assert_eq!(UpdateCheck::load(&path), UpdateCheck::default());
+
+    let check = UpdateCheck {
+        last_checked: Some(start()),
+    };
+    check.save(&path).unwrap();
+    assert_eq!(UpdateCheck::load(&path), check);
+
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(UpdateCheck::load(&path), UpdateCheck::default());
+}
@@ -0,0 +1,39 @@
+use ghdash::util::watchlist::{WatchedPr, Watchlist};
+use tempfile::TempDir;
+
+#[test]
+fn test_save_and_load_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("nested").join("watched.json");
+
+    let watchlist = Watchlist {
+        prs: vec![WatchedPr {
+            owner: "o".into(),
+            name: "r".into(),
+            number: 1,
+        }],
+    };
+    watchlist.save(&path).unwrap();
+
+    assert_eq!(Watchlist::load(&path).prs, watchlist.prs);
+}
+
+#[test]
+fn test_load_missing_or_corrupted_file_is_empty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("watched.json");
+    assert!(Watchlist::load(&path).prs.is_empty());
+    std::fs::write(&path, "not json").unwrap();
+    assert!(Watchlist::load(&path).prs.is_empty());
+}
+
+#[test]
+fn test_watched_pr_matches_repo_case_insensitively() {
+    let w = WatchedPr {
+        owner: "Org".into(),
+        name: "Repo".into(),
+        number: 7,
+    };
+    assert!(w.matches("org", "repo", 7));
+    assert!(!w.matches("org", "repo", 8));
+}
@@ -0,0 +1,501 @@
+use ghdash::app::actions::{Action, DataPayload};
+use ghdash::app::state::{AppState, ContentView, MergeFilter};
+use ghdash::app::update::update;
+use ghdash::github::CancelToken;
+use ghdash::github::models::{PrState, PrStateFilter, PullRequest};
+use ghdash::ui::glyphs::{ASCII, UNICODE};
+use ghdash::ui::widgets::{
+    column_window, comment_count_text, count_text, empty_state_message, error_max_scroll,
+    fitted_column_offset, merge_state_display, render_title, size_badge, title_count_text,
+    truncate_with_ellipsis, wrapped_line_count,
+};
+use ghdash::util::config::{PrColumn, default_column_priority, default_columns};
+use unicode_width::UnicodeWidthStr;
+
+mod common;
+
+#[test]
+fn test_render_title_leaves_shortcodes_when_disabled() {
+    assert_eq!(render_title(":rocket: Ship it", false), ":rocket: Ship it");
+}
+
+#[test]
+fn test_render_title_expands_known_shortcodes() {
+    assert_eq!(
+        render_title(":bug: Fix :sparkles:new parser", true),
+        "🐛 Fix ✨new parser"
+    );
+}
+
+#[test]
+fn test_render_title_keeps_unknown_and_stray_colons() {
+    assert_eq!(
+        render_title("fix: handle :notacode: in 10:30 logs", true),
+        "fix: handle :notacode: in 10:30 logs"
+    );
+    assert_eq!(render_title("chore: :zap:", true), "chore: ⚡");
+}
+
+#[test]
+fn test_truncate_counts_wide_chars_as_two_columns() {
+    let out = truncate_with_ellipsis("🚀🚀🚀🚀🚀", 7, "…");
+    assert_eq!(out, "🚀🚀🚀…");
+    assert!(out.width() <= 7);
+    // A wide char that would straddle the limit is dropped whole.
+    let out = truncate_with_ellipsis("修复登录重定向", 6, "…");
+    assert_eq!(out, "修复…");
+    assert_eq!(out.width(), 5);
+}
+
+#[test]
+fn test_truncate_short_strings_untouched() {
+    assert_eq!(truncate_with_ellipsis("abc", 3, "…"), "abc");
+    assert_eq!(truncate_with_ellipsis("abcdef", 3, "…"), "ab…");
+    assert_eq!(truncate_with_ellipsis("abcdefgh", 6, "…"), "abcde…");
+    assert_eq!(truncate_with_ellipsis("abc", 0, "…"), "");
+}
+
+#[test]
+fn test_truncate_with_ascii_ellipsis() {
+    assert_eq!(truncate_with_ellipsis("abcdefgh", 6, "..."), "abc...");
+    // Too narrow for the marker: just cut.
+    assert_eq!(truncate_with_ellipsis("abcdefgh", 2, "..."), "ab");
+}
+
+#[test]
+fn test_size_badge_buckets() {
+    let thresholds = [10, 100, 500, 1000];
+    assert_eq!(size_badge(0, &thresholds), "XS");
+    assert_eq!(size_badge(10, &thresholds), "XS");
+    assert_eq!(size_badge(11, &thresholds), "S");
+    assert_eq!(size_badge(500, &thresholds), "M");
+    assert_eq!(size_badge(1000, &thresholds), "L");
+    assert_eq!(size_badge(1001, &thresholds), "XL");
+}
+
+fn make_pr(number: u32, mergeable: &str) -> PullRequest {
+    PullRequest {
+        title: "Some change".into(),
+        author: "octocat".into(),
+        mergeable: Some(mergeable.into()),
+        ..common::make_pr("org-a", "api", number)
+    }
+}
+
+#[test]
+fn test_comment_count_sums_comments_and_reviews() {
+    let mut pr = make_pr(1, "MERGEABLE");
+    assert_eq!(comment_count_text(&pr), "");
+    pr.comment_count = 12;
+    pr.review_count = 3;
+    assert_eq!(comment_count_text(&pr), "15");
+}
+
+#[test]
+fn test_merge_state_hints_update_branch_only_when_known_behind() {
+    let mut pr = make_pr(1, "MERGEABLE");
+    assert_eq!(merge_state_display(&pr, &UNICODE).0, "✓ ok");
+    pr.is_behind = true;
+    assert_eq!(merge_state_display(&pr, &UNICODE).0, "✓ ok↓");
+
+    // Without a computed mergeable state the cell stays neutral.
+    pr.mergeable = Some("UNKNOWN".into());
+    assert_eq!(merge_state_display(&pr, &UNICODE).0, "?");
+}
+
+#[test]
+fn test_merge_state_marks_finished_prs() {
+    let mut pr = make_pr(1, "UNKNOWN");
+    pr.state = PrState::Closed;
+    assert_eq!(merge_state_display(&pr, &UNICODE).0, "⊘ cl");
+    pr.state = PrState::Merged;
+    assert_eq!(merge_state_display(&pr, &UNICODE).0, "⇒ mg");
+}
+
+#[test]
+fn test_merge_state_ascii_glyphs() {
+    let mut pr = make_pr(1, "CONFLICTING");
+    assert_eq!(merge_state_display(&pr, &ASCII).0, "x cf");
+    pr.mergeable = Some("MERGEABLE".into());
+    pr.is_behind = true;
+    assert_eq!(merge_state_display(&pr, &ASCII).0, "+ ok-");
+    pr.state = PrState::Merged;
+    assert!(merge_state_display(&pr, &ASCII).0.is_ascii());
+}
+
+fn loaded_state() -> AppState {
+    let mut state = AppState::new("me".into(), vec!["org-a".into()]);
+    state.loading = false;
+    state
+}
+
+#[test]
+fn test_empty_message_while_loading() {
+    let state = AppState::new("me".into(), vec!["org-a".into()]);
+    assert_eq!(empty_state_message(&state), "No data yet — loading...");
+}
+
+#[test]
+fn test_empty_message_when_nothing_to_show() {
+    let mut state = loaded_state();
+    assert_eq!(empty_state_message(&state), "No open pull requests");
+    state.content_view = ContentView::RecentlyMerged;
+    assert_eq!(
+        empty_state_message(&state),
+        "No recently merged pull requests"
+    );
+}
+
+#[test]
+fn test_empty_message_when_filters_hide_everything() {
+    let mut state = loaded_state();
+    state.inbox = (1..=14).map(|n| make_pr(n, "MERGEABLE")).collect();
+    state.search_query = "nothing matches this".into();
+    assert_eq!(
+        empty_state_message(&state),
+        "All 14 PRs are hidden by filters (press / to edit the search)"
+    );
+
+    state.search_query.clear();
+    state.merge_filter = MergeFilter::Conflicting;
+    assert!(state.current_pr_list().is_empty());
+    assert_eq!(
+        empty_state_message(&state),
+        "All 14 PRs are hidden by filters (press f to change the merge filter)"
+    );
+}
+
+#[test]
+fn test_empty_message_after_failed_fetch() {
+    let mut state = loaded_state();
+    state.content_view = ContentView::FailingChecks;
+    update(
+        &mut state,
+        Action::FetchFailed {
+            view: ContentView::AllOpenPrs,
+            msg: "Failed to fetch all open PRs: boom".into(),
+        },
+    );
+    // Derived views report their source's failure; the error modal still shows.
+    assert_eq!(
+        empty_state_message(&state),
+        "Fetch failed for this view — press r to retry"
+    );
+    assert!(state.error_message.is_some());
+
+    // Other views are unaffected.
+    state.content_view = ContentView::Inbox;
+    assert_eq!(empty_state_message(&state), "No open pull requests");
+}
+
+#[test]
+fn test_empty_message_after_failed_finished_fetch_and_retry() {
+    let mut state = loaded_state();
+    state.content_view = ContentView::RepoPrList {
+        owner: "org-a".into(),
+        name: "api".into(),
+    };
+    state.pr_state_filter = PrStateFilter::Merged;
+    assert_eq!(
+        empty_state_message(&state),
+        "Loading merged pull requests..."
+    );
+
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::RepoFinishedPrsFailed {
+            owner: "org-a".into(),
+            name: "api".into(),
+            state: PrStateFilter::Merged,
+            msg: "Failed to fetch merged PRs for org-a/api: boom".into(),
+            cancel: CancelToken::new(),
+        }),
+    );
+    assert_eq!(
+        empty_state_message(&state),
+        "Fetch failed for this view — press r to retry"
+    );
+    assert!(state.error_message.is_some());
+    // The closed list wasn't the one that failed.
+    state.pr_state_filter = PrStateFilter::Closed;
+    assert_eq!(
+        empty_state_message(&state),
+        "Loading closed pull requests..."
+    );
+
+    state.pr_state_filter = PrStateFilter::Merged;
+    update(
+        &mut state,
+        Action::DataLoaded(DataPayload::RepoFinishedPrs {
+            owner: "org-a".into(),
+            name: "api".into(),
+            state: PrStateFilter::Merged,
+            prs: vec![],
+            rate_limit: None,
+            cancel: CancelToken::new(),
+        }),
+    );
+    assert_eq!(empty_state_message(&state), "No merged pull requests");
+}
+
+#[test]
+fn test_count_text_shows_visible_over_total_only_when_filtered() {
+    assert_eq!(count_text((37, 37), false), "37");
+    assert_eq!(count_text((4, 37), true), "4/37");
+    assert_eq!(count_text((37, 37), true), "37/37");
+}
+
+#[test]
+fn test_title_count_text_says_how_many_the_filter_hides() {
+    assert_eq!(title_count_text((12, 12), false), "12");
+    assert_eq!(title_count_text((5, 12), true), "5 of 12");
+}
+
+#[test]
+fn test_column_window_keeps_everything_when_wide() {
+    let columns = default_columns();
+    let window = column_window(&columns, &default_column_priority(), 200, 0);
+    assert_eq!(window.columns, columns);
+    assert!(!window.hidden_left && !window.hidden_right);
+}
+
+#[test]
+fn test_column_window_drops_low_priority_columns_as_width_shrinks() {
+    use PrColumn::*;
+    let columns = vec![Number, State, Checks, Title, Author, Repo, Updated];
+    let priority = default_column_priority();
+    let expected: [(u16, Vec<PrColumn>); 7] = [
+        (200, columns.clone()),
+        (120, columns.clone()),
+        (91, columns.clone()),
+        // Updated goes first, then Author; Repo outranks both.
+        (90, vec![Number, State, Checks, Title, Author, Repo]),
+        (80, vec![Number, State, Checks, Title, Author, Repo]),
+        (70, vec![Number, State, Checks, Title, Repo]),
+        // Too narrow for Repo, but Author still fits in what's left.
+        (60, vec![Number, State, Checks, Title, Author]),
+    ];
+    for (width, want) in expected {
+        let window = column_window(&columns, &priority, width, 0);
+        assert_eq!(window.columns, want, "width {}", width);
+        assert_eq!(
+            window.hidden_right,
+            want.len() < columns.len(),
+            "width {}",
+            width
+        );
+        assert!(!window.hidden_left);
+    }
+}
+
+#[test]
+fn test_column_window_drops_comments_before_updated() {
+    let columns = default_columns();
+    let window = column_window(&columns, &default_column_priority(), 100, 0);
+    assert!(window.columns.contains(&PrColumn::Updated));
+    assert!(!window.columns.contains(&PrColumn::Comments));
+    assert!(window.hidden_right);
+}
+
+#[test]
+fn test_default_priority_ranks_assignees() {
+    use PrColumn::*;
+    let priority = default_column_priority();
+    let rank = |col| priority.iter().position(|c| *c == col).unwrap();
+    assert!(rank(Author) < rank(Assignees));
+    assert!(rank(Assignees) < rank(Updated));
+}
+
+#[test]
+fn test_column_window_follows_configured_priority_and_columns() {
+    use PrColumn::*;
+    // Only configured columns are candidates; Size isn't shown here at all.
+    let columns = vec![Title, Author, Updated, Repo];
+    let priority = vec![Updated, Author];
+    let window = column_window(&columns, &priority, 50, 0);
+    assert_eq!(window.columns, vec![Title, Author, Updated]);
+    assert!(window.hidden_right);
+}
+
+#[test]
+fn test_column_window_marks_hidden_columns_by_position() {
+    use PrColumn::*;
+    // Comments ranks below Number, so it drops first even though it's
+    // leftmost: the hidden column is on the left, not the right.
+    let columns = vec![Comments, Number, Title];
+    let priority = vec![Number, Comments];
+    let window = column_window(&columns, &priority, 30, 0);
+    assert_eq!(window.columns, vec![Number, Title]);
+    assert!(window.hidden_left);
+    assert!(!window.hidden_right);
+}
+
+#[test]
+fn test_column_window_offset_scrolls_but_keeps_title() {
+    use PrColumn::*;
+    let columns = vec![Number, State, Checks, Title, Author, Repo, Updated];
+    let priority = default_column_priority();
+    let window = column_window(&columns, &priority, 40, 3);
+    assert_eq!(window.columns, vec![PrColumn::Title, PrColumn::Author]);
+    assert!(window.hidden_left);
+    assert!(window.hidden_right);
+
+    // Scrolling past the end clamps to the last column.
+    let window = column_window(&columns, &priority, 40, 99);
+    assert_eq!(window.columns, vec![PrColumn::Title, PrColumn::Updated]);
+    assert!(window.hidden_left);
+    assert!(!window.hidden_right);
+}
+
+#[test]
+fn test_column_offset_resets_once_every_column_fits() {
+    let mut state = AppState::new("octocat".into(), vec![]);
+    state.column_offset = 2;
+    let narrow = ratatui::layout::Rect::new(0, 0, 60, 20);
+    assert_eq!(fitted_column_offset(narrow, &state), 2);
+    let wide = ratatui::layout::Rect::new(0, 0, 200, 20);
+    assert_eq!(fitted_column_offset(wide, &state), 0);
+}
+
+#[test]
+fn test_parse_color_names_hex_and_index() {
+    use ghdash::ui::theme::parse_color;
+    use ratatui::style::Color;
+
+    assert_eq!(parse_color("red"), Some(Color::Red));
+    assert_eq!(parse_color("LightBlue"), Some(Color::LightBlue));
+    assert_eq!(parse_color("#ff8800"), Some(Color::Rgb(0xff, 0x88, 0x00)));
+    assert_eq!(parse_color("208"), Some(Color::Indexed(208)));
+    assert_eq!(parse_color("blurple"), None);
+}
+
+#[test]
+fn test_unknown_org_color_fails_the_check() {
+    use ghdash::ui::theme::check_org_colors;
+    use ghdash::util::config::OrgColor;
+
+    let entry = |color: &str| OrgColor {
+        org: "my-org".into(),
+        color: color.into(),
+    };
+    assert!(check_org_colors(&[entry("cyan"), entry("#ff8800")]).is_ok());
+    let err = check_org_colors(&[entry("cyan"), entry("blurple")]).unwrap_err();
+    assert!(format!("{:#}", err).contains("blurple"));
+}
+
+#[test]
+fn test_spinner_steps_through_its_frames() {
+    use chrono::TimeZone;
+    use ghdash::ui::glyphs::{ASCII, SPINNER_FRAME_MS};
+    use ghdash::ui::widgets::spinner_frame;
+
+    let start = chrono::Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+    let frames: Vec<&str> = (0..5)
+        .map(|i| {
+            let at = start + chrono::Duration::milliseconds((i * SPINNER_FRAME_MS) as i64);
+            spinner_frame(&ASCII, at)
+        })
+        .collect();
+    assert_eq!(frames, vec!["|", "/", "-", "\\", "|"]);
+}
+
+fn caps_for(vars: &[(&str, &str)]) -> ghdash::ui::theme::TerminalCaps {
+    let vars: Vec<(String, String)> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    ghdash::ui::theme::TerminalCaps::detect(&|name| {
+        vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+    })
+}
+
+#[test]
+fn test_terminal_caps_detection() {
+    use ghdash::ui::theme::{ColorDepth, TerminalCaps};
+
+    assert_eq!(caps_for(&[]), TerminalCaps::FULL);
+    let modern = caps_for(&[
+        ("TERM", "xterm-256color"),
+        ("COLORTERM", "truecolor"),
+        ("LANG", "en_US.UTF-8"),
+    ]);
+    assert_eq!(modern, TerminalCaps::FULL);
+
+    let xterm = caps_for(&[("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")]);
+    assert_eq!(xterm.colors, ColorDepth::Ansi256);
+    assert!(xterm.unicode);
+
+    let console = caps_for(&[("TERM", "linux"), ("LANG", "en_US.UTF-8")]);
+    assert_eq!(console.colors, ColorDepth::Ansi16);
+    assert!(!console.unicode);
+
+    // LC_ALL wins over LANG.
+    let c_locale = caps_for(&[
+        ("TERM", "xterm-256color"),
+        ("LC_ALL", "C"),
+        ("LANG", "en_US.UTF-8"),
+    ]);
+    assert!(!c_locale.unicode);
+}
+
+#[test]
+fn test_theme_ascii_override_and_color_downgrade() {
+    use ghdash::ui::theme::{ColorDepth, TerminalCaps, Theme};
+    use ratatui::style::Color;
+
+    assert_eq!(Theme::new(TerminalCaps::FULL, None).glyphs, &UNICODE);
+    assert_eq!(Theme::new(TerminalCaps::FULL, Some(true)).glyphs, &ASCII);
+    let basic = TerminalCaps {
+        unicode: false,
+        colors: ColorDepth::Ansi16,
+    };
+    assert_eq!(Theme::new(basic, None).glyphs, &ASCII);
+    assert_eq!(Theme::new(basic, Some(false)).glyphs, &UNICODE);
+
+    let theme = Theme::new(basic, None);
+    assert_eq!(theme.color(Color::Rgb(0xff, 0x10, 0x10)), Color::LightRed);
+    assert_eq!(theme.color(Color::Indexed(22)), Color::Green);
+    assert_eq!(theme.color(Color::Magenta), Color::Magenta);
+
+    let theme = Theme::new(
+        TerminalCaps {
+            unicode: true,
+            colors: ColorDepth::Ansi256,
+        },
+        None,
+    );
+    assert_eq!(
+        theme.color(Color::Rgb(0xff, 0x87, 0x00)),
+        Color::Indexed(208)
+    );
+    assert_eq!(
+        theme.color(Color::Rgb(0x80, 0x80, 0x80)),
+        Color::Indexed(244)
+    );
+}
+
+#[test]
+fn test_wrapped_line_count() {
+    assert_eq!(wrapped_line_count("", 10), 1);
+    assert_eq!(wrapped_line_count("short", 10), 1);
+    assert_eq!(wrapped_line_count("two words", 5), 2);
+    assert_eq!(wrapped_line_count("aaaa bbbb cccc", 9), 2);
+    // Words longer than a row are broken across rows.
+    assert_eq!(wrapped_line_count("abcdefghijklmnopqrstuvwxy", 10), 3);
+    assert_eq!(wrapped_line_count("first\nsecond", 40), 2);
+}
+
+#[test]
+fn test_error_max_scroll_stops_at_the_last_line() {
+    let mut state = AppState::new("me".into(), vec![]);
+    let screen = ratatui::layout::Rect::new(0, 0, 80, 12);
+    assert_eq!(error_max_scroll(screen, &state), 0);
+
+    state.error_message = Some("short".into());
+    assert_eq!(error_max_scroll(screen, &state), 0);
+
+    // 20 lines in a modal of 8 rows: 4 for text, the rest border and hint.
+    let lines: Vec<String> = (1..=20).map(|i| format!("line {i}")).collect();
+    state.error_message = Some(lines.join("\n"));
+    assert_eq!(error_max_scroll(screen, &state), 16);
+}